@@ -0,0 +1,58 @@
+"""
+Benchmark: what process tag rules add to a get_process_list() poll.
+
+    python benchmarks/process_tags.py [polls]
+
+Times polls of the live process list with no rules, then with a typical
+rule set that needs every detail (name, cmdline, exe, username). The first
+tagged poll reads each process's details; the rest only look tags up, so
+their overhead is what a steady-state agent pays.
+"""
+import statistics
+import sys
+import time
+
+from pulse import core
+
+RULES = [
+    {"tag": "database", "match": {"name_regex": "^(postgres|mysqld|mongod|redis-server)$"}},
+    {"tag": "webserver", "match": {"name_regex": "^(nginx|httpd|apache2|caddy)$"}},
+    {"tag": "webserver", "match": {"cmdline_regex": r"gunicorn|uvicorn|http\.server"}},
+    {"tag": "build", "match": {"cmdline_regex": r"\b(cargo|rustc|cc1|make|ninja|gradle)\b"}},
+    {"tag": "browser", "match": {"name_regex": "chrom|firefox|safari"}},
+    {"tag": "system", "match": {"exe_prefix": "/usr/sbin/"}},
+    {"tag": "root", "match": {"username": "root"}},
+]
+
+
+def _poll_ms(polls: int) -> list:
+    times = []
+    for _ in range(polls):
+        start = time.perf_counter()
+        core.get_process_list()
+        times.append((time.perf_counter() - start) * 1000)
+    return times
+
+
+def main() -> None:
+    polls = int(sys.argv[1]) if len(sys.argv) > 1 else 50
+    processes = len(core.get_process_list())
+    plain = _poll_ms(polls)
+
+    core.set_process_tags(RULES)
+    try:
+        first = _poll_ms(1)[0]
+        tagged = _poll_ms(polls)
+    finally:
+        core.set_process_tags(None)
+
+    base, steady = statistics.median(plain), statistics.median(tagged)
+    print(f"{processes} processes, {polls} polls each (median)")
+    print(f"  no rules:           {base:8.3f} ms")
+    print(f"  first tagged poll:  {first:8.3f} ms")
+    print(f"  tagged, steady:     {steady:8.3f} ms  (+{steady - base:.3f} ms, "
+          f"{(steady - base) / base * 100:+.1f}%)")
+
+
+if __name__ == "__main__":
+    main()
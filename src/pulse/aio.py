@@ -0,0 +1,104 @@
+"""
+Pulse asyncio Support
+Awaitable variants of the collectors that would otherwise block the event loop.
+
+Blocking reads run on the loop's default executor; waits are asyncio sleeps or
+event-loop readers, so cancelling the awaiting task stops them immediately.
+Import this module explicitly (from pulse import aio); pulse.core doesn't load
+it, so synchronous users never touch asyncio.
+"""
+import asyncio
+import os
+import time
+from typing import Any, Dict, Iterable, List, Optional
+
+from pulse import core, validation
+from pulse.direct_os import LINUX, WINDOWS
+from pulse.errors import Timeout
+from pulse.state import SysState, using
+
+# How often async_wait_for_process_exit() checks when the OS offers no exit notification
+POLL_INTERVAL_SECS = 0.1
+
+
+async def async_cpu_percents(interval: float = 1.0) -> List[float]:
+    """Per-core CPU usage measured over the next interval seconds.
+
+    Uses a baseline of its own, so neither the module-level getters nor any
+    Monitor see their deltas moved.
+    """
+    # get_cpu_percents() repeats its last reading for shorter gaps
+    validation.at_least('interval', interval, 0.05)
+    # Both reads must reach the OS, whatever set_default_max_age() chose
+    with using(SysState()):
+        await asyncio.to_thread(core.get_cpu_percents, max_age_ms=0)
+        await asyncio.sleep(interval)
+        return await asyncio.to_thread(core.get_cpu_percents, max_age_ms=0)
+
+
+def _pid_exists(pid: int) -> bool:
+    if WINDOWS:
+        return pid in {p['pid'] for p in core.get_process_list()}
+    try:
+        os.kill(pid, 0)
+    except ProcessLookupError:
+        return False
+    except PermissionError:
+        return True
+    if LINUX:
+        # An exited child stays in the table as a zombie until it is reaped
+        try:
+            with open(f'/proc/{pid}/stat', 'r') as f:
+                return f.read().rpartition(')')[2].split()[0] != 'Z'
+        except (OSError, IndexError):
+            return False
+    return True
+
+
+async def _wait_pidfd(fd: int) -> None:
+    loop = asyncio.get_running_loop()
+    exited = loop.create_future()
+    loop.add_reader(fd, lambda: exited.done() or exited.set_result(None))
+    try:
+        await exited
+    finally:
+        loop.remove_reader(fd)
+
+
+async def _wait_polling(pid: int) -> None:
+    while await asyncio.to_thread(_pid_exists, pid):
+        await asyncio.sleep(POLL_INTERVAL_SECS)
+
+
+async def async_wait_for_process_exit(pid: int, timeout: Optional[float] = None) -> float:
+    """Wait until pid has exited; returns the seconds waited (0 if it was already gone).
+
+    On Linux the kernel signals the exit through a pidfd, elsewhere the pid
+    is polled. Raises pulse.errors.Timeout once timeout seconds pass.
+    """
+    validation.at_least('pid', pid, 1)
+    validation.optional_at_least('timeout', timeout, 0)
+    started = time.monotonic()
+    fd = None
+    if hasattr(os, 'pidfd_open'):
+        try:
+            fd = os.pidfd_open(pid)
+        except ProcessLookupError:
+            return 0.0
+        except OSError:
+            fd = None
+    try:
+        wait = _wait_pidfd(fd) if fd is not None else _wait_polling(pid)
+        await asyncio.wait_for(wait, timeout)
+    except asyncio.TimeoutError:
+        raise Timeout(f'pid {pid} still running after {timeout} s') from None
+    finally:
+        if fd is not None:
+            os.close(fd)
+    return time.monotonic() - started
+
+
+async def async_snapshot(include: Optional[Iterable[str]] = None,
+                         options: Optional[Dict[str, Dict[str, Any]]] = None) -> Dict[str, Any]:
+    """snapshot() collected on the executor instead of the event loop thread."""
+    return await asyncio.to_thread(core.snapshot, include, options)
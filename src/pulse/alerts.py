@@ -0,0 +1,197 @@
+"""
+Pulse Alerts
+Threshold rules with sustain periods and hysteresis, evaluated on demand.
+
+A rule fires once its condition has held for sustain_secs and resolves once
+the value falls back past clear_threshold, so a metric hovering around the
+threshold doesn't flap. While the background sampler runs, check_alerts()
+walks every sample taken since the previous check instead of taking one
+fresh reading, so short spikes between checks still count.
+"""
+import operator
+import threading
+from typing import Any, Callable, Dict, Iterable, List, Optional, Tuple
+
+# Comparisons a rule may use, and for each the direction clear_threshold must
+# lie in relative to threshold (-1: at or below, 1: at or above)
+OPERATORS: Dict[str, Tuple[Callable[[float, float], bool], int]] = {
+    '>': (operator.gt, -1),
+    '>=': (operator.ge, -1),
+    '<': (operator.lt, 1),
+    '<=': (operator.le, 1),
+}
+
+# Metric name (before any ":" argument) -> snapshot section it reads
+METRIC_SECTIONS = {
+    'cpu_total': 'cpu',
+    'memory_percent': 'memory',
+    'disk_percent': 'disks',
+    'process_cpu': 'processes',
+}
+
+_lock = threading.Lock()
+_rules: List[Dict[str, Any]] = []
+# Per rule: when the condition started holding, and whether the alert is firing
+_states: List[Dict[str, Any]] = []
+# Newest sampler timestamp already evaluated
+_last_sample: Optional[float] = None
+
+
+def _parse_rule(index: int, rule: Dict[str, Any]) -> Dict[str, Any]:
+    def bad(reason: str) -> ValueError:
+        return ValueError(f'Alert rule {index} ({rule!r}): {reason}')
+
+    if not isinstance(rule, dict):
+        raise bad('expected a dict')
+    unknown = set(rule) - {'metric', 'operator', 'threshold', 'clear_threshold', 'sustain_secs', 'name'}
+    if unknown:
+        raise bad(f"unknown keys {', '.join(sorted(unknown))}")
+
+    metric = rule.get('metric')
+    if not isinstance(metric, str):
+        raise bad('"metric" is required')
+    kind, _, argument = metric.partition(':')
+    if kind not in METRIC_SECTIONS:
+        raise bad(f"unknown metric {kind!r}; expected one of {', '.join(METRIC_SECTIONS)}")
+    if kind in ('disk_percent', 'process_cpu') and not argument:
+        raise bad(f'{kind} needs a target, e.g. "{kind}:{"/" if kind == "disk_percent" else "python"}"')
+    if kind in ('cpu_total', 'memory_percent') and argument:
+        raise bad(f'{kind} takes no target')
+
+    op = rule.get('operator', '>')
+    if op not in OPERATORS:
+        raise bad(f"unknown operator {op!r}; expected one of {', '.join(OPERATORS)}")
+    try:
+        threshold = float(rule['threshold'])
+        clear = float(rule.get('clear_threshold', threshold))
+        sustain = float(rule.get('sustain_secs', 0))
+    except KeyError:
+        raise bad('"threshold" is required') from None
+    except (TypeError, ValueError):
+        raise bad('threshold, clear_threshold, and sustain_secs must be numbers') from None
+    if sustain < 0:
+        raise bad('sustain_secs must be >= 0')
+    if (clear - threshold) * OPERATORS[op][1] < 0:
+        side = 'at or below' if OPERATORS[op][1] < 0 else 'at or above'
+        raise bad(f'clear_threshold must be {side} threshold for {op!r}')
+
+    return {
+        'name': str(rule.get('name', f'{metric} {op} {rule["threshold"]}')),
+        'metric': metric,
+        'kind': kind,
+        'target': argument,
+        'operator': op,
+        'threshold': threshold,
+        'clear_threshold': clear,
+        'sustain_secs': sustain,
+    }
+
+
+def set_alerts(rules: Iterable[Dict[str, Any]]) -> None:
+    """Replace the alert rules, forgetting any pending or firing state.
+
+    Each rule is a dict with "metric" ("cpu_total", "memory_percent",
+    "disk_percent:<mount point>", or "process_cpu:<process name>"),
+    "operator" (">", ">=", "<", "<="; default ">"), "threshold", and
+    optionally "sustain_secs" (default 0), "clear_threshold" (default
+    threshold), and "name". Raises ValueError naming the first bad rule.
+    """
+    global _last_sample
+    parsed = [_parse_rule(i, rule) for i, rule in enumerate(rules)]
+    with _lock:
+        _rules[:] = parsed
+        _states[:] = [{'since': None, 'firing': False} for _ in parsed]
+        _last_sample = None
+
+
+def metric_value(rule: Dict[str, Any], snap: Dict[str, Any]) -> Optional[float]:
+    """The rule's metric in a snapshot; None when the snapshot lacks it.
+
+    process_cpu sums every process with that name, 0 when none is running.
+    """
+    section = snap.get(METRIC_SECTIONS[rule['kind']])
+    if section is None:
+        return None
+    if rule['kind'] == 'cpu_total':
+        return sum(section) / len(section) if section else None
+    if rule['kind'] == 'memory_percent':
+        return section.get('percent')
+    if rule['kind'] == 'disk_percent':
+        for disk in section:
+            if disk['mountpoint'] == rule['target']:
+                return disk['percent']
+        return None
+    return float(sum(p['cpu_percent'] for p in section if p['name'] == rule['target']))
+
+
+def _alert(rule: Dict[str, Any], value: float, timestamp: float) -> Dict[str, Any]:
+    return {
+        'name': rule['name'],
+        'metric': rule['metric'],
+        'operator': rule['operator'],
+        'threshold': rule['threshold'],
+        'value': value,
+        'timestamp': timestamp,
+    }
+
+
+def evaluate(rule: Dict[str, Any], state: Dict[str, Any], value: Optional[float],
+             timestamp: float) -> Optional[Tuple[str, Dict[str, Any]]]:
+    """Advance one rule's state with a reading; ("fired" | "resolved", alert) on a transition."""
+    if value is None:
+        return None
+    compare = OPERATORS[rule['operator']][0]
+    if state['firing']:
+        if not compare(value, rule['clear_threshold']):
+            state['firing'], state['since'] = False, None
+            return 'resolved', _alert(rule, value, timestamp)
+        return None
+    if not compare(value, rule['threshold']):
+        state['since'] = None
+        return None
+    if state['since'] is None:
+        state['since'] = timestamp
+    if timestamp - state['since'] >= rule['sustain_secs']:
+        state['firing'] = True
+        return 'fired', {**_alert(rule, value, timestamp), 'since': state['since']}
+    return None
+
+
+def _readings(sections: List[str]) -> List[Dict[str, Any]]:
+    """Sampler snapshots not yet evaluated, plus one fresh snapshot of any section it doesn't collect."""
+    from pulse import core, sampler
+
+    global _last_sample
+    readings = []
+    missing = sections
+    status = sampler.sampler_status()
+    if status['running']:
+        readings = sampler.get_samples(since_timestamp=_last_sample)
+        if readings:
+            _last_sample = readings[-1]['timestamp']
+        missing = [s for s in sections if s not in status['sections']]
+    if missing:
+        readings.append(core.snapshot(include=missing))
+    return sorted(readings, key=lambda s: s['timestamp'])
+
+
+def check_alerts() -> Dict[str, List[Dict[str, Any]]]:
+    """Evaluate the rules and report what changed since the previous check.
+
+    Returns {"fired": [...], "resolved": [...]}; each alert names its rule
+    and metric and carries the value and timestamp of the reading that
+    caused the transition ("fired" alerts also say since when the condition
+    held). Alerts still firing from an earlier check are not repeated.
+    """
+    fired: List[Dict[str, Any]] = []
+    resolved: List[Dict[str, Any]] = []
+    with _lock:
+        if not _rules:
+            return {'fired': fired, 'resolved': resolved}
+        sections = sorted({METRIC_SECTIONS[rule['kind']] for rule in _rules})
+        for snap in _readings(sections):
+            for rule, state in zip(_rules, _states):
+                change = evaluate(rule, state, metric_value(rule, snap), snap['timestamp'])
+                if change is not None:
+                    (fired if change[0] == 'fired' else resolved).append(change[1])
+    return {'fired': fired, 'resolved': resolved}
@@ -0,0 +1,147 @@
+"""
+Pulse Anomalies
+Readings far from their recent baseline in the background sampler's history.
+
+No model, just a z-score: each series' mean and standard deviation over the
+window, excluding the newest reading, and how many deviations that reading
+lies away. A series that barely moved has a deviation near zero, so for it
+only an absolute change of ABSOLUTE_CHANGE or more counts.
+"""
+import math
+from typing import Any, Dict, List, Optional, Tuple
+
+from pulse import sampler, validation
+
+# Readings a baseline needs before its series is judged
+MIN_BASELINE = 5
+
+# Below this deviation a series is flat and judged by absolute change instead
+FLAT_STDDEV = 1e-6
+
+# Series kind -> the change from a flat baseline that is an anomaly anyway
+ABSOLUTE_CHANGE = {
+    'cpu_total': 20.0,
+    'memory_percent': 10.0,
+    'memory_used': 256.0 * 1024 * 1024,
+    'disk_percent': 5.0,
+    'disk_used': 1024.0 ** 3,
+    'net_recv_per_sec': 1024.0 * 1024,
+    'net_sent_per_sec': 1024.0 * 1024,
+    'net_rx_rate': 1024.0 * 1024,
+    'net_tx_rate': 1024.0 * 1024,
+}
+
+# Byte counter -> the series of its rate over all interfaces, and over each one
+RATES = (('bytes_recv', 'net_recv_per_sec', 'net_rx_rate'),
+         ('bytes_sent', 'net_sent_per_sec', 'net_tx_rate'))
+
+Series = Dict[str, List[Tuple[float, float]]]
+
+
+def baseline(values: List[float]) -> Tuple[float, float]:
+    """(mean, population standard deviation) of values."""
+    mean = sum(values) / len(values)
+    return mean, math.sqrt(sum((v - mean) ** 2 for v in values) / len(values))
+
+
+def zscore(value: float, mean: float, stddev: float) -> Optional[float]:
+    """Deviations value lies from mean; None for a flat baseline."""
+    return (value - mean) / stddev if stddev > FLAT_STDDEV else None
+
+
+def rate(before: Optional[float], current: Optional[float], elapsed: float) -> Optional[float]:
+    """Per-second growth of a byte counter; None if either reading is missing or it was reset."""
+    # A counter going backwards was reset (interface removed); skip that step
+    if elapsed <= 0 or before is None or current is None or current < before:
+        return None
+    return (current - before) / elapsed
+
+
+def series_from_samples(samples: List[Dict[str, Any]]) -> Series:
+    """[(timestamp, value)] per series name from sampler snapshots, oldest first.
+
+    Names follow the alert metrics: cpu_total, memory_percent, memory_used,
+    "disk_percent:<mountpoint>", "disk_used:<mountpoint>", and
+    net_recv_per_sec / net_sent_per_sec, which are rates between consecutive
+    samples' interface totals. "net_rx_rate:<interface>" and
+    "net_tx_rate:<interface>" are the same rates for each interface the
+    sampler recorded counters for.
+    """
+    series: Series = {}
+
+    def add(name: str, timestamp: float, value: Optional[float]) -> None:
+        if value is not None:
+            series.setdefault(name, []).append((timestamp, float(value)))
+
+    previous = None
+    for sample in samples:
+        timestamp = sample['timestamp']
+        cpu = sample.get('cpu')
+        if cpu:
+            add('cpu_total', timestamp, sum(cpu) / len(cpu))
+        memory = sample.get('memory')
+        if memory is not None:
+            add('memory_percent', timestamp, memory.get('percent'))
+            add('memory_used', timestamp, memory.get('used'))
+        for disk in sample.get('disks') or ():
+            add(f"disk_percent:{disk['mountpoint']}", timestamp, disk.get('percent'))
+            add(f"disk_used:{disk['mountpoint']}", timestamp, disk.get('used'))
+        network = sample.get('network')
+        before = previous.get('network') if previous is not None else None
+        if network is not None and before is not None:
+            elapsed = timestamp - previous['timestamp']
+            interfaces_before = before.get('interfaces') or {}
+            for key, total, per_interface in RATES:
+                add(total, timestamp, rate(before.get(key), network.get(key), elapsed))
+                for interface, counters in (network.get('interfaces') or {}).items():
+                    add(f'{per_interface}:{interface}', timestamp,
+                        rate(interfaces_before.get(interface, {}).get(key), counters.get(key), elapsed))
+        previous = sample
+    return series
+
+
+def find_anomalies(series: Series, zscore_threshold: float) -> List[Dict[str, Any]]:
+    """Series whose newest reading lies at least zscore_threshold deviations from the rest.
+
+    Each is {series, value, timestamp, mean, stddev, score}; score is None
+    for a flat baseline that the reading left by ABSOLUTE_CHANGE or more.
+    Sorted by how far out they lie, flat-baseline jumps first.
+    """
+    anomalies = []
+    for name, readings in series.items():
+        if len(readings) <= MIN_BASELINE:
+            continue
+        timestamp, value = readings[-1]
+        mean, stddev = baseline([v for _, v in readings[:-1]])
+        score = zscore(value, mean, stddev)
+        if score is None:
+            if abs(value - mean) < ABSOLUTE_CHANGE[name.partition(':')[0]]:
+                continue
+        elif abs(score) < zscore_threshold:
+            continue
+        anomalies.append({'series': name, 'value': value, 'timestamp': timestamp,
+                          'mean': mean, 'stddev': stddev, 'score': score})
+    anomalies.sort(key=lambda a: -math.inf if a['score'] is None else -abs(a['score']))
+    return anomalies
+
+
+def get_anomalies(window_secs: float = 300, zscore_threshold: float = 3.0) -> List[Dict[str, Any]]:
+    """Get the sampled series whose newest reading is an outlier against the last window_secs.
+
+    Covers the series_from_samples() series of whichever sections the
+    sampler collects, per-interface receive and send rates included; see
+    find_anomalies() for the entries. Empty until the sampler
+    has more than MIN_BASELINE readings in the window. Raises ValueError for
+    a window_secs or zscore_threshold that isn't positive.
+    """
+    validation.positive('window_secs', window_secs)
+    validation.positive('zscore_threshold', zscore_threshold)
+    samples = sampler.get_samples()
+    if not samples:
+        return []
+    # One sample before the window so the first network rate has a predecessor
+    since = samples[-1]['timestamp'] - window_secs
+    start = next((i for i, s in enumerate(samples) if s['timestamp'] >= since), len(samples))
+    series = series_from_samples(samples[max(0, start - 1):])
+    series = {name: [r for r in readings if r[0] >= since] for name, readings in series.items()}
+    return find_anomalies(series, zscore_threshold)
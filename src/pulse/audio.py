@@ -0,0 +1,98 @@
+"""
+Pulse Audio
+Read-only default output volume and mute state for desktop widgets.
+
+Linux asks the sound server through pactl (PulseAudio and PipeWire both ship
+it) and falls back to the ALSA Master control via amixer. Both run in the C
+locale: their labels ("Mute: yes", "[on]") are translated otherwise.
+"""
+import os
+import re
+import shutil
+import subprocess
+from typing import Any, Dict, List, Optional
+
+from pulse.capabilities import requires
+from pulse.direct_os import LINUX
+from pulse.errors import Unsupported
+
+_TIMEOUT_SECS = 2.0
+
+_PERCENT = re.compile(r'(\d+)%')
+
+
+def parse_pactl_volume(text: str) -> Optional[float]:
+    """Mean channel volume from `pactl get-sink-volume` output."""
+    # Only the first line holds channel volumes; "balance" follows on the next
+    percents = [int(p) for p in _PERCENT.findall(text.split('\n', 1)[0])]
+    return sum(percents) / len(percents) if percents else None
+
+
+def parse_pactl_mute(text: str) -> Optional[bool]:
+    """True/False from `pactl get-sink-mute` ("Mute: yes")."""
+    _, sep, value = text.partition(':')
+    if not sep:
+        return None
+    return value.strip() == 'yes'
+
+
+def parse_amixer(text: str) -> Dict[str, Any]:
+    """Volume and mute from `amixer get Master`, averaging playback channels."""
+    percents = []
+    switches = []
+    for line in text.splitlines():
+        if 'Playback' not in line or ':' not in line or 'Limits' in line:
+            continue
+        percent = _PERCENT.search(line)
+        if percent:
+            percents.append(int(percent.group(1)))
+        switch = re.search(r'\[(on|off)\]', line)
+        if switch:
+            switches.append(switch.group(1))
+    return {
+        'volume_percent': sum(percents) / len(percents) if percents else None,
+        # A control without a playback switch can't be muted
+        'muted': bool(switches) and all(s == 'off' for s in switches),
+    }
+
+
+def _run(args: List[str]) -> Optional[str]:
+    if shutil.which(args[0]) is None:
+        return None
+    try:
+        result = subprocess.run(args, capture_output=True, text=True, timeout=_TIMEOUT_SECS,
+                                env={**os.environ, 'LC_ALL': 'C'})
+    except (OSError, subprocess.TimeoutExpired):
+        return None
+    return result.stdout if result.returncode == 0 else None
+
+
+if LINUX:
+    @requires('audio')
+    def get_audio_state() -> Dict[str, Any]:
+        """Get the default sink's name, volume percent, and mute state.
+
+        Raises Unsupported when neither a sound server nor an ALSA
+        mixer is reachable.
+        """
+        sink = _run(['pactl', 'get-default-sink'])
+        if sink is not None:
+            volume = _run(['pactl', 'get-sink-volume', '@DEFAULT_SINK@'])
+            mute = _run(['pactl', 'get-sink-mute', '@DEFAULT_SINK@'])
+            return {
+                'sink': sink.strip() or None,
+                'volume_percent': parse_pactl_volume(volume) if volume else None,
+                'muted': parse_pactl_mute(mute) if mute else None,
+                'backend': 'pulseaudio',
+            }
+
+        mixer = _run(['amixer', 'get', 'Master'])
+        if mixer is not None:
+            return {'sink': 'Master', **parse_amixer(mixer), 'backend': 'alsa'}
+        raise Unsupported('No PulseAudio/PipeWire server or ALSA mixer available')
+
+else:
+    @requires('audio')
+    def get_audio_state() -> Dict[str, Any]:
+        """Output volume isn't readable on this platform yet."""
+        raise Unsupported('Audio state is only supported on Linux')
@@ -0,0 +1,301 @@
+"""
+Pulse Backend
+Where the core readings come from: the host, or data a test or demo injects.
+
+Every CPU, memory, process, network, disk, and disk I/O getter takes its raw
+reading from the active Backend and computes everything else (CPU percents
+and I/O rates from counter deltas, sorting, limits, filters, units, caching)
+the same way whichever backend answered. The live backend reads the OS; the
+mock backend returns what mock_set_data() last stored, so a dashboard shows
+reproducible figures and an app's tests assert exact values:
+
+    set_backend('mock')
+    mock_set_data('memory', {'total': 8 << 30, 'available': 2 << 30, ...})
+    mock_set_data('cpu', [{'busy': 100, 'total': 400}])   # cumulative ticks per core
+    mock_set_data('cpu', [{'busy': 300, 'total': 600}])   # next call: 100.0 percent
+
+Getters outside those sections (sensors, services, containers, ...) keep
+reading the host.
+"""
+import copy
+import threading
+from typing import Any, Dict, List, Optional, Tuple
+
+from pulse import validation
+
+BACKENDS = ('live', 'mock')
+
+# Sections mock_set_data() accepts, and the getters each one feeds
+MOCK_SECTIONS = ('cpu', 'memory', 'processes', 'network', 'disks', 'disk_io')
+
+# Fields a mocked disk needs; the rest of get_disk_info()'s usage figures default
+_DISK_FIELDS = ('mountpoint', 'total', 'used', 'free', 'available')
+
+
+class Backend:
+    """The raw readings behind the core getters; one method per reading."""
+    name = ''
+
+    def memory(self) -> Dict[str, Any]:
+        """get_memory_info()'s dict: total, available, used, percent, swap_* bytes."""
+        raise NotImplementedError
+
+    def cpu_times(self) -> List[Dict[str, float]]:
+        """Cumulative {busy, total} time per core; get_cpu_percents() diffs two readings."""
+        raise NotImplementedError
+
+    def process_rows(self) -> List[Tuple[int, str, float, int]]:
+        """(pid, name, cpu_percent, memory bytes) per process, unsorted."""
+        raise NotImplementedError
+
+    def process_start_times(self) -> Dict[int, float]:
+        """Start time per pid, telling a reused pid's new process apart; pids missing are unknown."""
+        raise NotImplementedError
+
+    def network(self) -> Dict[str, int]:
+        """Cumulative bytes_recv and bytes_sent over all interfaces."""
+        raise NotImplementedError
+
+    def interface_counters(self) -> Dict[str, Dict[str, int]]:
+        """Cumulative bytes_recv and bytes_sent per interface; empty where only the totals are known."""
+        raise NotImplementedError
+
+    def mounts(self) -> List[Dict[str, Any]]:
+        """Mounted filesystems: device, mountpoint, fstype, options."""
+        raise NotImplementedError
+
+    def disk_usage(self, mountpoint: str) -> Dict[str, Any]:
+        """Space and inode figures for a mount point; OSError if it can't be read."""
+        raise NotImplementedError
+
+    def disk_io(self) -> Dict[str, Dict[str, Any]]:
+        """Cumulative I/O counters per disk with is_partition and mount points."""
+        raise NotImplementedError
+
+
+class LiveBackend(Backend):
+    """The host, through the platform readers in pulse.direct_os and pulse.disks."""
+    name = 'live'
+
+    def memory(self) -> Dict[str, Any]:
+        from pulse import direct_os
+
+        return direct_os.read_memory_info()
+
+    def cpu_times(self) -> List[Dict[str, float]]:
+        from pulse import direct_os
+
+        return direct_os.read_cpu_times()
+
+    def process_rows(self) -> List[Tuple[int, str, float, int]]:
+        from pulse import direct_os
+
+        return direct_os.read_process_rows()
+
+    def process_start_times(self) -> Dict[int, float]:
+        from pulse import direct_os
+
+        return direct_os.read_process_start_times()
+
+    def network(self) -> Dict[str, int]:
+        from pulse import direct_os
+
+        return direct_os.read_network_stats()
+
+    def interface_counters(self) -> Dict[str, Dict[str, int]]:
+        from pulse import netstat
+
+        return netstat.read_interface_counters()
+
+    def mounts(self) -> List[Dict[str, Any]]:
+        from pulse import disks
+
+        return disks.read_mounts()
+
+    def disk_usage(self, mountpoint: str) -> Dict[str, Any]:
+        from pulse import disks
+
+        return disks.read_disk_usage(mountpoint)
+
+    def disk_io(self) -> Dict[str, Dict[str, Any]]:
+        from pulse import disks
+
+        return disks.read_disk_io()
+
+
+def _memory(payload: Any) -> Dict[str, Any]:
+    if not isinstance(payload, dict):
+        raise ValueError('memory payload must be a dict like get_memory_info() returns')
+    return dict(payload)
+
+
+def _cpu(payload: Any) -> List[Dict[str, float]]:
+    if not isinstance(payload, (list, tuple)) or not all(
+            isinstance(t, dict) and {'busy', 'total'} <= set(t) for t in payload):
+        raise ValueError('cpu payload must be a list of {"busy", "total"} cumulative times per core')
+    return [{'busy': t['busy'], 'total': t['total']} for t in payload]
+
+
+def _processes(payload: Any) -> List[Tuple[int, str, float, int]]:
+    fields = ('pid', 'name', 'cpu_percent', 'memory_info')
+    if not isinstance(payload, (list, tuple)) or not all(
+            isinstance(p, dict) and set(fields) <= set(p) for p in payload):
+        raise ValueError('processes payload must be a list of {"pid", "name", "cpu_percent", '
+                         '"memory_info"} like get_process_list() returns')
+    return [tuple(p[field] for field in fields) for p in payload]
+
+
+def _network(payload: Any) -> Dict[str, int]:
+    if not isinstance(payload, dict) or not {'bytes_recv', 'bytes_sent'} <= set(payload):
+        raise ValueError('network payload must be a dict with cumulative "bytes_recv" and "bytes_sent"')
+    return dict(payload)
+
+
+def _disks(payload: Any) -> List[Dict[str, Any]]:
+    from pulse import disks
+
+    if not isinstance(payload, (list, tuple)) or not all(
+            isinstance(d, dict) and set(_DISK_FIELDS) <= set(d) for d in payload):
+        raise ValueError(f'disks payload must be a list of dicts with {", ".join(_DISK_FIELDS)}')
+    return [{
+        'device': d.get('device', d['mountpoint']),
+        'fstype': d.get('fstype', 'ext4'),
+        'options': d.get('options', 'rw'),
+        'percent': disks.disk_percent(d['used'], d['available']),
+        **disks.inode_usage(None, None),
+        **d,
+    } for d in payload]
+
+
+def _disk_io(payload: Any) -> Dict[str, Dict[str, Any]]:
+    if not isinstance(payload, dict) or not all(isinstance(c, dict) for c in payload.values()):
+        raise ValueError('disk_io payload must be a dict of counters per disk name')
+    return {name: {'is_partition': False, 'mount_point': None, 'mount_points': [], **counters}
+            for name, counters in payload.items()}
+
+
+_PARSERS = {
+    'cpu': _cpu, 'memory': _memory, 'processes': _processes, 'network': _network,
+    'disks': _disks, 'disk_io': _disk_io,
+}
+
+
+class MockBackend(Backend):
+    """Readings injected with set_data(); a section never set reads as empty or zero."""
+    name = 'mock'
+
+    def __init__(self) -> None:
+        self._lock = threading.Lock()
+        self._data: Dict[str, Any] = {}
+
+    def set_data(self, section: str, payload: Any) -> None:
+        validation.one_of('section', section, MOCK_SECTIONS)
+        parsed = _PARSERS[section](copy.deepcopy(payload))
+        with self._lock:
+            self._data[section] = parsed
+
+    def clear(self, section: Optional[str] = None) -> None:
+        with self._lock:
+            if section is None:
+                self._data.clear()
+            else:
+                self._data.pop(section, None)
+
+    def _get(self, section: str, default: Any) -> Any:
+        # Copied out so a caller editing a result can't change the next reading
+        with self._lock:
+            return copy.deepcopy(self._data.get(section, default))
+
+    def memory(self) -> Dict[str, Any]:
+        return self._get('memory', {'total': 0, 'available': 0, 'used': 0, 'percent': 0.0,
+                                    'swap_total': 0, 'swap_used': 0})
+
+    def cpu_times(self) -> List[Dict[str, float]]:
+        return self._get('cpu', [])
+
+    def process_rows(self) -> List[Tuple[int, str, float, int]]:
+        return self._get('processes', [])
+
+    def process_start_times(self) -> Dict[int, float]:
+        # Mocked processes carry no start time
+        return {}
+
+    def network(self) -> Dict[str, int]:
+        return self._get('network', {'bytes_recv': 0, 'bytes_sent': 0})
+
+    def interface_counters(self) -> Dict[str, Dict[str, int]]:
+        # The mocked network section holds totals only
+        return {}
+
+    def mounts(self) -> List[Dict[str, Any]]:
+        return [{key: disk[key] for key in ('device', 'mountpoint', 'fstype', 'options')}
+                for disk in self._get('disks', [])]
+
+    def disk_usage(self, mountpoint: str) -> Dict[str, Any]:
+        from pulse import disks
+
+        # Like statvfs, any path answers for the mocked mount holding it
+        disk = disks.mount_for_path(mountpoint, self._get('disks', []))
+        if disk is None:
+            raise FileNotFoundError(2, 'No mocked disk holds this path', mountpoint)
+        return {key: value for key, value in disk.items()
+                if key not in ('device', 'mountpoint', 'fstype', 'options')}
+
+    def disk_io(self) -> Dict[str, Dict[str, Any]]:
+        return self._get('disk_io', {})
+
+
+LIVE = LiveBackend()
+MOCK = MockBackend()
+
+_BY_NAME = {backend.name: backend for backend in (LIVE, MOCK)}
+_active: Backend = LIVE
+
+
+def active() -> Backend:
+    """The backend the getters read from."""
+    return _active
+
+
+def set_backend(name: str) -> None:
+    """Switch every core getter to the "live" host readings or the "mock" data.
+
+    The default baselines and cached results are dropped so no rate is
+    computed across two backends; Monitors created before the switch should
+    be recreated. Injected mock data is kept across switches. Collectors
+    running in the isolation worker (spawn_worker()) read its host.
+    """
+    from pulse.state import STATE
+
+    global _active
+    validation.one_of('name', name, BACKENDS)
+    _active = _BY_NAME[name]
+    STATE.reset()
+
+
+def get_backend() -> str:
+    """The active backend's name: "live" or "mock"."""
+    return _active.name
+
+
+def mock_set_data(section: str, payload: Any) -> None:
+    """Inject the mock backend's reading for one of MOCK_SECTIONS.
+
+    payload has the shape the matching live reading has: "memory" and
+    "network" get_memory_info()'s and get_network_stats()'s dicts (network
+    counters cumulative), "processes" get_process_list()'s entries, "cpu" a
+    list of cumulative {"busy", "total"} times per core, "disks" dicts with
+    mountpoint, total, used, free, and available (device, fstype, options,
+    percent, and inode figures optional), and "disk_io" get_disk_io()'s
+    counters per disk. Each call replaces the section's previous payload, so
+    successive counters drive the rate getters. Raises ValueError for an
+    unknown section or a payload of the wrong shape.
+    """
+    MOCK.set_data(section, payload)
+
+
+def mock_clear(section: Optional[str] = None) -> None:
+    """Forget injected mock data: one section, or every section when None."""
+    if section is not None:
+        validation.one_of('section', section, MOCK_SECTIONS)
+    MOCK.clear(section)
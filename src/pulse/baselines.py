@@ -0,0 +1,168 @@
+"""
+Pulse Baselines
+Counter baselines saved to a file and restored after a restart.
+
+Rates are deltas between two readings, so a freshly started agent reports
+zero (or nothing) until its second call. save_state() writes the active
+state's previous readings and their timestamps; load_state() puts them back,
+and the first call after the restart computes its rate against them. That
+only holds while the counters and the monotonic clock kept running, which
+is checked against the boot time saved with them.
+
+A state file is MAGIC, a one-byte VERSION, then the baselines as UTF-8 JSON.
+Tuples and dicts with non-string keys are tagged so they load back exactly.
+"""
+import contextlib
+import json
+import os
+import time
+from typing import Any, Dict, Optional
+
+from pulse import log, validation
+from pulse.state import SUBSYSTEMS, active_state
+
+MAGIC = b'PULSESTATE'
+VERSION = 1
+
+# SysState attributes written by save_state(); the result cache is left out
+PERSISTED = (
+    'cpu_times', 'cpu_time', 'cpu_percents', 'udp_counters', 'disk_io', 'disk_io_time',
+    'process_io', 'process_io_time', 'sched_counters', 'rdt_counters', 'thermal_throttle',
+    'cgroup_cpu', 'namespace_network',
+)
+
+# Boot times the platform derives from uptime can wobble by a second
+BOOT_TIME_SLACK_SECS = 2.0
+
+
+def encode_value(value: Any) -> Any:
+    """value as plain JSON data, with tuples and non-string-keyed dicts tagged."""
+    if isinstance(value, tuple):
+        return {'__tuple__': [encode_value(v) for v in value]}
+    if isinstance(value, list):
+        return [encode_value(v) for v in value]
+    if isinstance(value, dict):
+        if all(isinstance(k, str) and not k.startswith('__') for k in value):
+            return {k: encode_value(v) for k, v in value.items()}
+        return {'__items__': [[encode_value(k), encode_value(v)] for k, v in value.items()]}
+    return value
+
+
+def decode_value(value: Any) -> Any:
+    """The inverse of encode_value()."""
+    if isinstance(value, list):
+        return [decode_value(v) for v in value]
+    if isinstance(value, dict):
+        if set(value) == {'__tuple__'}:
+            return tuple(decode_value(v) for v in value['__tuple__'])
+        if set(value) == {'__items__'}:
+            return {decode_value(k): decode_value(v) for k, v in value['__items__']}
+        return {k: decode_value(v) for k, v in value.items()}
+    return value
+
+
+def _boot_time() -> Optional[float]:
+    from pulse import system
+
+    return system.get_system_info()['boot_time']
+
+
+def dump_state(baselines: Dict[str, Any], boot_time: Optional[float], monotonic: float) -> bytes:
+    """A state file's bytes for baselines taken at monotonic time, since boot_time."""
+    body = {'boot_time': boot_time, 'monotonic': monotonic, 'saved_at': time.time(),
+            'baselines': encode_value(baselines)}
+    return MAGIC + bytes([VERSION]) + json.dumps(body, separators=(',', ':')).encode('utf-8')
+
+
+def parse_state(data: bytes) -> Dict[str, Any]:
+    """{boot_time, monotonic, saved_at, baselines} from a state file's bytes.
+
+    Raises ValueError for a missing header, another version, or a body that
+    doesn't decode.
+    """
+    if data[:len(MAGIC)] != MAGIC or len(data) <= len(MAGIC):
+        raise ValueError('not a Pulse state file (bad header)')
+    version = data[len(MAGIC)]
+    if version != VERSION:
+        raise ValueError(f'state file version {version}, expected {VERSION}')
+    body = json.loads(data[len(MAGIC) + 1:].decode('utf-8'))
+    if not isinstance(body, dict) or not isinstance(body.get('baselines'), dict):
+        raise ValueError('state file body is not a baseline set')
+    body['baselines'] = decode_value(body['baselines'])
+    return body
+
+
+def usable(body: Dict[str, Any], boot_time: Optional[float], monotonic: float) -> Optional[str]:
+    """Why saved baselines can't be used now, or None if they can.
+
+    The counters restart from zero and the monotonic clock with them after a
+    reboot, so a different (or unknown) boot time rules them out, as does a
+    monotonic clock now behind the time they were saved at.
+    """
+    saved = body.get('boot_time')
+    if saved is None or boot_time is None:
+        return 'boot time unknown'
+    if abs(saved - boot_time) > BOOT_TIME_SLACK_SECS:
+        return 'the system rebooted since they were saved'
+    if not isinstance(body.get('monotonic'), (int, float)) or monotonic < body['monotonic']:
+        return 'the monotonic clock went backwards'
+    return None
+
+
+@contextlib.contextmanager
+def _all_locks(state: Any) -> Any:
+    with contextlib.ExitStack() as stack:
+        for subsystem in SUBSYSTEMS:
+            stack.enter_context(state.lock(subsystem))
+        yield
+
+
+def save_state(path: str) -> None:
+    """Write the active state's counter baselines and their timestamps to path.
+
+    The file is replaced atomically, so an agent killed mid-save leaves the
+    previous one. Raises OSError if it can't be written.
+    """
+    validation.not_empty('path', path)
+    state = active_state()
+    with _all_locks(state):
+        baselines = {name: getattr(state, name) for name in PERSISTED}
+        data = dump_state(baselines, _boot_time(), time.monotonic())
+    partial = f'{path}.tmp'
+    with open(partial, 'wb') as f:
+        f.write(data)
+    os.replace(partial, path)
+
+
+def load_state(path: str) -> bool:
+    """Restore baselines saved by save_state(); True if they were applied.
+
+    Never raises for the file's sake: a missing file is skipped quietly, and
+    a corrupt file, one from another version, or one saved before the last
+    reboot is ignored with a warning on the "pulse_core" logger. Applied
+    baselines replace the active state's, and cached results are dropped.
+    """
+    validation.not_empty('path', path)
+    try:
+        with open(path, 'rb') as f:
+            data = f.read()
+    except OSError as e:
+        log.read_failed(path, e)
+        return False
+    try:
+        body = parse_state(data)
+    except (ValueError, TypeError, KeyError, IndexError) as e:
+        log.state_discarded(path, str(e))
+        return False
+    reason = usable(body, _boot_time(), time.monotonic())
+    if reason is not None:
+        log.state_discarded(path, reason)
+        return False
+
+    state = active_state()
+    with _all_locks(state):
+        for name in PERSISTED:
+            if name in body['baselines']:
+                setattr(state, name, body['baselines'][name])
+        state.cache = {}
+    return True
@@ -0,0 +1,128 @@
+"""
+Pulse Callbacks
+Python callables the background sampler invokes as it collects.
+
+Callbacks run on the sampler thread, one at a time, in registration order.
+An exception from a callback is recorded for get_callback_errors() and the
+sampler carries on. Nothing is invoked once the interpreter is finalizing.
+"""
+import sys
+import threading
+import traceback
+from collections import deque
+from typing import Any, Callable, Deque, Dict, List, Optional
+
+from pulse import alerts, clock, validation
+
+# Event name -> what the callback receives
+EVENTS = {
+    'sample': 'the snapshot dict just recorded',
+    'alert_fired': 'an alert dict from check_alerts()',
+    'alert_resolved': 'an alert dict from check_alerts()',
+    'process_started': 'the process dict from the sample it first appeared in',
+    'process_exited': 'the process dict from the last sample it appeared in',
+}
+
+# Oldest errors are dropped once this many are waiting to be drained
+MAX_ERRORS = 100
+
+_callbacks: Dict[str, List[Callable[[Any], Any]]] = {event: [] for event in EVENTS}
+_callbacks_lock = threading.Lock()
+_errors: Deque[Dict[str, Any]] = deque(maxlen=MAX_ERRORS)
+
+
+def _check_event(event: str) -> None:
+    validation.one_of('event', event, EVENTS)
+
+
+def register_callback(event: str, callback: Callable[[Any], Any]) -> None:
+    """Call callback(payload) on the sampler thread whenever event happens.
+
+    Events fire only while start_sampler() runs. Process events need the
+    "processes" section and compare consecutive samples, so with a process
+    limit set a process leaving the top-N counts as exited. Alert events
+    consume the sampler history on behalf of check_alerts(), which then
+    reports only what the callbacks haven't seen.
+    """
+    _check_event(event)
+    if not callable(callback):
+        raise TypeError(f'callback must be callable, got {type(callback).__name__}')
+    with _callbacks_lock:
+        _callbacks[event].append(callback)
+
+
+def unregister_callback(event: str, callback: Callable[[Any], Any]) -> None:
+    """Stop calling callback for event; a no-op if it isn't registered."""
+    _check_event(event)
+    with _callbacks_lock:
+        if callback in _callbacks[event]:
+            _callbacks[event].remove(callback)
+
+
+def clear_callbacks() -> None:
+    """Unregister every callback and drop any undrained errors."""
+    with _callbacks_lock:
+        for listeners in _callbacks.values():
+            listeners.clear()
+        _errors.clear()
+
+
+def get_callback_errors() -> List[Dict[str, Any]]:
+    """Drain the exceptions callbacks have raised since the previous call."""
+    with _callbacks_lock:
+        errors = list(_errors)
+        _errors.clear()
+    return errors
+
+
+def _listeners(event: str) -> List[Callable[[Any], Any]]:
+    with _callbacks_lock:
+        return list(_callbacks[event])
+
+
+def dispatch(event: str, payload: Any) -> None:
+    """Invoke event's callbacks with payload, recording rather than raising their errors."""
+    for callback in _listeners(event):
+        if sys.is_finalizing():
+            return
+        try:
+            callback(payload)
+        except Exception as e:
+            with _callbacks_lock:
+                _errors.append({
+                    'event': event,
+                    'callback': getattr(callback, '__qualname__', repr(callback)),
+                    'error': f'{type(e).__name__}: {e}',
+                    'traceback': traceback.format_exc(),
+                    'timestamp': clock.now(),
+                })
+
+
+def _processes_by_pid(sample: Optional[Dict[str, Any]]) -> Optional[Dict[int, Dict[str, Any]]]:
+    if sample is None or 'processes' not in sample:
+        return None
+    return {p['pid']: p for p in sample['processes']}
+
+
+def on_sample(sample: Dict[str, Any], previous: Optional[Dict[str, Any]]) -> None:
+    """Fire every event a newly recorded sample gives rise to."""
+    dispatch('sample', sample)
+
+    if _listeners('alert_fired') or _listeners('alert_resolved'):
+        changes = alerts.check_alerts()
+        for alert in changes['fired']:
+            dispatch('alert_fired', alert)
+        for alert in changes['resolved']:
+            dispatch('alert_resolved', alert)
+
+    if _listeners('process_started') or _listeners('process_exited'):
+        before, after = _processes_by_pid(previous), _processes_by_pid(sample)
+        # The first sample with processes is only a baseline
+        if before is None or after is None:
+            return
+        for pid, process in after.items():
+            if pid not in before:
+                dispatch('process_started', process)
+        for pid, process in before.items():
+            if pid not in after:
+                dispatch('process_exited', process)
@@ -0,0 +1,241 @@
+"""
+Pulse Capabilities
+Which optional features work on this host, and the check their getters share.
+
+Each feature has one probe: the platform it needs plus a cheap runtime test
+(a sysfs directory, a helper binary on PATH, a privilege). get_capabilities()
+reports the probes, and every getter in FEATURES outside EMPTY_WHEN_MISSING
+is wrapped with requires() so it raises Unsupported exactly when its
+feature reports False, saying what the feature needs as REQUIREMENTS words
+it. Those in EMPTY_WHEN_MISSING return an empty result instead.
+"""
+import functools
+import os
+import shutil
+from typing import Any, Callable, Dict, Tuple, Union
+
+from pulse.direct_os import FREEBSD, LINUX, MACOS, WINDOWS
+from pulse.errors import Unsupported
+
+
+def _temperatures() -> bool:
+    if LINUX:
+        from pulse import sensors
+
+        return os.path.isdir(sensors.SYS_CLASS_THERMAL) or os.path.isdir(sensors.SYS_CLASS_HWMON)
+    if FREEBSD:
+        from pulse import system
+
+        # psutil reads this, and it only exists once coretemp(4) or amdtemp(4) is loaded
+        return system.sysctl(b'dev.cpu.0.temperature') is not None
+    import psutil
+
+    return hasattr(psutil, 'sensors_temperatures')
+
+
+def _fans() -> bool:
+    if LINUX:
+        from pulse import sensors
+
+        return os.path.isdir(sensors.SYS_CLASS_HWMON)
+    return MACOS
+
+
+def _battery() -> bool:
+    if LINUX:
+        from pulse import power
+
+        return os.path.isdir(power.SYS_CLASS_POWER_SUPPLY)
+    import psutil
+
+    return hasattr(psutil, 'sensors_battery')
+
+
+def _backlight() -> bool:
+    if LINUX:
+        from pulse import power
+
+        return os.path.isdir(power.SYS_CLASS_BACKLIGHT)
+    return WINDOWS or MACOS
+
+
+def _load_average() -> bool:
+    return hasattr(os, 'getloadavg')
+
+
+def _process_io() -> bool:
+    if LINUX:
+        return os.path.exists('/proc/self/io')
+    import psutil
+
+    # psutil has no per-process I/O counters on macOS
+    return hasattr(psutil.Process, 'io_counters')
+
+
+def _connections() -> bool:
+    if LINUX:
+        from pulse import netstat
+
+        return os.path.isdir(netstat.PROC_NET)
+    return True
+
+
+def _udp_stats() -> bool:
+    from pulse import netstat
+
+    return LINUX and os.path.exists(f'{netstat.PROC_NET}/snmp')
+
+
+def _gpu() -> bool:
+    from pulse import gpu
+
+    return (LINUX and os.path.isdir(gpu.SYS_CLASS_DRM)) or gpu.nvml_available()
+
+
+def _audio() -> bool:
+    return LINUX and (shutil.which('pactl') is not None or shutil.which('amixer') is not None)
+
+
+def _interface_topology() -> bool:
+    from pulse import interfaces
+
+    return LINUX and os.path.isdir(interfaces.SYS_CLASS_NET)
+
+
+def _exit_accounting() -> bool:
+    if not LINUX:
+        return False
+    from pulse import exit_accounting
+
+    return exit_accounting.has_net_admin() and exit_accounting.taskstats_family() is not None
+
+
+def _services() -> bool:
+    from pulse import services
+
+    return LINUX and os.path.isdir(services.RUN_SYSTEMD_SYSTEM) and shutil.which('systemctl') is not None
+
+
+def _windows_services() -> bool:
+    return WINDOWS
+
+
+def _windows_process_info() -> bool:
+    return WINDOWS
+
+
+def _core_types() -> bool:
+    if not MACOS:
+        return False
+    from pulse import macos
+
+    return macos.efficiency_cpu_count() is not None
+
+
+def _entropy() -> bool:
+    from pulse import entropy
+
+    return LINUX and os.path.isdir(entropy.PROC_SYS_KERNEL_RANDOM)
+
+
+def _rdt() -> bool:
+    from pulse import resctrl
+
+    # info/L3_MON only appears when the CPU can monitor, not just allocate
+    return LINUX and os.path.isdir(os.path.join(resctrl.SYS_FS_RESCTRL, 'info', 'L3_MON'))
+
+
+def _namespaces() -> bool:
+    from pulse import processes
+
+    return LINUX and os.path.isdir(f'{processes.PROC}/self/ns')
+
+
+def _containers() -> bool:
+    from pulse import cgroups
+
+    return LINUX and os.path.isdir(cgroups.SYS_FS_CGROUP)
+
+
+# Feature -> (the pulse.core getter that needs it, its probe)
+FEATURES: Dict[str, Tuple[str, Callable[[], bool]]] = {
+    'temperatures': ('get_thermal_status', _temperatures),
+    'fans': ('get_fans', _fans),
+    'battery': ('get_battery_info', _battery),
+    'backlight': ('get_brightness', _backlight),
+    'load_average': ('get_load_average', _load_average),
+    'process_io': ('get_io_hogs', _process_io),
+    'connections': ('get_connections', _connections),
+    'udp_stats': ('get_udp_stats', _udp_stats),
+    'interface_topology': ('get_interface_topology', _interface_topology),
+    'gpu': ('get_gpu_info', _gpu),
+    'audio': ('get_audio_state', _audio),
+    'services': ('list_services', _services),
+    'windows_services': ('list_windows_services', _windows_services),
+    'windows_process_info': ('get_windows_process_info', _windows_process_info),
+    'core_types': ('get_cpu_core_type_usage', _core_types),
+    'entropy': ('get_entropy', _entropy),
+    'rdt': ('get_rdt_groups', _rdt),
+    'namespaces': ('group_processes_by_namespace', _namespaces),
+    'containers': ('list_containers', _containers),
+    'exit_accounting': ('get_exited_processes', _exit_accounting),
+}
+
+# Features whose getter returns an empty list where they're missing rather than raising
+EMPTY_WHEN_MISSING = ('rdt',)
+
+# Feature -> what its probe checks for, as get_capabilities(explain=True) reports it
+REQUIREMENTS: Dict[str, str] = {
+    'temperatures': 'Linux thermal or hwmon sysfs, FreeBSD coretemp(4) or amdtemp(4), or psutil sensors',
+    'fans': 'Linux hwmon sysfs or macOS',
+    'battery': 'Linux power_supply sysfs or psutil sensors',
+    'backlight': 'Linux backlight sysfs, Windows, or macOS',
+    'load_average': 'os.getloadavg()',
+    'process_io': '/proc/<pid>/io on Linux, or psutil I/O counters (not on macOS)',
+    'connections': '/proc/net on Linux',
+    'udp_stats': '/proc/net/snmp on Linux',
+    'interface_topology': '/sys/class/net on Linux',
+    'gpu': 'Linux DRM sysfs or the NVIDIA NVML library',
+    'audio': 'pactl or amixer on Linux',
+    'services': 'systemd and systemctl on Linux',
+    'windows_services': 'Windows',
+    'windows_process_info': 'Windows',
+    'core_types': 'macOS with efficiency cores',
+    'entropy': '/proc/sys/kernel/random on Linux',
+    'rdt': 'resctrl mounted with L3 monitoring on Linux',
+    'namespaces': '/proc/<pid>/ns on Linux',
+    'containers': 'cgroup sysfs on Linux',
+    'exit_accounting': 'CAP_NET_ADMIN and a kernel with taskstats (CONFIG_TASKSTATS) on Linux',
+}
+
+
+def supported(feature: str) -> bool:
+    """Run feature's probe; unknown feature names raise KeyError."""
+    return FEATURES[feature][1]()
+
+
+def get_capabilities(explain: bool = False) -> Dict[str, Union[bool, Dict[str, Any]]]:
+    """Map every feature in FEATURES to whether it works on this host.
+
+    A True feature's getter may still come back empty (a desktop with a
+    power_supply class but no battery); a False one's raises Unsupported.
+    explain maps each feature to {supported, requires} instead, requires
+    saying what it needs (exit_accounting: CAP_NET_ADMIN and taskstats).
+    """
+    if explain:
+        return {feature: {'supported': supported(feature), 'requires': REQUIREMENTS[feature]}
+                for feature in FEATURES}
+    return {feature: supported(feature) for feature in FEATURES}
+
+
+def requires(feature: str) -> Callable[[Callable[..., Any]], Callable[..., Any]]:
+    """Make the decorated getter raise Unsupported when feature's probe fails."""
+    def decorate(function: Callable[..., Any]) -> Callable[..., Any]:
+        @functools.wraps(function)
+        def checked(*args: Any, **kwargs: Any) -> Any:
+            if not supported(feature):
+                raise Unsupported(f'{function.__name__} needs "{feature}" '
+                                  f'({REQUIREMENTS[feature]}), which this host lacks')
+            return function(*args, **kwargs)
+        return checked
+    return decorate
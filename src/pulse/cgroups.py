@@ -0,0 +1,87 @@
+"""
+Pulse Cgroups
+cgroup v2 accounting shared by the service and container collectors.
+"""
+import os
+import time
+from typing import Dict, List, Optional, Tuple
+
+from pulse import log
+from pulse.collect import counter_delta
+from pulse.state import active_state
+
+SYS_FS_CGROUP = '/sys/fs/cgroup'
+
+
+def parse_flat_keyed(text: str) -> Dict[str, int]:
+    """Parse a flat-keyed cgroup file such as cpu.stat ("usage_usec 1234")."""
+    values = {}
+    for line in text.splitlines():
+        parts = line.split()
+        if len(parts) == 2:
+            try:
+                values[parts[0]] = int(parts[1])
+            except ValueError:
+                continue
+    return values
+
+
+def parse_limit(text: str) -> Optional[int]:
+    """A memory.max-style value; "max" (unlimited) is None."""
+    value = text.strip()
+    return None if value == 'max' or not value else int(value)
+
+
+def _read(path: str) -> Optional[str]:
+    try:
+        with open(path, 'r') as f:
+            return f.read()
+    except OSError as e:
+        log.read_failed(path, e)
+        return None
+
+
+def cgroup_dir(cgroup_path: str) -> str:
+    """Filesystem directory for a cgroup path like "/system.slice/ssh.service"."""
+    return os.path.join(SYS_FS_CGROUP, cgroup_path.lstrip('/'))
+
+
+def read_usage(directory: str) -> Dict[str, Optional[int]]:
+    """CPU time and memory of one cgroup directory; missing controllers are None."""
+    cpu = parse_flat_keyed(_read(os.path.join(directory, 'cpu.stat')) or '')
+    current = _read(os.path.join(directory, 'memory.current'))
+    maximum = _read(os.path.join(directory, 'memory.max'))
+    try:
+        memory_current = int(current) if current is not None else None
+        memory_max = parse_limit(maximum) if maximum is not None else None
+    except ValueError:
+        memory_current = memory_max = None
+    return {
+        'cpu_usage_usec': cpu.get('usage_usec'),
+        'memory_current': memory_current,
+        'memory_max': memory_max,
+    }
+
+
+def cpu_percent(key: str, usage_usec: Optional[int]) -> Optional[float]:
+    """CPU percent since the last call for key, from cumulative usage_usec.
+
+    Like per-process percentages this is relative to one core, so a busy
+    multi-threaded cgroup can exceed 100. The first call for a key is None.
+    """
+    if usage_usec is None:
+        return None
+    state = active_state()
+    with state.lock('cgroups'):
+        now = time.monotonic()
+        previous: Optional[Tuple[float, int]] = state.cgroup_cpu.get(key)
+        state.cgroup_cpu[key] = (now, usage_usec)
+    if previous is None or now <= previous[0]:
+        return None
+    return counter_delta(usage_usec, previous[1]) / ((now - previous[0]) * 1e6) * 100
+
+
+def read_pids(directory: str) -> List[int]:
+    """Member pids of one cgroup (cgroup.procs)."""
+    text = _read(os.path.join(directory, 'cgroup.procs')) or ''
+    return [int(p) for p in text.split() if p.isdigit()]
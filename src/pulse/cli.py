@@ -0,0 +1,125 @@
+"""
+Pulse CLI
+One-shot and streaming output from the collectors, for scripts and SSH sessions.
+
+    pulse-cli snapshot --json
+    pulse-cli top --sort cpu --limit 20
+    pulse-cli watch --interval 2
+
+JSON output is exactly what pulse.core returns (snapshot(), get_process_list()),
+field names included, so tooling written against the Python API reads it
+unchanged. Nothing here imports the TUI.
+"""
+import argparse
+import json
+import sys
+import time
+from typing import Any, Dict, List, Optional, Sequence
+
+from pulse import core, direct_os
+
+
+def _compact(data: Any) -> str:
+    return json.dumps(data, separators=(',', ':'))
+
+
+def _sections(value: str) -> List[str]:
+    return [section for section in value.split(',') if section]
+
+
+def _positive_float(value: str) -> float:
+    number = float(value)
+    if not number > 0:
+        raise argparse.ArgumentTypeError(f'must be > 0, got {value}')
+    return number
+
+
+def _positive_int(value: str) -> int:
+    number = int(value)
+    if number < 1:
+        raise argparse.ArgumentTypeError(f'must be >= 1, got {value}')
+    return number
+
+
+def _prime() -> None:
+    # CPU percents are deltas, so the first reading needs a baseline a moment older
+    core.init(['cpu', 'processes'])
+    time.sleep(0.1)
+
+
+def _snapshot(args: argparse.Namespace) -> int:
+    _prime()
+    snap = core.snapshot(include=args.include)
+    print(_compact(snap) if args.json else json.dumps(snap, indent=2))
+    return 0
+
+
+def format_table(processes: Sequence[Dict[str, Any]]) -> str:
+    """get_process_list() rows as a fixed-width PID/NAME/CPU%/MEMORY table."""
+    lines = [f"{'PID':>7}  {'NAME':<24} {'CPU%':>6} {'MEMORY':>12}"]
+    for proc in processes:
+        lines.append(f"{proc['pid']:>7}  {proc['name'][:24]:<24} "
+                     f"{proc['cpu_percent']:>6.1f} {proc['memory_info']:>12}")
+    return '\n'.join(lines)
+
+
+def _top(args: argparse.Namespace) -> int:
+    _prime()
+    processes = core.get_process_list(sort_by=args.sort, limit=args.limit)
+    print(_compact(processes) if args.json else format_table(processes))
+    return 0
+
+
+def _watch(args: argparse.Namespace) -> int:
+    core.init(['cpu', 'processes'])
+    emitted = 0
+    deadline = time.monotonic()
+    while args.count is None or emitted < args.count:
+        deadline += args.interval
+        time.sleep(max(0.0, deadline - time.monotonic()))
+        print(_compact(core.snapshot(include=args.include)), flush=True)
+        emitted += 1
+    return 0
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser(prog='pulse-cli', description=__doc__.split('\n')[2])
+    commands = parser.add_subparsers(dest='command', required=True)
+    sections = ', '.join(core.SNAPSHOT_SECTIONS)
+
+    snapshot = commands.add_parser('snapshot', help='print one snapshot()')
+    snapshot.add_argument('--json', action='store_true', help='one compact line instead of indented JSON')
+    snapshot.add_argument('--include', type=_sections, help=f'comma-separated sections of {sections}')
+    snapshot.set_defaults(run=_snapshot)
+
+    top = commands.add_parser('top', help='print the busiest processes once')
+    top.add_argument('--sort', choices=direct_os.SORT_KEYS, default='cpu')
+    top.add_argument('--limit', type=_positive_int, default=20)
+    top.add_argument('--json', action='store_true', help='the get_process_list() rows as JSON')
+    top.set_defaults(run=_top)
+
+    watch = commands.add_parser('watch', help='print a snapshot() as JSON lines every interval')
+    watch.add_argument('--interval', type=_positive_float, default=2.0, help='seconds between lines')
+    watch.add_argument('--count', type=_positive_int, help='stop after this many lines')
+    watch.add_argument('--include', type=_sections, help=f'comma-separated sections of {sections}')
+    watch.set_defaults(run=_watch)
+    return parser
+
+
+def main(argv: Optional[Sequence[str]] = None) -> int:
+    parser = build_parser()
+    args = parser.parse_args(argv)
+    try:
+        return args.run(args)
+    except ValueError as e:
+        parser.error(str(e))
+    except KeyboardInterrupt:
+        return 130
+    except BrokenPipeError:
+        # `pulse-cli watch | head` closed the pipe; that's a normal way to stop
+        sys.stderr.close()
+        return 0
+
+
+if __name__ == '__main__':
+    sys.exit(main())
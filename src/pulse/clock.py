@@ -0,0 +1,295 @@
+"""
+Pulse Clock
+Time synchronization state, estimated error, frequency drift, and timezone,
+plus the clock behind the API's output timestamps.
+
+"synchronized" is True/False when the OS could say, and None when it
+couldn't; the other fields follow the same rule.
+"""
+import ctypes
+import ctypes.util
+import os
+import re
+import subprocess
+import time
+from typing import Any, Dict, Optional
+
+from pulse import validation
+from pulse.direct_os import LINUX, WINDOWS
+from pulse.errors import Unsupported
+
+ETC_LOCALTIME = '/etc/localtime'
+ETC_TIMEZONE = '/etc/timezone'
+ETC_ADJTIME = '/etc/adjtime'
+
+# adjtimex() return values (include/uapi/linux/timex.h)
+_CLOCK_STATES = {
+    0: 'ok',
+    1: 'insert_leap',
+    2: 'delete_leap',
+    3: 'leap_in_progress',
+    4: 'leap_occurred',
+    5: 'unsynchronized',
+}
+_TIME_ERROR = 5
+_STA_UNSYNC = 0x0040
+_STA_NANO = 0x2000
+
+# timex.freq is in ppm scaled by 2^16
+_FREQ_SCALE = 65536
+
+
+def clock_from_timex(state: int, status: int, offset: int, freq: int,
+                     maxerror: int, esterror: int) -> Dict[str, Any]:
+    """Summarize adjtimex() output; offset is ns when STA_NANO is set, else µs."""
+    offset_scale = 1e9 if status & _STA_NANO else 1e6
+    return {
+        'synchronized': state != _TIME_ERROR and not status & _STA_UNSYNC,
+        'clock_state': _CLOCK_STATES.get(state, 'unknown'),
+        'offset_secs': offset / offset_scale,
+        'estimated_error_secs': esterror / 1e6,
+        'max_error_secs': maxerror / 1e6,
+        'frequency_ppm': freq / _FREQ_SCALE,
+    }
+
+
+def zone_from_localtime_link(target: str) -> Optional[str]:
+    """IANA zone name from where /etc/localtime points (".../zoneinfo/Europe/Berlin")."""
+    _, sep, zone = target.partition('zoneinfo/')
+    if not sep:
+        return None
+    return zone or None
+
+
+def parse_adjtime(text: str) -> Optional[bool]:
+    """Whether /etc/adjtime's third line says the RTC keeps UTC ("UTC" or "LOCAL")."""
+    lines = text.splitlines()
+    if len(lines) < 3:
+        return None
+    mode = lines[2].strip()
+    if mode == 'UTC':
+        return True
+    if mode == 'LOCAL':
+        return False
+    return None
+
+
+def parse_w32tm_status(text: str) -> Dict[str, Any]:
+    """Sync state, source, and phase offset from `w32tm /query /status`."""
+    fields = {}
+    for line in text.splitlines():
+        key, sep, value = line.partition(':')
+        if sep:
+            fields[key.strip()] = value.strip()
+
+    # Leap indicator 3 means "not synchronized"; 0-2 are synced with a leap flag
+    leap = re.match(r'\d+', fields.get('Leap Indicator', ''))
+    offset = re.match(r'-?[\d.]+', fields.get('Phase Offset', ''))
+    source = fields.get('Source')
+    return {
+        'synchronized': int(leap.group()) != 3 if leap else None,
+        'source': source if source and source != 'Local CMOS Clock' else None,
+        'offset_secs': float(offset.group()) if offset else None,
+    }
+
+
+def _timezone() -> Dict[str, Any]:
+    local = time.localtime()
+    return {'timezone': local.tm_zone or None, 'utc_offset_secs': local.tm_gmtoff}
+
+
+_UNKNOWN_SYNC = {
+    'synchronized': None,
+    'clock_state': None,
+    'offset_secs': None,
+    'estimated_error_secs': None,
+    'max_error_secs': None,
+    'frequency_ppm': None,
+}
+
+
+if LINUX:
+    class _Timeval(ctypes.Structure):
+        _fields_ = [('tv_sec', ctypes.c_long), ('tv_usec', ctypes.c_long)]
+
+    class _Timex(ctypes.Structure):
+        _fields_ = [
+            ('modes', ctypes.c_uint),
+            ('offset', ctypes.c_long),
+            ('freq', ctypes.c_long),
+            ('maxerror', ctypes.c_long),
+            ('esterror', ctypes.c_long),
+            ('status', ctypes.c_int),
+            ('constant', ctypes.c_long),
+            ('precision', ctypes.c_long),
+            ('tolerance', ctypes.c_long),
+            ('time', _Timeval),
+            ('tick', ctypes.c_long),
+            ('ppsfreq', ctypes.c_long),
+            ('jitter', ctypes.c_long),
+            ('shift', ctypes.c_int),
+            ('stabil', ctypes.c_long),
+            ('jitcnt', ctypes.c_long),
+            ('calcnt', ctypes.c_long),
+            ('errcnt', ctypes.c_long),
+            ('stbcnt', ctypes.c_long),
+            ('tai', ctypes.c_int),
+            ('_reserved', ctypes.c_int * 11),
+        ]
+
+    def _adjtimex() -> Dict[str, Any]:
+        try:
+            libc = ctypes.CDLL(ctypes.util.find_library('c') or 'libc.so.6', use_errno=True)
+        except OSError:
+            return dict(_UNKNOWN_SYNC)
+        # modes = 0 only reads; it never needs CAP_SYS_TIME
+        timex = _Timex()
+        state = libc.adjtimex(ctypes.byref(timex))
+        if state < 0:
+            return dict(_UNKNOWN_SYNC)
+        return clock_from_timex(state, timex.status, timex.offset, timex.freq,
+                                timex.maxerror, timex.esterror)
+
+    def _zone_name() -> Optional[str]:
+        try:
+            zone = zone_from_localtime_link(os.readlink(ETC_LOCALTIME))
+        except OSError:
+            zone = None
+        if zone is None:
+            try:
+                with open(ETC_TIMEZONE, 'r') as f:
+                    zone = f.read().strip() or None
+            except OSError:
+                pass
+        return zone
+
+    def _rtc_in_utc() -> Optional[bool]:
+        try:
+            with open(ETC_ADJTIME, 'r') as f:
+                return parse_adjtime(f.read())
+        except FileNotFoundError:
+            # hwclock and timedatectl both treat a missing adjtime as UTC
+            return True
+        except OSError:
+            return None
+
+    def get_clock_status() -> Dict[str, Any]:
+        """Get NTP sync state, error estimates, and frequency drift from adjtimex.
+
+        The kernel only reports synchronized once a time daemon (chrony,
+        ntpd, systemd-timesyncd) has disciplined the clock. Also reports the
+        IANA timezone, UTC offset, and whether the RTC keeps UTC.
+        """
+        zone = _timezone()
+        zone['timezone'] = _zone_name() or zone['timezone']
+        return {**_adjtimex(), **zone, 'rtc_in_utc': _rtc_in_utc()}
+
+elif WINDOWS:
+    from ctypes import wintypes
+
+    def _w32tm_status() -> Dict[str, Any]:
+        try:
+            result = subprocess.run(['w32tm', '/query', '/status'], capture_output=True, text=True,
+                                    timeout=5, creationflags=0x08000000)  # CREATE_NO_WINDOW
+        except (OSError, subprocess.TimeoutExpired):
+            return {}
+        # Fails when the Windows Time service is stopped
+        return parse_w32tm_status(result.stdout) if result.returncode == 0 else {}
+
+    def _rtc_in_utc() -> Optional[bool]:
+        import winreg
+
+        try:
+            with winreg.OpenKey(winreg.HKEY_LOCAL_MACHINE,
+                                r'SYSTEM\CurrentControlSet\Control\TimeZoneInformation') as key:
+                return bool(winreg.QueryValueEx(key, 'RealTimeIsUniversal')[0])
+        except FileNotFoundError:
+            # Windows keeps the RTC in local time unless told otherwise
+            return False
+        except OSError:
+            return None
+
+    def get_clock_status() -> Dict[str, Any]:
+        """Get sync state and phase offset from w32tm, plus the clock's tick adjustment.
+
+        Fields w32tm can't supply (error estimates) are None, as is everything
+        it reports when the Windows Time service isn't running.
+        """
+        status = dict(_UNKNOWN_SYNC)
+        w32tm = _w32tm_status()
+        status['synchronized'] = w32tm.get('synchronized')
+        status['offset_secs'] = w32tm.get('offset_secs')
+
+        adjustment, increment, disabled = wintypes.DWORD(), wintypes.DWORD(), wintypes.BOOL()
+        if ctypes.windll.kernel32.GetSystemTimeAdjustment(
+                ctypes.byref(adjustment), ctypes.byref(increment), ctypes.byref(disabled)):
+            # An enabled adjustment slews each tick by (adjustment - increment) / increment
+            if not disabled.value and increment.value:
+                status['frequency_ppm'] = (adjustment.value - increment.value) / increment.value * 1e6
+        return {**status, 'source': w32tm.get('source'), **_timezone(), 'rtc_in_utc': _rtc_in_utc()}
+
+else:
+    def get_clock_status() -> Dict[str, Any]:
+        """Sync state isn't exposed on this platform; only the timezone is reported."""
+        return {**_UNKNOWN_SYNC, **_timezone(), 'rtc_in_utc': None}
+
+
+# Output timestamps. Rates and sampler cadence always use time.monotonic(),
+# whichever source is chosen here, so a wall-clock step can't skew them.
+CLOCK_SOURCES = ('wall', 'monotonic', 'monotonic_raw')
+
+_source = 'wall'
+
+
+def _read(source: str) -> float:
+    if source == 'wall':
+        return time.time()
+    if source == 'monotonic':
+        return time.monotonic()
+    return time.clock_gettime(time.CLOCK_MONOTONIC_RAW)
+
+
+def set_clock(source: str) -> None:
+    """Choose the clock behind every "timestamp" field the API emits.
+
+    "wall" (the default) is seconds since the epoch; "monotonic" and
+    "monotonic_raw" are seconds since an arbitrary point (boot on Linux) that
+    never jump, the raw one also ignoring NTP slewing. Use get_clock_info()
+    to convert them to wall time.
+    """
+    global _source
+    validation.one_of('source', source, CLOCK_SOURCES)
+    if source == 'monotonic_raw' and not hasattr(time, 'CLOCK_MONOTONIC_RAW'):
+        raise Unsupported('CLOCK_MONOTONIC_RAW is not available on this platform')
+    _source = source
+
+
+def get_clock() -> str:
+    return _source
+
+
+def now() -> float:
+    """The current time on the clock chosen by set_clock()."""
+    return _read(_source)
+
+
+def get_clock_info() -> Dict[str, Any]:
+    """The chosen source, a reading of each clock, and their offsets from wall time.
+
+    wall = monotonic + monotonic_offset at the moment of reading; the
+    offset changes whenever the wall clock is stepped or the host resumes
+    from suspend. monotonic_raw fields are None where that clock is missing.
+    """
+    # Bracket the wall reading so the offset is accurate to half the gap
+    before = time.monotonic()
+    wall = time.time()
+    monotonic = (before + time.monotonic()) / 2
+    raw = _read('monotonic_raw') if hasattr(time, 'CLOCK_MONOTONIC_RAW') else None
+    return {
+        'source': _source,
+        'wall': wall,
+        'monotonic': monotonic,
+        'monotonic_raw': raw,
+        'monotonic_offset': wall - monotonic,
+        'monotonic_raw_offset': None if raw is None else wall - raw,
+    }
@@ -0,0 +1,191 @@
+"""
+Pulse Collect
+Typed samples, the /proc parsers, and the delta math behind the collectors.
+
+Everything here is plain data and pure functions, so another tool (a CLI,
+an exporter) can read the same figures as pulse.core without its JSON,
+caching, unit, and compatibility layers; pulse.collector's Collector reads
+them from this host. Each dataclass converts to and from the dict pulse.core returns for the
+same reading with to_dict() and from_dict().
+"""
+import dataclasses
+from typing import Any, Dict, List, Optional, Tuple
+
+
+@dataclasses.dataclass(frozen=True)
+class CpuSample:
+    """One core's busy percent since the previous reading."""
+    core: int
+    percent: float
+
+    def to_dict(self) -> Dict[str, Any]:
+        return dataclasses.asdict(self)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> 'CpuSample':
+        return cls(data['core'], data['percent'])
+
+
+@dataclasses.dataclass(frozen=True)
+class MemoryInfo:
+    """Physical memory and swap in bytes; fields a platform doesn't report are 0."""
+    total: int
+    available: int
+    used: int
+    percent: float
+    swap_total: int = 0
+    swap_used: int = 0
+
+    def to_dict(self) -> Dict[str, Any]:
+        return dataclasses.asdict(self)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> 'MemoryInfo':
+        return cls(*(data.get(field.name, 0) for field in dataclasses.fields(cls)))
+
+
+@dataclasses.dataclass(frozen=True)
+class ProcEntry:
+    """One process row: CPU percent and memory bytes as get_process_list() reports them."""
+    pid: int
+    name: str
+    cpu_percent: float
+    memory_info: int
+
+    def to_dict(self) -> Dict[str, Any]:
+        return dataclasses.asdict(self)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> 'ProcEntry':
+        return cls(data['pid'], data['name'], data['cpu_percent'], data['memory_info'])
+
+
+@dataclasses.dataclass(frozen=True)
+class DiskEntry:
+    """One mounted filesystem; sizes are None for a stale network mount."""
+    device: str
+    mountpoint: str
+    fstype: str
+    total: Optional[int]
+    used: Optional[int]
+    available: Optional[int]
+    percent: Optional[float]
+
+    def to_dict(self) -> Dict[str, Any]:
+        return dataclasses.asdict(self)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> 'DiskEntry':
+        return cls(*(data.get(field.name) for field in dataclasses.fields(cls)))
+
+
+@dataclasses.dataclass(frozen=True)
+class NetSample:
+    """Cumulative bytes over every interface except loopback."""
+    bytes_recv: int
+    bytes_sent: int
+
+    def to_dict(self) -> Dict[str, Any]:
+        return dataclasses.asdict(self)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> 'NetSample':
+        return cls(data['bytes_recv'], data['bytes_sent'])
+
+
+# /proc/meminfo keys -> get_memory_info() keys
+_MEMINFO_KEYS = {
+    'MemTotal': 'total',
+    'MemAvailable': 'available',
+    'MemFree': 'free',
+    'Buffers': 'buffers',
+    'Cached': 'cached',
+    'SwapTotal': 'swap_total',
+    'SwapFree': 'swap_free',
+}
+
+
+def parse_meminfo(text: str) -> Dict[str, Any]:
+    """get_memory_info()'s dict from the text of /proc/meminfo (kB values become bytes)."""
+    mem: Dict[str, Any] = {}
+    for line in text.splitlines():
+        parts = line.split()
+        key = _MEMINFO_KEYS.get(parts[0].rstrip(':')) if len(parts) > 1 else None
+        if key is not None:
+            mem[key] = int(parts[1]) * 1024
+    mem['used'] = mem.get('total', 0) - mem.get('available', 0)
+    mem['swap_used'] = mem.get('swap_total', 0) - mem.get('swap_free', 0)
+    mem['percent'] = (mem['used'] / mem['total'] * 100) if mem.get('total') else 0
+    return mem
+
+
+def parse_proc_stat(text: str) -> List[Dict[str, float]]:
+    """Per-core {busy, total} jiffies from the text of /proc/stat."""
+    times = []
+    for line in text.splitlines():
+        if line.startswith('cpu') and not line.startswith('cpu '):
+            parts = line.split()[1:]
+            # user, nice, system, idle, iowait, irq, softirq
+            user, nice, system, idle = (int(p) for p in parts[:4])
+            iowait = int(parts[4]) if len(parts) > 4 else 0
+            times.append({
+                'busy': user + nice + system,
+                'total': user + nice + system + idle + iowait,
+            })
+    return times
+
+
+def parse_net_dev(text: str) -> Dict[str, int]:
+    """Bytes received and sent over every non-loopback interface in /proc/net/dev."""
+    total_recv = total_sent = 0
+    for line in text.splitlines():
+        if ':' in line:
+            parts = line.replace(':', ' ', 1).split()
+            if parts[0] != 'lo':
+                total_recv += int(parts[1])
+                total_sent += int(parts[9])
+    return {'bytes_recv': total_recv, 'bytes_sent': total_sent}
+
+
+def parse_pid_stat(pid: int, stat: bytes) -> Tuple[int, str, float, int]:
+    """(pid, comm, 0.0, vsize) from the bytes of /proc/<pid>/stat.
+
+    comm may contain spaces or ')', so fields are split after its last ')'.
+    Raises ValueError or IndexError for a truncated file.
+    """
+    return parse_pid_stat_ticks(pid, stat)[0]
+
+
+def parse_pid_stat_ticks(pid: int, stat: bytes) -> Tuple[Tuple[int, str, float, int], int, int]:
+    """parse_pid_stat()'s row plus utime + stime and starttime, in clock ticks."""
+    end = stat.rindex(b')')
+    fields = stat[end + 2:].split()
+    # Fields 14 and 15 (utime, stime), 22 (starttime), and 23 (vsize), counted from the state field
+    row = (pid, stat[stat.index(b'(') + 1:end].decode('utf-8', 'replace'), 0, int(fields[20]))
+    return row, int(fields[11]) + int(fields[12]), int(fields[19])
+
+
+def process_cpu_percent(previous_ticks: int, current_ticks: int, elapsed: float, ticks_per_sec: float) -> float:
+    """One process's CPU percent (100 is one core) from two utime + stime readings elapsed seconds apart."""
+    if elapsed <= 0:
+        return 0.0
+    return max(0, current_ticks - previous_ticks) / ticks_per_sec / elapsed * 100
+
+
+def counter_delta(current: int, previous: int) -> int:
+    """Difference between two cumulative counter readings, clamping resets to 0."""
+    delta = current - previous
+    return delta if delta >= 0 else 0
+
+
+def counter_rate(current: int, previous: int, elapsed: float) -> float:
+    """Per-second rate of a cumulative counter; 0.0 for a reset or no elapsed time."""
+    return counter_delta(current, previous) / elapsed if elapsed > 0 else 0.0
+
+
+def cpu_percent(previous: Dict[str, float], current: Dict[str, float]) -> float:
+    """Busy percent of one core between two {busy, total} readings, capped at 100."""
+    delta_total = current['total'] - previous['total']
+    if delta_total <= 0:
+        return 0.0
+    return min(100.0, (current['busy'] - previous['busy']) / delta_total * 100)
@@ -0,0 +1,40 @@
+"""
+Pulse Collector
+Typed readings of this host, for tools that want pulse.collect's figures
+without pulse.core's API layer:
+
+    collector = Collector()
+    collector.cpu()        # [CpuSample(core=0, percent=...), ...]
+    collector.memory()     # MemoryInfo(total=..., available=..., ...)
+"""
+from typing import List, Optional
+
+from pulse import direct_os, disks
+from pulse.collect import CpuSample, DiskEntry, MemoryInfo, NetSample, ProcEntry
+from pulse.state import SysState, using
+
+
+class Collector:
+    """Typed readings with their own delta baselines, like a Monitor without the API layer."""
+
+    def __init__(self) -> None:
+        self.state = SysState()
+
+    def cpu(self) -> List[CpuSample]:
+        with using(self.state):
+            return [CpuSample(core, percent)
+                    for core, percent in enumerate(direct_os.get_cpu_percents())]
+
+    def memory(self) -> MemoryInfo:
+        return MemoryInfo.from_dict(direct_os.get_memory_info())
+
+    def processes(self, sort_by: Optional[str] = None,
+                  limit: Optional[int] = None) -> List[ProcEntry]:
+        with using(self.state):
+            return [ProcEntry(*row) for row in direct_os.get_process_rows(sort_by, limit)]
+
+    def disks(self) -> List[DiskEntry]:
+        return [DiskEntry.from_dict(disk) for disk in disks.get_disk_info()]
+
+    def network(self) -> NetSample:
+        return NetSample.from_dict(direct_os.get_network_stats())
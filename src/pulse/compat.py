@@ -0,0 +1,125 @@
+"""
+Pulse psutil Compatibility
+Optional psutil-style key names and value conventions for getter results.
+
+With set_compat_mode("psutil") the getters below return what the matching
+psutil call would, wherever Pulse has the data; keys without a psutil
+counterpart are left as they are.
+
+    getter              pulse                          psutil
+    ------------------  -----------------------------  ----------------------------------------
+    get_memory_info     total, available, percent,     virtual_memory(): same names
+                        used, free, buffers, cached
+                        swap_total, swap_used,         (no counterpart in virtual_memory();
+                        swap_free, is_dynamic,         kept as-is)
+                        compressed, pressure_level
+    get_network_stats   bytes_recv, bytes_sent         net_io_counters(): same names
+    get_process_list    pid, name, cpu_percent         Process.as_dict(): same names
+                        memory_info (int)              memory_info: {"vms": n} on Linux, where
+                                                       Pulse reports virtual size; {"rss": n}
+                                                       elsewhere
+    get_disk_info       device, mountpoint, fstype,    disk_partitions() + disk_usage(): same
+                        total, used, free, percent     names
+                        options                        opts
+    get_connections     local_address, local_port      laddr: (ip, port)
+                        remote_address, remote_port    raddr: (ip, port), () when unconnected
+                        status                         status: same ("ESTABLISHED", "NONE", ...)
+    get_battery_info    percent                        sensors_battery(): percent
+                        time_to_empty_secs             secsleft; -2 on AC power, -1 unknown
+                        state                          power_plugged: state != "discharging"
+"""
+import contextlib
+import contextvars
+from typing import Any, Callable, Dict, Iterator
+
+from pulse import validation
+from pulse.direct_os import LINUX
+
+MODES = ('pulse', 'psutil')
+
+# psutil.POWER_TIME_UNKNOWN and psutil.POWER_TIME_UNLIMITED
+POWER_TIME_UNKNOWN = -1
+POWER_TIME_UNLIMITED = -2
+
+_mode = 'pulse'
+
+# Set while collecting for consumers that read Pulse's own keys (Prometheus, alerts)
+_native: contextvars.ContextVar[bool] = contextvars.ContextVar('pulse_native_names', default=False)
+
+
+def set_compat_mode(mode: str) -> None:
+    """Switch getter output between Pulse's names ("pulse") and psutil's ("psutil")."""
+    global _mode
+    validation.one_of('mode', mode, MODES)
+    _mode = mode
+
+
+def get_compat_mode() -> str:
+    """The mode set by set_compat_mode(); "pulse" by default."""
+    return _mode
+
+
+def effective_mode() -> str:
+    """The mode adapt() applies here and now: "pulse" inside native_names()."""
+    return 'pulse' if _native.get() else _mode
+
+
+@contextlib.contextmanager
+def native_names() -> Iterator[None]:
+    """Report Pulse's own keys inside the block whatever the compat mode."""
+    token = _native.set(True)
+    try:
+        yield
+    finally:
+        _native.reset(token)
+
+
+def _rename(entry: Dict[str, Any], names: Dict[str, str]) -> Dict[str, Any]:
+    return {names.get(key, key): value for key, value in entry.items()}
+
+
+def _process(entry: Dict[str, Any]) -> Dict[str, Any]:
+    entry = dict(entry)
+    if 'memory_info' in entry:
+        entry['memory_info'] = {'vms' if LINUX else 'rss': entry['memory_info']}
+    return entry
+
+
+def _disk(entry: Dict[str, Any]) -> Dict[str, Any]:
+    return _rename(entry, {'options': 'opts'})
+
+
+def _connection(entry: Dict[str, Any]) -> Dict[str, Any]:
+    entry = dict(entry)
+    local = (entry.pop('local_address'), entry.pop('local_port'))
+    remote = (entry.pop('remote_address'), entry.pop('remote_port'))
+    return {'laddr': local, 'raddr': remote if remote[1] else (), **entry}
+
+
+def _battery(entry: Dict[str, Any]) -> Dict[str, Any]:
+    entry = dict(entry)
+    plugged = entry['state'] != 'discharging'
+    secs = entry.pop('time_to_empty_secs')
+    if plugged:
+        entry['secsleft'] = POWER_TIME_UNLIMITED
+    else:
+        entry['secsleft'] = POWER_TIME_UNKNOWN if secs is None else secs
+    entry['power_plugged'] = plugged
+    return entry
+
+
+_ADAPTERS: Dict[str, Callable[[Dict[str, Any]], Dict[str, Any]]] = {
+    'process': _process,
+    'disk': _disk,
+    'connection': _connection,
+    'battery': _battery,
+}
+
+
+def adapt(kind: str, result: Any) -> Any:
+    """Map a getter's result (a dict or a list of dicts) to the active mode."""
+    if _mode == 'pulse' or _native.get() or kind not in _ADAPTERS:
+        return result
+    if isinstance(result, list):
+        return [_ADAPTERS[kind](entry) for entry in result]
+    return _ADAPTERS[kind](result)
@@ -0,0 +1,249 @@
+"""
+Pulse Containers
+Per-container CPU, memory, network, and pids read straight from cgroupfs and
+procfs, so docker, podman, containerd, and CRI-O all work without a socket.
+"""
+import json
+import os
+import re
+import time
+from typing import Any, Dict, List, Optional
+
+from pulse import cgroups, clock, log, netstat, processes, validation
+from pulse.capabilities import requires
+from pulse.collect import counter_rate
+from pulse.direct_os import LINUX
+from pulse.errors import ProcessNotFound, Unsupported
+from pulse.state import active_state
+
+PROC = '/proc'
+DOCKER_CONTAINERS = '/var/lib/docker/containers'
+PODMAN_CONTAINERS_JSON = '/var/lib/containers/storage/overlay-containers/containers.json'
+
+# Leaf cgroup names: systemd drivers use "<prefix>-<id>.scope", cgroupfs drivers the bare id
+_CONTAINER_CGROUP = re.compile(r'^(?:(docker|libpod|cri-containerd|crio)-)?([0-9a-f]{64})(?:\.scope)?$')
+
+_PREFIX_RUNTIMES = {'docker': 'docker', 'libpod': 'podman', 'cri-containerd': 'containerd', 'crio': 'crio'}
+
+
+def match_container_cgroup(cgroup_path: str) -> Optional[Dict[str, str]]:
+    """{"id", "runtime"} when cgroup_path's leaf is a container, else None."""
+    match = _CONTAINER_CGROUP.match(os.path.basename(cgroup_path.rstrip('/')))
+    if match is None:
+        return None
+    prefix, container_id = match.groups()
+    if prefix:
+        runtime = _PREFIX_RUNTIMES[prefix]
+    elif '/docker/' in cgroup_path:
+        runtime = 'docker'
+    elif 'kubepods' in cgroup_path:
+        runtime = 'kubernetes'
+    else:
+        runtime = 'unknown'
+    return {'id': container_id, 'runtime': runtime}
+
+
+def parse_proc_cgroup(text: str) -> Optional[str]:
+    """The cgroup v2 path ("0::/system.slice/...") from /proc/<pid>/cgroup."""
+    for line in text.splitlines():
+        if line.startswith('0::'):
+            return line[3:].strip() or None
+    return None
+
+
+def docker_name(config_text: str) -> Optional[str]:
+    """Container name from docker's config.v2.json, without its leading "/"."""
+    try:
+        name = json.loads(config_text).get('Name')
+    except (ValueError, AttributeError):
+        return None
+    if not isinstance(name, str):
+        return None
+    return name.lstrip('/') or None
+
+
+def podman_names(containers_text: str) -> Dict[str, str]:
+    """{id: first name} from podman's containers.json."""
+    try:
+        entries = json.loads(containers_text)
+    except ValueError:
+        return {}
+    return {e['id']: e['names'][0] for e in entries
+            if isinstance(e, dict) and e.get('id') and e.get('names')}
+
+
+def _read(path: str) -> Optional[str]:
+    try:
+        with open(path, 'r') as f:
+            return f.read()
+    except OSError as e:
+        log.read_failed(path, e)
+        return None
+
+
+def _find_container_cgroups() -> Dict[str, Dict[str, str]]:
+    found = {}
+    for directory, subdirs, _ in os.walk(cgroups.SYS_FS_CGROUP):
+        path = '/' + os.path.relpath(directory, cgroups.SYS_FS_CGROUP)
+        match = match_container_cgroup(path)
+        if match is not None:
+            found[path] = match
+            # A container's own sub-cgroups belong to it; don't descend
+            subdirs.clear()
+    return found
+
+
+def _pids_by_cgroup() -> Dict[str, List[int]]:
+    members: Dict[str, List[int]] = {}
+    try:
+        pids = [p for p in os.listdir(PROC) if p.isdigit()]
+    except OSError:
+        return members
+    for pid in pids:
+        path = parse_proc_cgroup(_read(os.path.join(PROC, pid, 'cgroup')) or '')
+        if path is not None:
+            members.setdefault(path, []).append(int(pid))
+    return members
+
+
+def interface_rates(current: Dict[str, Dict[str, int]], previous: Optional[Dict[str, Dict[str, int]]],
+                    elapsed: float) -> Dict[str, Dict[str, Any]]:
+    """Each interface's counters plus bytes per second since previous (None without one)."""
+    interfaces = {}
+    for name, counters in current.items():
+        prev = (previous or {}).get(name)
+        interfaces[name] = dict(counters)
+        for key in ('bytes_recv', 'bytes_sent'):
+            interfaces[name][key + '_per_sec'] = counter_rate(counters[key], prev[key], elapsed) if prev else None
+    return interfaces
+
+
+def _namespace_network(pid: int) -> Optional[Dict[str, Any]]:
+    # /proc/<pid>/net shows the network namespace the pid lives in, no setns() needed
+    text = _read(os.path.join(PROC, str(pid), 'net', 'dev'))
+    if text is None:
+        return None
+    current = netstat.parse_net_dev_per_interface(text)
+    try:
+        inode = processes.parse_ns_link(os.readlink(os.path.join(PROC, str(pid), 'ns', 'net')))
+    except OSError:
+        # Other users' ns links need CAP_SYS_PTRACE; without the inode there's no baseline
+        inode = None
+
+    previous, elapsed = None, 0.0
+    if inode is not None:
+        state = active_state()
+        with state.lock('network'):
+            now = time.monotonic()
+            baseline = state.namespace_network.get(inode)
+            state.namespace_network[inode] = (now, current)
+        if baseline is not None:
+            elapsed = now - baseline[0]
+            previous = baseline[1]
+    return {
+        'pid': pid,
+        'namespace': inode,
+        'timestamp': clock.now(),
+        'interfaces': interface_rates(current, previous, elapsed),
+    }
+
+
+def _sum(interfaces: Optional[List[Dict[str, Any]]], key: str) -> Optional[float]:
+    if interfaces is None:
+        return None
+    values = [c[key] for c in interfaces]
+    return None if None in values else sum(values)
+
+
+def _network(pid: Optional[int], rates: bool) -> Dict[str, Optional[float]]:
+    if pid is None:
+        counters = None
+    elif rates:
+        network = _namespace_network(pid)
+        counters = None if network is None else network['interfaces']
+    else:
+        text = _read(os.path.join(PROC, str(pid), 'net', 'dev'))
+        counters = None if text is None else netstat.parse_net_dev_per_interface(text)
+    interfaces = None if counters is None else [c for name, c in counters.items() if name != 'lo']
+    totals = {
+        'net_bytes_recv': _sum(interfaces, 'bytes_recv'),
+        'net_bytes_sent': _sum(interfaces, 'bytes_sent'),
+    }
+    if rates:
+        totals['net_recv_per_sec'] = _sum(interfaces, 'bytes_recv_per_sec')
+        totals['net_sent_per_sec'] = _sum(interfaces, 'bytes_sent_per_sec')
+    return totals
+
+
+def _name(container_id: str, runtime: str, podman: Dict[str, str]) -> Optional[str]:
+    if runtime == 'docker':
+        config = _read(os.path.join(DOCKER_CONTAINERS, container_id, 'config.v2.json'))
+        return docker_name(config) if config else None
+    return podman.get(container_id)
+
+
+if LINUX:
+    @requires('namespaces')
+    def get_namespace_network(pid: int) -> Optional[Dict[str, Any]]:
+        """Get per-interface byte counters and rates of the network namespace pid is in.
+
+        {pid, namespace, timestamp, interfaces: {name: {bytes_recv, bytes_sent,
+        bytes_recv_per_sec, bytes_sent_per_sec}}}. Rates are since the previous
+        call for the same namespace (any member pid), None on the first. When
+        the namespace link is unreadable (another user's process) namespace and
+        the rates are None; when /proc/<pid>/net/dev is too, the result is None.
+        Raises ProcessNotFound if the process doesn't exist.
+        """
+        validation.at_least('pid', pid, 1)
+        if not os.path.isdir(os.path.join(PROC, str(pid))):
+            raise ProcessNotFound.for_pid(pid)
+        return _namespace_network(pid)
+
+    @requires('containers')
+    def list_containers(include_network_rates: bool = False) -> List[Dict[str, Any]]:
+        """Get every running container's CPU, memory, network, and member pids.
+
+        Containers are found by their cgroup v2 scopes, so any runtime works.
+        Names come from docker's or podman's on-disk state when it's readable
+        (usually root only), else None. cpu_percent is None on the first call.
+        include_network_rates adds net_recv_per_sec and net_sent_per_sec, as
+        get_namespace_network() measures them. Hosts with no containers return
+        an empty list.
+        """
+        found = _find_container_cgroups()
+        if not found:
+            return []
+        members = _pids_by_cgroup()
+        podman = podman_names(_read(PODMAN_CONTAINERS_JSON) or '[]')
+
+        containers = []
+        for path, match in sorted(found.items()):
+            pids = sorted(pid for group, group_pids in members.items()
+                          if group == path or group.startswith(path + '/') for pid in group_pids)
+            usage = cgroups.read_usage(cgroups.cgroup_dir(path))
+            memory_current, memory_max = usage['memory_current'], usage['memory_max']
+            containers.append({
+                'id': match['id'],
+                'name': _name(match['id'], match['runtime'], podman),
+                'runtime': match['runtime'],
+                'cgroup': path,
+                'cpu_percent': cgroups.cpu_percent(path, usage['cpu_usage_usec']),
+                'memory_current': memory_current,
+                'memory_max': memory_max,
+                'memory_percent': (memory_current / memory_max * 100
+                                   if memory_current is not None and memory_max else None),
+                **_network(pids[0] if pids else None, include_network_rates),
+                'pids': pids,
+            })
+        return containers
+
+else:
+    @requires('namespaces')
+    def get_namespace_network(pid: int) -> Optional[Dict[str, Any]]:
+        """Network namespaces are Linux-only."""
+        raise Unsupported('get_namespace_network is only supported on Linux')
+
+    @requires('containers')
+    def list_containers(include_network_rates: bool = False) -> List[Dict[str, Any]]:
+        """Containers are discovered through Linux cgroups."""
+        raise Unsupported('list_containers is only supported on Linux')
@@ -2,14 +2,538 @@
 Pulse Core - Hardware Interface Layer
 Unified access to system metrics using Direct OS Engine.
 """
-from pulse import direct_os
+import atexit
+import copy
+import functools
+import threading
+import time
+from typing import Any, Callable, Dict, Iterable, Optional
+
+from pulse import (
+    alerts, anomalies, audio, backend, baselines, callbacks, capabilities, clock, compat,
+    containers, cpu_sampling, deadlines, detectors, diagnostics, direct_os, disk_history, disks,
+    encoding, entropy, environment, exit_accounting, gpu, history, http_server, interfaces,
+    isolation, kmsg, leaderboard, limits, log, macos, mount_watcher, netstat, open_files, power,
+    privacy, processes, prometheus, recording, report, resctrl, sampler, scanner, sched, sensors,
+    services, snapshot_diff, socket_publisher, system, tags, targets, thermal, units, validation,
+    win_processes,
+)
+from pulse.errors import (
+    CorruptRecording, PermissionDenied, ProcessNotFound, PulseError, PulseInternalError,
+    ServiceNotFound, Timeout, Unsupported, WorkerRestarted,
+)
+from pulse.state import STATE, SysState, active_state, using
+
+_default_max_age_ms = 0.0
+
+# Subsystems whose getters go through _cached()
+CACHED_SUBSYSTEMS = ('cpu', 'memory', 'processes', 'network', 'disks')
+
+# Per subsystem: the floor set_min_refresh_interval() put on max_age_ms, and
+# how many calls refreshed vs. were answered from the cache
+_min_refresh_ms: Dict[str, float] = {}
+_refresh_counts: Dict[str, Dict[str, int]] = {
+    subsystem: {'refreshed': 0, 'cached': 0} for subsystem in CACHED_SUBSYSTEMS
+}
+_refresh_counts_lock = threading.Lock()
+
+
+def set_default_max_age(max_age_ms: float) -> None:
+    """Set the max_age_ms the cached getters use when a call doesn't pass one."""
+    global _default_max_age_ms
+    validation.at_least('max_age_ms', max_age_ms, 0)
+    _default_max_age_ms = max_age_ms
+
+
+def get_default_max_age() -> float:
+    """The max_age_ms set by set_default_max_age(); 0 by default."""
+    return _default_max_age_ms
+
+
+def set_min_refresh_interval(subsystem: str, millis: float) -> None:
+    """Refresh subsystem at most once every millis, whatever callers ask for.
+
+    Calls arriving sooner get the previous result, as if they had passed
+    max_age_ms=millis; a larger max_age_ms still applies, a smaller one or 0
+    can't bypass it. 0, the default, removes the floor. subsystem is one of
+    CACHED_SUBSYSTEMS.
+    """
+    validation.one_of('subsystem', subsystem, CACHED_SUBSYSTEMS)
+    validation.at_least('millis', millis, 0)
+    if millis:
+        _min_refresh_ms[subsystem] = millis
+    else:
+        _min_refresh_ms.pop(subsystem, None)
+
+
+def get_refresh_stats() -> Dict[str, Dict[str, float]]:
+    """Per cached subsystem: {calls, refreshed, cached, min_refresh_interval_ms} since import."""
+    with _refresh_counts_lock:
+        return {
+            subsystem: {'calls': counts['refreshed'] + counts['cached'], **counts,
+                        'min_refresh_interval_ms': _min_refresh_ms.get(subsystem, 0)}
+            for subsystem, counts in _refresh_counts.items()
+        }
+
+
+def _count_refresh(subsystem: str, refreshed: bool) -> None:
+    with _refresh_counts_lock:
+        _refresh_counts[subsystem]['refreshed' if refreshed else 'cached'] += 1
+
+
+def _cached(subsystem: str, module: Any, name: str) -> Callable[..., Any]:
+    """Wrap module.<name> so max_age_ms can reuse a recent result.
+
+    A result younger than max_age_ms for the same arguments is returned (as
+    a copy) instead of refreshing. With 0, the default, every call refreshes
+    and nothing is cached. set_min_refresh_interval() raises max_age_ms to
+    its floor. Results live in the active state, so each Monitor caches
+    separately; a refresh holds only its own subsystem's lock.
+    """
+    @functools.wraps(getattr(module, name))
+    def getter(*args: Any, max_age_ms: Optional[float] = None, **kwargs: Any) -> Any:
+        validation.optional_at_least('max_age_ms', max_age_ms, 0)
+        max_age = _default_max_age_ms if max_age_ms is None else max_age_ms
+        max_age = max(max_age, _min_refresh_ms.get(subsystem, 0))
+        if max_age <= 0:
+            _count_refresh(subsystem, True)
+            return getattr(module, name)(*args, **kwargs)
+
+        state = active_state()
+        key = (subsystem, repr((args, sorted(kwargs.items()))))
+        # Concurrent callers of one subsystem wait for a single refresh
+        with state.lock(subsystem):
+            now = time.monotonic()
+            hit = state.cache.get(key)
+            refresh = hit is None or (now - hit[0]) * 1000 >= max_age
+            if refresh:
+                hit = (now, getattr(module, name)(*args, **kwargs))
+                state.cache[key] = hit
+        _count_refresh(subsystem, refresh)
+        return copy.deepcopy(hit[1])
+    return getter
+
+
+def _in_units(kind: str, getter: Callable[..., Any]) -> Callable[..., Any]:
+    """Convert getter's byte fields to the units set_units() chose for kind."""
+    @functools.wraps(getter)
+    def converted(*args: Any, **kwargs: Any) -> Any:
+        result = getter(*args, **kwargs)
+        if isinstance(result, list):
+            return [units.convert(kind, entry) for entry in result]
+        return units.convert(kind, result)
+    return converted
+
+
+def _in_compat_mode(kind: str, getter: Callable[..., Any]) -> Callable[..., Any]:
+    """Rename getter's keys as set_compat_mode() asks for kind."""
+    @functools.wraps(getter)
+    def adapted(*args: Any, **kwargs: Any) -> Any:
+        return compat.adapt(kind, getter(*args, **kwargs))
+    return adapted
+
+
+enable_logging = log.enable_logging
+set_call_timeout = deadlines.set_call_timeout
+get_call_timeout = deadlines.get_call_timeout
 
 # Re-export all functions from direct_os
-init = direct_os.init
-get_memory_info = direct_os.get_memory_info
-get_cpu_percents = direct_os.get_cpu_percents
-get_process_list = direct_os.get_process_list
-get_network_stats = direct_os.get_network_stats
-get_disk_info = direct_os.get_disk_info
+get_memory_info = _in_units('memory', _cached('memory', direct_os, 'get_memory_info'))
+get_cpu_percents = _cached('cpu', direct_os, 'get_cpu_percents')
+get_cpu_core_type_usage = macos.get_cpu_core_type_usage
+get_process_list = _in_compat_mode('process', _cached('processes', direct_os, 'get_process_list'))
+get_network_stats = _in_units('network', _cached('network', direct_os, 'get_network_stats'))
 kill_process = direct_os.kill_process
 renice_process = direct_os.renice_process
+
+# System identity
+get_system_info = system.get_system_info
+get_uptime = system.get_uptime
+get_load_average = system.get_load_average
+get_scheduler_stats = sched.get_scheduler_stats
+get_hardware_info = system.get_hardware_info
+detect_environment = environment.detect_environment
+get_kernel_limits = limits.get_kernel_limits
+get_entropy = entropy.get_entropy
+get_kernel_messages = kmsg.get_kernel_messages
+get_clock_status = clock.get_clock_status
+
+# Power
+get_battery_info = _in_compat_mode('battery', power.get_battery_info)
+get_brightness = power.get_brightness
+set_brightness = power.set_brightness
+
+# Audio
+get_audio_state = audio.get_audio_state
+
+# Sensors
+get_fans = sensors.get_fans
+get_thermal_status = sensors.get_thermal_status
+get_gpu_info = gpu.get_gpu_info
+
+# Output conventions
+set_units = units.set_units
+get_units = units.get_units
+set_compat_mode = compat.set_compat_mode
+get_compat_mode = compat.get_compat_mode
+set_clock = clock.set_clock
+get_clock = clock.get_clock
+get_clock_info = clock.get_clock_info
+
+# Process detail
+get_io_hogs = processes.get_io_hogs
+get_process_limits = processes.get_process_limits
+get_process_namespaces = processes.get_process_namespaces
+get_limit_headroom = processes.get_limit_headroom
+list_limit_headroom = processes.list_limit_headroom
+group_processes_by_namespace = processes.group_processes_by_namespace
+iter_processes = processes.iter_processes
+get_process_changes = processes.get_process_changes
+get_process_scheduling = processes.get_process_scheduling
+get_process_threads = processes.get_process_threads
+get_open_files = open_files.get_open_files
+who_has_open = open_files.who_has_open
+get_windows_process_info = win_processes.get_windows_process_info
+resolve_target = targets.resolve_target
+
+# Process tags
+set_process_tags = tags.set_process_tags
+get_process_tags = tags.get_process_tags
+get_tag_summary = tags.get_tag_summary
+
+# Services
+list_services = services.list_services
+get_service = services.get_service
+list_windows_services = services.list_windows_services
+get_boot_metrics = services.get_boot_metrics
+
+# Containers
+list_containers = containers.list_containers
+get_namespace_network = containers.get_namespace_network
+get_rdt_groups = resctrl.get_rdt_groups
+
+# Socket telemetry
+get_connections = _in_compat_mode('connection', netstat.get_connections)
+get_udp_stats = netstat.get_udp_stats
+
+# Network topology
+get_interface_topology = interfaces.get_interface_topology
+
+# Storage
+get_disk_info = _in_compat_mode('disk', _in_units('disk', _cached('disks', disks, 'get_disk_info')))
+get_path_usage = disks.get_path_usage
+get_disk_io = disks.get_disk_io
+get_disk_io_rates = disks.get_disk_io_rates
+get_disk_health = disks.get_disk_health
+get_block_devices = disks.get_block_devices
+get_removable_media = disks.get_removable_media
+start_mount_watcher = mount_watcher.start_mount_watcher
+stop_mount_watcher = mount_watcher.stop_mount_watcher
+get_mount_events = mount_watcher.get_mount_events
+start_disk_sampler = disk_history.start_disk_sampler
+stop_disk_sampler = disk_history.stop_disk_sampler
+get_disk_projection = disk_history.get_disk_projection
+
+# Disk scanning
+scan_directory = scanner.scan_directory
+find_largest_files = scanner.find_largest_files
+
+# Background sampling
+start_sampler = sampler.start_sampler
+stop_sampler = sampler.stop_sampler
+get_samples = sampler.get_samples
+sampler_status = sampler.sampler_status
+get_cpu_history_buffer = sampler.get_cpu_history_buffer
+query_history = history.query_history
+get_cpu_leaderboard = leaderboard.get_cpu_leaderboard
+get_thermal_correlation = thermal.get_thermal_correlation
+
+# Exit accounting
+enable_exit_accounting = exit_accounting.enable_exit_accounting
+disable_exit_accounting = exit_accounting.disable_exit_accounting
+get_exited_processes = exit_accounting.get_exited_processes
+
+# Blocking CPU measurement
+sample_cpu = cpu_sampling.sample_cpu
+
+# Record and replay
+start_recording = recording.start_recording
+stop_recording = recording.stop_recording
+load_recording = recording.load_recording
+write_report = report.write_report
+
+# Crash isolation
+spawn_worker = isolation.spawn_worker
+stop_worker = isolation.stop_worker
+worker_status = isolation.worker_status
+
+# Built-in HTTP endpoint
+start_http_server = http_server.start_http_server
+stop_http_server = http_server.stop_http_server
+
+# Unix socket stream
+start_socket_publisher = socket_publisher.start_socket_publisher
+stop_socket_publisher = socket_publisher.stop_socket_publisher
+socket_publisher_status = socket_publisher.socket_publisher_status
+
+# Alerts
+set_alerts = alerts.set_alerts
+check_alerts = alerts.check_alerts
+get_anomalies = anomalies.get_anomalies
+
+# Process detectors
+get_crash_loops = privacy.scrubbed(detectors.get_crash_loops)
+get_runaway_processes = detectors.get_runaway_processes
+
+# Sampler callbacks
+register_callback = callbacks.register_callback
+unregister_callback = callbacks.unregister_callback
+get_callback_errors = callbacks.get_callback_errors
+
+# Capabilities
+get_capabilities = capabilities.get_capabilities
+
+# Diagnostics
+get_diagnostics = diagnostics.get_diagnostics
+reset_diagnostics = diagnostics.reset_diagnostics
+
+# Change detection
+take_snapshot = snapshot_diff.take_snapshot
+diff_snapshots = snapshot_diff.diff_snapshots
+
+# Privacy
+set_privacy_mode = privacy.set_privacy_mode
+get_privacy_mode = privacy.get_privacy_mode
+
+# Warm start
+save_state = baselines.save_state
+load_state = baselines.load_state
+
+# Data source
+set_backend = backend.set_backend
+get_backend = backend.get_backend
+mock_set_data = backend.mock_set_data
+mock_clear = backend.mock_clear
+
+
+# Named like the snapshot sections, so the sampler primes exactly the ones it collects
+INIT_SUBSYSTEMS = ('cpu', 'memory', 'processes', 'network', 'disks')
+
+
+def init(refresh: Optional[Iterable[str]] = None) -> None:
+    """Prime the delta baselines of the subsystems in refresh (default: all).
+
+    Priming makes the first real call report a rate instead of zeros. A
+    caller that only wants memory stats can pass ["memory"] and skip the
+    rest; subsystems left out still work and build their baseline on first
+    use. Raises ValueError for names outside INIT_SUBSYSTEMS.
+    """
+    if refresh is not None:
+        refresh = validation.all_of('refresh', refresh, INIT_SUBSYSTEMS)
+    enabled = set(INIT_SUBSYSTEMS if refresh is None else refresh)
+
+    # Memory is read fresh on every call and has nothing to prime
+    if 'cpu' in enabled:
+        get_cpu_percents()
+    if 'processes' in enabled and not direct_os.LINUX:
+        # psutil's per-process cpu_percent measures against its previous call
+        get_process_list()
+    if 'network' in enabled and capabilities.supported('udp_stats'):
+        get_udp_stats()
+    if 'disks' in enabled:
+        get_disk_io_rates()
+
+
+SNAPSHOT_SECTIONS = {
+    'cpu': get_cpu_percents,
+    'memory': get_memory_info,
+    'processes': get_process_list,
+    'network': get_network_stats,
+    'disks': get_disk_info,
+}
+
+
+def check_sections(sections: Iterable[str], options: Dict[str, Dict[str, Any]]) -> None:
+    """Raise ValueError naming any section, options key, or process field that doesn't exist."""
+    validation.all_of('include', sections, SNAPSHOT_SECTIONS)
+    validation.all_of('options', options, SNAPSHOT_SECTIONS)
+    fields = options.get('processes', {}).get('fields')
+    if fields is not None:
+        validation.all_of('fields', fields, direct_os.PROCESS_FIELDS)
+
+
+def snapshot(include: Optional[Iterable[str]] = None,
+             options: Optional[Dict[str, Dict[str, Any]]] = None) -> Dict[str, Any]:
+    """Collect several sections in one pass under a single timestamp.
+
+    include picks sections from SNAPSHOT_SECTIONS (default: all). options maps
+    a section to the keyword arguments of its collector, e.g.
+    {"processes": {"sort_by": "cpu", "limit": 10, "fields": ["pid", "name"]},
+    "disks": {"physical_only": False}}; "fields" trims each process dict.
+    Raises ValueError for unknown sections or fields. With as_json=True this
+    is the timestamped JSON document for exporters; as_msgpack=True gives the
+    same document as MessagePack bytes.
+    """
+    sections = list(SNAPSHOT_SECTIONS) if include is None else list(include)
+    options = options or {}
+    check_sections(sections, options)
+
+    result: Dict[str, Any] = {'timestamp': clock.now()}
+    for section in sections:
+        kwargs = dict(options.get(section, {}))
+        fields = kwargs.pop('fields', None) if section == 'processes' else None
+        data = SNAPSHOT_SECTIONS[section](**kwargs)
+        if fields is not None:
+            data = [{k: proc[k] for k in fields if k in proc} for proc in data]
+        result[section] = data
+    return result
+
+
+# Read-only collectors. Each takes as_json=True and is mirrored as a Monitor
+# method ("get_" dropped), except the readers of this process's background
+# threads (isolation.LOCAL_ONLY), which have no per-Monitor state to read.
+# Process-wide actions and background threads (kill, samplers, watchers) stay
+# module-level only.
+_COLLECTORS = (
+    'get_memory_info', 'get_cpu_percents', 'get_cpu_core_type_usage', 'get_process_list',
+    'get_process_changes', 'get_tag_summary',
+    'get_network_stats',
+    'get_system_info', 'get_uptime', 'get_load_average', 'get_hardware_info', 'detect_environment',
+    'get_scheduler_stats',
+    'get_kernel_limits', 'get_entropy', 'get_kernel_messages', 'get_clock_status', 'get_clock_info',
+    'get_battery_info', 'get_brightness', 'get_audio_state',
+    'get_fans', 'get_thermal_status', 'get_gpu_info',
+    'get_io_hogs', 'get_process_limits', 'get_process_namespaces', 'group_processes_by_namespace',
+    'get_process_scheduling', 'get_process_threads', 'get_open_files', 'who_has_open',
+    'get_limit_headroom', 'list_limit_headroom', 'resolve_target',
+    'get_windows_process_info',
+    'list_services', 'get_service', 'list_windows_services', 'get_boot_metrics', 'list_containers',
+    'get_namespace_network', 'get_rdt_groups',
+    'get_connections', 'get_udp_stats', 'get_interface_topology',
+    'get_disk_info', 'get_path_usage', 'get_disk_io', 'get_disk_io_rates', 'get_disk_health',
+    'get_block_devices', 'get_removable_media', 'get_mount_events', 'get_disk_projection',
+    'scan_directory', 'find_largest_files', 'get_samples', 'sampler_status', 'snapshot',
+    'get_anomalies', 'query_history', 'get_cpu_leaderboard', 'get_thermal_correlation',
+    'get_exited_processes',
+    'get_capabilities',
+)
+
+
+class Monitor:
+    """An independent poller that owns its own delta baselines.
+
+    Two components polling at different rates each keep a Monitor, so one's
+    samples never reset the other's CPU, I/O, or throttle baselines:
+
+        fast, slow = Monitor(), Monitor()
+        fast.cpu_percents()   # every 0.5 s: usage over the last 0.5 s
+        slow.cpu_percents()   # every 5 s: usage over the last 5 s
+
+    The module-level functions behave as a shared default Monitor whose
+    baselines are pulse.state.STATE. Construction runs init(refresh) against
+    the new baselines. A Monitor may be shared between threads: calls for
+    different subsystems run concurrently, and calls for the same one take
+    turns updating its baseline.
+    """
+
+    def __init__(self, refresh: Optional[Iterable[str]] = None):
+        self.state = SysState()
+        with using(self.state):
+            init(refresh)
+
+
+def _with_json(function: Callable[..., Any]) -> Callable[..., Any]:
+    """Add as_json and as_msgpack: return the timestamped result as compact JSON or MessagePack bytes."""
+    @functools.wraps(function)
+    def collector(*args: Any, as_json: bool = False, as_msgpack: bool = False, **kwargs: Any) -> Any:
+        result = function(*args, **kwargs)
+        if as_json or as_msgpack:
+            result = encoding.timestamped(result, clock.now())
+        return encoding.encode(result, as_json, as_msgpack)
+    return collector
+
+
+def _monitor_method(function: Callable[..., Any]) -> Callable[..., Any]:
+    @functools.wraps(function)
+    def method(self: Monitor, *args: Any, **kwargs: Any) -> Any:
+        with using(self.state):
+            return function(*args, **kwargs)
+    return method
+
+
+for _name in _COLLECTORS:
+    _isolated = diagnostics.guarded(_name, isolation.proxied(_name, globals()[_name]))
+    _timed = diagnostics.timed(_name, deadlines.bounded(_name, _isolated))
+    globals()[_name] = _with_json(privacy.scrubbed(_timed))
+    if _name not in isolation.LOCAL_ONLY:
+        setattr(Monitor, _name[len('get_'):] if _name.startswith('get_') else _name,
+                _monitor_method(globals()[_name]))
+del _name, _isolated, _timed
+
+
+def get_prometheus_metrics(prefix: Optional[str] = None, include_processes: bool = False,
+                           process_limit: int = 20) -> str:
+    """Take one snapshot() and render it in the Prometheus text exposition format.
+
+    Metrics are named <prefix>_cpu_usage_percent{core="0"},
+    <prefix>_memory_used_bytes, <prefix>_disk_total_bytes{mount="/"}, ...
+    with prefix defaulting to "pulse". Per-process series are opt-in with
+    include_processes and capped at the process_limit top CPU consumers, so
+    the label set stays bounded.
+    """
+    validation.at_least('process_limit', process_limit, 1)
+    include = ['cpu', 'memory', 'network', 'disks']
+    if include_processes:
+        include.append('processes')
+    # Metric names promise bytes and Pulse's keys whatever the output settings
+    with units.raw_units(), compat.native_names():
+        snap = snapshot(include=include,
+                        options={'processes': {'sort_by': 'cpu', 'limit': process_limit}})
+    return prometheus.render(snap, prefix)
+
+
+def get_snapshot_msgpack(include: Optional[Iterable[str]] = None,
+                         options: Optional[Dict[str, Dict[str, Any]]] = None) -> bytes:
+    """snapshot(include, options) as MessagePack bytes, for high-rate local IPC."""
+    return snapshot(include, options, as_msgpack=True)
+
+
+Monitor.prometheus_metrics = _monitor_method(get_prometheus_metrics)
+Monitor.snapshot_msgpack = _monitor_method(get_snapshot_msgpack)
+Monitor.take_snapshot = _monitor_method(take_snapshot)
+Monitor.iter_processes = _monitor_method(iter_processes)
+
+
+def shutdown(timeout: Optional[float] = 5.0) -> None:
+    """Stop every background thread and drop the shared baselines.
+
+    Closes an open recording and stops the sampler, disk sampler, mount
+    watcher, exit accounting, HTTP server, socket publisher, and isolated
+    worker, waiting up to timeout for each; unregisters callbacks; and
+    resets STATE, so the next call starts over as on first import.
+    Embedders that reload Pulse call it first; it also runs at exit.
+    """
+    try:
+        recording.stop_recording(timeout)
+    finally:
+        sampler.stop_sampler(timeout)
+        disk_history.stop_disk_sampler(timeout)
+        mount_watcher.stop_mount_watcher(timeout)
+        exit_accounting.disable_exit_accounting(timeout)
+        http_server.stop_http_server(timeout)
+        socket_publisher.stop_socket_publisher(timeout)
+        isolation.stop_worker(timeout)
+        callbacks.clear_callbacks()
+        STATE.reset()
+
+
+atexit.register(shutdown)
+
+
+def _fail_for_testing() -> None:
+    """Hidden test hook: breaks half-way through a baseline update, as a collector bug would."""
+    active_state().cpu_times = [{'busy': -1.0, 'total': -1.0}]
+    raise IndexError('deliberate failure from _fail_for_testing')
+
+
+_fail_for_testing = diagnostics.guarded('_fail_for_testing', _fail_for_testing)
@@ -0,0 +1,91 @@
+"""
+Pulse CPU Sampling
+Repeated CPU readings over a fixed duration, summarized in one blocking call.
+
+Each reading is due at start + k * interval, an absolute deadline, so time
+spent reading the counters (or a late wakeup) never pushes the later
+samples back and a long run doesn't drift; a reading that overruns later
+deadlines skips them rather than taking the missed readings back to back.
+Percents come from the counters
+read at consecutive deadlines, so the interval each one covers is exactly
+the time between two reads. The call sleeps between readings, leaving other
+threads free to run.
+"""
+import math
+import time
+from typing import Any, Dict, List, Optional
+
+from pulse import backend, validation
+from pulse.collect import cpu_percent
+
+
+def summarize(series: List[float]) -> Dict[str, Optional[float]]:
+    """{min, max, mean, stddev} of series (population stddev); all None when empty."""
+    if not series:
+        return {'min': None, 'max': None, 'mean': None, 'stddev': None}
+    mean = sum(series) / len(series)
+    return {'min': min(series), 'max': max(series), 'mean': mean,
+            'stddev': math.sqrt(sum((v - mean) ** 2 for v in series) / len(series))}
+
+
+def total_percent(previous: List[Dict[str, float]], current: List[Dict[str, float]]) -> float:
+    """Busy percent of all cores together between two per-core readings."""
+    busy = sum(c['busy'] - p['busy'] for p, c in zip(previous, current))
+    total = sum(c['total'] - p['total'] for p, c in zip(previous, current))
+    return min(100.0, max(0.0, busy / total * 100)) if total > 0 else 0.0
+
+
+def sample_cpu(duration_secs: float = 1.0, interval_secs: float = 0.1) -> Dict[str, Any]:
+    """Sample per-core and total CPU percent every interval_secs for duration_secs.
+
+    Returns {samples, interval_secs, elapsed_secs, interrupted, offsets,
+    total, cores}: offsets are each sample's seconds since the start, total
+    is {series, min, max, mean, stddev} over all cores together and cores
+    one such dict per core. Ctrl-C stops early and returns what was
+    collected with interrupted set. Once the number of CPUs changes, later
+    samples are left out. Raises ValueError unless
+    0 < interval_secs <= duration_secs.
+    """
+    validation.positive('duration_secs', duration_secs)
+    validation.positive('interval_secs', interval_secs)
+    if interval_secs > duration_secs:
+        raise ValueError(f'interval_secs must be <= duration_secs ({duration_secs!r}), '
+                         f'got {interval_secs!r}')
+    count = max(1, round(duration_secs / interval_secs))
+    offsets: List[float] = []
+    totals: List[float] = []
+    cores: List[List[float]] = []
+    interrupted = False
+
+    start = time.monotonic()
+    previous = backend.active().cpu_times()
+    try:
+        k = 1
+        while k <= count:
+            delay = start + k * interval_secs - time.monotonic()
+            if delay > 0:
+                time.sleep(delay)
+            current = backend.active().cpu_times()
+            now = time.monotonic()
+            if len(current) == len(previous):
+                if not cores:
+                    cores = [[] for _ in current]
+                if len(cores) == len(current):
+                    offsets.append(now - start)
+                    totals.append(total_percent(previous, current))
+                    for series, prev, curr in zip(cores, previous, current):
+                        series.append(cpu_percent(prev, curr))
+            previous = current
+            k = max(k + 1, math.ceil((time.monotonic() - start) / interval_secs))
+    except KeyboardInterrupt:
+        interrupted = True
+
+    return {
+        'samples': len(offsets),
+        'interval_secs': interval_secs,
+        'elapsed_secs': time.monotonic() - start,
+        'interrupted': interrupted,
+        'offsets': offsets,
+        'total': {'series': totals, **summarize(totals)},
+        'cores': [{'series': series, **summarize(series)} for series in cores],
+    }
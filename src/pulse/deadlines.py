@@ -0,0 +1,81 @@
+"""
+Pulse Call Deadlines
+An upper bound on how long a collector call may keep its caller waiting.
+
+A stale NFS mount or a wedged /proc read can block a collection for good,
+and with it every caller queued on the same subsystem lock. Given a
+timeout_ms (or set_call_timeout()'s default) a collector runs on a worker
+thread; a caller that outwaits it gets Timeout, while the worker finishes in
+the background, releases its lock normally, and leaves fresh baselines for
+the next call. Only one worker per collector, state, and arguments is in
+flight: later callers wait on it rather than piling up blocked threads.
+"""
+import contextvars
+import functools
+import threading
+from concurrent.futures import Future
+from concurrent.futures import TimeoutError as FutureTimeout
+from typing import Any, Callable, Dict, Optional, Tuple
+
+from pulse import validation
+from pulse.errors import Timeout
+from pulse.state import active_state
+
+_default_timeout_ms = 0.0
+
+_lock = threading.Lock()
+# (collector, id of its state, repr of its arguments) -> the running call
+_in_flight: Dict[Tuple[str, int, str], Future] = {}
+
+
+def set_call_timeout(timeout_ms: float) -> None:
+    """Set the timeout_ms collectors use when a call doesn't pass one; 0, the default, waits."""
+    global _default_timeout_ms
+    validation.at_least('timeout_ms', timeout_ms, 0)
+    _default_timeout_ms = timeout_ms
+
+
+def get_call_timeout() -> float:
+    """The timeout_ms set by set_call_timeout(); 0 by default."""
+    return _default_timeout_ms
+
+
+def _run(key: Tuple[str, int, str], future: Future, context: contextvars.Context,
+         function: Callable[..., Any], args: Tuple[Any, ...], kwargs: Dict[str, Any]) -> None:
+    try:
+        result = context.run(function, *args, **kwargs)
+    except BaseException as e:
+        future.set_exception(e)
+    else:
+        future.set_result(result)
+    finally:
+        with _lock:
+            if _in_flight.get(key) is future:
+                del _in_flight[key]
+
+
+def bounded(name: str, function: Callable[..., Any]) -> Callable[..., Any]:
+    """Wrap function with a timeout_ms keyword; past it the caller gets Timeout."""
+    @functools.wraps(function)
+    def call(*args: Any, timeout_ms: Optional[float] = None, **kwargs: Any) -> Any:
+        validation.optional_at_least('timeout_ms', timeout_ms, 0)
+        timeout = _default_timeout_ms if timeout_ms is None else timeout_ms
+        if timeout <= 0:
+            return function(*args, **kwargs)
+
+        key = (name, active_state().token, repr((args, sorted(kwargs.items()))))
+        with _lock:
+            future = _in_flight.get(key)
+            if future is None:
+                future = _in_flight[key] = Future()
+                # The worker sees the caller's state, units, and compat mode
+                worker = threading.Thread(
+                    target=_run, args=(key, future, contextvars.copy_context(), function, args, kwargs),
+                    name=f'pulse-{name}', daemon=True)
+                worker.start()
+        try:
+            return future.result(timeout / 1000)
+        except FutureTimeout:
+            raise Timeout(f'{name} did not finish within {timeout:g} ms; '
+                          'it keeps running in the background') from None
+    return call
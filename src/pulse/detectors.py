@@ -0,0 +1,197 @@
+"""
+Pulse Process Detectors
+Crash loops (a command restarting over and over) and runaways (a process pinned at high CPU).
+
+Both watch the process table across calls. Processes are tracked by
+(pid, start time), so a recycled pid is a new process, and state for
+processes missing for STALE_SECS is dropped. Each getter takes one fresh
+reading and reports against everything seen so far; the first call only
+learns what is already running. The readings keep CPU baselines of their
+own, so polling the detectors doesn't shorten get_process_list()'s window.
+"""
+import hashlib
+import os
+import threading
+import time
+from typing import Any, Dict, List, Optional, Tuple
+
+from pulse import backend, direct_os, log, validation
+from pulse.direct_os import LINUX
+from pulse.processes import PROC
+from pulse.state import SysState, using
+
+# (pid, start time, name, cmdline, cpu_percent); start time only needs to
+# tell two processes with the same pid apart
+Observation = Tuple[int, float, str, str, float]
+
+# State for a process not seen for this long is forgotten
+STALE_SECS = 60.0
+
+# Longest window_secs get_crash_loops() accepts; starts older than this are dropped
+MAX_WINDOW_SECS = 3600.0
+
+# The per-process CPU baselines both detectors read against
+_state = SysState()
+
+
+def cmdline_hash(cmdline: str) -> str:
+    """Short stable digest grouping processes started from the same command line."""
+    return hashlib.sha1(cmdline.encode('utf-8', 'surrogateescape')).hexdigest()[:12]
+
+
+class CrashLoopDetector:
+    """Counts process starts per (name, cmdline hash) from successive readings."""
+
+    def __init__(self) -> None:
+        self.last_seen: Dict[Tuple[int, float], float] = {}
+        # (name, cmdline hash) -> [cmdline, monotonic start times, (pid, start) keys]
+        self.commands: Dict[Tuple[str, str], List[Any]] = {}
+        self.primed = False
+
+    def observe(self, observations: List[Observation], now: float) -> None:
+        """Record one reading taken at monotonic time now."""
+        for pid, start, name, cmdline, _ in observations:
+            key = (pid, start)
+            command = self.commands.setdefault((name, cmdline_hash(cmdline)), [cmdline, [], set()])
+            if key not in self.last_seen and self.primed:
+                command[1].append(now)
+            command[2].add(key)
+            self.last_seen[key] = now
+        self.primed = True
+        stale = [key for key, seen in self.last_seen.items() if now - seen > STALE_SECS]
+        for key in stale:
+            del self.last_seen[key]
+        for group, command in list(self.commands.items()):
+            command[1] = [t for t in command[1] if now - t <= MAX_WINDOW_SECS]
+            command[2] &= self.last_seen.keys()
+            if not command[1] and not command[2]:
+                del self.commands[group]
+
+    def report(self, count: int, window_secs: float, now: float) -> List[Dict[str, Any]]:
+        """Commands started more than count times in the last window_secs, most starts first."""
+        loops = []
+        for (name, digest), (cmdline, starts, keys) in self.commands.items():
+            recent = [t for t in starts if now - t <= window_secs]
+            if len(recent) > count:
+                loops.append({
+                    'name': name,
+                    'cmdline_hash': digest,
+                    'cmdline': cmdline,
+                    'starts': len(recent),
+                    'last_start_secs_ago': now - recent[-1],
+                    'pids': sorted(key[0] for key in keys if self.last_seen.get(key) == now),
+                })
+        loops.sort(key=lambda loop: (-loop['starts'], loop['name']))
+        return loops
+
+
+class RunawayDetector:
+    """Tracks how long each process has stayed above a CPU threshold."""
+
+    def __init__(self) -> None:
+        self.threshold: Optional[float] = None
+        # (pid, start) -> [name, cpu_percent, above since (monotonic) or None, last seen]
+        self.processes: Dict[Tuple[int, float], List[Any]] = {}
+
+    def observe(self, observations: List[Observation], threshold: float, now: float) -> None:
+        """Record one reading; a threshold different from the previous one starts over."""
+        if threshold != self.threshold:
+            self.threshold = threshold
+            self.processes.clear()
+        for pid, start, name, _, cpu in observations:
+            state = self.processes.setdefault((pid, start), [name, cpu, None, now])
+            state[1], state[3] = cpu, now
+            if cpu <= threshold:
+                state[2] = None
+            elif state[2] is None:
+                state[2] = now
+        for key in [k for k, s in self.processes.items() if now - s[3] > STALE_SECS]:
+            del self.processes[key]
+
+    def report(self, sustain_secs: float, now: float) -> List[Dict[str, Any]]:
+        """Processes in the reading at now that have been above the threshold for sustain_secs."""
+        runaways = [
+            {'pid': pid, 'name': name, 'cpu_percent': cpu, 'above_secs': now - since}
+            for (pid, _), (name, cpu, since, seen) in self.processes.items()
+            if seen == now and since is not None and now - since >= sustain_secs
+        ]
+        runaways.sort(key=lambda r: (-r['above_secs'], r['pid']))
+        return runaways
+
+
+if LINUX:
+    def read_processes() -> List[Observation]:
+        """Every visible process with its start time (clock ticks since boot) and cmdline."""
+        with using(_state):
+            rows = direct_os.get_process_rows()
+            # The start times the same read keyed its CPU baselines by
+            starts = backend.active().process_start_times()
+        observations = []
+        for pid, name, cpu, _ in rows:
+            try:
+                with open(f'{PROC}/{pid}/cmdline', 'rb') as f:
+                    cmdline = f.read().rstrip(b'\0').replace(b'\0', b' ')
+            except OSError as e:
+                # Exited since the rows were read
+                log.process_skipped(pid, f'{PROC}/{pid}', e)
+                continue
+            observations.append((pid, starts.get(pid, 0.0), name, os.fsdecode(cmdline), cpu))
+        return observations
+
+else:
+    def read_processes() -> List[Observation]:
+        """Every visible process with its create time and cmdline via psutil."""
+        import psutil
+
+        with using(_state):
+            cpu_by_pid = {pid: cpu for pid, _, cpu, _ in direct_os.get_process_rows()}
+        observations = []
+        for p in psutil.process_iter(['pid', 'name', 'create_time', 'cmdline']):
+            info = p.info
+            if info['pid'] not in cpu_by_pid:
+                continue
+            observations.append((info['pid'], info['create_time'] or 0.0, info['name'] or '?',
+                                 ' '.join(info['cmdline'] or ()), cpu_by_pid[info['pid']]))
+        return observations
+
+
+_lock = threading.Lock()
+_crash_loops = CrashLoopDetector()
+_runaways = RunawayDetector()
+
+
+def get_crash_loops(count: int = 3, window_secs: float = 300) -> List[Dict[str, Any]]:
+    """Get the commands that started more than count times in the last window_secs.
+
+    A command is a process name plus a hash of its command line; each entry
+    is {name, cmdline_hash, cmdline, starts, last_start_secs_ago, pids},
+    pids being the instances running now. Starts are noticed when a call
+    finds a new (pid, start time), so poll more often than the command
+    restarts; the first call counts nothing. window_secs is at most
+    MAX_WINDOW_SECS.
+    """
+    validation.at_least('count', count, 1)
+    validation.between('window_secs', window_secs, 1, MAX_WINDOW_SECS)
+    observations = read_processes()
+    with _lock:
+        now = time.monotonic()
+        _crash_loops.observe(observations, now)
+        return _crash_loops.report(count, window_secs, now)
+
+
+def get_runaway_processes(cpu_threshold: float = 90.0,
+                          sustain_secs: float = 60) -> List[Dict[str, Any]]:
+    """Get the processes above cpu_threshold percent at every call for sustain_secs.
+
+    Each is {pid, name, cpu_percent, above_secs}. A process counts as above
+    from the first call that saw it there, so the calls must come more often
+    than sustain_secs, and changing cpu_threshold starts the tracking over.
+    cpu_percent is per process as in get_process_list() (100 is one core).
+    """
+    validation.positive('cpu_threshold', cpu_threshold)
+    validation.at_least('sustain_secs', sustain_secs, 0)
+    observations = read_processes()
+    with _lock:
+        now = time.monotonic()
+        _runaways.observe(observations, cpu_threshold, now)
+        return _runaways.report(sustain_secs, now)
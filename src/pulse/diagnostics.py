@@ -0,0 +1,120 @@
+"""
+Pulse Diagnostics
+Call counts and durations per collector, to find which one is slow on a host,
+and the guard that turns a collector's bug into a PulseInternalError.
+"""
+import functools
+import platform
+import sys
+import threading
+import time
+from collections import deque
+from typing import Any, Callable, Dict
+
+import pulse
+from pulse.errors import PulseError, PulseInternalError
+
+# What collectors raise on purpose; anything else is a bug in the collector
+EXPECTED_ERRORS = (PulseError, OSError, ValueError, TypeError)
+
+# Durations averaged per collector
+WINDOW = 32
+
+_lock = threading.Lock()
+# Collector name -> {"calls": int, "internal_errors": int, "last_ms": float,
+# "recent": deque of ms}
+_stats: Dict[str, Dict[str, Any]] = {}
+
+
+def _entry(name: str) -> Dict[str, Any]:
+    stats = _stats.get(name)
+    if stats is None:
+        stats = _stats[name] = {'calls': 0, 'internal_errors': 0, 'last_ms': 0.0,
+                                'recent': deque(maxlen=WINDOW)}
+    return stats
+
+
+def _record(name: str, elapsed_ms: float) -> None:
+    with _lock:
+        stats = _entry(name)
+        stats['calls'] += 1
+        stats['last_ms'] = elapsed_ms
+        stats['recent'].append(elapsed_ms)
+
+
+def timed(name: str, function: Callable[..., Any]) -> Callable[..., Any]:
+    """Wrap function so every call, including ones that raise, is recorded under name."""
+    @functools.wraps(function)
+    def recorded(*args: Any, **kwargs: Any) -> Any:
+        started = time.perf_counter()
+        try:
+            return function(*args, **kwargs)
+        finally:
+            _record(name, (time.perf_counter() - started) * 1000)
+    return recorded
+
+
+def guarded(name: str, function: Callable[..., Any]) -> Callable[..., Any]:
+    """Wrap function so an unexpected exception surfaces as PulseInternalError.
+
+    Errors in EXPECTED_ERRORS pass through untouched. Anything else means the
+    collector broke part-way, possibly after replacing some baselines and not
+    others, so the active state is reset before raising; the next call starts
+    from fresh baselines instead of inheriting the damage.
+    """
+    @functools.wraps(function)
+    def contained(*args: Any, **kwargs: Any) -> Any:
+        try:
+            return function(*args, **kwargs)
+        except EXPECTED_ERRORS:
+            raise
+        except Exception as e:
+            from pulse.state import active_state
+
+            active_state().reset()
+            with _lock:
+                _entry(name)['internal_errors'] += 1
+            raise PulseInternalError(f'{name} failed unexpectedly: {type(e).__name__}: {e}') from e
+    return contained
+
+
+def _counts() -> Dict[str, int]:
+    from pulse import direct_os, disks, netstat
+
+    return {
+        'processes': len(direct_os.get_process_rows()),
+        'interfaces': len(netstat.read_interface_counters()),
+        'disks': len(disks.filter_mounts(disks.list_mounts(), True)),
+    }
+
+
+def get_diagnostics() -> Dict[str, Any]:
+    """Per-collector timings plus what the host makes the collectors walk.
+
+    "collectors" maps each called getter to its call count, last duration,
+    and the average of its last WINDOW durations, in milliseconds. "counts"
+    holds the current process, interface, and disk totals.
+    """
+    with _lock:
+        collectors: Dict[str, Dict[str, Any]] = {
+            name: {
+                'calls': stats['calls'],
+                'internal_errors': stats['internal_errors'],
+                'last_ms': stats['last_ms'],
+                'avg_ms': sum(stats['recent']) / len(stats['recent']) if stats['recent'] else 0.0,
+            }
+            for name, stats in _stats.items()
+        }
+    return {
+        'collectors': collectors,
+        'counts': _counts(),
+        'version': pulse.__version__,
+        'python': platform.python_version(),
+        'platform': sys.platform,
+    }
+
+
+def reset_diagnostics() -> None:
+    """Forget every recorded call."""
+    with _lock:
+        _stats.clear()
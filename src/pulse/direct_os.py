@@ -6,16 +6,99 @@ Zero external build dependencies - just pure Python.
 This module provides the same API as psutil but uses direct kernel calls
 for maximum performance on critical paths.
 """
+import itertools
+import operator
 import os
 import sys
 import time
 import signal
-from typing import List, Dict, Optional, Any
+from typing import List, Dict, Optional, Any, Tuple
+
+from pulse import backend, log, validation
+from pulse.collect import (
+    cpu_percent, parse_meminfo, parse_net_dev, parse_pid_stat_ticks, parse_proc_stat, process_cpu_percent,
+)
+from pulse.errors import PermissionDenied, ProcessNotFound
+from pulse.state import SysState, active_state
 
 # Platform detection
 WINDOWS = sys.platform == 'win32'
 LINUX = sys.platform.startswith('linux')
 MACOS = sys.platform == 'darwin'
+FREEBSD = sys.platform.startswith('freebsd')
+
+# Calls closer together than this repeat the previous reading; the kernel's
+# jiffy counters barely move in less time, so a fresh delta would be noise
+_MIN_CPU_INTERVAL = 0.05
+
+# (pid, name, cpu_percent, memory bytes): one process before it becomes a dict
+ProcessRow = Tuple[int, str, float, int]
+
+PROCESS_FIELDS = ('pid', 'name', 'cpu_percent', 'memory_info')
+
+# sort_by values; both sort descending
+SORT_KEYS = ('cpu', 'mem')
+
+_CPU_KEY = operator.itemgetter(2)
+_MEMORY_KEY = operator.itemgetter(3)
+
+# Ids of held process collections, unique across every state
+_snapshot_ids = itertools.count(1)
+
+
+def _cpu_percents_since_last() -> List[float]:
+    """Per-core busy percent between the active state's previous sample and now."""
+    state = active_state()
+    # Read inside the lock so concurrent callers store baselines in time order
+    with state.lock('cpu'):
+        return _cpu_percents_against(state, backend.active().cpu_times())
+
+
+def _cpu_percents_against(state: SysState, current: List[Dict[str, float]]) -> List[float]:
+    now = time.monotonic()
+    if state.cpu_times is None or len(state.cpu_times) != len(current):
+        # First sample (or CPUs hotplugged): only a baseline so far
+        state.cpu_times, state.cpu_time = current, now
+        state.cpu_percents = [0.0] * len(current)
+        return list(state.cpu_percents)
+    if now - state.cpu_time < _MIN_CPU_INTERVAL:
+        return list(state.cpu_percents)
+
+    percents = [cpu_percent(prev, curr) for prev, curr in zip(state.cpu_times, current)]
+    state.cpu_times, state.cpu_time, state.cpu_percents = current, now, percents
+    return list(percents)
+
+
+def _psutil_process_rows(psutil) -> List[ProcessRow]:
+    rows = []
+    for p in psutil.process_iter(['pid', 'name', 'cpu_percent', 'memory_info']):
+        try:
+            info = p.info
+            rows.append((
+                info['pid'],
+                info['name'] or '?',
+                info['cpu_percent'] or 0,
+                info['memory_info'].rss if info['memory_info'] else 0,
+            ))
+        except (psutil.NoSuchProcess, psutil.AccessDenied):
+            continue
+    return rows
+
+
+def _psutil_start_times(psutil) -> Dict[int, float]:
+    starts = {}
+    for p in psutil.process_iter(['create_time']):
+        if p.info['create_time'] is not None:
+            starts[p.pid] = p.info['create_time']
+    return starts
+
+
+def _psutil_cpu_times(psutil) -> List[Dict[str, float]]:
+    times = []
+    for t in psutil.cpu_times(percpu=True):
+        total = sum(t)
+        times.append({'busy': total - t.idle - getattr(t, 'iowait', 0.0), 'total': total})
+    return times
 
 # ============================================================================
 # LINUX IMPLEMENTATION (Uses /proc - already fast!)
@@ -25,202 +108,121 @@ if LINUX:
     
     _CLOCK_TICKS = os.sysconf('SC_CLK_TCK')
     _PAGE_SIZE = os.sysconf('SC_PAGE_SIZE')
-    _last_cpu_times = None
-    _last_cpu_check = 0
     
-    def get_memory_info() -> Dict[str, int]:
-        """Get memory info from /proc/meminfo."""
-        mem = {}
+    def read_memory_info() -> Dict[str, int]:
+        """Get memory info from /proc/meminfo.
+
+        WSL2 grows and shrinks its VM's memory on demand, so there total is
+        the ceiling Windows grants right now and can change between readings;
+        is_dynamic marks it.
+        """
+        from pulse import environment
+
         try:
             with open('/proc/meminfo', 'r') as f:
-                for line in f:
-                    parts = line.split()
-                    key = parts[0].rstrip(':')
-                    value = int(parts[1]) * 1024  # Convert KB to bytes
-                    if key == 'MemTotal':
-                        mem['total'] = value
-                    elif key == 'MemAvailable':
-                        mem['available'] = value
-                    elif key == 'MemFree':
-                        mem['free'] = value
-                    elif key == 'Buffers':
-                        mem['buffers'] = value
-                    elif key == 'Cached':
-                        mem['cached'] = value
-                    elif key == 'SwapTotal':
-                        mem['swap_total'] = value
-                    elif key == 'SwapFree':
-                        mem['swap_free'] = value
+                mem = parse_meminfo(f.read())
         except Exception:
-            pass
-        
-        mem['used'] = mem.get('total', 0) - mem.get('available', 0)
-        mem['swap_used'] = mem.get('swap_total', 0) - mem.get('swap_free', 0)
-        mem['percent'] = (mem['used'] / mem['total'] * 100) if mem.get('total') else 0
+            mem = parse_meminfo('')
+        mem['is_dynamic'] = environment.running_under_wsl()
         return mem
     
-    def get_cpu_percents() -> List[float]:
-        """Get per-core CPU percentages from /proc/stat."""
-        global _last_cpu_times, _last_cpu_check
-        
-        def read_cpu_times():
-            times = []
-            with open('/proc/stat', 'r') as f:
-                for line in f:
-                    if line.startswith('cpu') and not line.startswith('cpu '):
-                        parts = line.split()[1:]
-                        # user, nice, system, idle, iowait, irq, softirq
-                        user = int(parts[0])
-                        nice = int(parts[1])
-                        system = int(parts[2])
-                        idle = int(parts[3])
-                        iowait = int(parts[4]) if len(parts) > 4 else 0
-                        times.append({
-                            'busy': user + nice + system,
-                            'total': user + nice + system + idle + iowait
-                        })
-            return times
-        
-        current = read_cpu_times()
-        now = time.time()
-        
-        if _last_cpu_times is None or now - _last_cpu_check > 0.05: # Reduced from 0.1 for high-res pulse
-            _last_cpu_times = current
-            _last_cpu_check = now
-            return [0.0] * len(current)
-        
-        percents = []
-        for prev, curr in zip(_last_cpu_times, current):
-            delta_busy = curr['busy'] - prev['busy']
-            delta_total = curr['total'] - prev['total']
-            if delta_total > 0:
-                percents.append(min(100.0, (delta_busy / delta_total) * 100))
-            else:
-                percents.append(0.0)
-        
-        # Do NOT update _last_cpu_times here if we want a rolling window?
-        # Actually standard psutil logic IS to update.
-        _last_cpu_times = current
-        _last_cpu_check = now
-        return percents
-    
-    def get_process_list(sort_by: Optional[str] = None, limit: Optional[int] = None) -> List[Dict[str, Any]]:
-        """Get process list from /proc filesystem."""
-        processes = []
-        
+    def read_cpu_times() -> List[Dict[str, float]]:
+        """Per-core {busy, total} jiffies from /proc/stat."""
+        with open('/proc/stat', 'r') as f:
+            return parse_proc_stat(f.read())
+
+    def read_process_rows() -> List[ProcessRow]:
+        """Process rows from the /proc filesystem.
+
+        Everything comes from one read of /proc/<pid>/stat: comm is the same
+        (15-character) name /proc/<pid>/comm holds, and vsize the same bytes as
+        /proc/<pid>/statm's size field times the page size. cpu_percent is
+        the growth of utime + stime since the active state's previous read,
+        tracked by (pid, start time) so a reused pid starts over; a process
+        that read hasn't seen reports 0.
+        """
+        readings = []
+        append = readings.append
+
         for pid_str in os.listdir('/proc'):
             if not pid_str.isdigit():
                 continue
-            
-            pid = int(pid_str)
+
             try:
-                # Read comm (process name)
-                with open(f'/proc/{pid}/comm', 'r') as f:
-                    name = f.read().strip()
-                
-                # Read stat for CPU info
-                with open(f'/proc/{pid}/stat', 'r') as f:
-                    stat = f.read().split()
-                
-                # Read statm for memory
-                with open(f'/proc/{pid}/statm', 'r') as f:
-                    statm = f.read().split()
-                
-                utime = int(stat[13])
-                stime = int(stat[14])
-                memory_pages = int(statm[0])
-                
-                processes.append({
-                    'pid': pid,
-                    'name': name,
-                    'cpu_percent': 0,  # Would need delta tracking per-process
-                    'memory_info': memory_pages * _PAGE_SIZE,
-                })
-            except (FileNotFoundError, PermissionError, IndexError):
+                with open(f'/proc/{pid_str}/stat', 'rb') as f:
+                    append(parse_pid_stat_ticks(int(pid_str), f.read()))
+            except (FileNotFoundError, ProcessLookupError, PermissionError, IndexError,
+                    ValueError) as e:
+                log.process_skipped(int(pid_str), f'/proc/{pid_str}/stat', e)
                 continue
-        
-        # Sort
-        if sort_by == 'cpu':
-            processes.sort(key=lambda x: x['cpu_percent'], reverse=True)
-        elif sort_by == 'mem':
-            processes.sort(key=lambda x: x['memory_info'], reverse=True)
-        
-        if limit:
-            processes = processes[:limit]
-        
-        return processes
-    
-    def get_network_stats() -> Dict[str, int]:
+        return _with_cpu_percents(readings)
+
+    def _with_cpu_percents(readings: List[Tuple[ProcessRow, int, int]]) -> List[ProcessRow]:
+        state = active_state()
+        now = time.monotonic()
+        with state.lock('processes'):
+            previous = state.process_cpu
+            if previous is not None and now - previous[0] < _MIN_CPU_INTERVAL:
+                # Too soon for a fresh delta: repeat the previous read's percents
+                known = previous[1]
+                return [(row[0], row[1], known[(row[0], start)][1] if (row[0], start) in known else 0.0,
+                         row[3]) for row, _, start in readings]
+            baseline = previous[1] if previous is not None else {}
+            elapsed = now - previous[0] if previous is not None else 0.0
+            rows = []
+            current = {}
+            for row, ticks, start in readings:
+                key = (row[0], start)
+                before = baseline.get(key)
+                percent = process_cpu_percent(before[0], ticks, elapsed, _CLOCK_TICKS) if before else 0.0
+                current[key] = (ticks, percent)
+                rows.append((row[0], row[1], percent, row[3]))
+            state.process_cpu = (now, current)
+        return rows
+
+    def read_process_start_times() -> Dict[int, float]:
+        """pid -> start time (clock ticks after boot) of each process the active state's last read saw."""
+        state = active_state()
+        with state.lock('processes'):
+            held = state.process_cpu
+        return {pid: start for pid, start in held[1]} if held is not None else {}
+
+    def read_network_stats() -> Dict[str, int]:
         """Get network I/O from /proc/net/dev."""
-        total_recv = 0
-        total_sent = 0
-        
         try:
             with open('/proc/net/dev', 'r') as f:
-                for line in f:
-                    if ':' in line:
-                        parts = line.split()
-                        iface = parts[0].rstrip(':')
-                        if iface != 'lo':  # Skip loopback
-                            total_recv += int(parts[1])
-                            total_sent += int(parts[9])
+                return parse_net_dev(f.read())
         except Exception:
-            pass
-        
-        return {'bytes_recv': total_recv, 'bytes_sent': total_sent}
+            return parse_net_dev('')
     
-    def get_disk_info() -> List[Dict[str, Any]]:
-        """Get disk usage from /proc/mounts and statvfs."""
-        disks = []
-        seen = set()
-        
-        try:
-            with open('/proc/mounts', 'r') as f:
-                for line in f:
-                    parts = line.split()
-                    device = parts[0]
-                    mount = parts[1]
-                    fstype = parts[2]
-                    
-                    if not device.startswith('/dev/') or device in seen:
-                        continue
-                    seen.add(device)
-                    
-                    try:
-                        stat = os.statvfs(mount)
-                        total = stat.f_blocks * stat.f_frsize
-                        free = stat.f_bfree * stat.f_frsize
-                        used = total - free
-                        
-                        disks.append({
-                            'device': device,
-                            'mountpoint': mount,
-                            'fstype': fstype,
-                            'total': total,
-                            'used': used,
-                            'free': free,
-                            'percent': (used / total * 100) if total else 0
-                        })
-                    except OSError:
-                        continue
-        except Exception:
-            pass
-        
-        return disks
-
     def kill_process(pid: int) -> None:
-        """Kill a process."""
+        """Kill a process.
+
+        Raises ProcessNotFound when it doesn't exist and PermissionDenied when
+        it belongs to another user.
+        """
+        validation.at_least('pid', pid, 1)
         try:
             os.kill(pid, signal.SIGTERM)
         except ProcessLookupError:
-            pass
+            raise ProcessNotFound.for_pid(pid) from None
+        except PermissionError:
+            raise PermissionDenied(f'Not allowed to signal pid {pid}') from None
 
     def renice_process(pid: int, nice_value: int) -> None:
-        """Change process priority (nice value)."""
+        """Change process priority (nice value).
+
+        Raises ProcessNotFound when it doesn't exist and PermissionDenied when
+        it belongs to another user or the value is below what may be set.
+        """
+        validation.at_least('pid', pid, 1)
+        validation.between('nice_value', nice_value, -20, 19)
         try:
             os.setpriority(os.PRIO_PROCESS, pid, nice_value)
-        except (ProcessLookupError, PermissionError):
-            pass
+        except ProcessLookupError:
+            raise ProcessNotFound.for_pid(pid) from None
+        except PermissionError:
+            raise PermissionDenied(f'Not allowed to renice pid {pid} to {nice_value}') from None
 
 # ============================================================================
 # WINDOWS IMPLEMENTATION (Uses ctypes + kernel32/psapi)
@@ -248,7 +250,7 @@ elif WINDOWS:
             ('ullAvailExtendedVirtual', ctypes.c_ulonglong),
         ]
     
-    def get_memory_info() -> Dict[str, int]:
+    def read_memory_info() -> Dict[str, int]:
         """Get memory info using GlobalMemoryStatusEx."""
         mem_status = MEMORYSTATUSEX()
         mem_status.dwLength = ctypes.sizeof(MEMORYSTATUSEX)
@@ -273,90 +275,50 @@ elif WINDOWS:
             _psutil_fallback = psutil
         return _psutil_fallback
     
-    def get_cpu_percents() -> List[float]:
-        """Get per-core CPU percentages."""
-        return _get_psutil().cpu_percent(percpu=True)
-    
-    def get_process_list(sort_by: Optional[str] = None, limit: Optional[int] = None) -> List[Dict[str, Any]]:
-        """Get process list using Windows API."""
+    def read_cpu_times() -> List[Dict[str, float]]:
+        return _psutil_cpu_times(_get_psutil())
+
+    def read_process_rows() -> List[ProcessRow]:
+        """Process rows using Windows API."""
         # For Windows, psutil is actually quite optimized, so we use it
-        psutil = _get_psutil()
-        processes = []
-        
-        for p in psutil.process_iter(['pid', 'name', 'cpu_percent', 'memory_info']):
-            try:
-                info = p.info
-                processes.append({
-                    'pid': info['pid'],
-                    'name': info['name'] or '?',
-                    'cpu_percent': info['cpu_percent'] or 0,
-                    'memory_info': info['memory_info'].rss if info['memory_info'] else 0,
-                })
-            except (psutil.NoSuchProcess, psutil.AccessDenied):
-                continue
-        
-        if sort_by == 'cpu':
-            processes.sort(key=lambda x: x['cpu_percent'], reverse=True)
-        elif sort_by == 'mem':
-            processes.sort(key=lambda x: x['memory_info'], reverse=True)
-        
-        if limit:
-            processes = processes[:limit]
-        
-        return processes
+        return _psutil_process_rows(_get_psutil())
+
+    def read_process_start_times() -> Dict[int, float]:
+        return _psutil_start_times(_get_psutil())
     
-    def get_network_stats() -> Dict[str, int]:
+    def read_network_stats() -> Dict[str, int]:
         """Get network I/O."""
         stats = _get_psutil().net_io_counters()
         return {'bytes_recv': stats.bytes_recv, 'bytes_sent': stats.bytes_sent}
     
-    def get_disk_info() -> List[Dict[str, Any]]:
-        """Get disk usage."""
-        psutil = _get_psutil()
-        disks = []
-        
-        for part in psutil.disk_partitions():
-            try:
-                usage = psutil.disk_usage(part.mountpoint)
-                disks.append({
-                    'device': part.device,
-                    'mountpoint': part.mountpoint,
-                    'fstype': part.fstype,
-                    'total': usage.total,
-                    'used': usage.used,
-                    'free': usage.free,
-                    'percent': usage.percent
-                })
-            except (PermissionError, OSError):
-                continue
-        
-        return disks
+    def kill_process(pid: int) -> None:
+        """Kill a process, falling back to taskkill /F when psutil is refused.
 
-    def kill_process(pid: int) -> str:
-        """Kill a process with force fallback."""
+        Raises ProcessNotFound when it doesn't exist and PermissionDenied when
+        the force kill fails too.
+        """
+        validation.at_least('pid', pid, 1)
         try:
             p = _get_psutil().Process(pid)
             p.kill() # Try SIGKILL/Terminate immediately
             try:
                 p.wait(timeout=0.1)
-            except: pass
-            return f"Terminated PID {pid}"
+            except _get_psutil().TimeoutExpired:
+                pass
         except _get_psutil().NoSuchProcess:
-            return f"Process {pid} already gone"
+            raise ProcessNotFound.for_pid(pid) from None
         except _get_psutil().AccessDenied:
             # Windows Force Kill
             import subprocess
-            try:
-                subprocess.run(["taskkill", "/F", "/PID", str(pid)], 
-                             capture_output=True, creationflags=0x08000000) # CREATE_NO_WINDOW
-                return f"Force Killed PID {pid}"
-            except Exception as e:
-                return f"Access Denied: {e}"
-        except Exception as e:
-            return f"Kill Failed: {e}"
+            result = subprocess.run(["taskkill", "/F", "/PID", str(pid)],
+                                    capture_output=True, creationflags=0x08000000) # CREATE_NO_WINDOW
+            if result.returncode != 0:
+                raise PermissionDenied(f'Not allowed to signal pid {pid}') from None
 
     def renice_process(pid: int, nice_value: int) -> None:
         """Change process priority."""
+        validation.at_least('pid', pid, 1)
+        validation.between('nice_value', nice_value, -20, 19)
         try:
             p = _get_psutil().Process(pid)
             # Windows priority classes: IDLE, BELOW_NORMAL, NORMAL, ABOVE_NORMAL, HIGH, REALTIME
@@ -367,8 +329,10 @@ elif WINDOWS:
             elif nice_value < 10: priority = _get_psutil().BELOW_NORMAL_PRIORITY_CLASS
             else: priority = _get_psutil().IDLE_PRIORITY_CLASS
             p.nice(priority)
-        except:
-            pass
+        except _get_psutil().NoSuchProcess:
+            raise ProcessNotFound.for_pid(pid) from None
+        except _get_psutil().AccessDenied:
+            raise PermissionDenied(f'Not allowed to change the priority of pid {pid}') from None
 
 # ============================================================================
 # MACOS IMPLEMENTATION
@@ -377,78 +341,214 @@ elif MACOS:
     # For macOS, we use psutil as the /proc alternatives are limited
     import psutil
     
-    def get_memory_info() -> Dict[str, int]:
-        mem = psutil.virtual_memory()
-        swap = psutil.swap_memory()
-        return {
-            'total': mem.total,
-            'available': mem.available,
-            'used': mem.used,
-            'percent': mem.percent,
-            'swap_total': swap.total,
-            'swap_used': swap.used,
-        }
-    
-    def get_cpu_percents() -> List[float]:
-        return psutil.cpu_percent(percpu=True)
+    def read_memory_info() -> Dict[str, int]:
+        """Get memory as Activity Monitor counts it, plus compressed bytes and pressure_level."""
+        from pulse import macos
+
+        return macos.read_memory_info()
     
-    def get_process_list(sort_by: Optional[str] = None, limit: Optional[int] = None) -> List[Dict[str, Any]]:
-        processes = []
-        for p in psutil.process_iter(['pid', 'name', 'cpu_percent', 'memory_info']):
-            try:
-                info = p.info
-                processes.append({
-                    'pid': info['pid'],
-                    'name': info['name'] or '?',
-                    'cpu_percent': info['cpu_percent'] or 0,
-                    'memory_info': info['memory_info'].rss if info['memory_info'] else 0,
-                })
-            except (psutil.NoSuchProcess, psutil.AccessDenied):
-                continue
-        
-        if sort_by == 'cpu':
-            processes.sort(key=lambda x: x['cpu_percent'], reverse=True)
-        elif sort_by == 'mem':
-            processes.sort(key=lambda x: x['memory_info'], reverse=True)
-        
-        if limit:
-            processes = processes[:limit]
-        
-        return processes
+    def read_cpu_times() -> List[Dict[str, float]]:
+        return _psutil_cpu_times(psutil)
+
+    def read_process_rows() -> List[ProcessRow]:
+        return _psutil_process_rows(psutil)
+
+    def read_process_start_times() -> Dict[int, float]:
+        return _psutil_start_times(psutil)
     
-    def get_network_stats() -> Dict[str, int]:
+    def read_network_stats() -> Dict[str, int]:
         stats = psutil.net_io_counters()
         return {'bytes_recv': stats.bytes_recv, 'bytes_sent': stats.bytes_sent}
     
-    def get_disk_info() -> List[Dict[str, Any]]:
-        disks = []
-        for part in psutil.disk_partitions():
-            try:
-                usage = psutil.disk_usage(part.mountpoint)
-                disks.append({
-                    'device': part.device,
-                    'mountpoint': part.mountpoint,
-                    'fstype': part.fstype,
-                    'total': usage.total,
-                    'used': usage.used,
-                    'free': usage.free,
-                    'percent': usage.percent
-                })
-            except (PermissionError, OSError):
-                continue
-        return disks
-
     def kill_process(pid: int) -> None:
+        """Kill a process.
+
+        Raises ProcessNotFound when it doesn't exist and PermissionDenied when
+        it belongs to another user.
+        """
+        validation.at_least('pid', pid, 1)
         try:
             os.kill(pid, signal.SIGTERM)
-        except:
-            pass
+        except ProcessLookupError:
+            raise ProcessNotFound.for_pid(pid) from None
+        except PermissionError:
+            raise PermissionDenied(f'Not allowed to signal pid {pid}') from None
 
     def renice_process(pid: int, nice_value: int) -> None:
+        """Change process priority (nice value).
+
+        Raises ProcessNotFound when it doesn't exist and PermissionDenied when
+        it belongs to another user or the value is below what may be set.
+        """
+        validation.at_least('pid', pid, 1)
+        validation.between('nice_value', nice_value, -20, 19)
         try:
             psutil.Process(pid).nice(nice_value)
-        except:
-            pass
+        except psutil.NoSuchProcess:
+            raise ProcessNotFound.for_pid(pid) from None
+        except psutil.AccessDenied:
+            raise PermissionDenied(f'Not allowed to renice pid {pid} to {nice_value}') from None
+
+# ============================================================================
+# FREEBSD IMPLEMENTATION
+# ============================================================================
+elif FREEBSD:
+    # No procfs by default: memory and CPU times come from sysctl, processes
+    # and network counters from psutil as on macOS
+    import psutil
+
+    from pulse import freebsd
+
+    def read_memory_info() -> Dict[str, int]:
+        """Get memory info from hw.physmem, the vm.stats.vm page counters, and vm.swap_info."""
+        return freebsd.read_memory_info()
+
+    def read_cpu_times() -> List[Dict[str, float]]:
+        """Per-core {busy, total} ticks from kern.cp_times."""
+        return freebsd.read_cp_times()
+
+    def read_process_rows() -> List[ProcessRow]:
+        return _psutil_process_rows(psutil)
+
+    def read_process_start_times() -> Dict[int, float]:
+        return _psutil_start_times(psutil)
+
+    def read_network_stats() -> Dict[str, int]:
+        stats = psutil.net_io_counters()
+        return {'bytes_recv': stats.bytes_recv, 'bytes_sent': stats.bytes_sent}
+
+    def kill_process(pid: int) -> None:
+        """Kill a process.
+
+        Raises ProcessNotFound when it doesn't exist and PermissionDenied when
+        it belongs to another user.
+        """
+        validation.at_least('pid', pid, 1)
+        try:
+            os.kill(pid, signal.SIGTERM)
+        except ProcessLookupError:
+            raise ProcessNotFound.for_pid(pid) from None
+        except PermissionError:
+            raise PermissionDenied(f'Not allowed to signal pid {pid}') from None
+
+    def renice_process(pid: int, nice_value: int) -> None:
+        """Change process priority (nice value).
+
+        Raises ProcessNotFound when it doesn't exist and PermissionDenied when
+        it belongs to another user or the value is below what may be set.
+        """
+        validation.at_least('pid', pid, 1)
+        validation.between('nice_value', nice_value, -20, 19)
+        try:
+            os.setpriority(os.PRIO_PROCESS, pid, nice_value)
+        except ProcessLookupError:
+            raise ProcessNotFound.for_pid(pid) from None
+        except PermissionError:
+            raise PermissionDenied(f'Not allowed to renice pid {pid} to {nice_value}') from None
+
+# ============================================================================
+# READINGS (all platforms, through the active backend)
+# ============================================================================
+def get_memory_info() -> Dict[str, Any]:
+    """Get physical memory and swap in bytes: total, available, used, percent, swap_*."""
+    return backend.active().memory()
+
+
+def get_cpu_percents() -> List[float]:
+    """Get per-core CPU percentages since the previous call."""
+    return _cpu_percents_since_last()
+
+
+def get_network_stats(physical_only: bool = False, dedupe_bonded: bool = False) -> Dict[str, int]:
+    """Get cumulative network I/O: bytes_recv and bytes_sent over all interfaces.
+
+    dedupe_bonded counts only interfaces that aren't a bond's slave or a
+    bridge's port, so bonded and bridged traffic is counted once;
+    physical_only does the same and also leaves out stacks with no physical
+    interface underneath (see pulse.interfaces).
+    """
+    if physical_only or dedupe_bonded:
+        from pulse import interfaces
+
+        return interfaces.network_totals(physical_only)
+    return backend.active().network()
+
+
+# ============================================================================
+# PROCESS LIST (all platforms)
+# ============================================================================
+def collect_process_rows() -> Tuple[int, List[ProcessRow]]:
+    """Read the process rows and hold them in the active state under a new snapshot id."""
+    rows = backend.active().process_rows()
+    snapshot_id = next(_snapshot_ids)
+    active_state().process_rows = (snapshot_id, rows)
+    return snapshot_id, rows
+
+
+def _held_rows(snapshot_id: int) -> List[ProcessRow]:
+    held = active_state().process_rows
+    if held is None or held[0] != snapshot_id:
+        latest = f'; the latest is {held[0]}' if held is not None else ''
+        raise ValueError(f'snapshot_id {snapshot_id} is no longer held{latest}')
+    return list(held[1])
+
+
+def get_process_rows(sort_by: Optional[str] = None, limit: Optional[int] = None,
+                     snapshot_id: Optional[int] = None) -> List[ProcessRow]:
+    """Process rows sorted by 'cpu' or 'mem' (descending) and cut to limit.
+
+    snapshot_id reuses the collection get_process_changes() returned that id
+    for instead of reading again. Raises ValueError for another sort_by, a
+    limit below 1, or a snapshot_id other than the latest one.
+    """
+    if sort_by is not None:
+        validation.one_of('sort_by', sort_by, SORT_KEYS)
+    validation.optional_at_least('limit', limit, 1)
+    rows = backend.active().process_rows() if snapshot_id is None else _held_rows(snapshot_id)
+    if sort_by == 'cpu':
+        rows.sort(key=_CPU_KEY, reverse=True)
+    elif sort_by == 'mem':
+        rows.sort(key=_MEMORY_KEY, reverse=True)
+    if limit is not None:
+        rows = rows[:limit]
+    return rows
+
+
+def process_dict(row: ProcessRow) -> Dict[str, Any]:
+    """The get_process_list() dict for one row."""
+    return {'pid': row[0], 'name': row[1], 'cpu_percent': row[2], 'memory_info': row[3]}
+
+
+def get_process_list(sort_by: Optional[str] = None, limit: Optional[int] = None,
+                     snapshot_id: Optional[int] = None,
+                     realtime_only: bool = False) -> List[Dict[str, Any]]:
+    """Get process list: pid, name, cpu_percent, and memory_info (bytes) per process.
+
+    With snapshot_id, from the collection get_process_changes() took in this tick.
+    While set_process_tags() rules are set, each entry also has its "tags".
+    realtime_only keeps the processes scheduled fifo, rr, or deadline (limit
+    applies after the filter), each with its sched_policy and rt_priority.
+    """
+    from pulse import processes, tags
+
+    if not realtime_only:
+        rows = get_process_rows(sort_by, limit, snapshot_id)
+        entries = [process_dict(row) for row in rows]
+    else:
+        validation.optional_at_least('limit', limit, 1)
+        rows, entries = [], []
+        for row in get_process_rows(sort_by, None, snapshot_id):
+            sched = processes.is_realtime(row[0])
+            if sched is not None:
+                rows.append(row)
+                entries.append({**process_dict(row), **sched})
+        rows, entries = rows[:limit], entries[:limit]
+    tagged = tags.tag_rows(rows, complete=limit is None and not realtime_only)
+    if tagged is not None:
+        for entry, row_tags in zip(entries, tagged):
+            entry['tags'] = row_tags
+    return entries
+
 
 # ============================================================================
 # INITIALIZATION
@@ -0,0 +1,111 @@
+"""
+Pulse Disk History
+Background sampling of per-mount usage and fill-rate projection.
+"""
+import threading
+import time
+from collections import deque
+from typing import Any, Deque, Dict, List, Optional, Sequence, Tuple
+
+from pulse import disks, validation
+
+# Per-mount samples kept; a day of history at the default one-minute interval
+MAX_SAMPLES = 1440
+
+# Slopes below this many bytes per second count as a flat trend
+_FLAT_SLOPE = 1e-6
+
+# (monotonic timestamp, used bytes, available bytes)
+Sample = Tuple[float, int, int]
+
+_history: Dict[str, Deque[Sample]] = {}
+_history_lock = threading.Lock()
+_thread: Optional[threading.Thread] = None
+_stop = threading.Event()
+_control_lock = threading.Lock()
+
+
+def linear_fit(points: Sequence[Tuple[float, float]]) -> Optional[Tuple[float, float]]:
+    """Least-squares (slope, intercept) through (x, y) points.
+
+    None with fewer than two points or when every x is identical.
+    """
+    n = len(points)
+    if n < 2:
+        return None
+    mean_x = sum(x for x, _ in points) / n
+    mean_y = sum(y for _, y in points) / n
+    sxx = sum((x - mean_x) ** 2 for x, _ in points)
+    if sxx == 0:
+        return None
+    sxy = sum((x - mean_x) * (y - mean_y) for x, y in points)
+    slope = sxy / sxx
+    return slope, mean_y - slope * mean_x
+
+
+def project(samples: Sequence[Sample]) -> Dict[str, Any]:
+    """Fill rate and time-to-full from a series of usage samples.
+
+    time_to_full_secs is None when the trend is flat or shrinking.
+    """
+    fit = linear_fit([(t, used) for t, used, _ in samples])
+    if fit is None:
+        return {'samples': len(samples), 'fill_rate_bytes_per_day': None, 'time_to_full_secs': None}
+    slope = fit[0]
+    available = samples[-1][2]
+    return {
+        'samples': len(samples),
+        'fill_rate_bytes_per_day': slope * 86400,
+        'time_to_full_secs': available / slope if slope > _FLAT_SLOPE else None,
+    }
+
+
+def _record(timestamp: float, entries: List[Dict[str, Any]]) -> None:
+    with _history_lock:
+        for disk in entries:
+            if disk['used'] is None:
+                continue
+            samples = _history.get(disk['mountpoint'])
+            if samples is None:
+                samples = _history[disk['mountpoint']] = deque(maxlen=MAX_SAMPLES)
+            samples.append((timestamp, disk['used'], disk['available']))
+
+
+def _run(interval_secs: float) -> None:
+    while not _stop.is_set():
+        _record(time.monotonic(), disks.get_disk_info())
+        _stop.wait(interval_secs)
+
+
+def start_disk_sampler(interval_secs: float = 60.0) -> None:
+    """Start recording per-mount used bytes every interval_secs; a no-op if running."""
+    validation.positive('interval_secs', interval_secs)
+    global _thread
+    with _control_lock:
+        if _thread is not None and _thread.is_alive():
+            return
+        _stop.clear()
+        _thread = threading.Thread(
+            target=_run, args=(interval_secs,), name='pulse-disk-sampler', daemon=True,
+        )
+        _thread.start()
+
+
+def stop_disk_sampler(timeout: Optional[float] = None) -> None:
+    """Stop the sampler thread; recorded history is kept."""
+    global _thread
+    with _control_lock:
+        _stop.set()
+        if _thread is not None:
+            _thread.join(timeout)
+            _thread = None
+
+
+def get_disk_projection(mount_point: str) -> Dict[str, Any]:
+    """Project when a mount fills up from the sampler's history.
+
+    Raises KeyError for a mount point the sampler hasn't recorded.
+    """
+    with _history_lock:
+        samples = list(_history[mount_point])
+    return {'mount_point': mount_point, **project(samples)}
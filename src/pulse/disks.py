@@ -0,0 +1,1104 @@
+"""
+Pulse Storage Telemetry
+Block device activity and filesystem details beyond basic capacity.
+"""
+import glob
+import os
+import re
+import struct
+import threading
+import time
+from typing import Any, Dict, Iterable, List, Optional
+
+from pulse import backend, environment, fs_special, log, sensors, validation
+from pulse.collect import counter_delta, counter_rate
+from pulse.direct_os import LINUX, WINDOWS
+from pulse.state import active_state
+
+PROC_DISKSTATS = '/proc/diskstats'
+PROC_MOUNTS = '/proc/mounts'
+SYS_CLASS_BLOCK = '/sys/class/block'
+DEV = '/dev'
+DEV_DISK_BY_LABEL = '/dev/disk/by-label'
+
+# Filesystems hidden from get_disk_info() unless physical_only=False or overridden.
+# Snap/Flatpak images, container layers, RAM-backed and kernel interface mounts.
+PSEUDO_FILESYSTEMS = frozenset({
+    'squashfs', 'overlay', 'tmpfs', 'devtmpfs', 'ramfs', 'proc', 'sysfs',
+    'devpts', 'cgroup', 'cgroup2', 'mqueue', 'debugfs', 'tracefs', 'securityfs',
+    'pstore', 'bpf', 'configfs', 'fusectl', 'hugetlbfs', 'autofs', 'binfmt_misc',
+    'efivarfs', 'nsfs', 'rpc_pipefs', 'devfs',
+})
+
+# /proc/diskstats always counts 512-byte sectors regardless of the device
+SECTOR_SIZE = 512
+
+# Field names after "major minor name", in kernel order (Documentation/admin-guide/iostats.rst).
+# Kernels before 4.18 stop after weighted_io_time_ms (14 columns total), 4.18 adds
+# the discard group (18) and 5.5 the flush group (20).
+_DISKSTATS_FIELDS = [
+    'read_count', 'read_merged', 'read_sectors', 'read_time_ms',
+    'write_count', 'write_merged', 'write_sectors', 'write_time_ms',
+    'in_progress', 'io_time_ms', 'weighted_io_time_ms',
+    'discard_count', 'discard_merged', 'discard_sectors', 'discard_time_ms',
+    'flush_count', 'flush_time_ms',
+]
+
+# Remote filesystems whose space queries can hang when the server goes away.
+# sshfs shows up as fuse.sshfs in /proc/mounts.
+NETWORK_FILESYSTEMS = frozenset({
+    'nfs', 'nfs4', 'cifs', 'smbfs', 'smb3', 'sshfs', 'fuse.sshfs', 'afpfs', 'webdav',
+})
+
+# Filesystems WSL mounts Windows drives with: drvfs under WSL1, 9p under WSL2
+WSL_DRIVE_FILESYSTEMS = frozenset({'drvfs', '9p'})
+
+_DRIVE_LETTER = re.compile(r'^[A-Za-z]:')
+
+# Seconds to wait for a network filesystem's space query before marking it stale
+NETWORK_TIMEOUT_SECS = 2.0
+
+# Cumulative counter -> rate key emitted by get_disk_io_rates()
+_RATE_KEYS = {
+    'read_bytes': 'read_bytes_per_sec',
+    'write_bytes': 'write_bytes_per_sec',
+    'read_count': 'read_ops_per_sec',
+    'write_count': 'write_ops_per_sec',
+}
+
+# Whole-disk names whose partitions append "p<N>" (nvme0n1p2, mmcblk0p1)
+_P_SEPARATED = re.compile(r'^((?:nvme|mmcblk|loop|nbd|md)\S*\d)p\d+$')
+# Classic names whose partitions append the number directly (sda1, vdb3, xvda1)
+_NUMBER_SUFFIXED = re.compile(r'^((?:sd|hd|vd|xvd)[a-z]+)\d+$')
+
+# Device name prefixes that never correspond to physical storage
+_VIRTUAL_PREFIXES = ('loop', 'ram', 'zram')
+
+
+def disk_percent(used: int, available: int) -> float:
+    """Usage percent relative to used + available space, matching df and psutil.
+
+    Reserved root blocks are excluded, and pseudo-mounts reporting no space give 0.
+    """
+    usable = used + available
+    return (used / usable * 100) if usable > 0 else 0.0
+
+
+def inode_usage(total: Optional[int], free: Optional[int]) -> Dict[str, Any]:
+    """Inode totals for a filesystem; None throughout when it has no inode table.
+
+    FAT, btrfs, and some network filesystems report f_files == 0, which must not
+    read as "100% used" to dashboards.
+    """
+    if not total or free is None:
+        return {'inodes_total': None, 'inodes_used': None, 'inodes_free': None, 'inodes_percent': None}
+    used = total - free
+    return {
+        'inodes_total': total,
+        'inodes_used': used,
+        'inodes_free': free,
+        'inodes_percent': used / total * 100,
+    }
+
+
+def unescape_mount_field(field: str) -> str:
+    """Undo the kernel's octal escaping in /proc/mounts (\\040 for space, \\011 tab, ...)."""
+    return re.sub(r'\\([0-7]{3})', lambda m: chr(int(m.group(1), 8)), field)
+
+
+def parse_mount_options(raw: str) -> List[str]:
+    """Split a comma-separated option string into its options.
+
+    Values keep any '=' they contain (uid=1000, subvol=/@home), and commas inside
+    double quotes don't split, as in SELinux context="system_u:...:s0:c1,c2".
+    """
+    options = []
+    current = []
+    quoted = False
+    for char in raw:
+        if char == '"':
+            quoted = not quoted
+        elif char == ',' and not quoted:
+            if current:
+                options.append(''.join(current))
+            current = []
+            continue
+        current.append(char)
+    if current:
+        options.append(''.join(current))
+    return options
+
+
+def is_network_mount(mount: Dict[str, Any]) -> bool:
+    """Whether a mount is served over the network (Windows tags mapped drives "remote")."""
+    return mount['fstype'] in NETWORK_FILESYSTEMS or 'remote' in parse_mount_options(mount['options'])
+
+
+def is_windows_drive(mount: Dict[str, Any]) -> bool:
+    """Whether a mount is a Windows drive WSL exposes (C:\\ on /mnt/c).
+
+    WSL2 also serves its own files over 9p (the "drivers" share), so a 9p mount
+    only counts when it names a drive letter or the drvfs share.
+    """
+    if mount['fstype'] not in WSL_DRIVE_FILESYSTEMS:
+        return False
+    return bool(_DRIVE_LETTER.match(mount['device'])) or 'aname=drvfs' in mount['options']
+
+
+def filter_mounts(mounts: Iterable[Dict[str, Any]], physical_only: bool = True,
+                  exclude_fs: Optional[Iterable[str]] = None,
+                  include_fs: Optional[Iterable[str]] = None) -> List[Dict[str, Any]]:
+    """Drop pseudo filesystems and collapse bind mounts of the same device.
+
+    exclude_fs replaces the default PSEUDO_FILESYSTEMS list; include_fs, when given,
+    keeps only those filesystem types. Of several mounts sharing a block device and
+    filesystem, the shortest mount point wins.
+    """
+    if not physical_only:
+        return list(mounts)
+
+    excluded = PSEUDO_FILESYSTEMS if exclude_fs is None else frozenset(exclude_fs)
+    included = None if include_fs is None else frozenset(include_fs)
+
+    chosen: Dict[Any, Dict[str, Any]] = {}
+    for mount in mounts:
+        fstype = mount['fstype']
+        if included is not None:
+            if fstype not in included:
+                continue
+        elif fstype in excluded:
+            continue
+        # Windows drive letters aren't device paths; elsewhere real disks live in
+        # /dev, apart from network shares named "server:/export" or "//host/share"
+        # zfs datasets named "pool/dataset", and the Windows drives WSL mounts
+        elif (not WINDOWS and not mount['device'].startswith('/dev/')
+              and not is_network_mount(mount) and fstype != 'zfs'
+              and not is_windows_drive(mount)):
+            continue
+
+        # Pseudo devices ("tmpfs", "none") name a type, not an instance
+        device = mount['device']
+        if WINDOWS or device.startswith('/dev/'):
+            key: Any = (device, fstype)
+        else:
+            key = id(mount)
+        best = chosen.get(key)
+        if best is None or len(mount['mountpoint']) < len(best['mountpoint']):
+            chosen[key] = mount
+    return list(chosen.values())
+
+
+def mount_for_path(path: str, mounts: Iterable[Dict[str, Any]]) -> Optional[Dict[str, Any]]:
+    """Find the mount containing an absolute path: the longest matching mount point.
+
+    Later entries win ties, since a mount stacked over another hides it.
+    """
+    target = os.path.normcase(path)
+    best = None
+    for mount in mounts:
+        mountpoint = os.path.normcase(mount['mountpoint'])
+        prefix = mountpoint.rstrip('/\\') + os.sep
+        if target != mountpoint and not (target + os.sep).startswith(prefix):
+            continue
+        if best is None or len(mountpoint) >= len(os.path.normcase(best['mountpoint'])):
+            best = mount
+    return best
+
+
+def parse_diskstats(text: str) -> Dict[str, Dict[str, int]]:
+    """Parse /proc/diskstats into {name: counters}, handling 14/18/20 column layouts."""
+    stats = {}
+    for line in text.splitlines():
+        parts = line.split()
+        if len(parts) < 14:
+            continue
+        try:
+            values = [int(v) for v in parts[3:]]
+        except ValueError:
+            continue
+        entry = dict(zip(_DISKSTATS_FIELDS, values))
+        entry['read_bytes'] = entry.pop('read_sectors') * SECTOR_SIZE
+        entry['write_bytes'] = entry.pop('write_sectors') * SECTOR_SIZE
+        if 'discard_sectors' in entry:
+            entry['discard_bytes'] = entry.pop('discard_sectors') * SECTOR_SIZE
+        stats[parts[2]] = entry
+    return stats
+
+
+def _read_attr(path: str) -> Optional[str]:
+    """Read a sysfs attribute, returning None when missing, unreadable, or blank."""
+    try:
+        with open(path, 'r') as f:
+            value = f.read().strip()
+    except (OSError, UnicodeDecodeError) as e:
+        log.read_failed(path, e)
+        return None
+    return value or None
+
+
+def parent_block_device(name: str) -> str:
+    """Resolve a partition name to its whole-disk device (nvme0n1p2 -> nvme0n1).
+
+    sysfs is authoritative when present; the naming conventions cover the rest.
+    Whole-disk names are returned unchanged.
+    """
+    if is_partition(name):
+        parent = os.path.basename(os.path.dirname(os.path.realpath(os.path.join(SYS_CLASS_BLOCK, name))))
+        if parent:
+            return parent
+    for pattern in (_P_SEPARATED, _NUMBER_SUFFIXED):
+        match = pattern.match(name)
+        if match:
+            return match.group(1)
+    return name
+
+
+def decode_udev_string(name: str) -> str:
+    """Undo udev's \\xNN escaping of link names (spaces, '/', and invalid UTF-8)."""
+    raw = re.sub(rb'\\x([0-9a-fA-F]{2})', lambda m: bytes([int(m.group(1), 16)]), name.encode('utf-8'))
+    return raw.decode('utf-8', 'replace')
+
+
+def block_device_name(device: str) -> str:
+    """Kernel name for a device path, following /dev/mapper and /dev/disk/by-* links."""
+    return os.path.basename(os.path.realpath(device))
+
+
+def is_partition(name: str) -> bool:
+    """Whether a block device is a partition (sysfs exposes a 'partition' attribute)."""
+    return os.path.exists(os.path.join(SYS_CLASS_BLOCK, name, 'partition'))
+
+
+def mount_points_by_device(mounts: Iterable[Dict[str, Any]]) -> Dict[str, List[str]]:
+    """Kernel device name -> every mount point it appears at, bind mounts included."""
+    points: Dict[str, List[str]] = {}
+    for mount in mounts:
+        if not mount['device'].startswith('/dev/'):
+            continue
+        points.setdefault(block_device_name(mount['device']), []).append(mount['mountpoint'])
+    for mountpoints in points.values():
+        mountpoints.sort(key=len)
+    return points
+
+
+if LINUX:
+    def read_mounts() -> List[Dict[str, Any]]:
+        """List mounted filesystems from /proc/mounts."""
+        mounts = []
+        try:
+            with open(PROC_MOUNTS, 'r') as f:
+                for line in f:
+                    parts = line.split()
+                    if len(parts) < 4:
+                        continue
+                    mounts.append({
+                        'device': unescape_mount_field(parts[0]),
+                        'mountpoint': unescape_mount_field(parts[1]),
+                        'fstype': parts[2],
+                        'options': unescape_mount_field(parts[3]),
+                    })
+        except OSError:
+            pass
+        return mounts
+
+    def read_disk_io() -> Dict[str, Dict[str, Any]]:
+        """Get cumulative I/O counters per block device and partition from /proc/diskstats.
+
+        Each entry lists the mount points its filesystem is mounted at; mount_point
+        is the shortest of them, or None when unmounted.
+        """
+        try:
+            with open(PROC_DISKSTATS, 'r') as f:
+                stats = parse_diskstats(f.read())
+        except OSError:
+            return {}
+
+        mounted = mount_points_by_device(list_mounts())
+        disks = {}
+        for name, counters in stats.items():
+            if name.startswith(_VIRTUAL_PREFIXES):
+                continue
+            mount_points = mounted.get(name, [])
+            counters['is_partition'] = is_partition(name)
+            counters['mount_point'] = mount_points[0] if mount_points else None
+            counters['mount_points'] = mount_points
+            disks[name] = counters
+        return disks
+
+else:
+    def read_mounts() -> List[Dict[str, Any]]:
+        """List mounted filesystems via psutil."""
+        import psutil
+
+        return [
+            {
+                'device': part.device,
+                'mountpoint': part.mountpoint,
+                'fstype': part.fstype,
+                # On Windows psutil derives 'ro' from GetVolumeInformation flags
+                'options': part.opts,
+            }
+            for part in psutil.disk_partitions(all=True)
+        ]
+
+    def read_disk_io() -> Dict[str, Dict[str, Any]]:
+        """Get cumulative I/O counters per physical disk via psutil."""
+        import psutil
+
+        try:
+            counters = psutil.disk_io_counters(perdisk=True) or {}
+        except (RuntimeError, OSError):
+            return {}
+
+        return {
+            name: {
+                'read_count': c.read_count,
+                'write_count': c.write_count,
+                'read_bytes': c.read_bytes,
+                'write_bytes': c.write_bytes,
+                'read_time_ms': c.read_time,
+                'write_time_ms': c.write_time,
+                'is_partition': False,
+                'mount_point': None,
+                'mount_points': [],
+            }
+            for name, c in counters.items()
+        }
+
+
+if LINUX:
+    def _disk_kind(disk: str) -> str:
+        """"ssd" | "hdd" | "unknown" for a whole disk from its queue attributes."""
+        rotational = _read_attr(os.path.join(SYS_CLASS_BLOCK, disk, 'queue', 'rotational'))
+        if disk.startswith('nvme') or rotational == '0':
+            return 'ssd'
+        if rotational == '1':
+            return 'hdd'
+        return 'unknown'
+
+    def get_device_identity(device: str) -> Dict[str, Any]:
+        """Kind ("ssd" | "hdd" | "unknown"), model, and serial for a mounted device."""
+        disk = parent_block_device(block_device_name(device))
+        base = os.path.join(SYS_CLASS_BLOCK, disk)
+        return {
+            'kind': _disk_kind(disk),
+            'model': _read_attr(os.path.join(base, 'device', 'model')),
+            'serial': (_read_attr(os.path.join(base, 'device', 'serial'))
+                       or _read_attr(os.path.join(base, 'serial'))),
+        }
+
+    # Negotiated USB link speed in Mbit/s (sysfs "speed") -> the name users know
+    _USB_SPEEDS = {
+        '1.5': 'USB 1.0', '12': 'USB 1.1', '480': 'USB 2.0',
+        '5000': 'USB 3.0', '10000': 'USB 3.1', '20000': 'USB 3.2',
+    }
+
+    def _usb_device_dir(disk: str) -> Optional[str]:
+        """The USB device above a disk in the sysfs hierarchy: the first ancestor with idVendor."""
+        path = os.path.realpath(os.path.join(SYS_CLASS_BLOCK, disk, 'device'))
+        while True:
+            if os.path.exists(os.path.join(path, 'idVendor')):
+                return path
+            parent = os.path.dirname(path)
+            if parent == path:
+                return None
+            path = parent
+
+    def _is_removable(disk: str) -> bool:
+        # USB SSDs and card readers often report removable=0 but are still hotplugged
+        return (_read_attr(os.path.join(SYS_CLASS_BLOCK, disk, 'removable')) == '1'
+                or _usb_device_dir(disk) is not None)
+
+    def get_bus_info(disk: str) -> Dict[str, Optional[str]]:
+        """Vendor, product, and bus ("USB 3.0", "NVMe", "SATA", ...) for a whole disk."""
+        usb = _usb_device_dir(disk)
+        if usb is not None:
+            speed = _read_attr(os.path.join(usb, 'speed'))
+            return {
+                'vendor': _read_attr(os.path.join(usb, 'manufacturer')),
+                'product': _read_attr(os.path.join(usb, 'product')),
+                'bus': _USB_SPEEDS.get(speed or '', 'USB'),
+            }
+
+        device = os.path.join(SYS_CLASS_BLOCK, disk, 'device')
+        path = os.path.realpath(device)
+        if disk.startswith('nvme'):
+            bus = 'NVMe'
+        elif '/ata' in path:
+            bus = 'SATA'
+        elif '/mmc' in path:
+            bus = 'MMC'
+        elif '/virtio' in path:
+            bus = 'virtio'
+        else:
+            bus = None
+        return {
+            'vendor': _read_attr(os.path.join(device, 'vendor')),
+            'product': _read_attr(os.path.join(device, 'model')),
+            'bus': bus,
+        }
+
+elif WINDOWS:
+    import ctypes
+    from ctypes import wintypes
+
+    _IOCTL_STORAGE_QUERY_PROPERTY = 0x2D1400
+    _STORAGE_DEVICE_PROPERTY = 0
+    _STORAGE_DEVICE_SEEK_PENALTY_PROPERTY = 7
+    _FILE_SHARE_READ_WRITE = 0x1 | 0x2
+    _OPEN_EXISTING = 3
+    _INVALID_HANDLE_VALUE = ctypes.c_void_p(-1).value
+
+    class _STORAGE_PROPERTY_QUERY(ctypes.Structure):
+        _fields_ = [
+            ('PropertyId', ctypes.c_int),
+            ('QueryType', ctypes.c_int),
+            ('AdditionalParameters', ctypes.c_ubyte * 1),
+        ]
+
+    class _STORAGE_DEVICE_DESCRIPTOR(ctypes.Structure):
+        _fields_ = [
+            ('Version', wintypes.DWORD),
+            ('Size', wintypes.DWORD),
+            ('DeviceType', ctypes.c_ubyte),
+            ('DeviceTypeModifier', ctypes.c_ubyte),
+            ('RemovableMedia', ctypes.c_ubyte),
+            ('CommandQueueing', ctypes.c_ubyte),
+            ('VendorIdOffset', wintypes.DWORD),
+            ('ProductIdOffset', wintypes.DWORD),
+            ('ProductRevisionOffset', wintypes.DWORD),
+            ('SerialNumberOffset', wintypes.DWORD),
+            ('BusType', ctypes.c_int),
+            ('RawPropertiesLength', wintypes.DWORD),
+        ]
+
+    class _DEVICE_SEEK_PENALTY_DESCRIPTOR(ctypes.Structure):
+        _fields_ = [
+            ('Version', wintypes.DWORD),
+            ('Size', wintypes.DWORD),
+            ('IncursSeekPenalty', ctypes.c_ubyte),
+        ]
+
+    def _query_storage_property(handle, property_id: int, size: int):
+        query = _STORAGE_PROPERTY_QUERY(property_id, 0)
+        buffer = ctypes.create_string_buffer(size)
+        returned = wintypes.DWORD()
+        ok = ctypes.windll.kernel32.DeviceIoControl(
+            handle, _IOCTL_STORAGE_QUERY_PROPERTY,
+            ctypes.byref(query), ctypes.sizeof(query),
+            buffer, size, ctypes.byref(returned), None,
+        )
+        return buffer if ok else None
+
+    def _descriptor_string(buffer, offset: int) -> Optional[str]:
+        if not offset or offset >= len(buffer):
+            return None
+        raw = buffer.raw[offset:].split(b'\0', 1)[0]
+        return raw.decode('ascii', 'replace').strip() or None
+
+    # STORAGE_BUS_TYPE values worth naming
+    _STORAGE_BUS_TYPES = {
+        1: 'SCSI', 2: 'ATAPI', 3: 'ATA', 4: 'IEEE 1394', 7: 'USB', 10: 'SAS',
+        11: 'SATA', 12: 'SD', 13: 'MMC', 14: 'Virtual', 17: 'NVMe',
+    }
+    _BUS_TYPE_USB = 7
+
+    def _open_volume(device: str):
+        """A query-only handle to a volume like "C:\\", or None if it can't be opened."""
+        kernel32 = ctypes.windll.kernel32
+        kernel32.CreateFileW.restype = wintypes.HANDLE
+        handle = kernel32.CreateFileW(
+            '\\\\.\\' + device.rstrip('\\'), 0, _FILE_SHARE_READ_WRITE,
+            None, _OPEN_EXISTING, 0, None,
+        )
+        if not handle or handle == _INVALID_HANDLE_VALUE:
+            return None
+        return handle
+
+    def get_device_identity(device: str) -> Dict[str, Any]:
+        """Kind, model, and serial from the volume's storage device descriptors."""
+        identity: Dict[str, Any] = {'kind': 'unknown', 'model': None, 'serial': None}
+        kernel32 = ctypes.windll.kernel32
+        handle = _open_volume(device)
+        if handle is None:
+            return identity
+        try:
+            buffer = _query_storage_property(handle, _STORAGE_DEVICE_PROPERTY, 1024)
+            if buffer is not None:
+                desc = _STORAGE_DEVICE_DESCRIPTOR.from_buffer_copy(buffer)
+                identity['model'] = _descriptor_string(buffer, desc.ProductIdOffset)
+                identity['serial'] = _descriptor_string(buffer, desc.SerialNumberOffset)
+
+            buffer = _query_storage_property(
+                handle, _STORAGE_DEVICE_SEEK_PENALTY_PROPERTY,
+                ctypes.sizeof(_DEVICE_SEEK_PENALTY_DESCRIPTOR),
+            )
+            if buffer is not None:
+                penalty = _DEVICE_SEEK_PENALTY_DESCRIPTOR.from_buffer_copy(buffer)
+                identity['kind'] = 'hdd' if penalty.IncursSeekPenalty else 'ssd'
+        finally:
+            kernel32.CloseHandle(handle)
+        return identity
+
+else:
+    def get_device_identity(device: str) -> Dict[str, Any]:
+        """Device identity isn't exposed on this platform."""
+        return {'kind': 'unknown', 'model': None, 'serial': None}
+
+
+if LINUX:
+    def read_labels() -> Dict[str, str]:
+        """Map resolved device paths to filesystem labels from /dev/disk/by-label."""
+        labels = {}
+        try:
+            names = os.listdir(DEV_DISK_BY_LABEL)
+        except OSError:
+            return labels
+        for name in names:
+            target = os.path.realpath(os.path.join(DEV_DISK_BY_LABEL, name))
+            labels[target] = decode_udev_string(name)
+        return labels
+
+    def get_volume_label(device: str, labels: Optional[Dict[str, str]] = None) -> Optional[str]:
+        """Filesystem label for a device, or None if it has none."""
+        if labels is None:
+            labels = read_labels()
+        return labels.get(os.path.realpath(device))
+
+elif WINDOWS:
+    def read_labels() -> Dict[str, str]:
+        """Volume names are fetched per drive on Windows."""
+        return {}
+
+    def get_volume_label(device: str, labels: Optional[Dict[str, str]] = None) -> Optional[str]:
+        """Volume name via GetVolumeInformationW, or None if it has none."""
+        name = ctypes.create_unicode_buffer(261)
+        root = device if device.endswith('\\') else device + '\\'
+        ok = ctypes.windll.kernel32.GetVolumeInformationW(
+            root, name, len(name), None, None, None, None, 0,
+        )
+        if not ok:
+            return None
+        return name.value or None
+
+else:
+    def read_labels() -> Dict[str, str]:
+        return {}
+
+    def get_volume_label(device: str, labels: Optional[Dict[str, str]] = None) -> Optional[str]:
+        """Labels aren't exposed on this platform."""
+        return None
+
+
+if WINDOWS:
+    def read_disk_usage(mountpoint: str) -> Dict[str, Any]:
+        """Space figures for a volume via GetDiskFreeSpaceEx."""
+        import shutil
+
+        usage = shutil.disk_usage(mountpoint)
+        return {
+            'total': usage.total,
+            'used': usage.used,
+            'free': usage.free,
+            'available': usage.free,
+            'percent': disk_percent(usage.used, usage.free),
+            **inode_usage(None, None),
+        }
+
+else:
+    def read_disk_usage(mountpoint: str) -> Dict[str, Any]:
+        """Space figures for a mount point via statvfs."""
+        stat = os.statvfs(mountpoint)
+        total = stat.f_blocks * stat.f_frsize
+        free = stat.f_bfree * stat.f_frsize
+        available = stat.f_bavail * stat.f_frsize
+        used = total - free
+        return {
+            'total': total,
+            'used': used,
+            'free': free,
+            'available': available,
+            'percent': disk_percent(used, available),
+            **inode_usage(stat.f_files, stat.f_ffree),
+        }
+
+
+def list_mounts() -> List[Dict[str, Any]]:
+    """Mounted filesystems as device, mountpoint, fstype, and options, from the active backend."""
+    return backend.active().mounts()
+
+
+def disk_usage(mountpoint: str) -> Dict[str, Any]:
+    """Space and inode figures for one mount point from the active backend."""
+    return backend.active().disk_usage(mountpoint)
+
+
+def get_disk_io() -> Dict[str, Dict[str, Any]]:
+    """Get cumulative I/O counters per disk from the active backend.
+
+    Each entry carries is_partition, mount_point (the shortest of its mount
+    points, None when unmounted), and mount_points next to its counters.
+    """
+    return backend.active().disk_io()
+
+
+# Space queries still running per mount point, shared across calls so a hung
+# server costs one stuck thread rather than one per get_disk_info() call.
+_usage_jobs: Dict[str, Dict[str, Any]] = {}
+_usage_jobs_lock = threading.Lock()
+
+
+def _run_usage_job(mountpoint: str, job: Dict[str, Any]) -> None:
+    try:
+        job['result'] = disk_usage(mountpoint)
+    except OSError as e:
+        job['error'] = e
+    job['done'].set()
+
+
+def _usage_with_timeout(mountpoint: str, timeout: float) -> Optional[Dict[str, Any]]:
+    """disk_usage() on a daemon thread; None if it hasn't answered within timeout.
+
+    Raises the query's OSError if it failed outright.
+    """
+    with _usage_jobs_lock:
+        job = _usage_jobs.get(mountpoint)
+        if job is None:
+            job = {'done': threading.Event(), 'result': None, 'error': None}
+            _usage_jobs[mountpoint] = job
+            threading.Thread(
+                target=_run_usage_job, args=(mountpoint, job),
+                name=f'pulse-statvfs:{mountpoint}', daemon=True,
+            ).start()
+
+    if not job['done'].wait(timeout):
+        return None
+    with _usage_jobs_lock:
+        if _usage_jobs.get(mountpoint) is job:
+            del _usage_jobs[mountpoint]
+    if job['error'] is not None:
+        raise job['error']
+    return job['result']
+
+
+def _stale_usage() -> Dict[str, Any]:
+    return {
+        'total': None, 'used': None, 'free': None, 'available': None, 'percent': None,
+        **inode_usage(None, None),
+    }
+
+
+def _special_usage(mount: Dict[str, Any], usage: Dict[str, Any]) -> Dict[str, Any]:
+    """Swap in filesystem-aware figures where statvfs is known to mislead."""
+    if mount['fstype'] == 'btrfs':
+        space = fs_special.btrfs_space(mount['device'])
+        if space is not None:
+            return {**usage, **space, 'percent': disk_percent(space['used'], space['available'])}
+    elif mount['fstype'] == 'zfs':
+        return fs_special.zfs_space(mount['device'], usage)
+    return usage
+
+
+def get_disk_info(physical_only: bool = True,
+                  exclude_fs: Optional[Iterable[str]] = None,
+                  include_fs: Optional[Iterable[str]] = None,
+                  network_timeout: float = NETWORK_TIMEOUT_SECS) -> List[Dict[str, Any]]:
+    """Get usage for mounted filesystems, hiding pseudo and duplicate mounts by default.
+
+    Network filesystems are queried on a worker thread; one that doesn't answer
+    within network_timeout seconds is reported with None figures and "stale" set
+    instead of blocking the whole call. btrfs figures come from its allocation
+    counters; zfs datasets are flagged "may_be_inaccurate". Under WSL, the
+    drvfs/9p mounts of Windows drives are flagged "is_windows_drive"; their
+    figures are the Windows volume's. used_space is the same bytes as used.
+    exclude_fs and include_fs only apply with physical_only and can't both
+    be given.
+    """
+    if exclude_fs is not None:
+        exclude_fs = validation.names('exclude_fs', exclude_fs)
+    if include_fs is not None:
+        include_fs = validation.names('include_fs', include_fs)
+        if exclude_fs is not None:
+            raise validation.conflict('include_fs', 'exclude_fs')
+    if not physical_only and (exclude_fs is not None or include_fs is not None):
+        raise validation.conflict('exclude_fs/include_fs', 'physical_only=False')
+    validation.positive('network_timeout', network_timeout)
+    disks = []
+    labels = read_labels()
+    wsl = environment.running_under_wsl()
+    for mount in filter_mounts(list_mounts(), physical_only, exclude_fs, include_fs):
+        is_network = is_network_mount(mount)
+        try:
+            if is_network:
+                usage = _usage_with_timeout(mount['mountpoint'], network_timeout)
+            else:
+                usage = _special_usage(mount, disk_usage(mount['mountpoint']))
+        except OSError:
+            continue
+
+        options = parse_mount_options(mount['options'])
+        if is_network:
+            identity = {'kind': 'unknown', 'model': None, 'serial': None}
+        else:
+            identity = get_device_identity(mount['device'])
+
+        disks.append({
+            'device': mount['device'],
+            'mountpoint': mount['mountpoint'],
+            'fstype': mount['fstype'],
+            'label': None if is_network else get_volume_label(mount['device'], labels),
+            **(usage if usage is not None else _stale_usage()),
+            'used_space': usage['used'] if usage is not None else None,
+            'is_read_only': 'ro' in options,
+            'options': mount['options'],
+            'mount_options': options,
+            'is_network': is_network,
+            'is_windows_drive': wsl and is_windows_drive(mount),
+            'stale': usage is None,
+            **identity,
+        })
+    return disks
+
+
+if LINUX:
+    def get_block_devices(include_virtual: bool = False) -> List[Dict[str, Any]]:
+        """List every disk and partition sysfs knows about, mounted or not.
+
+        Mounted entries carry their filesystem's figures from get_disk_info();
+        unmounted ones (a blank new SSD, an unused partition) have None there.
+        Loop, ram, and zram devices are skipped unless include_virtual is set.
+        """
+        try:
+            names = sorted(os.listdir(SYS_CLASS_BLOCK))
+        except OSError:
+            return []
+
+        mounted = mount_points_by_device(list_mounts())
+        info = {d['mountpoint']: d for d in get_disk_info(physical_only=False)}
+        devices = []
+        for name in names:
+            if not include_virtual and name.startswith(_VIRTUAL_PREFIXES):
+                continue
+            partition = is_partition(name)
+            disk = parent_block_device(name) if partition else name
+            base = os.path.join(SYS_CLASS_BLOCK, disk)
+            sectors = _read_attr(os.path.join(SYS_CLASS_BLOCK, name, 'size'))
+            if _is_removable(disk):
+                kind = 'removable'
+            else:
+                kind = _disk_kind(disk)
+
+            mount_points = mounted.get(name, [])
+            fs = info.get(mount_points[0]) if mount_points else None
+            devices.append({
+                'name': name,
+                'parent': disk if partition else None,
+                'is_partition': partition,
+                'size': int(sectors) * SECTOR_SIZE if sectors and sectors.isdigit() else None,
+                'model': _read_attr(os.path.join(base, 'device', 'model')),
+                'kind': kind,
+                'is_mounted': bool(mount_points),
+                'mount_point': mount_points[0] if mount_points else None,
+                'mount_points': mount_points,
+                'fstype': fs['fstype'] if fs else None,
+                'label': fs['label'] if fs else None,
+                'used': fs['used'] if fs else None,
+                'available': fs['available'] if fs else None,
+                'percent': fs['percent'] if fs else None,
+            })
+        return devices
+
+else:
+    def get_block_devices(include_virtual: bool = False) -> List[Dict[str, Any]]:
+        """Unmounted block devices aren't enumerated on this platform."""
+        return []
+
+
+if LINUX:
+    def get_removable_media() -> List[Dict[str, Any]]:
+        """Removable and USB-attached disks with vendor, product, and bus.
+
+        Mount points include those of the disk's partitions. A drive pulled out
+        mid-read reports None for whatever sysfs no longer has.
+        """
+        devices = get_block_devices()
+        media = []
+        for disk in devices:
+            if disk['is_partition'] or disk['kind'] != 'removable':
+                continue
+            mount_points = list(disk['mount_points'])
+            for part in devices:
+                if part['parent'] == disk['name']:
+                    mount_points += part['mount_points']
+            media.append({
+                'name': disk['name'],
+                'size': disk['size'],
+                **get_bus_info(disk['name']),
+                'is_mounted': bool(mount_points),
+                'mount_points': mount_points,
+            })
+        return media
+
+elif WINDOWS:
+    def get_removable_media() -> List[Dict[str, Any]]:
+        """Removable and USB-attached volumes with vendor, product, and bus.
+
+        The strings come from the storage device descriptor, the same ones
+        SetupAPI assembles device friendly names from.
+        """
+        import psutil
+
+        kernel32 = ctypes.windll.kernel32
+        media = []
+        for part in psutil.disk_partitions(all=False):
+            handle = _open_volume(part.device)
+            if handle is None:
+                continue
+            try:
+                buffer = _query_storage_property(handle, _STORAGE_DEVICE_PROPERTY, 1024)
+            finally:
+                kernel32.CloseHandle(handle)
+            if buffer is None:
+                continue
+            desc = _STORAGE_DEVICE_DESCRIPTOR.from_buffer_copy(buffer)
+            if not desc.RemovableMedia and desc.BusType != _BUS_TYPE_USB:
+                continue
+            try:
+                size = disk_usage(part.mountpoint)['total']
+            except OSError:
+                size = None
+            media.append({
+                'name': part.device,
+                'size': size,
+                'vendor': _descriptor_string(buffer, desc.VendorIdOffset),
+                'product': _descriptor_string(buffer, desc.ProductIdOffset),
+                'bus': _STORAGE_BUS_TYPES.get(desc.BusType),
+                'is_mounted': True,
+                'mount_points': [part.mountpoint],
+            })
+        return media
+
+else:
+    def get_removable_media() -> List[Dict[str, Any]]:
+        """Removable media details aren't exposed on this platform."""
+        return []
+
+
+def get_path_usage(path: str) -> Dict[str, Any]:
+    """Get space figures for the volume holding an arbitrary path.
+
+    Symlinks are followed; the mount point and filesystem it resolved to are
+    included. Raises FileNotFoundError for paths that don't exist.
+    """
+    validation.not_empty('path', path)
+    resolved = os.path.realpath(path)
+    if not os.path.exists(resolved):
+        raise FileNotFoundError(f"No such file or directory: '{path}'")
+
+    usage = disk_usage(resolved)
+    mount = mount_for_path(resolved, list_mounts())
+    return {
+        'path': resolved,
+        'total': usage['total'],
+        'used': usage['used'],
+        'available': usage['available'],
+        'percent': usage['percent'],
+        'mountpoint': mount['mountpoint'] if mount else None,
+        'fstype': mount['fstype'] if mount else None,
+        'device': mount['device'] if mount else None,
+    }
+
+
+def saturation_stats(prev: Dict[str, int], curr: Dict[str, int], elapsed: float) -> Dict[str, float]:
+    """iostat-style %util, await, and queue depth between two diskstats samples.
+
+    io_time_ms only advances while at least one request is in flight, so the
+    busy fraction of wall time can't honestly exceed 100%; any overshoot comes
+    from sampling skew and is clamped. Deep NVMe queues show up in queue_depth.
+    """
+    elapsed_ms = elapsed * 1000
+    ops = (counter_delta(curr['read_count'], prev['read_count'])
+           + counter_delta(curr['write_count'], prev['write_count']))
+    wait_ms = (counter_delta(curr['read_time_ms'], prev['read_time_ms'])
+               + counter_delta(curr['write_time_ms'], prev['write_time_ms']))
+    busy_ms = counter_delta(curr['io_time_ms'], prev['io_time_ms'])
+    weighted_ms = counter_delta(curr['weighted_io_time_ms'], prev['weighted_io_time_ms'])
+    return {
+        'util_percent': min(100.0, busy_ms / elapsed_ms * 100) if elapsed_ms > 0 else 0.0,
+        'avg_latency_ms': wait_ms / ops if ops else 0.0,
+        'queue_depth': weighted_ms / elapsed_ms if elapsed_ms > 0 else 0.0,
+    }
+
+
+def get_disk_io_rates() -> Dict[str, Dict[str, Any]]:
+    """Get per-disk throughput since the previous call.
+
+    Counters that went backwards (device reset or hotplug) clamp to zero, and the
+    first call reports zero rates while it establishes the baseline. On Linux
+    each disk also carries util_percent, avg_latency_ms, and queue_depth.
+    """
+    state = active_state()
+    with state.lock('disks'):
+        current = get_disk_io()
+        now = time.monotonic()
+        previous = state.disk_io or {}
+        elapsed = now - state.disk_io_time if state.disk_io is not None else 0.0
+        state.disk_io = current
+        state.disk_io_time = now
+
+    rates = {}
+    for name, counters in current.items():
+        prev = previous.get(name)
+        rate: Dict[str, Any] = {
+            'is_partition': counters['is_partition'],
+            'mount_point': counters['mount_point'],
+            'mount_points': counters['mount_points'],
+        }
+        for key, rate_key in _RATE_KEYS.items():
+            rate[rate_key] = 0.0 if prev is None else counter_rate(counters[key], prev[key], elapsed)
+        if 'io_time_ms' in counters:
+            rate.update(saturation_stats(prev or counters, counters, elapsed))
+        rates[name] = rate
+    return rates
+
+
+# NVMe SMART / Health Information log page (NVMe base spec, Get Log Page 02h)
+_NVME_SMART_LOG_LEN = 512
+_NVME_ADMIN_GET_LOG_PAGE = 0x02
+_NVME_LOG_SMART = 0x02
+# Composite temperature above which a drive is considered hot when hwmon has no crit
+_NVME_DEFAULT_CRIT_C = 80.0
+
+
+def parse_nvme_smart_log(data: bytes) -> Dict[str, Any]:
+    """Decode the fields of an NVMe SMART log page that the health summary uses."""
+    if len(data) < _NVME_SMART_LOG_LEN:
+        raise ValueError(f"SMART log is {len(data)} bytes, expected {_NVME_SMART_LOG_LEN}")
+    critical_warning = data[0]
+    kelvin = struct.unpack_from('<H', data, 1)[0]
+    # Power-on hours is a 128-bit little-endian counter at offset 128
+    low, high = struct.unpack_from('<QQ', data, 128)
+    return {
+        'critical_warning': critical_warning,
+        'temperature_c': kelvin - 273.15 if kelvin else None,
+        'available_spare': data[3],
+        'percentage_used': data[5],
+        'power_on_hours': low | (high << 64),
+    }
+
+
+def _whole_disks() -> List[str]:
+    """Names of non-virtual whole disks under /sys/class/block."""
+    try:
+        names = sorted(os.listdir(SYS_CLASS_BLOCK))
+    except OSError:
+        return []
+    return [n for n in names if not n.startswith(_VIRTUAL_PREFIXES) and not is_partition(n)]
+
+
+def _hwmon_temperature(disk: str) -> Dict[str, Optional[float]]:
+    """Temperature and critical threshold from the hwmon node owning a disk.
+
+    NVMe controllers carry hwmon directly (device/hwmonN); SATA disks get one
+    from the drivetemp module (device/hwmon/hwmonN).
+    """
+    base = os.path.join(SYS_CLASS_BLOCK, disk, 'device')
+    for pattern in ('hwmon*', os.path.join('hwmon', 'hwmon*')):
+        for hwmon in sorted(glob.glob(os.path.join(base, pattern))):
+            for channel in sensors.read_channels(hwmon, 'temp'):
+                if channel['index'] != 1:
+                    continue
+                crit = channel.get('crit')
+                return {
+                    'temperature_c': channel['input'] / 1000,
+                    'critical_c': crit / 1000 if crit else None,
+                }
+    return {'temperature_c': None, 'critical_c': None}
+
+
+def _nvme_controller(disk: str) -> Optional[str]:
+    """Character device for an NVMe namespace's controller (nvme0n1 -> /dev/nvme0)."""
+    match = re.match(r'^(nvme\d+)n\d+$', disk)
+    return os.path.join(DEV, match.group(1)) if match else None
+
+
+if LINUX:
+    import fcntl
+
+    # _IOWR('N', 0x41, struct nvme_admin_cmd), the struct being 72 bytes
+    _NVME_IOCTL_ADMIN_CMD = 0xC0484E41
+
+    def read_nvme_smart_log(controller: str) -> Optional[bytes]:
+        """Fetch the SMART log via an admin ioctl; None without access (usually root)."""
+        import ctypes
+
+        buffer = ctypes.create_string_buffer(_NVME_SMART_LOG_LEN)
+        numd = _NVME_SMART_LOG_LEN // 4 - 1
+        # struct nvme_admin_cmd from linux/nvme_ioctl.h
+        cmd = struct.pack(
+            '<BBHIIIQQIIIIIIIIII',
+            _NVME_ADMIN_GET_LOG_PAGE, 0, 0,          # opcode, flags, rsvd1
+            0xFFFFFFFF, 0, 0,                        # nsid (controller-wide), cdw2, cdw3
+            0, ctypes.addressof(buffer),             # metadata, addr
+            0, _NVME_SMART_LOG_LEN,                  # metadata_len, data_len
+            (numd << 16) | _NVME_LOG_SMART,          # cdw10
+            0, 0, 0, 0, 0,                           # cdw11-cdw15
+            0, 0,                                    # timeout_ms, result
+        )
+        try:
+            fd = os.open(controller, os.O_RDONLY)
+        except OSError:
+            return None
+        try:
+            fcntl.ioctl(fd, _NVME_IOCTL_ADMIN_CMD, bytearray(cmd))
+        except OSError:
+            return None
+        finally:
+            os.close(fd)
+        return buffer.raw
+
+    def get_disk_health() -> List[Dict[str, Any]]:
+        """Get temperature and wear per disk, summarised as ok / warning / unknown.
+
+        Temperatures come from hwmon and need no privileges; NVMe wear and
+        power-on hours need read access to the controller device.
+        """
+        health = []
+        for disk in _whole_disks():
+            temps = _hwmon_temperature(disk)
+            entry: Dict[str, Any] = {
+                'name': disk,
+                'temperature_c': temps['temperature_c'],
+                'power_on_hours': None,
+                'percentage_used': None,
+            }
+
+            warning = False
+            controller = _nvme_controller(disk)
+            smart_log = read_nvme_smart_log(controller) if controller else None
+            if smart_log is not None:
+                smart = parse_nvme_smart_log(smart_log)
+                entry['power_on_hours'] = smart['power_on_hours']
+                entry['percentage_used'] = smart['percentage_used']
+                if entry['temperature_c'] is None:
+                    entry['temperature_c'] = smart['temperature_c']
+                warning = smart['critical_warning'] != 0 or smart['percentage_used'] >= 100
+
+            temperature = entry['temperature_c']
+            if temperature is not None:
+                critical = temps['critical_c'] or _NVME_DEFAULT_CRIT_C
+                warning = warning or temperature >= critical
+
+            readable = temperature is not None or smart_log is not None
+            entry['health'] = 'warning' if warning else ('ok' if readable else 'unknown')
+            health.append(entry)
+        return health
+
+else:
+    def get_disk_health() -> List[Dict[str, Any]]:
+        """Health data isn't readable here; every disk reports "unknown"."""
+        return [
+            {'name': name, 'temperature_c': None, 'power_on_hours': None,
+             'percentage_used': None, 'health': 'unknown'}
+            for name in get_disk_io()
+        ]
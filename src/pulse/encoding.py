@@ -0,0 +1,46 @@
+"""
+Pulse Encoding
+The compact wire forms collectors return for as_json=True and as_msgpack=True.
+
+Both encode the same value, and collector results only use string keys, so
+a MessagePack payload decodes to exactly what json.loads() gives for the JSON
+one. Each carries the time it was collected: a dict result gains a
+"timestamp" key next to its own, anything else is sent as
+{"timestamp": ..., "data": result}. MessagePack needs the optional msgpack package:
+pip install pulse-monitor[msgpack].
+"""
+import json
+from typing import Any, Union
+
+from pulse import validation
+from pulse.errors import Unsupported
+
+
+def timestamped(data: Any, timestamp: float) -> Any:
+    """data as the wire forms send it; a dict with a timestamp of its own (a snapshot) keeps it."""
+    if isinstance(data, dict):
+        return data if 'timestamp' in data else {**data, 'timestamp': timestamp}
+    return {'timestamp': timestamp, 'data': data}
+
+
+def to_json(data: Any) -> str:
+    return json.dumps(data, separators=(',', ':'))
+
+
+def to_msgpack(data: Any) -> bytes:
+    """data as MessagePack; raises Unsupported without the msgpack package."""
+    try:
+        import msgpack
+    except ImportError:
+        raise Unsupported('as_msgpack needs the msgpack package '
+                          '(pip install pulse-monitor[msgpack])') from None
+    return msgpack.packb(data, use_bin_type=True)
+
+
+def encode(data: Any, as_json: bool = False, as_msgpack: bool = False) -> Union[Any, str, bytes]:
+    """data itself, or its JSON string or MessagePack bytes when asked for."""
+    if as_json and as_msgpack:
+        raise validation.conflict('as_msgpack', 'as_json')
+    if as_msgpack:
+        return to_msgpack(data)
+    return to_json(data) if as_json else data
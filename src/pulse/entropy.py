@@ -0,0 +1,73 @@
+"""
+Pulse Entropy
+The kernel's entropy pool and whether its CRNG is ready to hand out random bytes.
+
+Until the CRNG is initialized, /dev/random and a blocking getrandom() wait,
+which on a headless VM with few interrupts can stall services at boot. Since
+Linux 5.18 the pool is a fixed 256 bits and entropy_avail stays at 256 once
+initialized, so crng_ready is the figure that matters on modern kernels.
+"""
+import errno
+import os
+from typing import Any, Callable, Dict, Optional
+
+from pulse import log
+from pulse.capabilities import requires
+from pulse.direct_os import LINUX
+from pulse.errors import Unsupported
+
+PROC_SYS_KERNEL_RANDOM = '/proc/sys/kernel/random'
+
+
+def _read_int(path: str) -> Optional[int]:
+    try:
+        with open(path, 'r') as f:
+            return int(f.read().strip())
+    except (OSError, ValueError) as e:
+        log.read_failed(path, e)
+        return None
+
+
+def probe_crng(getrandom: Optional[Callable[[int, int], bytes]] = getattr(os, 'getrandom', None),
+               flags: int = getattr(os, 'GRND_NONBLOCK', 0x1)) -> Optional[bool]:
+    """Whether the CRNG is initialized, from a one-byte non-blocking getrandom().
+
+    getrandom(GRND_NONBLOCK) fails with EAGAIN exactly while the CRNG is not
+    yet seeded; None means the probe couldn't run (no getrandom, ENOSYS).
+    """
+    if getrandom is None:
+        return None
+    try:
+        getrandom(1, flags)
+    except OSError as e:
+        if e.errno == errno.EAGAIN:
+            return False
+        return None
+    return True
+
+
+def entropy_status(entropy_avail: Optional[int], poolsize: Optional[int],
+                   crng_ready: Optional[bool]) -> Dict[str, Any]:
+    """{entropy_avail, poolsize, percent, crng_ready}; percent is None when either count is unknown."""
+    percent = entropy_avail / poolsize * 100 if entropy_avail is not None and poolsize else None
+    return {'entropy_avail': entropy_avail, 'poolsize': poolsize, 'percent': percent,
+            'crng_ready': crng_ready}
+
+
+if LINUX:
+    @requires('entropy')
+    def get_entropy() -> Dict[str, Any]:
+        """Get the entropy pool's fill (bits) and whether the kernel CRNG is initialized.
+
+        crng_ready False means reads from /dev/random and blocking getrandom()
+        calls are waiting right now.
+        """
+        return entropy_status(_read_int(os.path.join(PROC_SYS_KERNEL_RANDOM, 'entropy_avail')),
+                              _read_int(os.path.join(PROC_SYS_KERNEL_RANDOM, 'poolsize')),
+                              probe_crng())
+
+else:
+    @requires('entropy')
+    def get_entropy() -> Dict[str, Any]:
+        """The entropy pool counters only exist in Linux's /proc."""
+        raise Unsupported('get_entropy is only supported on Linux')
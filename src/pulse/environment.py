@@ -0,0 +1,218 @@
+"""
+Pulse Environment Detection
+Whether we're running under a hypervisor or inside a container.
+
+Each heuristic is a small function over text it is handed, so they can be
+tested against captured files; detect_environment() reads the files and takes
+the first confident answer.
+"""
+import functools
+import os
+import sys
+from typing import Any, Dict, Mapping, Optional
+
+from pulse import log, privacy, system
+from pulse.direct_os import FREEBSD, LINUX, MACOS
+
+PROC_CPUINFO = '/proc/cpuinfo'
+PROC_1_CGROUP = '/proc/1/cgroup'
+PROC_1_ENVIRON = '/proc/1/environ'
+PROC_OSRELEASE = '/proc/sys/kernel/osrelease'
+SYS_HYPERVISOR_TYPE = '/sys/hypervisor/type'
+AVAILABLE_CLOCKSOURCE = '/sys/devices/system/clocksource/clocksource0/available_clocksource'
+DOCKERENV = '/.dockerenv'
+CONTAINERENV = '/run/.containerenv'
+
+# Paravirtual clock sources only a guest of that hypervisor registers
+_CLOCKSOURCE_HYPERVISORS = {
+    'kvm-clock': 'kvm',
+    'xen': 'xen',
+    'hyperv_clocksource_tsc_page': 'hyperv',
+    'hyperv_clocksource_msr': 'hyperv',
+    'vmware-tsc': 'vmware',
+}
+
+# FreeBSD's kern.vm_guest values -> hypervisor; "generic" is an unrecognised one
+_VM_GUEST_HYPERVISORS = {
+    'none': 'none',
+    'generic': 'unknown',
+    'bhyve': 'bhyve',
+    'kvm': 'kvm',
+    'hv': 'hyperv',
+    'vmware': 'vmware',
+    'xen': 'xen',
+    'vbox': 'virtualbox',
+    'parallels': 'parallels',
+    'nvmm': 'nvmm',
+}
+
+# cgroup path fragments -> container runtime, most specific first: pods run
+# under containerd or docker paths too, so kubepods has to win
+_CGROUP_CONTAINERS = (
+    ('kubepods', 'kubernetes'),
+    ('libpod', 'podman'),
+    ('docker', 'docker'),
+    ('lxc', 'lxc'),
+)
+
+# Values of the "container" variable systemd and runtimes set for PID 1
+_CONTAINER_ENV_VALUES = {
+    'docker': 'docker',
+    'podman': 'podman',
+    'oci': 'podman',
+    'lxc': 'lxc',
+    'lxc-libvirt': 'lxc',
+}
+
+
+def _read(path: str) -> Optional[str]:
+    try:
+        with open(path, 'r', errors='replace') as f:
+            return f.read()
+    except OSError as e:
+        log.read_failed(path, e)
+        return None
+
+
+def hypervisor_from_vm_guest(vm_guest: str) -> str:
+    """FreeBSD's kern.vm_guest sysctl: the kernel's own guess at its hypervisor."""
+    return _VM_GUEST_HYPERVISORS.get(vm_guest.rstrip('\0').strip(), 'unknown')
+
+
+def cpuinfo_hypervisor_flag(cpuinfo: str) -> bool:
+    """Whether /proc/cpuinfo reports the CPUID hypervisor-present bit."""
+    for line in cpuinfo.splitlines():
+        if line.startswith('flags'):
+            return 'hypervisor' in line.split(':', 1)[-1].split()
+    return False
+
+
+def hypervisor_from_clocksource(available: str) -> Optional[str]:
+    """Hypervisor implied by the kernel's available clock sources."""
+    for source in available.split():
+        if source in _CLOCKSOURCE_HYPERVISORS:
+            return _CLOCKSOURCE_HYPERVISORS[source]
+    return None
+
+
+def hypervisor_from_sys_hypervisor(hypervisor_type: str) -> Optional[str]:
+    """/sys/hypervisor/type names Xen guests (dom0 included) directly."""
+    value = hypervisor_type.strip()
+    return value or None
+
+
+def container_from_cgroup(cgroup: str) -> Optional[str]:
+    """Container runtime named in PID 1's cgroup paths (cgroup v1 hosts, mostly)."""
+    for line in cgroup.splitlines():
+        path = line.split(':', 2)[-1]
+        for fragment, runtime in _CGROUP_CONTAINERS:
+            if fragment in path:
+                return runtime
+    return None
+
+
+def container_from_environ(environ: Mapping[str, str]) -> Optional[str]:
+    """Container runtime from environment variables runtimes set."""
+    if 'KUBERNETES_SERVICE_HOST' in environ:
+        return 'kubernetes'
+    return _CONTAINER_ENV_VALUES.get(environ.get('container', ''))
+
+
+def parse_environ_block(raw: str) -> Dict[str, str]:
+    """Parse a NUL-separated /proc/<pid>/environ block."""
+    environ = {}
+    for entry in raw.split('\0'):
+        key, sep, value = entry.partition('=')
+        if sep:
+            environ[key] = value
+    return environ
+
+
+def container_from_marker_files(dockerenv: bool, containerenv: bool) -> Optional[str]:
+    """Runtime from the marker files docker and podman drop into the rootfs."""
+    if containerenv:
+        return 'podman'
+    if dockerenv:
+        return 'docker'
+    return None
+
+
+def is_wsl(osrelease: str) -> bool:
+    """WSL kernels carry "microsoft" in their release string (WSL2: -microsoft-standard)."""
+    return 'microsoft' in osrelease.lower()
+
+
+@functools.lru_cache(maxsize=None)
+def running_under_wsl() -> bool:
+    """Whether this is a WSL kernel; disks, sensors, power, and memory adjust their figures for it.
+
+    Read once: the kernel can't change under a running process.
+    """
+    return LINUX and is_wsl(_read(PROC_OSRELEASE) or '')
+
+
+def detect_virtualization() -> str:
+    """"kvm" | "vmware" | "hyperv" | "xen" | ... | "none" | "unknown"."""
+    hardware = system.get_hardware_info()
+    named = system.dmi_hypervisor(hardware['manufacturer'], hardware['product_name'])
+    if named is not None:
+        return named
+
+    if LINUX:
+        named = hypervisor_from_sys_hypervisor(_read(SYS_HYPERVISOR_TYPE) or '')
+        if named is not None:
+            return named
+        clocksource = hypervisor_from_clocksource(_read(AVAILABLE_CLOCKSOURCE) or '')
+        if clocksource is not None:
+            return clocksource
+        cpuinfo = _read(PROC_CPUINFO)
+        if cpuinfo is None:
+            return 'unknown'
+        return 'unknown' if cpuinfo_hypervisor_flag(cpuinfo) else 'none'
+    if MACOS:
+        # kern.hv_vmm_present is 1 inside any hypervisor but doesn't name it
+        present = system.sysctl(b'kern.hv_vmm_present')
+        if present is None:
+            return 'unknown'
+        return 'unknown' if int.from_bytes(present, 'little') else 'none'
+    if FREEBSD:
+        vm_guest = system.sysctl(b'kern.vm_guest')
+        return hypervisor_from_vm_guest(vm_guest.decode('ascii', 'replace')) if vm_guest else 'unknown'
+    return 'none' if hardware['manufacturer'] else 'unknown'
+
+
+def detect_container() -> str:
+    """"docker" | "podman" | "lxc" | "kubernetes" | "wsl" | "jail" | "none"."""
+    if FREEBSD:
+        jailed = system.sysctl(b'security.jail.jailed')
+        return 'jail' if jailed and int.from_bytes(jailed, sys.byteorder) else 'none'
+    if not LINUX:
+        return 'none'
+    pid1_environ = {}
+    if privacy.environ_allowed():
+        pid1_environ = parse_environ_block(_read(PROC_1_ENVIRON) or '')
+    for runtime in (
+        container_from_environ(os.environ),
+        container_from_environ(pid1_environ),
+        container_from_cgroup(_read(PROC_1_CGROUP) or ''),
+        container_from_marker_files(os.path.exists(DOCKERENV), os.path.exists(CONTAINERENV)),
+    ):
+        if runtime is not None:
+            return runtime
+    if running_under_wsl():
+        return 'wsl'
+    return 'none'
+
+
+def detect_environment() -> Dict[str, Any]:
+    """Get the hypervisor and container runtime this process runs under.
+
+    {"virtualization": ..., "container": ..., "wsl": bool}; virtualization is
+    "unknown" when a hypervisor is present but can't be named. "wsl" is set
+    even when a container runtime inside WSL wins "container".
+    """
+    return {
+        'virtualization': detect_virtualization(),
+        'container': detect_container(),
+        'wsl': running_under_wsl(),
+    }
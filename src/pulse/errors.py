@@ -0,0 +1,54 @@
+"""
+Pulse Errors
+Exception types raised across the library.
+
+Each also derives from the matching builtin, so code written against
+ProcessLookupError, PermissionError, NotImplementedError, TimeoutError, or
+KeyError keeps working.
+"""
+
+
+class PulseError(Exception):
+    """Base class for every error Pulse raises on purpose."""
+
+
+class ProcessNotFound(PulseError, ProcessLookupError):
+    """The pid doesn't exist (or exited while being read)."""
+
+    @classmethod
+    def for_pid(cls, pid: int, detail: str = '') -> 'ProcessNotFound':
+        """The error every getter raises for a missing pid; detail is appended as is."""
+        return cls(f'No such process: {pid}{detail}')
+
+
+class PermissionDenied(PulseError, PermissionError):
+    """The OS refused access; usually fixable by running with more privilege."""
+
+
+class ServiceNotFound(PulseError, KeyError):
+    """The service manager doesn't know a unit or service by that name."""
+
+
+class Unsupported(PulseError, NotImplementedError):
+    """The feature isn't available on this platform or host."""
+
+
+class Timeout(PulseError, TimeoutError):
+    """A helper process or kernel interface didn't answer in time."""
+
+
+class PulseInternalError(PulseError, RuntimeError):
+    """A collector failed in a way it never should (a bug); the message names both."""
+
+
+class WorkerRestarted(PulseError, RuntimeWarning):
+    """The isolated worker died and was replaced; the call it was serving is lost."""
+
+
+class CorruptRecording(PulseError, ValueError):
+    """A recording file is truncated or damaged; offset is where the bad bytes start."""
+
+    def __init__(self, path: str, offset: int, reason: str):
+        super().__init__(f'{path}: {reason} at byte offset {offset}')
+        self.path = path
+        self.offset = offset
@@ -0,0 +1,420 @@
+"""
+Pulse Exit Accounting
+CPU time, peak RSS, and I/O of processes that exited, however briefly they ran.
+
+Polls miss a process that starts and ends between two of them. The kernel's
+taskstats interface instead sends a record for every task as it exits to
+the generic netlink sockets registered for the CPUs it ran on; a background
+thread receives them and get_exited_processes() drains what it has gathered.
+Registering needs CAP_NET_ADMIN and a kernel built with CONFIG_TASKSTATS.
+
+Each thread sends its own record, and a multi-threaded process's last
+thread to exit, whichever it is, is followed by an aggregate for its thread
+group. Kernels whose records carry the thread group id (taskstats version
+11 and later) have a process's exited threads folded into it, so it is
+reported once, when that aggregate arrives; on older kernels every thread
+is reported on its own.
+"""
+import errno
+import functools
+import os
+import select
+import socket
+import struct
+import threading
+from collections import OrderedDict, deque
+from typing import Any, Callable, Deque, Dict, List, Optional, Set, Tuple
+
+from pulse import clock, log, processes
+from pulse.capabilities import requires
+from pulse.errors import PermissionDenied
+
+SYS_CPU_ONLINE = '/sys/devices/system/cpu/online'
+PROC_SELF_STATUS = '/proc/self/status'
+
+CAP_NET_ADMIN = 12
+
+# Oldest records are dropped once this many are waiting to be drained
+MAX_PENDING_EXITS = 8192
+
+# How long the receiving thread waits before checking whether to stop
+_POLL_SECS = 0.5
+_RCVBUF_BYTES = 4 << 20
+
+NETLINK_GENERIC = 16
+NLMSG_ERROR = 2
+NLM_F_REQUEST = 1
+NLM_F_ACK = 4
+GENL_ID_CTRL = 0x10
+CTRL_CMD_GETFAMILY = 3
+CTRL_ATTR_FAMILY_ID = 1
+CTRL_ATTR_FAMILY_NAME = 2
+
+TASKSTATS_CMD_GET = 1
+TASKSTATS_CMD_NEW = 2
+TASKSTATS_CMD_ATTR_REGISTER_CPUMASK = 3
+TASKSTATS_TYPE_TGID = 2
+TASKSTATS_TYPE_STATS = 3
+TASKSTATS_TYPE_AGGR_PID = 4
+TASKSTATS_TYPE_AGGR_TGID = 5
+
+_NLMSGHDR = struct.Struct('=IHHII')
+_GENLMSGHDR = struct.Struct('=BBH')
+_NLATTR = struct.Struct('=HH')
+
+# struct taskstats (linux/taskstats.h) field offsets; ac_tgid from version 11
+_TASKSTATS_FIELDS = {
+    'exit_code': (4, 'I'), 'comm': (80, '32s'), 'uid': (120, 'I'), 'pid': (128, 'I'),
+    'ppid': (132, 'I'), 'btime': (136, 'I'), 'etime': (144, 'Q'), 'utime': (152, 'Q'),
+    'stime': (160, 'Q'), 'hiwater_rss': (200, 'Q'), 'read_bytes': (248, 'Q'),
+    'write_bytes': (256, 'Q'),
+}
+_TGID_OFFSET = 368
+_TGID_VERSION = 11
+
+_records: Deque[Dict[str, Any]] = deque(maxlen=MAX_PENDING_EXITS)
+_records_lock = threading.Lock()
+_thread: Optional[threading.Thread] = None
+_stop = threading.Event()
+_control_lock = threading.Lock()
+
+
+def _attribute(kind: int, data: bytes) -> bytes:
+    packed = _NLATTR.pack(_NLATTR.size + len(data), kind) + data
+    return packed + b'\0' * (-len(packed) % 4)
+
+
+def parse_attributes(data: bytes) -> List[Tuple[int, bytes]]:
+    """(type, payload) of each netlink attribute in data, nesting flags cleared."""
+    attributes = []
+    offset = 0
+    while offset + _NLATTR.size <= len(data):
+        length, kind = _NLATTR.unpack_from(data, offset)
+        if length < _NLATTR.size:
+            break
+        attributes.append((kind & 0x3fff, data[offset + _NLATTR.size:offset + length]))
+        offset += (length + 3) & ~3
+    return attributes
+
+
+def parse_messages(data: bytes) -> List[Tuple[int, bytes]]:
+    """(type, payload) of each netlink message in one datagram."""
+    messages = []
+    offset = 0
+    while offset + _NLMSGHDR.size <= len(data):
+        length, kind = _NLMSGHDR.unpack_from(data, offset)[:2]
+        if length < _NLMSGHDR.size:
+            break
+        messages.append((kind, data[offset + _NLMSGHDR.size:offset + length]))
+        offset += (length + 3) & ~3
+    return messages
+
+
+def parse_taskstats(data: bytes) -> Dict[str, Any]:
+    """One exited task from a struct taskstats.
+
+    {pid, tgid, ppid, name, uid, exit_code, signal, start_time, elapsed_secs,
+    cpu_user_secs, cpu_system_secs, peak_rss_bytes, read_bytes,
+    write_bytes}; tgid is None before taskstats version 11. signal is the
+    one that killed the task, or None if it exited.
+    """
+    (version,) = struct.unpack_from('=H', data, 0)
+    fields = {name: struct.unpack_from('=' + code, data, offset)[0]
+              for name, (offset, code) in _TASKSTATS_FIELDS.items()}
+    tgid = None
+    if version >= _TGID_VERSION and len(data) >= _TGID_OFFSET + 4:
+        (tgid,) = struct.unpack_from('=I', data, _TGID_OFFSET)
+    status = fields['exit_code']
+    return {
+        'pid': fields['pid'],
+        'tgid': tgid,
+        'ppid': fields['ppid'],
+        'name': fields['comm'].split(b'\0', 1)[0].decode('utf-8', 'replace'),
+        'uid': fields['uid'],
+        'exit_code': (status >> 8) & 0xff,
+        'signal': status & 0x7f or None,
+        'start_time': fields['btime'],
+        # Times are in microseconds, the high-water RSS in KiB
+        'elapsed_secs': fields['etime'] / 1e6,
+        'cpu_user_secs': fields['utime'] / 1e6,
+        'cpu_system_secs': fields['stime'] / 1e6,
+        'peak_rss_bytes': fields['hiwater_rss'] * 1024,
+        'read_bytes': fields['read_bytes'],
+        'write_bytes': fields['write_bytes'],
+    }
+
+
+def parse_exit(payload: bytes) -> List[Tuple[int, Any]]:
+    """The entries in a TASKSTATS_CMD_NEW message's payload, in order.
+
+    Each is (TASKSTATS_TYPE_AGGR_PID, an exited task's record) or
+    (TASKSTATS_TYPE_AGGR_TGID, the thread group id whose last thread
+    exited); the group's aggregate has no name or CPU time of its own, so
+    only its id is kept.
+    """
+    entries: List[Tuple[int, Any]] = []
+    for kind, nested in parse_attributes(payload[_GENLMSGHDR.size:]):
+        for inner, data in parse_attributes(nested):
+            if kind == TASKSTATS_TYPE_AGGR_PID and inner == TASKSTATS_TYPE_STATS:
+                entries.append((kind, parse_taskstats(data)))
+            elif kind == TASKSTATS_TYPE_AGGR_TGID and inner == TASKSTATS_TYPE_TGID:
+                entries.append((kind, struct.unpack_from('=I', data, 0)[0]))
+    return entries
+
+
+def merge_thread(process: Dict[str, Any], thread: Dict[str, Any]) -> None:
+    """Fold an exited thread's CPU time and I/O into its process's record."""
+    for key in ('cpu_user_secs', 'cpu_system_secs', 'read_bytes', 'write_bytes'):
+        process[key] += thread[key]
+    process['peak_rss_bytes'] = max(process['peak_rss_bytes'], thread['peak_rss_bytes'])
+    process['threads'] += thread['threads']
+
+
+def live_threads(pid: int) -> int:
+    """How many threads /proc/<pid>/status counts, an exited main thread included; 0 once it's gone."""
+    path = f'/proc/{pid}/status'
+    try:
+        with open(path, 'r') as f:
+            return processes.parse_status_usage(f.read()).get('threads', 0)
+    except FileNotFoundError:
+        return 0
+    except (OSError, ValueError) as e:
+        log.read_failed(path, e)
+        return 0
+
+
+class ThreadFolder:
+    """Holds exited threads until their thread group's aggregate says the process is gone.
+
+    The kernel sends no aggregate for a process that only ever ran one
+    thread. A main thread that exits with no threads folded and none left
+    running (per live_threads) is held as possibly alone: any other thread
+    had already sent its record, and the aggregate with it, so once
+    everything received so far is added, settle() finishes it on its own.
+    """
+
+    def __init__(self, max_groups: int = MAX_PENDING_EXITS, live_threads: Callable[[int], int] = live_threads):
+        self.max_groups = max_groups
+        self.live_threads = live_threads
+        self._groups: 'OrderedDict[int, Dict[str, Any]]' = OrderedDict()
+        self._alone: Set[int] = set()
+
+    def add(self, record: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+        """The record of a task without a thread group id, or None once record is folded."""
+        record = dict(record, threads=1)
+        tgid = record.pop('tgid')
+        if tgid is None or tgid == 0:
+            return record
+        group = self._groups.pop(tgid, None)
+        if tgid == record['pid']:
+            if group is None and self.live_threads(tgid) <= 1:
+                self._alone.add(tgid)
+            # The main thread's name and exit status stand for the process
+            if group is not None:
+                merge_thread(record, group)
+            group = record
+        elif group is None:
+            group = dict(record, pid=tgid)
+        else:
+            merge_thread(group, record)
+            self._alone.discard(tgid)
+        self._groups[tgid] = group
+        while len(self._groups) > self.max_groups:
+            self._alone.discard(self._groups.popitem(last=False)[0])
+        return None
+
+    def finish(self, tgid: int) -> Optional[Dict[str, Any]]:
+        """The process record for a thread group whose aggregate arrived; None if none is held."""
+        self._alone.discard(tgid)
+        return self._groups.pop(tgid, None)
+
+    def settle(self) -> List[Dict[str, Any]]:
+        """The records of main threads held as possibly alone; call with nothing left to receive."""
+        settled = [self._groups.pop(tgid) for tgid in [tgid for tgid in self._groups if tgid in self._alone]]
+        self._alone.clear()
+        return settled
+
+
+def cap_effective(status: str) -> int:
+    """The CapEff bitmask from /proc/<pid>/status text; 0 if missing."""
+    for line in status.splitlines():
+        if line.startswith('CapEff:'):
+            return int(line.split(':', 1)[1], 16)
+    return 0
+
+
+def has_net_admin() -> bool:
+    """Whether this process holds CAP_NET_ADMIN."""
+    try:
+        with open(PROC_SELF_STATUS, 'r') as f:
+            return bool(cap_effective(f.read()) >> CAP_NET_ADMIN & 1)
+    except (OSError, ValueError) as e:
+        log.read_failed(PROC_SELF_STATUS, e)
+        return False
+
+
+def _request(sock: socket.socket, family: int, command: int, attributes: bytes, flags: int = 0) -> None:
+    payload = _GENLMSGHDR.pack(command, 1, 0) + attributes
+    sock.send(_NLMSGHDR.pack(_NLMSGHDR.size + len(payload), family, NLM_F_REQUEST | flags, 0, 0) + payload)
+
+
+def _reply(sock: socket.socket) -> bytes:
+    """The payload of the answer to the last request; OSError if it is an error."""
+    for kind, payload in parse_messages(sock.recv(65536)):
+        if kind == NLMSG_ERROR:
+            (code,) = struct.unpack_from('=i', payload, 0)
+            if code:
+                raise OSError(-code, os.strerror(-code))
+        return payload
+    raise OSError(errno.EPROTO, 'Empty netlink reply')
+
+
+def _open_socket() -> socket.socket:
+    sock = socket.socket(socket.AF_NETLINK, socket.SOCK_RAW, NETLINK_GENERIC)
+    try:
+        sock.bind((0, 0))
+    except OSError:
+        sock.close()
+        raise
+    return sock
+
+
+def _family_id(sock: socket.socket) -> Optional[int]:
+    _request(sock, GENL_ID_CTRL, CTRL_CMD_GETFAMILY, _attribute(CTRL_ATTR_FAMILY_NAME, b'TASKSTATS\0'))
+    try:
+        payload = _reply(sock)
+    except FileNotFoundError:
+        # ENOENT: the kernel has no taskstats
+        return None
+    for kind, data in parse_attributes(payload[_GENLMSGHDR.size:]):
+        if kind == CTRL_ATTR_FAMILY_ID:
+            return struct.unpack_from('=H', data, 0)[0]
+    return None
+
+
+@functools.lru_cache(maxsize=None)
+def taskstats_family() -> Optional[int]:
+    """The TASKSTATS generic netlink family id, or None if the kernel lacks it; looked up once."""
+    try:
+        sock = _open_socket()
+    except OSError:
+        return None
+    try:
+        sock.settimeout(_POLL_SECS)
+        return _family_id(sock)
+    except OSError:
+        return None
+    finally:
+        sock.close()
+
+
+def _cpu_mask() -> str:
+    try:
+        with open(SYS_CPU_ONLINE, 'r') as f:
+            return f.read().strip()
+    except OSError as e:
+        log.read_failed(SYS_CPU_ONLINE, e)
+        return f'0-{(os.cpu_count() or 1) - 1}'
+
+
+def _register() -> Tuple[socket.socket, int]:
+    sock = _open_socket()
+    try:
+        sock.setsockopt(socket.SOL_SOCKET, socket.SO_RCVBUF, _RCVBUF_BYTES)
+        sock.settimeout(_POLL_SECS)
+        family = _family_id(sock)
+        if family is None:
+            raise OSError(errno.ENOENT, 'The kernel has no taskstats netlink family')
+        mask = _attribute(TASKSTATS_CMD_ATTR_REGISTER_CPUMASK, _cpu_mask().encode() + b'\0')
+        _request(sock, family, TASKSTATS_CMD_GET, mask, NLM_F_ACK)
+        _reply(sock)
+    except PermissionError:
+        sock.close()
+        raise PermissionDenied('Exit accounting needs CAP_NET_ADMIN to register for taskstats') from None
+    except OSError:
+        sock.close()
+        raise
+    return sock, family
+
+
+def _run(sock: socket.socket, family: int) -> None:
+    folder = ThreadFolder()
+    try:
+        while not _stop.is_set():
+            try:
+                data = sock.recv(65536)
+            except socket.timeout:
+                continue
+            except OSError as e:
+                # ENOBUFS: the socket overflowed and records were lost; keep receiving
+                if e.errno != errno.ENOBUFS:
+                    log.read_failed('taskstats', e)
+                    return
+                continue
+            now = clock.now()
+            finished = []
+            for kind, payload in parse_messages(data):
+                if kind != family or payload[:1] != bytes([TASKSTATS_CMD_NEW]):
+                    continue
+                for entry, value in parse_exit(payload):
+                    if entry == TASKSTATS_TYPE_AGGR_PID:
+                        process = folder.add(value)
+                    else:
+                        process = folder.finish(value)
+                    if process is not None:
+                        finished.append(dict(process, exited_at=now))
+            if not select.select([sock], [], [], 0)[0]:
+                finished.extend(dict(process, exited_at=now) for process in folder.settle())
+            if finished:
+                with _records_lock:
+                    _records.extend(finished)
+    finally:
+        sock.close()
+
+
+@requires('exit_accounting')
+def enable_exit_accounting() -> None:
+    """Start collecting a record for every process that exits; a no-op if already on.
+
+    Raises Unsupported without CAP_NET_ADMIN or where taskstats isn't
+    available (get_capabilities(explain=True) says why), and
+    PermissionDenied if the kernel refuses the registration regardless.
+    """
+    global _thread
+    with _control_lock:
+        if _thread is not None and _thread.is_alive():
+            return
+        sock, family = _register()
+        _stop.clear()
+        with _records_lock:
+            _records.clear()
+        _thread = threading.Thread(target=_run, args=(sock, family), name='pulse-exit-accounting', daemon=True)
+        _thread.start()
+
+
+def disable_exit_accounting(timeout: Optional[float] = None) -> None:
+    """Stop collecting exit records and wait for the receiving thread to exit."""
+    global _thread
+    with _control_lock:
+        _stop.set()
+        if _thread is not None:
+            _thread.join(timeout)
+            _thread = None
+
+
+@requires('exit_accounting')
+def get_exited_processes() -> List[Dict[str, Any]]:
+    """Drain the processes that exited since the previous call, oldest first.
+
+    Each is {pid, ppid, name, uid, exit_code, signal, start_time, elapsed_secs,
+    cpu_user_secs, cpu_system_secs, peak_rss_bytes, read_bytes,
+    write_bytes, threads, exited_at}: start_time is in unix seconds, the
+    CPU and I/O totals cover every thread, and signal names the one that
+    killed it (None if it exited). Empty until enable_exit_accounting(). At
+    most MAX_PENDING_EXITS are kept between calls; older ones are dropped.
+    Raises Unsupported without CAP_NET_ADMIN or taskstats.
+    """
+    with _records_lock:
+        records = list(_records)
+        _records.clear()
+    return records
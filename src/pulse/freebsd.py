@@ -0,0 +1,118 @@
+"""
+Pulse FreeBSD
+sysctl readers for the figures Linux takes from /proc.
+
+FreeBSD mounts no procfs by default, so memory, swap, and CPU times come
+from sysctl. Every value is decoded by a parser over the raw bytes the kernel
+returns, so the layouts can be tested against captured buffers on any
+platform; only the read_* functions touch the kernel.
+"""
+import ctypes
+import struct
+import sys
+from typing import Any, Dict, List, Optional, Tuple
+
+# kern.cp_times holds these tick counters for every CPU, in this order (sys/resource.h)
+CPUSTATES = ('user', 'nice', 'system', 'interrupt', 'idle')
+
+# vm.stats.vm page counters get_memory_info() needs
+PAGE_COUNTERS = ('v_free_count', 'v_active_count', 'v_inactive_count', 'v_laundry_count',
+                 'v_wire_count')
+
+# struct xswdev from vm.swap_info.<n> (sys/sys/conf.h, 64-bit dev_t since FreeBSD 12):
+# version, dev, flags, nblks (pages), used (pages)
+_XSWDEV = struct.Struct('=I4xQiii')
+
+_LONG_SIZE = ctypes.sizeof(ctypes.c_long)
+
+
+def parse_uint(data: bytes, byteorder: str = sys.byteorder) -> int:
+    """An unsigned integer sysctl (int, u_int, long, u_long) of whatever width came back."""
+    return int.from_bytes(data, byteorder)
+
+
+def parse_cp_times(data: bytes, long_size: int = _LONG_SIZE,
+                   byteorder: str = sys.byteorder) -> List[Dict[str, float]]:
+    """Per-CPU {busy, total} ticks from kern.cp_times, one CPUSTATES row of longs per CPU."""
+    width = long_size * len(CPUSTATES)
+    times = []
+    for offset in range(0, len(data) - width + 1, width):
+        ticks = [int.from_bytes(data[offset + i * long_size:offset + (i + 1) * long_size], byteorder)
+                 for i in range(len(CPUSTATES))]
+        # Idle is the last state; everything else kept the CPU busy
+        times.append({'busy': sum(ticks[:-1]), 'total': sum(ticks)})
+    return times
+
+
+def parse_xswdev(data: bytes) -> Tuple[int, int]:
+    """(pages, used pages) of one swap device from a vm.swap_info.<n> buffer."""
+    if len(data) < _XSWDEV.size:
+        raise ValueError(f'xswdev needs {_XSWDEV.size} bytes, got {len(data)}')
+    _, _, _, pages, used = _XSWDEV.unpack_from(data)
+    return pages, used
+
+
+def memory_from_counters(physmem: int, page_size: int, pages: Dict[str, int],
+                         swap: List[Tuple[int, int]]) -> Dict[str, Any]:
+    """get_memory_info()'s dict from hw.physmem, hw.pagesize, PAGE_COUNTERS, and swap devices.
+
+    Like top(1), inactive and laundry pages count as available: the pager
+    reclaims them before it touches anything in use.
+    """
+    free = pages.get('v_free_count', 0) * page_size
+    available = free + (pages.get('v_inactive_count', 0) + pages.get('v_laundry_count', 0)) * page_size
+    swap_total = sum(total for total, _ in swap) * page_size
+    swap_used = sum(used for _, used in swap) * page_size
+    return {
+        'total': physmem,
+        'available': available,
+        'free': free,
+        'used': physmem - available,
+        'percent': (physmem - available) / physmem * 100 if physmem else 0,
+        'swap_total': swap_total,
+        'swap_used': swap_used,
+        'swap_free': swap_total - swap_used,
+    }
+
+
+def _libc() -> Any:
+    import ctypes.util
+
+    return ctypes.CDLL(ctypes.util.find_library('c'), use_errno=True)
+
+
+def read_swap_devices() -> List[Tuple[int, int]]:
+    """(pages, used pages) per swap device: vm.swap_info.0, .1, ... until one is missing."""
+    libc = _libc()
+    mib = (ctypes.c_int * 16)()
+    length = ctypes.c_size_t(len(mib) - 1)
+    if libc.sysctlnametomib(b'vm.swap_info', mib, ctypes.byref(length)) != 0:
+        return []
+    devices = []
+    buffer = ctypes.create_string_buffer(64)
+    for index in range(len(mib)):
+        mib[length.value] = index
+        size = ctypes.c_size_t(len(buffer))
+        if libc.sysctl(mib, length.value + 1, buffer, ctypes.byref(size), None, 0) != 0:
+            break
+        devices.append(parse_xswdev(buffer.raw[:size.value]))
+    return devices
+
+
+def _read_uint(name: bytes) -> Optional[int]:
+    from pulse import system
+
+    value = system.sysctl(name)
+    return parse_uint(value) if value is not None else None
+
+
+def read_cp_times() -> List[Dict[str, float]]:
+    from pulse import system
+
+    return parse_cp_times(system.sysctl(b'kern.cp_times') or b'')
+
+
+def read_memory_info() -> Dict[str, Any]:
+    pages = {name: _read_uint(b'vm.stats.vm.' + name.encode()) or 0 for name in PAGE_COUNTERS}
+    return memory_from_counters(_read_uint(b'hw.physmem') or 0, _read_uint(b'hw.pagesize') or 4096,
+                                pages, read_swap_devices())
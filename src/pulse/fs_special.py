@@ -0,0 +1,111 @@
+"""
+Pulse Filesystem Specials
+Space accounting for filesystems where statvfs figures mislead.
+
+btrfs allocates in chunks per RAID profile, so statvfs "available" ignores
+mirroring and metadata reservations; the per-profile allocation counters in
+sysfs give the estimate `btrfs filesystem usage` reports. ZFS datasets share a
+pool, so their statvfs view is kept but flagged as possibly inaccurate.
+"""
+import os
+from typing import Any, Dict, Optional
+
+SYS_FS_BTRFS = '/sys/fs/btrfs'
+PROC_SPL_KSTAT_ZFS = '/proc/spl/kstat/zfs'
+
+_BTRFS_BLOCK_GROUPS = ('data', 'metadata', 'system')
+_SECTOR_SIZE = 512
+
+
+def _read_int(path: str) -> Optional[int]:
+    try:
+        with open(path, 'r') as f:
+            return int(f.read().strip())
+    except (OSError, ValueError):
+        return None
+
+
+def find_btrfs_filesystem(device_name: str) -> Optional[str]:
+    """sysfs directory of the btrfs filesystem that includes a block device."""
+    try:
+        uuids = os.listdir(SYS_FS_BTRFS)
+    except OSError:
+        return None
+    for uuid in uuids:
+        if os.path.isdir(os.path.join(SYS_FS_BTRFS, uuid, 'devices', device_name)):
+            return os.path.join(SYS_FS_BTRFS, uuid)
+    return None
+
+
+def parse_btrfs_allocation(fs_dir: str) -> Optional[Dict[str, int]]:
+    """Estimate btrfs space from /sys/fs/btrfs/<uuid>/{allocation,devices}.
+
+    Free space is unused room in already-allocated data chunks plus unallocated
+    device space divided by the data profile's replication ratio (2 for RAID1).
+    """
+    groups = {}
+    for group in _BTRFS_BLOCK_GROUPS:
+        base = os.path.join(fs_dir, 'allocation', group)
+        total = _read_int(os.path.join(base, 'total_bytes'))
+        used = _read_int(os.path.join(base, 'bytes_used'))
+        disk_total = _read_int(os.path.join(base, 'disk_total'))
+        if total is None or used is None or disk_total is None:
+            return None
+        groups[group] = {'total': total, 'used': used, 'disk_total': disk_total}
+
+    device_bytes = 0
+    devices_dir = os.path.join(fs_dir, 'devices')
+    try:
+        devices = os.listdir(devices_dir)
+    except OSError:
+        return None
+    for device in devices:
+        sectors = _read_int(os.path.join(devices_dir, device, 'size'))
+        if sectors is None:
+            return None
+        device_bytes += sectors * _SECTOR_SIZE
+
+    data = groups['data']
+    ratio = data['disk_total'] / data['total'] if data['total'] else 1.0
+    allocated = sum(g['disk_total'] for g in groups.values())
+    unallocated = max(0, device_bytes - allocated)
+    used = sum(g['used'] for g in groups.values())
+    available = max(0, data['total'] - data['used']) + int(unallocated / ratio)
+    return {
+        'total': used + available,
+        'used': used,
+        'free': available,
+        'available': available,
+        'data_ratio': ratio,
+    }
+
+
+def zfs_pool_state(pool: str) -> Optional[str]:
+    """Pool health (ONLINE, DEGRADED, ...) from the SPL kstat tree."""
+    try:
+        with open(os.path.join(PROC_SPL_KSTAT_ZFS, pool, 'state'), 'r') as f:
+            return f.read().strip() or None
+    except OSError:
+        return None
+
+
+def btrfs_space(device: str) -> Optional[Dict[str, Any]]:
+    """Profile-aware btrfs space figures for a mounted device, if sysfs has them."""
+    fs_dir = find_btrfs_filesystem(os.path.basename(os.path.realpath(device)))
+    if fs_dir is None:
+        return None
+    return parse_btrfs_allocation(fs_dir)
+
+
+def zfs_space(dataset: str, usage: Dict[str, Any]) -> Dict[str, Any]:
+    """Keep the dataset's statvfs figures but flag them and add the pool state.
+
+    Datasets share pool free space and compression/reservations skew totals, so
+    statvfs is the best cheap answer but shouldn't be trusted to the byte.
+    """
+    return {
+        **usage,
+        'may_be_inaccurate': True,
+        'pool': dataset.split('/', 1)[0],
+        'pool_state': zfs_pool_state(dataset.split('/', 1)[0]),
+    }
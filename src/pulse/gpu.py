@@ -0,0 +1,167 @@
+"""
+Pulse GPU
+Utilization, memory, and temperature for every GPU, whatever the vendor.
+
+NVIDIA cards are read through NVML when the driver's library is installed;
+AMD and Intel cards (and NVIDIA ones without NVML) through DRM sysfs.
+"""
+import ctypes
+import ctypes.util
+import functools
+import os
+import re
+from typing import Any, Dict, List, Optional
+
+from pulse import log, sensors
+from pulse.capabilities import requires
+from pulse.direct_os import LINUX, WINDOWS
+
+SYS_CLASS_DRM = '/sys/class/drm'
+
+# PCI vendor ids
+_PCI_VENDORS = {'0x1002': 'amd', '0x8086': 'intel', '0x10de': 'nvidia'}
+
+_CARD = re.compile(r'^card\d+$')
+
+_NVML_SUCCESS = 0
+_NVML_TEMPERATURE_GPU = 0
+
+
+def _read_attr(path: str) -> Optional[str]:
+    try:
+        with open(path, 'r') as f:
+            return f.read().strip() or None
+    except OSError as e:
+        log.read_failed(path, e)
+        return None
+
+
+def _read_int(path: str) -> Optional[int]:
+    value = _read_attr(path)
+    try:
+        return int(value) if value is not None else None
+    except ValueError:
+        return None
+
+
+def _drm_temperature(device: str) -> Optional[float]:
+    for chip in sensors.numbered_paths(os.path.join(device, 'hwmon', 'hwmon*')):
+        for channel in sensors.read_channels(chip, 'temp'):
+            return channel['input'] / 1000
+    return None
+
+
+def read_drm_card(card: str) -> Dict[str, Any]:
+    """One /sys/class/drm/cardN entry; attributes the driver lacks are None.
+
+    amdgpu exposes busy percent and VRAM counters; i915 has neither for
+    integrated parts, so those cards mostly report identity and temperature.
+    """
+    device = os.path.join(card, 'device')
+    driver = os.path.realpath(os.path.join(device, 'driver'))
+    return {
+        'index': int(os.path.basename(card)[len('card'):]),
+        'name': _read_attr(os.path.join(device, 'product_name')),
+        'vendor': _PCI_VENDORS.get(_read_attr(os.path.join(device, 'vendor')) or '', 'unknown'),
+        'driver': os.path.basename(driver) if os.path.exists(driver) else None,
+        'utilization_percent': _read_int(os.path.join(device, 'gpu_busy_percent')),
+        'memory_used': _read_int(os.path.join(device, 'mem_info_vram_used')),
+        'memory_total': _read_int(os.path.join(device, 'mem_info_vram_total')),
+        'temperature_c': _drm_temperature(device),
+    }
+
+
+def read_drm_cards() -> List[Dict[str, Any]]:
+    """Every DRM card (connectors like card0-DP-1 are skipped)."""
+    try:
+        names = os.listdir(SYS_CLASS_DRM)
+    except OSError:
+        return []
+    cards = [os.path.join(SYS_CLASS_DRM, n) for n in names if _CARD.match(n)]
+    return [read_drm_card(c) for c in sorted(cards, key=lambda c: int(os.path.basename(c)[4:]))]
+
+
+class _NvmlUtilization(ctypes.Structure):
+    _fields_ = [('gpu', ctypes.c_uint), ('memory', ctypes.c_uint)]
+
+
+class _NvmlMemory(ctypes.Structure):
+    _fields_ = [('total', ctypes.c_ulonglong), ('free', ctypes.c_ulonglong), ('used', ctypes.c_ulonglong)]
+
+
+@functools.lru_cache(maxsize=None)
+def _load_nvml() -> Optional[Any]:
+    # Looked up once: find_library runs ldconfig, and hosts without NVIDIA would fork on every poll
+    if WINDOWS:
+        candidates = ['nvml.dll', os.path.join(os.environ.get('ProgramFiles', ''),
+                                               'NVIDIA Corporation', 'NVSMI', 'nvml.dll')]
+    else:
+        candidates = ['libnvidia-ml.so.1', ctypes.util.find_library('nvidia-ml')]
+    for name in candidates:
+        if not name:
+            continue
+        try:
+            return ctypes.CDLL(name)
+        except OSError:
+            continue
+    return None
+
+
+def nvml_available() -> bool:
+    """Whether NVIDIA's NVML library can be loaded on this host."""
+    return _load_nvml() is not None
+
+
+def read_nvml_devices() -> Optional[List[Dict[str, Any]]]:
+    """NVIDIA GPUs via NVML, or None when the library isn't available."""
+    nvml = _load_nvml()
+    if nvml is None or nvml.nvmlInit_v2() != _NVML_SUCCESS:
+        return None
+    try:
+        count = ctypes.c_uint()
+        if nvml.nvmlDeviceGetCount_v2(ctypes.byref(count)) != _NVML_SUCCESS:
+            return []
+        devices = []
+        for index in range(count.value):
+            handle = ctypes.c_void_p()
+            if nvml.nvmlDeviceGetHandleByIndex_v2(index, ctypes.byref(handle)) != _NVML_SUCCESS:
+                continue
+            name = ctypes.create_string_buffer(96)
+            util = _NvmlUtilization()
+            memory = _NvmlMemory()
+            temp = ctypes.c_uint()
+            has_name = nvml.nvmlDeviceGetName(handle, name, len(name)) == _NVML_SUCCESS
+            has_util = nvml.nvmlDeviceGetUtilizationRates(handle, ctypes.byref(util)) == _NVML_SUCCESS
+            has_memory = nvml.nvmlDeviceGetMemoryInfo(handle, ctypes.byref(memory)) == _NVML_SUCCESS
+            has_temp = nvml.nvmlDeviceGetTemperature(
+                handle, _NVML_TEMPERATURE_GPU, ctypes.byref(temp)) == _NVML_SUCCESS
+            devices.append({
+                'index': index,
+                'name': name.value.decode('utf-8', 'replace') if has_name else None,
+                'vendor': 'nvidia',
+                'driver': 'nvidia',
+                'utilization_percent': util.gpu if has_util else None,
+                'memory_used': memory.used if has_memory else None,
+                'memory_total': memory.total if has_memory else None,
+                'temperature_c': float(temp.value) if has_temp else None,
+            })
+        return devices
+    finally:
+        nvml.nvmlShutdown()
+
+
+@requires('gpu')
+def get_gpu_info() -> List[Dict[str, Any]]:
+    """Get utilization, VRAM, and temperature for every GPU, tagged with its vendor.
+
+    NVML supplies NVIDIA cards when present, so their DRM entries are dropped
+    to avoid listing a card twice. Fields a driver doesn't expose are None.
+    """
+    nvidia = read_nvml_devices()
+    gpus = list(nvidia or [])
+    if LINUX:
+        for card in read_drm_cards():
+            if nvidia is not None and card['vendor'] == 'nvidia':
+                continue
+            gpus.append(card)
+    return gpus
@@ -0,0 +1,68 @@
+"""
+Pulse History Queries
+Summary statistics of one sampled series over a recent window.
+
+Aggregates are computed here from the background sampler's history, so a
+"last 5 minutes" card asks for a handful of numbers instead of every sample.
+Series are the ones anomalies.series_from_samples() extracts.
+"""
+import math
+from typing import Any, Dict, Iterable, List, Optional
+
+from pulse import sampler, validation
+from pulse.anomalies import series_from_samples
+
+PERCENTILES = {'p50': 50.0, 'p95': 95.0, 'p99': 99.0}
+AGGREGATES = ('min', 'max', 'avg', *PERCENTILES)
+
+
+def nearest_rank(values: List[float], percent: float) -> float:
+    """The percent-th percentile of sorted values by nearest rank.
+
+    That is the smallest value with at least percent% of the values at or
+    below it, so the result is always one of the values.
+    """
+    rank = max(1, math.ceil(percent / 100 * len(values)))
+    return values[rank - 1]
+
+
+def aggregate(values: Iterable[float], aggregates: Iterable[str]) -> Dict[str, Optional[float]]:
+    """Each of aggregates over values; all None when there are none."""
+    ordered = sorted(values)
+    result: Dict[str, Optional[float]] = {}
+    for name in aggregates:
+        if not ordered:
+            result[name] = None
+        elif name == 'min':
+            result[name] = ordered[0]
+        elif name == 'max':
+            result[name] = ordered[-1]
+        elif name == 'avg':
+            result[name] = sum(ordered) / len(ordered)
+        else:
+            result[name] = nearest_rank(ordered, PERCENTILES[name])
+    return result
+
+
+def query_history(metric: str, window_secs: float = 300,
+                  aggregates: Iterable[str] = AGGREGATES) -> Dict[str, Any]:
+    """Get aggregates of one sampled series over the last window_secs.
+
+    metric names a series such as "cpu_total", "memory_used",
+    "net_recv_per_sec", "net_rx_rate:eth0", or "disk_used:/"; aggregates picks from "min",
+    "max", "avg", "p50", "p95", and "p99" (default: all). The window ends at
+    the newest sample. The result also holds "samples", the readings used.
+    Raises ValueError for an unknown aggregate, a window_secs that isn't
+    positive, or a metric the sampler history doesn't hold, listing the ones
+    it does.
+    """
+    names = validation.all_of('aggregates', aggregates, AGGREGATES)
+    validation.positive('window_secs', window_secs)
+    samples = sampler.get_samples()
+    series = series_from_samples(samples)
+    if metric not in series:
+        tracked = ', '.join(sorted(series)) or 'none; is the sampler running?'
+        raise ValueError(f'Unknown metric {metric!r}; tracked: {tracked}')
+    since = samples[-1]['timestamp'] - window_secs
+    values = [value for timestamp, value in series[metric] if timestamp >= since]
+    return {**aggregate(values, names), 'samples': len(values)}
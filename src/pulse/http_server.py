@@ -0,0 +1,124 @@
+"""
+Pulse HTTP Server
+A minimal built-in endpoint serving metrics, snapshots, and a health check.
+
+GET /metrics is get_prometheus_metrics(), /snapshot is snapshot(as_json=True),
+and /healthz answers "ok". Each response body is collected in full before
+any byte is written, so a slow client holds a socket, never a state lock.
+"""
+import socket
+import threading
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from typing import Any, Callable, Dict, Optional, Tuple
+
+from pulse import validation
+
+PROMETHEUS_CONTENT_TYPE = 'text/plain; version=0.0.4; charset=utf-8'
+
+_lock = threading.Lock()
+_server: Optional[ThreadingHTTPServer] = None
+_thread: Optional[threading.Thread] = None
+
+
+def parse_address(addr: str) -> Tuple[str, int]:
+    """Split "host:port", "[v6]:port", or ":port" (all interfaces) into (host, port)."""
+    validation.not_empty('addr', addr)
+    host, sep, port = addr.rpartition(':')
+    if not sep or not port.isdigit() or int(port) > 65535:
+        raise ValueError(f'Invalid addr {addr!r}; expected host:port')
+    return host.strip('[]'), int(port)
+
+
+def _metrics() -> Tuple[str, str]:
+    from pulse import core
+
+    return PROMETHEUS_CONTENT_TYPE, core.get_prometheus_metrics()
+
+
+def _snapshot() -> Tuple[str, str]:
+    from pulse import core
+
+    return 'application/json', core.snapshot(as_json=True)
+
+
+def _healthz() -> Tuple[str, str]:
+    return 'text/plain; charset=utf-8', 'ok\n'
+
+
+ROUTES: Dict[str, Callable[[], Tuple[str, str]]] = {
+    '/metrics': _metrics,
+    '/snapshot': _snapshot,
+    '/healthz': _healthz,
+}
+
+
+class _IPv6Server(ThreadingHTTPServer):
+    address_family = socket.AF_INET6
+
+
+class _Handler(BaseHTTPRequestHandler):
+    server_version = 'Pulse'
+
+    def do_GET(self) -> None:
+        route = ROUTES.get(self.path.split('?', 1)[0])
+        if route is None:
+            status, content_type, body = 404, 'text/plain; charset=utf-8', 'not found\n'
+        else:
+            try:
+                content_type, body = route()
+                status = 200
+            except Exception as e:
+                status, content_type, body = 500, 'text/plain; charset=utf-8', f'{type(e).__name__}: {e}\n'
+        data = body.encode('utf-8')
+        self.send_response(status)
+        self.send_header('Content-Type', content_type)
+        self.send_header('Content-Length', str(len(data)))
+        self.end_headers()
+        self.wfile.write(data)
+
+    def log_message(self, format: str, *args: Any) -> None:
+        pass
+
+
+def start_http_server(addr: str = '127.0.0.1:9184') -> str:
+    """Serve /metrics, /snapshot, and /healthz on addr from a background thread.
+
+    Returns the bound "host:port", so ":0" or "127.0.0.1:0" picks a free
+    port. A bracketed host ("[::1]:9184") is served over IPv6. Raises
+    OSError naming addr when it can't be bound, and RuntimeError while a
+    server is already running.
+    """
+    global _server, _thread
+    host, port = parse_address(addr)
+    with _lock:
+        if _server is not None:
+            raise RuntimeError(f'Already serving on {_bound(_server)}')
+        try:
+            server_class = _IPv6Server if ':' in host else ThreadingHTTPServer
+            server = server_class((host, port), _Handler)
+        except OSError as e:
+            raise OSError(e.errno, f'Cannot serve on {addr}: {e.strerror or e}') from None
+        server.daemon_threads = True
+        _server = server
+        _thread = threading.Thread(target=server.serve_forever, name='pulse-http', daemon=True)
+        _thread.start()
+        return _bound(server)
+
+
+def stop_http_server(timeout: Optional[float] = None) -> None:
+    """Stop serving and close the socket; a no-op when no server is running."""
+    global _server, _thread
+    with _lock:
+        server, thread = _server, _thread
+        _server = _thread = None
+    if server is None:
+        return
+    server.shutdown()
+    server.server_close()
+    if thread is not None:
+        thread.join(timeout)
+
+
+def _bound(server: ThreadingHTTPServer) -> str:
+    host, port = server.server_address[:2]
+    return f'[{host}]:{port}' if ':' in host else f'{host}:{port}'
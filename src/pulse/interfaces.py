@@ -0,0 +1,144 @@
+"""
+Pulse Interfaces
+Network interface topology: bond and bridge masters and the interfaces under them.
+
+Traffic through a bond is counted on the bond and again on the slave it
+left by, and traffic into a bridge on the bridge and on its port, so a sum
+over every interface counts it twice (three times for a bridge over a bond).
+Only interfaces that are no other interface's slave, the top of each stack,
+carry it once; the deduplicated totals sum those.
+"""
+import copy
+import os
+import threading
+from typing import Any, Dict, List, Optional, Set, Tuple
+
+from pulse import backend, log
+from pulse.capabilities import requires, supported
+
+SYS_CLASS_NET = '/sys/class/net'
+
+KINDS = ('bond', 'bridge', 'physical', 'virtual', 'loopback')
+
+_lock = threading.Lock()
+# (interface names, topology) as last read; read again when the names change
+_topology: Optional[Tuple[Tuple[str, ...], Dict[str, Dict[str, Any]]]] = None
+
+
+def _bond_slaves(directory: str) -> Optional[List[str]]:
+    path = os.path.join(directory, 'bonding', 'slaves')
+    try:
+        with open(path, 'r') as f:
+            return sorted(f.read().split())
+    except FileNotFoundError:
+        return None
+    except OSError as e:
+        log.read_failed(path, e)
+        return None
+
+
+def _interface_kind(name: str, directory: str) -> Tuple[str, List[str]]:
+    slaves = _bond_slaves(directory)
+    if slaves is not None:
+        return 'bond', slaves
+    try:
+        return 'bridge', sorted(os.listdir(os.path.join(directory, 'brif')))
+    except OSError:
+        pass
+    if name == 'lo':
+        return 'loopback', []
+    # Only interfaces backed by hardware have a device link
+    return ('physical' if os.path.exists(os.path.join(directory, 'device')) else 'virtual'), []
+
+
+def read_topology(root: str = SYS_CLASS_NET) -> Dict[str, Dict[str, Any]]:
+    """{name: {kind, master, slaves}} for every interface under a /sys/class/net.
+
+    kind is one of KINDS. slaves are a bond's slaves or a bridge's ports,
+    and master the bond or bridge an interface belongs to (None at the top
+    of its stack).
+    """
+    try:
+        names = sorted(os.listdir(root))
+    except OSError as e:
+        log.read_failed(root, e)
+        return {}
+    topology = {}
+    for name in names:
+        kind, slaves = _interface_kind(name, os.path.join(root, name))
+        topology[name] = {'kind': kind, 'master': None, 'slaves': slaves}
+    for name, entry in topology.items():
+        for slave in entry['slaves']:
+            if slave in topology:
+                topology[slave]['master'] = name
+    return topology
+
+
+def _reaches_physical(topology: Dict[str, Dict[str, Any]], name: str, seen: Set[str]) -> bool:
+    entry = topology.get(name)
+    if entry is None or name in seen:
+        return False
+    seen.add(name)
+    return entry['kind'] == 'physical' or any(
+        _reaches_physical(topology, slave, seen) for slave in entry['slaves'])
+
+
+def top_interfaces(topology: Dict[str, Dict[str, Any]], physical_only: bool = False) -> List[str]:
+    """The interfaces whose counters a deduplicated total sums.
+
+    Those are the ones with no master, loopback aside. physical_only also
+    drops stacks with no physical interface at the bottom (veth pairs,
+    tunnels, a bridge whose ports are all virtual).
+    """
+    return [name for name, entry in topology.items()
+            if entry['master'] is None and entry['kind'] != 'loopback'
+            and (not physical_only or _reaches_physical(topology, name, set()))]
+
+
+def sum_counters(counters: Dict[str, Dict[str, int]], names: List[str]) -> Dict[str, int]:
+    """bytes_recv and bytes_sent summed over the named interfaces' counters."""
+    totals = {'bytes_recv': 0, 'bytes_sent': 0}
+    for name in names:
+        if name in counters:
+            totals['bytes_recv'] += counters[name]['bytes_recv']
+            totals['bytes_sent'] += counters[name]['bytes_sent']
+    return totals
+
+
+def current_topology() -> Dict[str, Dict[str, Any]]:
+    """The host's topology, read again only when interfaces were added or removed."""
+    global _topology
+    try:
+        names = tuple(sorted(os.listdir(SYS_CLASS_NET)))
+    except OSError:
+        names = ()
+    with _lock:
+        if _topology is not None and _topology[0] == names:
+            return _topology[1]
+    topology = read_topology(SYS_CLASS_NET)
+    with _lock:
+        _topology = (names, topology)
+    return topology
+
+
+def network_totals(physical_only: bool) -> Dict[str, int]:
+    """get_network_stats()'s totals over the top interfaces only.
+
+    Hosts without a topology to read, and backends without per-interface
+    counters (the mock), keep the ordinary totals.
+    """
+    counters = backend.active().interface_counters() if supported('interface_topology') else {}
+    if not counters:
+        return backend.active().network()
+    return sum_counters(counters, top_interfaces(current_topology(), physical_only))
+
+
+@requires('interface_topology')
+def get_interface_topology() -> Dict[str, Dict[str, Any]]:
+    """Get every network interface's kind, master, and slaves from /sys/class/net.
+
+    kind is "bond", "bridge", "physical", "virtual", or "loopback"; slaves
+    lists a bond's slaves or a bridge's ports and master names the bond or
+    bridge an interface is under (None if it isn't).
+    """
+    return copy.deepcopy(current_topology())
@@ -0,0 +1,223 @@
+"""
+Pulse Isolation
+Opt-in crash containment: collectors run in a child worker process.
+
+After spawn_worker(), every pulse.core collector sends its call to the
+worker (pulse.worker) and returns the worker's answer, so a crash or hang
+while reading some exotic /proc entry kills the worker instead of the
+caller. Calls are serialized over one pipe, as MessagePack when the msgpack
+package is installed and JSON otherwise (see pulse.protocol); either way
+tuples arrive as lists, and bytes need MessagePack.
+
+A worker found dead is restarted by the call that notices, which raises
+WorkerRestarted once; the following calls go to the new worker. A call
+that times out kills the worker and raises Timeout; the next call starts a
+fresh one without a WorkerRestarted, since the caller has already been told.
+"""
+import functools
+import os
+import subprocess
+import sys
+import threading
+import weakref
+from typing import Any, Callable, Dict, List, Optional, Set, Tuple
+
+from pulse import errors, protocol, validation
+from pulse.errors import PulseError, Timeout, WorkerRestarted
+from pulse.state import STATE, SysState, active_state
+
+DEFAULT_TIMEOUT_SECS = 10.0
+
+WORKER_COMMAND = (sys.executable, '-m', 'pulse.worker')
+
+# Collectors that read this process's own background threads, never proxied
+LOCAL_ONLY = ('get_samples', 'sampler_status', 'get_mount_events', 'get_disk_projection',
+              'query_history', 'get_anomalies', 'get_cpu_leaderboard', 'get_thermal_correlation',
+              'get_exited_processes')
+
+# Error types a worker response can name, re-raised as themselves here
+_ERRORS = {cls.__name__: cls for cls in (
+    ValueError, TypeError, KeyError, OSError, FileNotFoundError, PermissionError,
+    ProcessLookupError, NotImplementedError, TimeoutError,
+    errors.PulseError, errors.ProcessNotFound, errors.PermissionDenied, errors.Unsupported,
+    errors.Timeout, errors.PulseInternalError, errors.ServiceNotFound,
+)}
+
+_lock = threading.Lock()
+_enabled = False
+_timeout = DEFAULT_TIMEOUT_SECS
+_process: Optional[subprocess.Popen] = None
+_receiver: Optional[protocol.Receiver] = None
+_codec = protocol.DEFAULT_CODEC
+_next_id = 0
+_restarts = 0
+# Tokens of the states the worker has been sent, and of those since collected
+# here, which the next request tells the worker to drop
+_tracked: Set[int] = set()
+_released: List[int] = []
+
+
+def _start() -> None:
+    global _process, _receiver
+    # The worker imports the same pulse package as this process
+    package_root = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+    path = os.environ.get('PYTHONPATH')
+    env = dict(os.environ, PYTHONPATH=package_root if not path else package_root + os.pathsep + path)
+    _process = subprocess.Popen(
+        WORKER_COMMAND + (_codec,),
+        stdin=subprocess.PIPE, stdout=subprocess.PIPE, env=env,
+    )
+    _receiver = protocol.Receiver(_process.stdout, name=f'pulse-worker-{_process.pid}', codec=_codec)
+
+
+def _close(stream: Any) -> None:
+    try:
+        stream.close()
+    except OSError:
+        pass
+
+
+def _kill() -> None:
+    """Kill the worker, if any; the next call then starts a new one without complaint."""
+    global _process, _receiver
+    if _process is not None:
+        _process.kill()
+        _process.wait()
+        _close(_process.stdin)
+        _close(_process.stdout)
+    _process = _receiver = None
+
+
+def spawn_worker(timeout: float = DEFAULT_TIMEOUT_SECS) -> None:
+    """Start the worker and route every collector through it until stop_worker().
+
+    timeout bounds each call; one that takes longer kills the worker and
+    raises Timeout. Calling it again only changes the timeout.
+    """
+    global _enabled, _timeout
+    validation.positive('timeout', timeout)
+    with _lock:
+        _timeout = timeout
+        if _process is None or _process.poll() is not None:
+            _kill()
+            _start()
+        _enabled = True
+
+
+def stop_worker(timeout: Optional[float] = None) -> None:
+    """Run collectors in this process again and let the worker exit."""
+    global _enabled
+    with _lock:
+        _enabled = False
+        if _process is None:
+            return
+        # Closing stdin is the worker's cue to exit
+        _close(_process.stdin)
+        try:
+            _process.wait(timeout)
+        except subprocess.TimeoutExpired:
+            pass
+        _kill()
+
+
+def worker_status() -> Dict[str, Any]:
+    """Whether isolation is on, the worker's pid (None when none runs), and its restart count."""
+    with _lock:
+        running = _process is not None and _process.poll() is None
+        return {
+            'enabled': _enabled,
+            'pid': _process.pid if running else None,
+            'timeout': _timeout,
+            'restarts': _restarts,
+        }
+
+
+def _settings() -> Dict[str, Any]:
+    from pulse import clock, compat, core, privacy, tags, units
+
+    return {
+        'units': units.effective_units(),
+        'compat': compat.effective_mode(),
+        'clock': clock.get_clock(),
+        'max_age_ms': core.get_default_max_age(),
+        # Results are scrubbed here; the worker only needs to keep out of environ
+        'drop_environ': not privacy.environ_allowed(),
+        'process_tags': tags.get_process_tags(),
+    }
+
+
+def _restarted(reason: str) -> WorkerRestarted:
+    global _restarts
+    _kill()
+    _start()
+    _restarts += 1
+    return WorkerRestarted(f'{reason}; a new worker was started, so retrying is safe')
+
+
+def _release(token: int) -> None:
+    # Runs from the garbage collector, possibly while _lock is held, so it only appends
+    _released.append(token)
+
+
+def _state_token(state: SysState) -> Optional[int]:
+    """The worker's key for state: None for the module-level STATE, else its token."""
+    if state is STATE:
+        return None
+    if state.token not in _tracked:
+        _tracked.add(state.token)
+        weakref.finalize(state, _release, state.token)
+    return state.token
+
+
+def _error(error: Dict[str, str]) -> Exception:
+    cls = _ERRORS.get(error['type'])
+    if cls is None:
+        return PulseError(f"{error['type']} in the worker: {error['message']}")
+    return cls(error['message'])
+
+
+def call(function: str, args: Tuple[Any, ...], kwargs: Dict[str, Any], state: Optional[int]) -> Any:
+    """Run core.<function>(*args, **kwargs) in the worker and return its result."""
+    global _next_id
+    with _lock:
+        if _process is None or _process.poll() is not None:
+            if _process is not None:
+                raise _restarted(f'The worker (pid {_process.pid}) exited with {_process.returncode}')
+            _kill()
+            _start()
+        _next_id += 1
+        released = [_released.pop() for _ in range(len(_released))]
+        _tracked.difference_update(released)
+        request = {'id': _next_id, 'function': function, 'args': list(args), 'kwargs': kwargs,
+                   'state': state, 'released': released, 'settings': _settings()}
+        try:
+            protocol.write_message(_process.stdin, request, _codec)
+            response = _receiver.receive(_timeout)
+        except Timeout:
+            _kill()
+            raise Timeout(f'{function} got no answer from the worker within {_timeout} s; '
+                          f'the worker was killed') from None
+        except (OSError, EOFError):
+            raise _restarted(f'The worker died during {function}') from None
+        if response.get('id') != request['id']:
+            _kill()
+            raise PulseError(f"Worker answered request {response.get('id')} "
+                             f"to request {request['id']}; the worker was killed")
+    if 'error' in response:
+        raise _error(response['error'])
+    return response['result']
+
+
+def proxied(name: str, function: Callable[..., Any]) -> Callable[..., Any]:
+    """Wrap the collector core.<name> to run in the worker while isolation is on."""
+    if name in LOCAL_ONLY:
+        return function
+
+    @functools.wraps(function)
+    def isolated(*args: Any, **kwargs: Any) -> Any:
+        if not _enabled:
+            return function(*args, **kwargs)
+        with _lock:
+            token = _state_token(active_state())
+        return call(name, args, kwargs, token)
+    return isolated
@@ -0,0 +1,118 @@
+"""
+Pulse Kernel Log
+Structured, incrementally pollable reads of the kernel ring buffer (/dev/kmsg).
+"""
+import errno
+import os
+import re
+from typing import Any, Dict, List, Optional
+
+from pulse import validation
+from pulse.direct_os import LINUX
+from pulse.errors import PermissionDenied
+
+DEV_KMSG = '/dev/kmsg'
+
+# syslog severities, most severe first; a record's level is its priority & 7
+LEVELS = ('emerg', 'alert', 'crit', 'err', 'warning', 'notice', 'info', 'debug')
+
+# Each read() of /dev/kmsg returns exactly one record; 8 KiB fits the largest
+_RECORD_BUFFER = 8192
+
+
+def _unescape(text: str) -> str:
+    # The kernel writes non-printable bytes (and backslash itself) as \xNN
+    raw = re.sub(rb'\\x([0-9a-fA-F]{2})', lambda m: bytes([int(m.group(1), 16)]), text.encode('utf-8'))
+    return raw.decode('utf-8', 'replace')
+
+
+def parse_kmsg_record(record: str) -> Optional[Dict[str, Any]]:
+    """Parse one /dev/kmsg record (Documentation/ABI/testing/dev-kmsg).
+
+    The first line is "<prio>,<seq>,<usec>,<flags>[,...];<message>"; any
+    following lines starting with a space are KEY=value device properties
+    such as SUBSYSTEM and DEVICE. Returns None for malformed records.
+    """
+    lines = record.rstrip('\n').split('\n')
+    header, sep, message = lines[0].partition(';')
+    if not sep:
+        return None
+    fields = header.split(',')
+    try:
+        priority, seq, usec = int(fields[0]), int(fields[1]), int(fields[2])
+    except (ValueError, IndexError):
+        return None
+
+    properties = {}
+    for line in lines[1:]:
+        if line.startswith(' '):
+            key, _, value = line[1:].partition('=')
+            properties[key] = _unescape(value)
+
+    return {
+        'seq': seq,
+        'timestamp': usec / 1_000_000,
+        'level': LEVELS[priority & 7],
+        'facility': priority >> 3,
+        # "c" marks a fragment continued by the next record, "+" a continuation
+        'continuation': len(fields) > 3 and fields[3] in ('c', '+'),
+        'message': _unescape(message),
+        'properties': properties,
+    }
+
+
+def _level_rank(level: Optional[str]) -> int:
+    if level is None:
+        return len(LEVELS) - 1
+    validation.one_of('level', level.lower(), LEVELS)
+    return LEVELS.index(level.lower())
+
+
+if LINUX:
+    def get_kernel_messages(since_seq: Optional[int] = None,
+                            level: Optional[str] = None) -> List[Dict[str, Any]]:
+        """Get kernel log records newer than since_seq, at level or more severe.
+
+        Pass the last returned "seq" back in to poll incrementally. Never blocks.
+        Raises PermissionDenied when /dev/kmsg isn't readable, as with
+        kernel.dmesg_restrict=1 for unprivileged users.
+        """
+        validation.optional_at_least('since_seq', since_seq, 0)
+        max_rank = _level_rank(level)
+        try:
+            fd = os.open(DEV_KMSG, os.O_RDONLY | os.O_NONBLOCK)
+        except PermissionError:
+            raise PermissionDenied(f'{DEV_KMSG} is not readable (kernel.dmesg_restrict?)') from None
+        messages = []
+        try:
+            while True:
+                try:
+                    chunk = os.read(fd, _RECORD_BUFFER)
+                except BlockingIOError:
+                    break
+                except OSError as e:
+                    # EPIPE: records we were about to read were overwritten; the
+                    # next read resumes at the oldest surviving one
+                    if e.errno == errno.EPIPE:
+                        continue
+                    raise
+                if not chunk:
+                    break
+                entry = parse_kmsg_record(chunk.decode('utf-8', 'replace'))
+                if entry is None:
+                    continue
+                if since_seq is not None and entry['seq'] <= since_seq:
+                    continue
+                if LEVELS.index(entry['level']) <= max_rank:
+                    messages.append(entry)
+        finally:
+            os.close(fd)
+        return messages
+
+else:
+    def get_kernel_messages(since_seq: Optional[int] = None,
+                            level: Optional[str] = None) -> List[Dict[str, Any]]:
+        """The kernel ring buffer isn't exposed on this platform; always empty."""
+        validation.optional_at_least('since_seq', since_seq, 0)
+        _level_rank(level)
+        return []
@@ -0,0 +1,220 @@
+"""
+Pulse Leaderboard
+Which processes used the most CPU time over a recent window, exited ones included.
+
+While the sampler runs with its "processes" section, each sample also reads
+every process's cumulative CPU time (user plus system), notes it on the
+sample's process entries as cpu_time, and records how much
+it grew since the previous sample under (pid, start_time), so a reused pid
+starts a new entry. get_cpu_leaderboard() sums that growth over the window:
+a burst between two polls counts in full, and a process that has exited
+keeps its total, marked exited, until it ages out. CPU time a process used
+after its last sample and before it exited is missed.
+
+Growth is kept in BUCKET_SECS buckets for RETENTION_SECS, and at most
+MAX_ENTRIES processes are tracked; beyond that the ones that used the least
+are dropped first.
+"""
+import os
+import threading
+import time
+from collections import deque
+from typing import Any, Deque, Dict, List, Optional, Tuple
+
+from pulse import log, validation
+from pulse.direct_os import LINUX
+
+RETENTION_SECS = 3600.0
+BUCKET_SECS = 5.0
+MAX_ENTRIES = 1024
+GROUP_BY = ('process', 'name')
+
+# (pid, start_time) -> (name, cumulative CPU seconds)
+Readings = Dict[Tuple[int, float], Tuple[str, float]]
+
+
+class Leaderboard:
+    """Per-process CPU time growth between updates, bounded in time and entries."""
+
+    def __init__(self, max_entries: int = MAX_ENTRIES, retention_secs: float = RETENTION_SECS,
+                 bucket_secs: float = BUCKET_SECS):
+        self.max_entries = max_entries
+        self.retention_secs = retention_secs
+        self.bucket_secs = bucket_secs
+        self._lock = threading.Lock()
+        self._entries: Dict[Tuple[int, float], Dict[str, Any]] = {}
+        self._last: Optional[float] = None
+
+    def clear(self) -> None:
+        with self._lock:
+            self._entries = {}
+            self._last = None
+
+    def __len__(self) -> int:
+        with self._lock:
+            return len(self._entries)
+
+    def _add(self, buckets: Deque[List[float]], now: float, used: float) -> None:
+        if used <= 0:
+            return
+        if buckets and now - buckets[-1][0] < self.bucket_secs:
+            buckets[-1][1] += used
+        else:
+            buckets.append([now, used])
+
+    def update(self, readings: Readings, now: float) -> None:
+        """Record readings taken at now (unix seconds).
+
+        A process first seen after the first update that started since the
+        previous one counts all its CPU time; any other newcomer only sets
+        its baseline. Processes missing from readings are marked exited.
+        """
+        with self._lock:
+            previous = self._last
+            for key, (name, cpu) in readings.items():
+                entry = self._entries.get(key)
+                if entry is None:
+                    entry = self._entries[key] = {'pid': key[0], 'name': name, 'start_time': key[1],
+                                                  'cpu': cpu, 'exited': False, 'buckets': deque()}
+                    if previous is not None and key[1] >= previous:
+                        self._add(entry['buckets'], now, cpu)
+                    continue
+                # CPU time never goes down; a smaller reading is a different read, not usage
+                self._add(entry['buckets'], now, max(0.0, cpu - entry['cpu']))
+                entry.update(name=name, cpu=cpu, exited=False)
+            for key, entry in self._entries.items():
+                if key not in readings:
+                    entry['exited'] = True
+            self._prune(now)
+            self._last = now
+
+    def _prune(self, now: float) -> None:
+        since = now - self.retention_secs
+        for key in list(self._entries):
+            buckets = self._entries[key]['buckets']
+            while buckets and buckets[0][0] <= since:
+                buckets.popleft()
+            if not buckets and self._entries[key]['exited']:
+                del self._entries[key]
+        excess = len(self._entries) - self.max_entries
+        if excess > 0:
+            # Least CPU first; among equals, exited processes go before running ones
+            ranked = sorted(self._entries, key=lambda k: (_total(self._entries[k]['buckets']),
+                                                          not self._entries[k]['exited']))
+            for key in ranked[:excess]:
+                del self._entries[key]
+
+    def top(self, window_secs: float, n: int, group_by: str = 'process') -> List[Dict[str, Any]]:
+        """The n biggest CPU users over the window_secs before the latest update.
+
+        By process, entries are {pid, name, start_time, cpu_seconds, exited};
+        by name, {name, cpu_seconds, processes, exited} where exited means
+        every process of that name has. Processes that used nothing are left out.
+        """
+        with self._lock:
+            if self._last is None:
+                return []
+            since = self._last - window_secs
+            used = [(entry, _total(entry['buckets'], since)) for entry in self._entries.values()]
+        used = [(entry, seconds) for entry, seconds in used if seconds > 0]
+        if group_by == 'process':
+            ranked = [{'pid': entry['pid'], 'name': entry['name'], 'start_time': entry['start_time'],
+                       'cpu_seconds': seconds, 'exited': entry['exited']} for entry, seconds in used]
+        else:
+            by_name: Dict[str, Dict[str, Any]] = {}
+            for entry, seconds in used:
+                group = by_name.setdefault(entry['name'], {'name': entry['name'], 'cpu_seconds': 0.0,
+                                                           'processes': 0, 'exited': True})
+                group['cpu_seconds'] += seconds
+                group['processes'] += 1
+                group['exited'] = group['exited'] and entry['exited']
+            ranked = list(by_name.values())
+        ranked.sort(key=lambda e: e['cpu_seconds'], reverse=True)
+        return ranked[:n]
+
+
+def _total(buckets: Deque[List[float]], since: float = float('-inf')) -> float:
+    return sum(used for started, used in buckets if started > since)
+
+
+if LINUX:
+    from pulse import system
+    from pulse.processes import PROC, parse_proc_stat
+
+    _CLOCK_TICKS = os.sysconf('SC_CLK_TCK')
+
+    def read_cpu_times() -> Readings:
+        """Every process's name and cumulative user plus system CPU seconds from /proc."""
+        try:
+            with open(system.PROC_STAT, 'r') as f:
+                boot = float(system.parse_btime(f.read()) or 0)
+        except OSError as e:
+            log.read_failed(system.PROC_STAT, e)
+            return {}
+        readings = {}
+        for name in os.listdir(PROC):
+            if not name.isdigit():
+                continue
+            path = f'{PROC}/{name}/stat'
+            try:
+                with open(path, 'r') as f:
+                    comm, fields = parse_proc_stat(f.read())
+                # stat fields 14 and 15 (utime, stime) and 22 (starttime)
+                cpu = (int(fields[11]) + int(fields[12])) / _CLOCK_TICKS
+                start = boot + int(fields[19]) / _CLOCK_TICKS
+            except (OSError, ValueError, IndexError) as e:
+                log.process_skipped(int(name), path, e)
+                continue
+            readings[(int(name), start)] = (comm, cpu)
+        return readings
+
+else:
+    def read_cpu_times() -> Readings:
+        """Every process's name and cumulative user plus system CPU seconds via psutil."""
+        import psutil
+
+        readings = {}
+        for process in psutil.process_iter(['name', 'cpu_times', 'create_time']):
+            info = process.info
+            if info['cpu_times'] is None or info['create_time'] is None:
+                continue
+            readings[(process.pid, info['create_time'])] = (
+                info['name'] or '', info['cpu_times'].user + info['cpu_times'].system)
+        return readings
+
+
+BOARD = Leaderboard()
+
+
+def on_sample(sample: Dict[str, Any]) -> None:
+    """Record every process's CPU time; the sampler calls this before recording each sample.
+
+    Each of the sample's processes also gets the cpu_time (cumulative user
+    plus system seconds) and start_time read for it, which is what
+    pulse.report integrates.
+    """
+    readings = read_cpu_times()
+    # Wall time whatever set_clock() chose, as newcomers' start times are compared with it
+    BOARD.update(readings, time.time())
+    by_pid = {pid: (start, cpu) for (pid, start), (_, cpu) in readings.items()}
+    for proc in sample.get('processes') or ():
+        if proc['pid'] in by_pid:
+            proc['start_time'], proc['cpu_time'] = by_pid[proc['pid']]
+
+
+def get_cpu_leaderboard(window_secs: float = 3600, n: int = 10,
+                        group_by: str = 'process') -> List[Dict[str, Any]]:
+    """Get the processes that used the most CPU seconds over the last window_secs.
+
+    Needs the sampler running with its "processes" section; the window ends
+    at its latest sample. group_by "process" ranks {pid, name, start_time,
+    cpu_seconds, exited} entries, "name" sums them into {name, cpu_seconds,
+    processes, exited}. exited marks processes that have ended (for a name,
+    all of them). Empty until the sampler has taken two samples. Raises
+    ValueError for a window_secs outside 1..RETENTION_SECS, an n below 1, or
+    another group_by.
+    """
+    validation.between('window_secs', window_secs, 1, RETENTION_SECS)
+    validation.at_least('n', n, 1)
+    validation.one_of('group_by', group_by, GROUP_BY)
+    return BOARD.top(window_secs, n, group_by)
@@ -0,0 +1,142 @@
+"""
+Pulse Kernel Limits
+System-wide resource tables and how close they are to full.
+"""
+import os
+from typing import Any, Dict, Optional, Tuple
+
+from pulse import log, system
+from pulse.direct_os import FREEBSD, LINUX, MACOS
+
+PROC = '/proc'
+PROC_SYS_FS = '/proc/sys/fs'
+PROC_SYS_KERNEL = '/proc/sys/kernel'
+PROC_LOADAVG = '/proc/loadavg'
+
+_INOTIFY_LINK = 'anon_inode:inotify'
+_EPOLL_LINK = 'anon_inode:[eventpoll]'
+
+
+def limit_entry(current: Optional[int], maximum: Optional[int]) -> Dict[str, Any]:
+    """{current, max, percent}; percent is None when either side is unknown."""
+    percent = current / maximum * 100 if current is not None and maximum else None
+    return {'current': current, 'max': maximum, 'percent': percent}
+
+
+def parse_file_nr(text: str) -> Tuple[int, int, int]:
+    """(allocated, free, max) from /proc/sys/fs/file-nr."""
+    allocated, free, maximum = (int(v) for v in text.split()[:3])
+    return allocated, free, maximum
+
+
+def count_fdinfo_entries(text: str, prefix: str) -> int:
+    """Lines in an fdinfo file starting with prefix ("inotify wd:", "tfd:")."""
+    return sum(1 for line in text.splitlines() if line.startswith(prefix))
+
+
+def parse_loadavg_tasks(text: str) -> int:
+    """Total scheduling entities (threads) from the "running/total" field of /proc/loadavg."""
+    return int(text.split()[3].split('/')[1])
+
+
+def _read_int(path: str) -> Optional[int]:
+    try:
+        with open(path, 'r') as f:
+            return int(f.read().split()[0])
+    except (OSError, ValueError, IndexError) as e:
+        log.read_failed(path, e)
+        return None
+
+
+def scan_user_watches(uid: int) -> Dict[str, int]:
+    """Count one user's inotify instances/watches and epoll watches across /proc.
+
+    Processes that exit mid-walk or whose fds we may not read are skipped, so
+    without root the totals only cover processes we can see.
+    """
+    totals = {'inotify_instances': 0, 'inotify_watches': 0, 'epoll_watches': 0}
+    try:
+        pids = [p for p in os.listdir(PROC) if p.isdigit()]
+    except OSError:
+        return totals
+
+    for pid in pids:
+        base = os.path.join(PROC, pid)
+        try:
+            if os.stat(base).st_uid != uid:
+                continue
+            fds = os.listdir(os.path.join(base, 'fd'))
+        except OSError:
+            continue
+        for fd in fds:
+            try:
+                link = os.readlink(os.path.join(base, 'fd', fd))
+                if link == _INOTIFY_LINK:
+                    prefix = 'inotify wd:'
+                elif link == _EPOLL_LINK:
+                    prefix = 'tfd:'
+                else:
+                    continue
+                with open(os.path.join(base, 'fdinfo', fd), 'r') as f:
+                    entries = count_fdinfo_entries(f.read(), prefix)
+            except OSError:
+                continue
+            if prefix == 'tfd:':
+                totals['epoll_watches'] += entries
+            else:
+                totals['inotify_instances'] += 1
+                totals['inotify_watches'] += entries
+    return totals
+
+
+if LINUX:
+    def get_kernel_limits() -> Dict[str, Dict[str, Any]]:
+        """Get open-file, thread, pid, inotify, and epoll usage against their kernel limits.
+
+        open_files (file-nr against file-max), threads, and pids are system-wide;
+        the inotify and epoll figures are for the current user, matching how the kernel
+        enforces fs.inotify.max_user_* and fs.epoll.max_user_watches.
+        """
+        limits: Dict[str, Dict[str, Any]] = {}
+        try:
+            with open(os.path.join(PROC_SYS_FS, 'file-nr'), 'r') as f:
+                allocated, free, maximum = parse_file_nr(f.read())
+            # "free" has been 0 since 2.6; kept for older kernels' accounting
+            limits['open_files'] = limit_entry(allocated - free, maximum)
+        except (OSError, ValueError):
+            pass
+
+        # Every thread holds a pid, so one task count measures both tables
+        try:
+            with open(PROC_LOADAVG, 'r') as f:
+                tasks: Optional[int] = parse_loadavg_tasks(f.read())
+        except (OSError, ValueError, IndexError):
+            tasks = None
+        limits['threads'] = limit_entry(tasks, _read_int(os.path.join(PROC_SYS_KERNEL, 'threads-max')))
+        limits['pids'] = limit_entry(tasks, _read_int(os.path.join(PROC_SYS_KERNEL, 'pid_max')))
+
+        usage = scan_user_watches(os.getuid())
+        limits['inotify_watches'] = limit_entry(
+            usage['inotify_watches'], _read_int(os.path.join(PROC_SYS_FS, 'inotify', 'max_user_watches')))
+        limits['inotify_instances'] = limit_entry(
+            usage['inotify_instances'], _read_int(os.path.join(PROC_SYS_FS, 'inotify', 'max_user_instances')))
+        limits['epoll_watches'] = limit_entry(
+            usage['epoll_watches'], _read_int(os.path.join(PROC_SYS_FS, 'epoll', 'max_user_watches')))
+        return limits
+
+elif MACOS or FREEBSD:
+    # The same counter under each kernel's name
+    _OPEN_FILES = b'kern.num_files' if MACOS else b'kern.openfiles'
+
+    def get_kernel_limits() -> Dict[str, Dict[str, Any]]:
+        """Get system-wide open files against kern.maxfiles."""
+        current, maximum = system.sysctl(_OPEN_FILES), system.sysctl(b'kern.maxfiles')
+        if current is None or maximum is None:
+            return {}
+        return {'open_files': limit_entry(int.from_bytes(current, 'little'),
+                                          int.from_bytes(maximum, 'little'))}
+
+else:
+    def get_kernel_limits() -> Dict[str, Dict[str, Any]]:
+        """Kernel resource tables aren't exposed on this platform."""
+        return {}
@@ -0,0 +1,66 @@
+"""
+Pulse Logging
+Why a collector left something out, on the "pulse_core" logger.
+
+Collectors degrade instead of failing: an unreadable sysfs file reads as
+None, a process that exits mid-scan is skipped. Each such path reports here,
+with the path and errno as attributes of the record ("path", "errno").
+Nothing is emitted until enable_logging() picks a level; records then go
+through Python's logging like any other, so handlers are configured as usual.
+"""
+import logging
+from typing import Optional
+
+from pulse import validation
+
+LOGGER_NAME = 'pulse_core'
+
+# enable_logging() levels; "off" silences the logger again
+LEVELS = {
+    'debug': logging.DEBUG,
+    'info': logging.INFO,
+    'warning': logging.WARNING,
+    'error': logging.ERROR,
+    'off': logging.CRITICAL + 1,
+}
+
+logger = logging.getLogger(LOGGER_NAME)
+logger.addHandler(logging.NullHandler())
+# Quiet by default, even when the application logs everything at the root
+logger.setLevel(LEVELS['off'])
+
+
+def enable_logging(level: str = 'debug') -> None:
+    """Emit records at level and above on the "pulse_core" logger.
+
+    Missing files and vanished processes are logged at debug; permission and
+    I/O errors, which usually mean a reading is wrong rather than absent, at
+    warning. Raises ValueError for a level not in LEVELS.
+    """
+    validation.one_of('level', level, LEVELS)
+    logger.setLevel(LEVELS[level])
+
+
+def read_failed(path: str, error: Exception) -> None:
+    """Record that path couldn't be read or parsed, so the value it held is missing."""
+    errno: Optional[int] = getattr(error, 'errno', None)
+    # Absent files are routine (optional attributes, exited processes), as are unparsable ones
+    routine = isinstance(error, FileNotFoundError) or errno is None
+    level = logging.DEBUG if routine else logging.WARNING
+    if logger.isEnabledFor(level):
+        reason = getattr(error, 'strerror', None) or error
+        logger.log(level, 'Cannot read %s: %s', path, reason, extra={'path': path, 'errno': errno})
+
+
+def process_skipped(pid: int, path: str, error: Exception) -> None:
+    """Record that a scan left out pid because path couldn't be read."""
+    if logger.isEnabledFor(logging.DEBUG):
+        errno: Optional[int] = getattr(error, 'errno', None)
+        logger.debug('Skipped pid %s: cannot read %s: %s', pid, path,
+                     getattr(error, 'strerror', None) or error,
+                     extra={'pid': pid, 'path': path, 'errno': errno})
+
+
+def state_discarded(path: str, reason: str) -> None:
+    """Record that saved baselines at path were ignored, so rates start over."""
+    logger.warning('Ignoring saved state %s: %s', path, reason, extra={'path': path, 'errno': None})
@@ -0,0 +1,137 @@
+"""
+Pulse macOS
+Memory pressure, compressed memory, and CPU core types the way Activity Monitor shows them.
+
+Free bytes mean little on macOS, which fills spare memory with cache and
+compresses before it swaps; the kernel's pressure level is the number to
+watch. Figures come from host_statistics64(HOST_VM_INFO64) and a few
+sysctls. Every buffer is decoded by a parser over raw bytes, so the layouts
+can be tested on any platform; only the read_* functions call into the kernel.
+"""
+import ctypes
+import struct
+from typing import Any, Dict, List, Optional
+
+from pulse.capabilities import requires
+
+# struct vm_statistics64 (mach/vm_statistics.h), in declaration order
+VM_STATISTICS64_FIELDS = (
+    'free_count', 'active_count', 'inactive_count', 'wire_count',
+    'zero_fill_count', 'reactivations', 'pageins', 'pageouts', 'faults', 'cow_faults',
+    'lookups', 'hits', 'purges',
+    'purgeable_count', 'speculative_count',
+    'decompressions', 'compressions', 'swapins', 'swapouts',
+    'compressor_page_count', 'throttled_count', 'external_page_count', 'internal_page_count',
+    'total_uncompressed_pages_in_compressor',
+)
+_VM_STATISTICS64 = struct.Struct('<4I9Q2I4Q4IQ')
+
+# struct xsw_usage from vm.swapusage: total, avail, used bytes, page size, encrypted
+_XSW_USAGE = struct.Struct('<3QIi')
+
+# kern.memorystatus_vm_pressure_level values (kNOTE_MEMORYSTATUS_PRESSURE_*)
+PRESSURE_LEVELS = {1: 'normal', 2: 'warn', 4: 'critical'}
+
+_HOST_VM_INFO64 = 4
+
+
+def parse_vm_statistics64(data: bytes) -> Dict[str, int]:
+    """Page counters by field name from a vm_statistics64 buffer."""
+    if len(data) < _VM_STATISTICS64.size:
+        raise ValueError(f'vm_statistics64 needs {_VM_STATISTICS64.size} bytes, got {len(data)}')
+    return dict(zip(VM_STATISTICS64_FIELDS, _VM_STATISTICS64.unpack_from(data)))
+
+
+def parse_xsw_usage(data: bytes) -> Dict[str, int]:
+    """{swap_total, swap_used} bytes from a vm.swapusage buffer."""
+    if len(data) < _XSW_USAGE.size:
+        raise ValueError(f'xsw_usage needs {_XSW_USAGE.size} bytes, got {len(data)}')
+    total, _, used, _, _ = _XSW_USAGE.unpack_from(data)
+    return {'swap_total': total, 'swap_used': used}
+
+
+def pressure_level(value: Optional[int]) -> Optional[str]:
+    """"normal" | "warn" | "critical" from kern.memorystatus_vm_pressure_level, None if unknown."""
+    return PRESSURE_LEVELS.get(value) if value is not None else None
+
+
+def memory_from_vm_statistics(stats: Dict[str, int], page_size: int, total: int) -> Dict[str, Any]:
+    """get_memory_info()'s byte figures, matching Activity Monitor's "Memory Used".
+
+    Used is app memory (anonymous pages the kernel can't simply drop) plus
+    wired plus compressed; file cache and purgeable pages count as available.
+    """
+    app = max(0, stats['internal_page_count'] - stats['purgeable_count'])
+    compressed = stats['compressor_page_count'] * page_size
+    used = min(total, (app + stats['wire_count']) * page_size + compressed)
+    return {
+        'total': total,
+        'available': total - used,
+        'used': used,
+        'free': stats['free_count'] * page_size,
+        'compressed': compressed,
+        'percent': used / total * 100 if total else 0,
+    }
+
+
+def usage_by_core_type(percents: List[float], efficiency_cpus: int) -> Dict[str, Dict[str, Any]]:
+    """Average busy percent of the efficiency and performance cores.
+
+    macOS numbers the efficiency cores first, so they are the leading
+    efficiency_cpus entries of the per-CPU list.
+    """
+    groups = {'performance': percents[efficiency_cpus:], 'efficiency': percents[:efficiency_cpus]}
+    return {
+        kind: {'logical_cpus': len(cpus), 'percent': sum(cpus) / len(cpus) if cpus else None}
+        for kind, cpus in groups.items()
+    }
+
+
+def _sysctl_int(name: bytes) -> Optional[int]:
+    from pulse import system
+
+    value = system.sysctl(name)
+    return int.from_bytes(value, 'little') if value is not None else None
+
+
+def efficiency_cpu_count() -> Optional[int]:
+    """Logical efficiency CPUs, or None on a Mac without two performance levels (Intel)."""
+    if (_sysctl_int(b'hw.nperflevels') or 0) < 2:
+        return None
+    # perflevel0 is the fastest cluster, perflevel1 the efficiency one
+    return _sysctl_int(b'hw.perflevel1.logicalcpu')
+
+
+def read_vm_statistics() -> Dict[str, int]:
+    libsystem = ctypes.CDLL('/usr/lib/libSystem.dylib')
+    libsystem.mach_host_self.restype = ctypes.c_uint
+    buffer = ctypes.create_string_buffer(_VM_STATISTICS64.size)
+    count = ctypes.c_uint(_VM_STATISTICS64.size // 4)
+    result = libsystem.host_statistics64(libsystem.mach_host_self(), _HOST_VM_INFO64,
+                                         buffer, ctypes.byref(count))
+    if result != 0:
+        raise OSError(f'host_statistics64 failed with kern_return_t {result}')
+    return parse_vm_statistics64(buffer.raw)
+
+
+def read_memory_info() -> Dict[str, Any]:
+    from pulse import system
+
+    memory = memory_from_vm_statistics(read_vm_statistics(), _sysctl_int(b'hw.pagesize') or 4096,
+                                       _sysctl_int(b'hw.memsize') or 0)
+    swap = system.sysctl(b'vm.swapusage')
+    memory.update(parse_xsw_usage(swap) if swap else {'swap_total': 0, 'swap_used': 0})
+    memory['pressure_level'] = pressure_level(_sysctl_int(b'kern.memorystatus_vm_pressure_level'))
+    return memory
+
+
+@requires('core_types')
+def get_cpu_core_type_usage() -> Dict[str, Dict[str, Any]]:
+    """Get busy percent averaged over the performance and the efficiency cores.
+
+    Apple Silicon only: {"performance": {logical_cpus, percent}, "efficiency":
+    {...}}. It shares get_cpu_percents()'s baseline, so the first call reads 0.
+    """
+    from pulse import direct_os
+
+    return usage_by_core_type(direct_os.get_cpu_percents(), efficiency_cpu_count() or 0)
@@ -0,0 +1,126 @@
+"""
+Pulse Mount Watcher
+Background detection of filesystems being mounted and unmounted.
+
+On Linux the kernel flags /proc/self/mounts with POLLPRI whenever the mount
+table changes, so the watcher sleeps in poll() instead of re-reading it. Other
+platforms compare periodic snapshots on the same thread; the API is identical.
+"""
+import select
+import threading
+from collections import deque
+from typing import Any, Deque, Dict, Iterable, List, Optional, Tuple
+
+from pulse import clock, disks, validation
+from pulse.direct_os import LINUX
+
+PROC_SELF_MOUNTS = '/proc/self/mounts'
+
+# Oldest events are dropped once this many are waiting to be drained
+MAX_PENDING_EVENTS = 1024
+
+_events: Deque[Dict[str, Any]] = deque(maxlen=MAX_PENDING_EVENTS)
+_events_lock = threading.Lock()
+_thread: Optional[threading.Thread] = None
+_stop = threading.Event()
+_control_lock = threading.Lock()
+
+
+def _mount_key(mount: Dict[str, Any]) -> Tuple[str, str, str]:
+    return (mount['device'], mount['mountpoint'], mount['fstype'])
+
+
+def diff_mounts(before: Iterable[Dict[str, Any]], after: Iterable[Dict[str, Any]],
+                timestamp: float) -> List[Dict[str, Any]]:
+    """Events turning one mount table snapshot into the next, unmounts first."""
+    old = {_mount_key(m): m for m in before}
+    new = {_mount_key(m): m for m in after}
+    events = []
+    for action, source, other in (('unmounted', old, new), ('mounted', new, old)):
+        for key, mount in source.items():
+            if key not in other:
+                events.append({
+                    'action': action,
+                    'mount_point': mount['mountpoint'],
+                    'device': mount['device'],
+                    'fs_type': mount['fstype'],
+                    'timestamp': timestamp,
+                })
+    return events
+
+
+def _snapshot(physical_only: bool) -> List[Dict[str, Any]]:
+    return disks.filter_mounts(disks.list_mounts(), physical_only)
+
+
+def _wait_for_change(poller: Optional[Any], interval_secs: float) -> None:
+    """Block until the mount table may have changed or the interval elapses."""
+    if poller is None:
+        _stop.wait(interval_secs)
+    else:
+        poller.poll(interval_secs * 1000)
+
+
+def _run(interval_secs: float, physical_only: bool) -> None:
+    poller = None
+    mounts_file = None
+    if LINUX:
+        try:
+            mounts_file = open(PROC_SELF_MOUNTS, 'r')
+            poller = select.poll()
+            poller.register(mounts_file, select.POLLPRI | select.POLLERR)
+        except (OSError, AttributeError):
+            poller = None
+
+    try:
+        previous = _snapshot(physical_only)
+        while not _stop.is_set():
+            _wait_for_change(poller, interval_secs)
+            if _stop.is_set():
+                break
+            current = _snapshot(physical_only)
+            events = diff_mounts(previous, current, clock.now())
+            if events:
+                with _events_lock:
+                    _events.extend(events)
+            previous = current
+    finally:
+        if mounts_file is not None:
+            mounts_file.close()
+
+
+def start_mount_watcher(interval_secs: float = 2.0, physical_only: bool = True) -> None:
+    """Start watching for mount table changes; a no-op if already running.
+
+    interval_secs bounds how quickly stop_mount_watcher() takes effect, and is the
+    snapshot period where the OS offers no change notification.
+    """
+    validation.positive('interval_secs', interval_secs)
+    global _thread
+    with _control_lock:
+        if _thread is not None and _thread.is_alive():
+            return
+        _stop.clear()
+        _thread = threading.Thread(
+            target=_run, args=(interval_secs, physical_only),
+            name='pulse-mount-watcher', daemon=True,
+        )
+        _thread.start()
+
+
+def stop_mount_watcher(timeout: Optional[float] = None) -> None:
+    """Stop the watcher thread and wait for it to exit."""
+    global _thread
+    with _control_lock:
+        _stop.set()
+        if _thread is not None:
+            _thread.join(timeout)
+            _thread = None
+
+
+def get_mount_events() -> List[Dict[str, Any]]:
+    """Drain mount/unmount events recorded since the previous call."""
+    with _events_lock:
+        events = list(_events)
+        _events.clear()
+    return events
@@ -0,0 +1,225 @@
+"""
+Pulse Socket Telemetry
+Socket tables and protocol counters read from /proc/net on Linux.
+"""
+import ipaddress
+import sys
+from typing import Any, Dict, List, Optional, Tuple
+
+from pulse import log, validation
+from pulse.capabilities import requires
+from pulse.collect import counter_delta
+from pulse.direct_os import LINUX
+from pulse.state import active_state
+
+PROC_NET = '/proc/net'
+
+# Kernel socket state codes (include/net/tcp_states.h)
+TCP_STATES = {
+    '01': 'ESTABLISHED',
+    '02': 'SYN_SENT',
+    '03': 'SYN_RECV',
+    '04': 'FIN_WAIT1',
+    '05': 'FIN_WAIT2',
+    '06': 'TIME_WAIT',
+    '07': 'CLOSE',
+    '08': 'CLOSE_WAIT',
+    '09': 'LAST_ACK',
+    '0A': 'LISTEN',
+    '0B': 'CLOSING',
+    '0C': 'NEW_SYN_RECV',
+}
+
+# /proc/net/snmp "Udp:" columns we expose, mapped to dict keys
+UDP_COUNTERS = {
+    'InDatagrams': 'in_datagrams',
+    'NoPorts': 'no_ports',
+    'InErrors': 'in_errors',
+    'OutDatagrams': 'out_datagrams',
+    'RcvbufErrors': 'rcvbuf_errors',
+    'SndbufErrors': 'sndbuf_errors',
+    'InCsumErrors': 'in_csum_errors',
+}
+
+_CONNECTION_KINDS = {
+    'tcp': ('tcp', 'tcp6'),
+    'tcp4': ('tcp',),
+    'tcp6': ('tcp6',),
+    'udp': ('udp', 'udp6'),
+    'udp4': ('udp',),
+    'udp6': ('udp6',),
+    'inet': ('tcp', 'tcp6', 'udp', 'udp6'),
+}
+
+
+def decode_address(hex_addr: str) -> Tuple[str, int]:
+    """Decode a /proc/net "ADDR:PORT" pair into (ip, port).
+
+    The address is stored as host-endian 32-bit words, the port as big-endian hex.
+    """
+    addr, port = hex_addr.split(':')
+    raw = bytes.fromhex(addr)
+    if sys.byteorder == 'little':
+        raw = b''.join(raw[i:i + 4][::-1] for i in range(0, len(raw), 4))
+    return str(ipaddress.ip_address(raw)), int(port, 16)
+
+
+def parse_socket_line(line: str, protocol: str) -> Optional[Dict[str, Any]]:
+    """Parse one row of /proc/net/{tcp,tcp6,udp,udp6}; returns None for headers."""
+    parts = line.split()
+    if len(parts) < 10 or not parts[0].endswith(':'):
+        return None
+    try:
+        local_ip, local_port = decode_address(parts[1])
+        remote_ip, remote_port = decode_address(parts[2])
+        tx_queue, rx_queue = (int(q, 16) for q in parts[4].split(':'))
+        state = parts[3].upper()
+        entry = {
+            'protocol': protocol,
+            'local_address': local_ip,
+            'local_port': local_port,
+            'remote_address': remote_ip,
+            'remote_port': remote_port,
+            'tx_queue': tx_queue,
+            'rx_queue': rx_queue,
+            'uid': int(parts[7]),
+            'inode': int(parts[9]),
+        }
+    except ValueError:
+        return None
+
+    if protocol.startswith('udp'):
+        # UDP sockets are either connected (01) or merely bound (07)
+        entry['status'] = 'ESTABLISHED' if state == '01' else 'NONE'
+        entry['drops'] = int(parts[12]) if len(parts) > 12 and parts[12].isdigit() else None
+    else:
+        entry['status'] = TCP_STATES.get(state, 'UNKNOWN')
+    return entry
+
+
+def parse_snmp(text: str) -> Dict[str, Dict[str, int]]:
+    """Parse /proc/net/snmp header/value line pairs into {section: {field: value}}."""
+    sections: Dict[str, Dict[str, int]] = {}
+    lines = text.splitlines()
+    for header, values in zip(lines[::2], lines[1::2]):
+        name, _, fields = header.partition(':')
+        _, _, numbers = values.partition(':')
+        try:
+            sections[name] = dict(zip(fields.split(), (int(v) for v in numbers.split())))
+        except ValueError:
+            continue
+    return sections
+
+
+def parse_net_dev_per_interface(text: str) -> Dict[str, Dict[str, int]]:
+    """Per-interface byte counters from /proc/net/dev (or /proc/<pid>/net/dev)."""
+    interfaces = {}
+    for line in text.splitlines()[2:]:
+        name, sep, counters = line.partition(':')
+        fields = counters.split()
+        if not sep or len(fields) < 9:
+            continue
+        interfaces[name.strip()] = {'bytes_recv': int(fields[0]), 'bytes_sent': int(fields[8])}
+    return interfaces
+
+
+if LINUX:
+    def read_interface_counters() -> Dict[str, Dict[str, int]]:
+        """Byte counters per network interface, loopback included."""
+        try:
+            with open(f'{PROC_NET}/dev', 'r') as f:
+                return parse_net_dev_per_interface(f.read())
+        except OSError as e:
+            log.read_failed(f'{PROC_NET}/dev', e)
+            return {}
+
+else:
+    def read_interface_counters() -> Dict[str, Dict[str, int]]:
+        """Byte counters per network interface via psutil."""
+        import psutil
+
+        return {
+            name: {'bytes_recv': c.bytes_recv, 'bytes_sent': c.bytes_sent}
+            for name, c in psutil.net_io_counters(pernic=True).items()
+        }
+
+
+@requires('udp_stats')
+def get_udp_stats() -> Dict[str, Any]:
+    """Get cumulative UDP counters from /proc/net/snmp plus deltas since the last call.
+
+    Raises Unsupported where /proc/net/snmp is unavailable (non-Linux).
+    """
+    state = active_state()
+    with state.lock('network'):
+        try:
+            with open(f'{PROC_NET}/snmp', 'r') as f:
+                udp = parse_snmp(f.read()).get('Udp', {})
+        except OSError:
+            return {}
+        if not udp:
+            return {}
+
+        counters = {key: udp.get(field, 0) for field, key in UDP_COUNTERS.items()}
+        previous = state.udp_counters or counters
+        state.udp_counters = counters
+
+    stats: Dict[str, Any] = dict(counters)
+    stats['deltas'] = {key: counter_delta(value, previous[key]) for key, value in counters.items()}
+    return stats
+
+
+if LINUX:
+    @requires('connections')
+    def get_connections(kind: str = 'tcp') -> List[Dict[str, Any]]:
+        """Get sockets from /proc/net for kind tcp/tcp4/tcp6/udp/udp4/udp6/inet."""
+        validation.one_of('kind', kind, _CONNECTION_KINDS)
+        connections = []
+        for protocol in _CONNECTION_KINDS.get(kind, ()):
+            try:
+                with open(f'{PROC_NET}/{protocol}', 'r') as f:
+                    for line in f:
+                        entry = parse_socket_line(line, protocol)
+                        if entry is not None:
+                            connections.append(entry)
+            except OSError:
+                continue
+        return connections
+
+else:
+    @requires('connections')
+    def get_connections(kind: str = 'tcp') -> List[Dict[str, Any]]:
+        """Get sockets via psutil; queue depths are not exposed on this platform."""
+        validation.one_of('kind', kind, _CONNECTION_KINDS)
+        import psutil
+        import socket
+
+        protocols = _CONNECTION_KINDS.get(kind, ())
+        connections = []
+        try:
+            entries = psutil.net_connections(kind='inet')
+        except psutil.AccessDenied:
+            return []
+
+        for c in entries:
+            protocol = 'udp' if c.type == socket.SOCK_DGRAM else 'tcp'
+            if c.family == socket.AF_INET6:
+                protocol += '6'
+            if protocol not in protocols:
+                continue
+            entry = {
+                'protocol': protocol,
+                'local_address': c.laddr.ip if c.laddr else '',
+                'local_port': c.laddr.port if c.laddr else 0,
+                'remote_address': c.raddr.ip if c.raddr else '',
+                'remote_port': c.raddr.port if c.raddr else 0,
+                'tx_queue': None,
+                'rx_queue': None,
+                'uid': None,
+                'inode': None,
+                'status': c.status,
+            }
+            if protocol.startswith('udp'):
+                entry['drops'] = None
+            connections.append(entry)
+        return connections
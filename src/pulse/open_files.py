@@ -0,0 +1,224 @@
+"""
+Pulse Open Files
+Each file descriptor a process holds, and the processes holding a given file.
+
+On Linux every /proc/<pid>/fd entry is a symlink whose target says what the
+descriptor refers to: a path, "socket:[<inode>]", "pipe:[<inode>]", or
+"anon_inode:<kind>" (eventfd, epoll, inotify, ...). /proc/<pid>/fdinfo/<fd>
+adds the file offset and open flags. A socket's inode is the one
+get_connections() reports, so the two join on it.
+"""
+import os
+import re
+import time
+from typing import Any, Dict, List, Optional, Tuple
+
+from pulse import log, validation
+from pulse.direct_os import LINUX
+from pulse.errors import PermissionDenied, ProcessNotFound
+
+FD_TYPES = ('file', 'socket', 'pipe', 'anon')
+
+# who_has_open() reads every fd of every process, so it stops after this long
+DEFAULT_TIMEOUT_SECS = 5.0
+
+# O_ACCMODE bits of the open flags
+_ACCESS_MODES = {0: 'r', 1: 'w', 2: 'rw'}
+
+_PSEUDO = re.compile(r'^(socket|pipe):\[(\d+)\]$')
+
+
+def classify_target(target: str) -> Dict[str, Any]:
+    """{type, path, inode} for an fd symlink's target.
+
+    Sockets and pipes carry their inode; anonymous inodes and other pseudo
+    files ("anon_inode:[eventfd]", "net:[4026531840]") are "anon". path is
+    the target as read, like lsof's NAME column.
+    """
+    match = _PSEUDO.match(target)
+    if match:
+        return {'type': match.group(1), 'path': target, 'inode': int(match.group(2))}
+    if target.startswith('/'):
+        return {'type': 'file', 'path': target, 'inode': None}
+    return {'type': 'anon', 'path': target, 'inode': None}
+
+
+def parse_fdinfo(text: str) -> Dict[str, Any]:
+    """{pos, flags, mode} from /proc/<pid>/fdinfo/<fd>; flags is the octal open flags as an int."""
+    fields = {}
+    for line in text.splitlines():
+        key, sep, value = line.partition(':')
+        if sep:
+            fields[key.strip()] = value.strip()
+    try:
+        pos: Optional[int] = int(fields['pos'])
+    except (KeyError, ValueError):
+        pos = None
+    try:
+        flags: Optional[int] = int(fields['flags'], 8)
+    except (KeyError, ValueError):
+        flags = None
+    return {'pos': pos, 'flags': flags,
+            'mode': _ACCESS_MODES.get(flags & 0o3) if flags is not None else None}
+
+
+if LINUX:
+    from pulse.processes import PROC
+
+    def _fd_entry(pid: int, fd: int) -> Optional[Dict[str, Any]]:
+        try:
+            target = os.readlink(f'{PROC}/{pid}/fd/{fd}')
+        except FileNotFoundError:
+            # Closed between listing and reading
+            return None
+        entry = {'fd': fd, **classify_target(target)}
+        try:
+            with open(f'{PROC}/{pid}/fdinfo/{fd}', 'r') as f:
+                entry.update(parse_fdinfo(f.read()))
+        except OSError:
+            entry.update(pos=None, flags=None, mode=None)
+        return entry
+
+    def _list_fds(pid: int) -> List[int]:
+        return sorted(int(name) for name in os.listdir(f'{PROC}/{pid}/fd') if name.isdigit())
+
+    def get_open_files(pid: int, limit: Optional[int] = None) -> List[Dict[str, Any]]:
+        """Get a process's file descriptors: {fd, type, path, inode, pos, flags, mode}, by fd.
+
+        type is "file", "socket", "pipe", or "anon"; inode is set for
+        sockets (matching get_connections()' inode) and pipes. pos, flags
+        (the open flags), and mode ("r", "w", or "rw") come from fdinfo and
+        are None where it can't be read. limit keeps the lowest fds. Raises
+        ProcessNotFound if the process doesn't exist and PermissionDenied if
+        its fds may not be read.
+        """
+        validation.at_least('pid', pid, 1)
+        validation.optional_at_least('limit', limit, 1)
+        try:
+            fds = _list_fds(pid)
+        except FileNotFoundError:
+            raise ProcessNotFound.for_pid(pid) from None
+        except PermissionError:
+            raise PermissionDenied(f'Not allowed to read open files of pid {pid}') from None
+        files = []
+        for fd in fds:
+            try:
+                entry = _fd_entry(pid, fd)
+            except PermissionError:
+                raise PermissionDenied(f'Not allowed to read open files of pid {pid}') from None
+            if entry is not None:
+                files.append(entry)
+                if limit is not None and len(files) >= limit:
+                    break
+        return files
+
+    def _holders(pid: int, wanted: Tuple[int, int]) -> List[int]:
+        held = []
+        for fd in _list_fds(pid):
+            try:
+                st = os.stat(f'{PROC}/{pid}/fd/{fd}')
+            except OSError:
+                continue
+            if (st.st_dev, st.st_ino) == wanted:
+                held.append(fd)
+        return held
+
+    def _process_name(pid: int) -> Optional[str]:
+        try:
+            with open(f'{PROC}/{pid}/comm', 'r') as f:
+                return f.read().rstrip('\n')
+        except OSError:
+            return None
+
+    def _pids() -> List[int]:
+        return sorted(int(name) for name in os.listdir(PROC) if name.isdigit())
+
+else:
+    def get_open_files(pid: int, limit: Optional[int] = None) -> List[Dict[str, Any]]:
+        """Get a process's open regular files via psutil: {fd, type, path, inode, pos, flags, mode}.
+
+        psutil lists files only, so every entry is a "file" and inode, pos,
+        flags, and mode are None (fd is -1 on Windows). Raises
+        ProcessNotFound if the process doesn't exist and PermissionDenied if
+        its files may not be read.
+        """
+        validation.at_least('pid', pid, 1)
+        validation.optional_at_least('limit', limit, 1)
+        import psutil
+
+        try:
+            opened = psutil.Process(pid).open_files()
+        except psutil.NoSuchProcess:
+            raise ProcessNotFound.for_pid(pid) from None
+        except psutil.AccessDenied:
+            raise PermissionDenied(f'Not allowed to read open files of pid {pid}') from None
+        files = [{'fd': f.fd, 'type': 'file', 'path': f.path, 'inode': None, 'pos': None,
+                  'flags': None, 'mode': None} for f in sorted(opened, key=lambda f: (f.fd, f.path))]
+        return files[:limit]
+
+    def _holders(pid: int, wanted: Tuple[int, int]) -> List[int]:
+        import psutil
+
+        try:
+            opened = psutil.Process(pid).open_files()
+        except psutil.NoSuchProcess:
+            raise FileNotFoundError(pid) from None
+        except psutil.AccessDenied:
+            raise PermissionError(pid) from None
+        held = []
+        for f in opened:
+            try:
+                st = os.stat(f.path)
+            except OSError:
+                continue
+            if (st.st_dev, st.st_ino) == wanted:
+                held.append(f.fd)
+        return sorted(held)
+
+    def _process_name(pid: int) -> Optional[str]:
+        import psutil
+
+        try:
+            return psutil.Process(pid).name()
+        except (psutil.NoSuchProcess, psutil.AccessDenied):
+            return None
+
+    def _pids() -> List[int]:
+        import psutil
+
+        return sorted(psutil.pids())
+
+
+def who_has_open(path: str, timeout_secs: float = DEFAULT_TIMEOUT_SECS) -> Dict[str, Any]:
+    """Find the processes with path open: {path, processes: [{pid, name, fds}], skipped, timed_out}.
+
+    Files are compared by device and inode, so a path reached through a
+    symlink or bind mount still matches. Processes whose fds may not be read
+    are left out and counted in "skipped". The scan stops after
+    timeout_secs with "timed_out" set and the processes found so far.
+    Raises FileNotFoundError if path doesn't exist.
+    """
+    validation.not_empty('path', path)
+    validation.positive('timeout_secs', timeout_secs)
+    st = os.stat(path)
+    wanted = (st.st_dev, st.st_ino)
+    deadline = time.monotonic() + timeout_secs
+    processes = []
+    skipped = 0
+    timed_out = False
+    for pid in _pids():
+        if time.monotonic() > deadline:
+            timed_out = True
+            break
+        try:
+            fds = _holders(pid, wanted)
+        except FileNotFoundError:
+            continue
+        except PermissionError as e:
+            log.process_skipped(pid, f'/proc/{pid}/fd' if LINUX else str(pid), e)
+            skipped += 1
+            continue
+        if fds:
+            processes.append({'pid': pid, 'name': _process_name(pid), 'fds': fds})
+    return {'path': os.path.realpath(path), 'processes': processes, 'skipped': skipped,
+            'timed_out': timed_out}
@@ -40,8 +40,12 @@ class CPUPanel(Panel):
     def action_kill_process(self):
         """Kill selected process via keyboard."""
         if self.selected_pid:
-            core.kill_process(self.selected_pid)
-            self.notify(f"Terminated PID {self.selected_pid}")
+            try:
+                core.kill_process(self.selected_pid)
+            except core.PulseError as e:
+                self.notify(str(e), severity="error")
+            else:
+                self.notify(f"Terminated PID {self.selected_pid}")
             self.selected_pid = None
 
     def action_renice_up(self):
@@ -91,9 +95,7 @@ class CPUPanel(Panel):
             return
             
         if event.button.id == "btn-kill":
-            res = core.kill_process(self.selected_pid)
-            self.notify(res)
-            self.selected_pid = None
+            self.action_kill_process()
         elif event.button.id.startswith("btn-renice"):
             try:
                 p = psutil.Process(self.selected_pid)
@@ -0,0 +1,326 @@
+"""
+Pulse Power
+Battery charge, state, and wear; screen backlight brightness.
+"""
+import ctypes
+import os
+from typing import Any, Dict, List, Optional
+
+from pulse import environment, log, validation
+from pulse.capabilities import requires
+from pulse.direct_os import LINUX, MACOS, WINDOWS
+from pulse.errors import PermissionDenied, Unsupported
+
+SYS_CLASS_POWER_SUPPLY = '/sys/class/power_supply'
+SYS_CLASS_BACKLIGHT = '/sys/class/backlight'
+
+# POWER_SUPPLY_STATUS -> reported state. "Not charging" is a plugged-in battery
+# held below 100% by a charge threshold, which users read as full.
+_STATES = {
+    'Charging': 'charging',
+    'Discharging': 'discharging',
+    'Full': 'full',
+    'Not charging': 'full',
+}
+
+
+def parse_power_supply_uevent(text: str) -> Dict[str, str]:
+    """Parse a power_supply uevent file into {"STATUS": ..., "ENERGY_NOW": ...}."""
+    fields = {}
+    for line in text.splitlines():
+        key, sep, value = line.partition('=')
+        if sep and key.startswith('POWER_SUPPLY_'):
+            fields[key[len('POWER_SUPPLY_'):]] = value.strip()
+    return fields
+
+
+def _int_field(fields: Dict[str, str], key: str) -> Optional[int]:
+    try:
+        return int(fields[key])
+    except (KeyError, ValueError):
+        return None
+
+
+def _ratio_percent(numerator: Optional[int], denominator: Optional[int]) -> Optional[float]:
+    if numerator is None or not denominator:
+        return None
+    return numerator / denominator * 100
+
+
+def battery_from_uevent(name: str, fields: Dict[str, str]) -> Dict[str, Any]:
+    """Summarize one battery from its uevent fields.
+
+    Drivers report either energy (µWh, with power in µW) or charge (µAh, with
+    current in µA); the two are never mixed within one battery, so whichever
+    family is present drives the time and health estimates.
+    """
+    if 'ENERGY_NOW' in fields:
+        now, full, design = (_int_field(fields, k) for k in ('ENERGY_NOW', 'ENERGY_FULL', 'ENERGY_FULL_DESIGN'))
+        rate = _int_field(fields, 'POWER_NOW')
+    else:
+        now, full, design = (_int_field(fields, k) for k in ('CHARGE_NOW', 'CHARGE_FULL', 'CHARGE_FULL_DESIGN'))
+        rate = _int_field(fields, 'CURRENT_NOW')
+    # Some drivers report discharge as a negative current
+    rate = abs(rate) if rate else None
+
+    state = _STATES.get(fields.get('STATUS', ''))
+    percent = _int_field(fields, 'CAPACITY')
+
+    time_to_empty = _int_field(fields, 'TIME_TO_EMPTY_NOW')
+    time_to_full = _int_field(fields, 'TIME_TO_FULL_NOW')
+    if state == 'discharging' and time_to_empty is None and now is not None and rate:
+        time_to_empty = int(now / rate * 3600)
+    if state == 'charging' and time_to_full is None and now is not None and full and rate:
+        time_to_full = int(max(0, full - now) / rate * 3600)
+
+    # Many drivers expose cycle_count but leave it at 0 when they don't track it
+    cycles = _int_field(fields, 'CYCLE_COUNT')
+    return {
+        'name': name,
+        'percent': float(percent) if percent is not None else _ratio_percent(now, full),
+        'state': state,
+        'time_to_empty_secs': time_to_empty if state == 'discharging' else None,
+        'time_to_full_secs': time_to_full if state == 'charging' else None,
+        'cycle_count': cycles or None,
+        'health_percent': _ratio_percent(full, design),
+    }
+
+
+if LINUX:
+    @requires('battery')
+    def get_battery_info() -> List[Dict[str, Any]]:
+        """Get every battery's charge, state, and wear from /sys/class/power_supply.
+
+        Machines without a battery (desktops, VMs) return an empty list, as
+        does WSL, whose power_supply entries are stand-ins rather than the
+        host's battery; kernels without the power_supply class raise
+        Unsupported.
+        """
+        if environment.running_under_wsl():
+            return []
+        try:
+            supplies = sorted(os.listdir(SYS_CLASS_POWER_SUPPLY))
+        except OSError as e:
+            log.read_failed(SYS_CLASS_POWER_SUPPLY, e)
+            return []
+
+        batteries = []
+        for name in supplies:
+            path = os.path.join(SYS_CLASS_POWER_SUPPLY, name, 'uevent')
+            try:
+                with open(path, 'r') as f:
+                    fields = parse_power_supply_uevent(f.read())
+            except OSError as e:
+                log.read_failed(path, e)
+                continue
+            # Peripherals (mice, headsets) report SCOPE=Device; only system batteries count
+            if fields.get('TYPE') != 'Battery' or fields.get('SCOPE') == 'Device':
+                continue
+            if fields.get('PRESENT') == '0':
+                continue
+            batteries.append(battery_from_uevent(name, fields))
+        return batteries
+
+else:
+    @requires('battery')
+    def get_battery_info() -> List[Dict[str, Any]]:
+        """Get the system battery's charge and state via psutil; wear isn't exposed."""
+        import psutil
+
+        try:
+            battery = psutil.sensors_battery()
+        except (AttributeError, NotImplementedError, OSError, RuntimeError):
+            return []
+        if battery is None:
+            return []
+
+        if battery.power_plugged:
+            state = 'full' if battery.percent >= 100 else 'charging'
+        else:
+            state = 'discharging'
+        secs_left = battery.secsleft if isinstance(battery.secsleft, int) and battery.secsleft >= 0 else None
+        return [{
+            'name': 'battery',
+            'percent': float(battery.percent),
+            'state': state,
+            'time_to_empty_secs': secs_left if state == 'discharging' else None,
+            'time_to_full_secs': None,
+            'cycle_count': None,
+            'health_percent': None,
+        }]
+
+
+def backlight_entry(name: str, backlight_type: Optional[str],
+                    brightness: int, max_brightness: int) -> Dict[str, Any]:
+    """One backlight's raw level and its percent of the device maximum."""
+    return {
+        'name': name,
+        'type': backlight_type,
+        'percent': _ratio_percent(brightness, max_brightness),
+        'brightness': brightness,
+        'max_brightness': max_brightness,
+    }
+
+
+def _check_percent(percent: float) -> None:
+    validation.between('percent', percent, 0, 100)
+
+
+if LINUX:
+    def _read_backlight_attr(device: str, attr: str) -> Optional[str]:
+        path = os.path.join(device, attr)
+        try:
+            with open(path, 'r') as f:
+                return f.read().strip()
+        except OSError as e:
+            log.read_failed(path, e)
+            return None
+
+    @requires('backlight')
+    def get_brightness() -> List[Dict[str, Any]]:
+        """Get every backlight's brightness from /sys/class/backlight.
+
+        Laptop panels show up as intel_backlight/amdgpu_bl0/acpi_video0 and
+        external monitors as ddcci* when the ddcci driver is loaded. Machines
+        without a backlight interface raise Unsupported.
+        """
+        try:
+            names = sorted(os.listdir(SYS_CLASS_BACKLIGHT))
+        except OSError as e:
+            log.read_failed(SYS_CLASS_BACKLIGHT, e)
+            return []
+
+        backlights = []
+        for name in names:
+            device = os.path.join(SYS_CLASS_BACKLIGHT, name)
+            # actual_brightness is what the hardware reports; brightness is the last request
+            raw = _read_backlight_attr(device, 'actual_brightness') or _read_backlight_attr(device, 'brightness')
+            maximum = _read_backlight_attr(device, 'max_brightness')
+            try:
+                brightness, max_brightness = int(raw or ''), int(maximum or '')
+            except ValueError:
+                continue
+            backlights.append(backlight_entry(name, _read_backlight_attr(device, 'type'),
+                                              brightness, max_brightness))
+        return backlights
+
+    def set_brightness(percent: float, name: Optional[str] = None) -> None:
+        """Set one backlight (or every backlight when name is None) to percent.
+
+        Raises PermissionDenied when the brightness file isn't writable, which
+        without a udev rule means anyone but root, and FileNotFoundError when
+        there is no such backlight.
+        """
+        _check_percent(percent)
+        targets = [b for b in get_brightness() if name is None or b['name'] == name]
+        if not targets:
+            raise FileNotFoundError(f"No backlight device{f' named {name}' if name else ''}")
+        for backlight in targets:
+            level = round(percent / 100 * backlight['max_brightness'])
+            path = os.path.join(SYS_CLASS_BACKLIGHT, backlight['name'], 'brightness')
+            try:
+                with open(path, 'w') as f:
+                    f.write(str(level))
+            except PermissionError:
+                raise PermissionDenied(f'{path} is not writable; a udev rule or root is needed') from None
+
+elif WINDOWS:
+    from ctypes import wintypes
+
+    class _PhysicalMonitor(ctypes.Structure):
+        _fields_ = [('handle', wintypes.HANDLE), ('description', wintypes.WCHAR * 128)]
+
+    _MONITOR_ENUM_PROC = ctypes.WINFUNCTYPE(
+        wintypes.BOOL, wintypes.HMONITOR, wintypes.HDC, ctypes.POINTER(wintypes.RECT), wintypes.LPARAM)
+
+    @requires('backlight')
+    def get_brightness() -> List[Dict[str, Any]]:
+        """Get brightness of every monitor that answers DDC/CI via dxva2.
+
+        Internal laptop panels usually don't speak DDC/CI and are skipped.
+        """
+        try:
+            dxva2 = ctypes.WinDLL('dxva2')
+        except OSError as e:
+            log.read_failed('dxva2.dll', e)
+            return []
+
+        monitors = []
+
+        def collect(hmonitor, hdc, rect, lparam):
+            monitors.append(hmonitor)
+            return True
+
+        ctypes.windll.user32.EnumDisplayMonitors(None, None, _MONITOR_ENUM_PROC(collect), 0)
+
+        backlights = []
+        for hmonitor in monitors:
+            count = wintypes.DWORD()
+            if not dxva2.GetNumberOfPhysicalMonitorsFromHMONITOR(hmonitor, ctypes.byref(count)) or not count.value:
+                continue
+            physical = (_PhysicalMonitor * count.value)()
+            if not dxva2.GetPhysicalMonitorsFromHMONITOR(hmonitor, count.value, physical):
+                continue
+            try:
+                for monitor in physical:
+                    low, current, high = wintypes.DWORD(), wintypes.DWORD(), wintypes.DWORD()
+                    if not dxva2.GetMonitorBrightness(monitor.handle, ctypes.byref(low),
+                                                      ctypes.byref(current), ctypes.byref(high)):
+                        continue
+                    backlights.append(backlight_entry(monitor.description or f'monitor{len(backlights)}',
+                                                      'ddcci', current.value - low.value, high.value - low.value))
+            finally:
+                dxva2.DestroyPhysicalMonitors(count.value, physical)
+        return backlights
+
+    def set_brightness(percent: float, name: Optional[str] = None) -> None:
+        """Setting brightness is only supported through Linux sysfs."""
+        _check_percent(percent)
+        raise Unsupported('set_brightness is only supported on Linux')
+
+elif MACOS:
+    _MAX_DISPLAYS = 16
+
+    @requires('backlight')
+    def get_brightness() -> List[Dict[str, Any]]:
+        """Get brightness of every active display DisplayServices can read.
+
+        DisplayServices only answers for Apple panels (built-in and Studio
+        Display); other external monitors are skipped.
+        """
+        try:
+            graphics = ctypes.CDLL('/System/Library/Frameworks/CoreGraphics.framework/CoreGraphics')
+            services = ctypes.CDLL('/System/Library/PrivateFrameworks/DisplayServices.framework/DisplayServices')
+        except OSError as e:
+            log.read_failed('DisplayServices.framework', e)
+            return []
+
+        displays = (ctypes.c_uint32 * _MAX_DISPLAYS)()
+        count = ctypes.c_uint32()
+        if graphics.CGGetActiveDisplayList(_MAX_DISPLAYS, displays, ctypes.byref(count)) != 0:
+            return []
+
+        backlights = []
+        for display in displays[:count.value]:
+            level = ctypes.c_float()
+            if services.DisplayServicesGetBrightness(display, ctypes.byref(level)) != 0:
+                continue
+            # Brightness is a 0.0-1.0 float; scale to the same integer range sysfs uses
+            backlights.append(backlight_entry(f'display{display}', None, round(level.value * 100), 100))
+        return backlights
+
+    def set_brightness(percent: float, name: Optional[str] = None) -> None:
+        """Setting brightness is only supported through Linux sysfs."""
+        _check_percent(percent)
+        raise Unsupported('set_brightness is only supported on Linux')
+
+else:
+    @requires('backlight')
+    def get_brightness() -> List[Dict[str, Any]]:
+        """Backlights aren't exposed on this platform."""
+        return []
+
+    def set_brightness(percent: float, name: Optional[str] = None) -> None:
+        """Backlights aren't exposed on this platform."""
+        _check_percent(percent)
+        raise Unsupported('set_brightness is only supported on Linux')
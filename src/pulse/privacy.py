@@ -0,0 +1,119 @@
+"""
+Pulse Privacy
+Pseudonymized process identity for results that leave the host.
+
+set_privacy_mode() turns on any of: usernames replaced by an HMAC-SHA256
+of the name under a caller's key (stable across calls and restarts, so
+series still line up, but not reversible without the key), command lines
+cut to the executable's base name, and no reads of any process's environ.
+Every pulse.core collector's result passes through scrub() before it is
+returned or encoded, so dicts, JSON, MessagePack, Prometheus, the socket
+stream, and recordings all carry the scrubbed values.
+"""
+import functools
+import hashlib
+import hmac
+import threading
+from typing import Any, Callable, Dict, Optional, Union
+
+from pulse import validation
+
+OPTIONS = ('hash_usernames', 'key', 'cmdline_executable_only', 'drop_environ')
+
+# Result keys holding a user name, and the keys dropped with drop_environ
+USERNAME_KEYS = frozenset({'username', 'user', 'owner'})
+ENVIRON_KEYS = frozenset({'environ', 'env'})
+
+# Hex digits of the HMAC a hashed username keeps
+HASH_LENGTH = 16
+
+_lock = threading.Lock()
+_key: Optional[bytes] = None
+_mode: Dict[str, bool] = {'hash_usernames': False, 'cmdline_executable_only': False,
+                          'drop_environ': False}
+
+
+def hash_username(name: str, key: bytes) -> str:
+    """The pseudonym a username gets under key."""
+    return hmac.new(key, name.encode('utf-8', 'surrogateescape'), hashlib.sha256).hexdigest()[:HASH_LENGTH]
+
+
+def executable_only(cmdline: str) -> str:
+    """The base name of a command line's first word ("/usr/bin/python3 app.py" -> "python3")."""
+    words = cmdline.split()
+    if not words:
+        return ''
+    return words[0].replace('\\', '/').rsplit('/', 1)[-1]
+
+
+def scrub_value(value: Any, key: Optional[bytes], cmdline_executable: bool, drop_environ: bool) -> Any:
+    """value with the identity fields under any dict, at any depth, pseudonymized."""
+    if isinstance(value, list):
+        return [scrub_value(v, key, cmdline_executable, drop_environ) for v in value]
+    if not isinstance(value, dict):
+        return value
+    scrubbed = {}
+    for name, item in value.items():
+        if drop_environ and name in ENVIRON_KEYS:
+            continue
+        if key is not None and name in USERNAME_KEYS and isinstance(item, str):
+            item = hash_username(item, key)
+        elif cmdline_executable and name == 'cmdline':
+            if isinstance(item, str):
+                item = executable_only(item)
+            elif isinstance(item, list):
+                item = [executable_only(str(item[0]))] if item else []
+        else:
+            item = scrub_value(item, key, cmdline_executable, drop_environ)
+        scrubbed[name] = item
+    return scrubbed
+
+
+def scrub(value: Any) -> Any:
+    """value as the active privacy mode allows it out; unchanged with the mode off."""
+    with _lock:
+        key = _key if _mode['hash_usernames'] else None
+        cmdline_executable, drop_environ = _mode['cmdline_executable_only'], _mode['drop_environ']
+    if key is None and not cmdline_executable and not drop_environ:
+        return value
+    return scrub_value(value, key, cmdline_executable, drop_environ)
+
+
+def scrubbed(function: Callable[..., Any]) -> Callable[..., Any]:
+    """Wrap a getter so its result goes through scrub()."""
+    @functools.wraps(function)
+    def private(*args: Any, **kwargs: Any) -> Any:
+        return scrub(function(*args, **kwargs))
+    return private
+
+
+def environ_allowed() -> bool:
+    """Whether collectors may read a process's environ."""
+    return not _mode['drop_environ']
+
+
+def set_privacy_mode(options: Optional[Dict[str, Union[bool, str, bytes]]] = None) -> None:
+    """Pseudonymize process identity in every result; None (or {}) turns it all off.
+
+    options may set "hash_usernames" (needs "key", the HMAC key as str or
+    bytes), "cmdline_executable_only", and "drop_environ", each False when
+    left out. Raises ValueError for an unknown option or hash_usernames
+    without a key.
+    """
+    global _key
+    options = dict(options or {})
+    validation.all_of('options', list(options), OPTIONS)
+    key = options.pop('key', None)
+    if isinstance(key, str):
+        key = key.encode('utf-8')
+    if options.get('hash_usernames') and not key:
+        raise ValueError('hash_usernames needs a non-empty key')
+    with _lock:
+        _key = key or None
+        _mode.update({name: bool(options.get(name, False)) for name in _mode})
+
+
+def get_privacy_mode() -> Dict[str, bool]:
+    """The active privacy settings; the key itself is never reported."""
+    with _lock:
+        return {**_mode, 'has_key': _key is not None}
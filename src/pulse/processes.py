@@ -0,0 +1,749 @@
+"""
+Pulse Process Telemetry
+Per-process detail beyond the basic process list.
+"""
+import os
+import stat
+import time
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+
+from pulse import compat, direct_os, log, validation
+from pulse.capabilities import requires
+from pulse.collect import counter_delta
+from pulse.direct_os import LINUX, WINDOWS
+from pulse.errors import PermissionDenied, ProcessNotFound, Unsupported
+from pulse.state import active_state
+
+PROC = '/proc'
+
+# /proc/<pid>/limits row names -> keys; matched by name since column widths
+# have changed between kernel versions
+LIMIT_NAMES = {
+    'Max cpu time': 'cpu_time',
+    'Max file size': 'file_size',
+    'Max data size': 'data_size',
+    'Max stack size': 'stack_size',
+    'Max core file size': 'core_file_size',
+    'Max resident set': 'resident_set',
+    'Max processes': 'processes',
+    'Max open files': 'open_files',
+    'Max locked memory': 'locked_memory',
+    'Max address space': 'address_space',
+    'Max file locks': 'file_locks',
+    'Max pending signals': 'pending_signals',
+    'Max msgqueue size': 'msgqueue_size',
+    'Max nice priority': 'nice_priority',
+    'Max realtime priority': 'realtime_priority',
+    'Max realtime timeout': 'realtime_timeout',
+}
+
+
+# Limits get_limit_headroom() pairs with usage: open fds, virtual memory bytes,
+# largest regular file open, and the tasks (threads) of the owner's real uid
+HEADROOM_LIMITS = ('open_files', 'address_space', 'file_size', 'processes')
+
+DEFAULT_WARN_PERCENT = 80.0
+
+# /proc/<pid>/stat state letters -> the status names psutil reports
+PROCESS_STATES = {
+    'R': 'running',
+    'S': 'sleeping',
+    'D': 'disk-sleep',
+    'T': 'stopped',
+    't': 'tracing-stop',
+    'Z': 'zombie',
+    'X': 'dead',
+    'I': 'idle',
+    'P': 'parked',
+    'W': 'waking',
+}
+
+# Namespace kinds get_process_namespaces() reports, as named under /proc/<pid>/ns
+NAMESPACE_KINDS = ('pid', 'net', 'mnt', 'uts')
+
+# Fixed inode numbers of the kernel's initial namespaces (PROC_*_INIT_INO in
+# include/linux/proc_ns.h); net and mnt have none and are compared with pid 1's
+INIT_NAMESPACE_INODES = {'pid': 0xEFFFFFFC, 'uts': 0xEFFFFFFE}
+
+
+def parse_proc_stat(text: str) -> Tuple[str, List[str]]:
+    """Split /proc/<pid>/stat into (comm, fields from "state" onward).
+
+    comm is parenthesised and may itself contain spaces or ')', so it is cut at
+    the last closing parenthesis. fields[0] is stat field 3 (state), fields[19]
+    is field 22 (starttime, in clock ticks since boot).
+    """
+    start = text.index('(')
+    end = text.rindex(')')
+    return text[start + 1:end], text[end + 2:].split()
+
+
+def parse_proc_io(text: str) -> Dict[str, int]:
+    """Parse /proc/<pid>/io "key: value" lines."""
+    counters = {}
+    for line in text.splitlines():
+        key, _, value = line.partition(':')
+        if value.strip().isdigit():
+            counters[key.strip()] = int(value)
+    return counters
+
+
+def parse_ns_link(target: str) -> Optional[int]:
+    """Inode number from a /proc/<pid>/ns link target such as "net:[4026531840]"."""
+    _, sep, rest = target.partition(':[')
+    if not sep or not rest.endswith(']') or not rest[:-1].isdigit():
+        return None
+    return int(rest[:-1])
+
+
+def group_by_namespace(namespaces: Dict[int, Dict[str, Optional[int]]], kind: str,
+                       host_inode: Optional[int]) -> List[Dict[str, Any]]:
+    """Cluster {pid: namespaces} by kind's inode, host namespace first, then largest.
+
+    Processes whose kind link couldn't be read are left out.
+    """
+    members: Dict[int, List[int]] = {}
+    for pid, inodes in namespaces.items():
+        inode = inodes.get(kind)
+        if inode is not None:
+            members.setdefault(inode, []).append(pid)
+    groups = [{'kind': kind, 'inode': inode, 'is_host': inode == host_inode, 'pids': sorted(pids)}
+              for inode, pids in members.items()]
+    groups.sort(key=lambda g: (not g['is_host'], -len(g['pids']), g['inode']))
+    return groups
+
+
+def _limit_value(value: str) -> Optional[int]:
+    return None if value == 'unlimited' else int(value)
+
+
+def parse_proc_limits(text: str) -> Dict[str, Dict[str, Any]]:
+    """Parse /proc/<pid>/limits into {key: {soft, hard, unit}}; unlimited is None."""
+    limits = {}
+    for line in text.splitlines():
+        for name, key in LIMIT_NAMES.items():
+            if line.startswith(name + ' '):
+                parts = line[len(name):].split()
+                try:
+                    soft, hard = _limit_value(parts[0]), _limit_value(parts[1])
+                except (IndexError, ValueError):
+                    break
+                limits[key] = {'soft': soft, 'hard': hard, 'unit': parts[2] if len(parts) > 2 else None}
+                break
+    return limits
+
+
+def parse_status_usage(text: str) -> Dict[str, int]:
+    """{uid (real), threads, vm_size (bytes)} from /proc/<pid>/status; kernel threads lack vm_size."""
+    usage = {}
+    for line in text.splitlines():
+        name, _, value = line.partition(':')
+        if name == 'Uid':
+            usage['uid'] = int(value.split()[0])
+        elif name == 'Threads':
+            usage['threads'] = int(value)
+        elif name == 'VmSize':
+            usage['vm_size'] = int(value.split()[0]) * 1024
+    return usage
+
+
+def headroom_entry(usage: Optional[int], soft: Optional[int], warn_percent: float) -> Dict[str, Any]:
+    """{usage, limit, percent, warning} against the soft limit.
+
+    percent is None when the usage is unknown or the limit unlimited.
+    """
+    percent = usage / soft * 100 if usage is not None and soft else None
+    return {'usage': usage, 'limit': soft, 'percent': percent,
+            'warning': percent is not None and percent >= warn_percent}
+
+
+def limit_headroom(pid: int, limits: Dict[str, Dict[str, Any]], usage: Dict[str, Optional[int]],
+                   warn_percent: float) -> Dict[str, Any]:
+    """{pid, limits: {name: headroom_entry}, warnings} for the HEADROOM_LIMITS the platform reports."""
+    entries = {name: headroom_entry(usage.get(name), limits[name]['soft'], warn_percent)
+               for name in HEADROOM_LIMITS if name in limits}
+    return {'pid': pid, 'limits': entries,
+            'warnings': [name for name, entry in entries.items() if entry['warning']]}
+
+
+if LINUX:
+    def get_process_limits(pid: int) -> Dict[str, Dict[str, Any]]:
+        """Get a process's resource limits from /proc/<pid>/limits.
+
+        Raises ProcessNotFound if the process doesn't exist.
+        """
+        validation.at_least('pid', pid, 1)
+        try:
+            with open(f'{PROC}/{pid}/limits', 'r') as f:
+                return parse_proc_limits(f.read())
+        except FileNotFoundError:
+            raise ProcessNotFound.for_pid(pid) from None
+        except PermissionError:
+            raise PermissionDenied(f'Not allowed to read limits of pid {pid}') from None
+
+else:
+    def get_process_limits(pid: int) -> Dict[str, Dict[str, Any]]:
+        """Get a process's open-file and address-space limits via psutil, where supported."""
+        validation.at_least('pid', pid, 1)
+        import psutil
+
+        try:
+            proc = psutil.Process(pid)
+        except psutil.NoSuchProcess:
+            raise ProcessNotFound.for_pid(pid) from None
+        if not hasattr(proc, 'rlimit'):
+            return {}
+        limits = {}
+        for key, resource, unit in (('open_files', 'RLIMIT_NOFILE', 'files'),
+                                    ('address_space', 'RLIMIT_AS', 'bytes')):
+            if not hasattr(psutil, resource):
+                continue
+            try:
+                soft, hard = proc.rlimit(getattr(psutil, resource))
+            except psutil.AccessDenied:
+                raise PermissionDenied(f'Not allowed to read limits of pid {pid}') from None
+            infinity = getattr(psutil, 'RLIM_INFINITY', -1)
+            limits[key] = {
+                'soft': None if soft == infinity else soft,
+                'hard': None if hard == infinity else hard,
+                'unit': unit,
+            }
+        return limits
+
+
+if LINUX:
+    def _task_counts() -> Dict[int, int]:
+        """Tasks (threads) per real uid, which is what RLIMIT_NPROC counts."""
+        counts: Dict[int, int] = {}
+        for name in os.listdir(PROC):
+            if not name.isdigit():
+                continue
+            try:
+                with open(f'{PROC}/{name}/status', 'r') as f:
+                    usage = parse_status_usage(f.read())
+            except (OSError, ValueError) as e:
+                log.process_skipped(int(name), f'{PROC}/{name}/status', e)
+                continue
+            if 'uid' in usage:
+                counts[usage['uid']] = counts.get(usage['uid'], 0) + usage.get('threads', 1)
+        return counts
+
+    def _largest_open_file(pid: int, fds: List[str]) -> int:
+        largest = 0
+        for fd in fds:
+            try:
+                st = os.stat(f'{PROC}/{pid}/fd/{fd}')
+            except OSError:
+                continue
+            if stat.S_ISREG(st.st_mode):
+                largest = max(largest, st.st_size)
+        return largest
+
+    def _process_usage(pid: int, tasks: Dict[int, int]) -> Dict[str, Optional[int]]:
+        try:
+            with open(f'{PROC}/{pid}/status', 'r') as f:
+                status = parse_status_usage(f.read())
+        except FileNotFoundError:
+            raise ProcessNotFound.for_pid(pid) from None
+        try:
+            fds: Optional[List[str]] = os.listdir(f'{PROC}/{pid}/fd')
+        except OSError as e:
+            # Other users' fd tables need CAP_SYS_PTRACE
+            log.read_failed(f'{PROC}/{pid}/fd', e)
+            fds = None
+        return {
+            'open_files': len(fds) if fds is not None else None,
+            'address_space': status.get('vm_size'),
+            'file_size': _largest_open_file(pid, fds) if fds is not None else None,
+            'processes': tasks.get(status['uid']) if 'uid' in status else None,
+        }
+
+else:
+    def _task_counts() -> Dict[int, int]:
+        return {}
+
+    def _process_usage(pid: int, tasks: Dict[int, int]) -> Dict[str, Optional[int]]:
+        import psutil
+
+        try:
+            proc = psutil.Process(pid)
+            with proc.oneshot():
+                open_files = proc.num_fds() if hasattr(proc, 'num_fds') else proc.num_handles()
+                return {'open_files': open_files, 'address_space': proc.memory_info().vms}
+        except psutil.NoSuchProcess:
+            raise ProcessNotFound.for_pid(pid) from None
+        except psutil.AccessDenied:
+            return {}
+
+
+def get_limit_headroom(pid: int, warn_percent: float = DEFAULT_WARN_PERCENT) -> Dict[str, Any]:
+    """Get how close a process is to its soft rlimits: {pid, limits, warnings}.
+
+    limits maps open_files, address_space, file_size (largest regular file
+    it has open), and processes (tasks of its real uid) to {usage, limit,
+    percent, warning}; percent is None for an unlimited limit or usage the
+    caller may not read. warnings lists the limits at or above warn_percent.
+    Only open_files and address_space are reported outside Linux. Raises
+    ProcessNotFound if the process doesn't exist.
+    """
+    validation.at_least('pid', pid, 1)
+    validation.between('warn_percent', warn_percent, 0, 100)
+    usage = _process_usage(pid, _task_counts())
+    return limit_headroom(pid, get_process_limits(pid), usage, warn_percent)
+
+
+def list_limit_headroom(pids: Optional[Iterable[int]] = None,
+                        warn_percent: float = DEFAULT_WARN_PERCENT) -> List[Dict[str, Any]]:
+    """get_limit_headroom() for each of pids (default: every process), in pid order.
+
+    Processes that exit or refuse their limits mid-scan are left out.
+    """
+    validation.between('warn_percent', warn_percent, 0, 100)
+    if pids is None:
+        pids = [row[0] for row in direct_os.get_process_rows() if row[0] > 0]
+    tasks = _task_counts()
+    entries = []
+    for pid in sorted(set(pids)):
+        validation.at_least('pid', pid, 1)
+        try:
+            entries.append(limit_headroom(pid, get_process_limits(pid), _process_usage(pid, tasks),
+                                          warn_percent))
+        except (ProcessNotFound, PermissionDenied):
+            continue
+    return entries
+
+
+if LINUX:
+    def _collect_process_io() -> Dict[Tuple[int, int], Dict[str, Any]]:
+        """Storage-layer read/write bytes per (pid, start_time) from /proc/<pid>/io."""
+        collected = {}
+        for pid_str in os.listdir(PROC):
+            if not pid_str.isdigit():
+                continue
+            pid = int(pid_str)
+            try:
+                with open(f'{PROC}/{pid}/stat', 'r') as f:
+                    name, fields = parse_proc_stat(f.read())
+                with open(f'{PROC}/{pid}/io', 'r') as f:
+                    io = parse_proc_io(f.read())
+                start_time = int(fields[19])
+            except (OSError, ValueError, IndexError) as e:
+                # Exited mid-scan, or another user's process without privileges
+                log.process_skipped(pid, f'{PROC}/{pid}/io', e)
+                continue
+            collected[(pid, start_time)] = {
+                'pid': pid,
+                'name': name,
+                'read_bytes': io.get('read_bytes', 0),
+                'write_bytes': io.get('write_bytes', 0),
+            }
+        return collected
+
+else:
+    def _collect_process_io() -> Dict[Tuple[int, int], Dict[str, Any]]:
+        """Read/write bytes per (pid, create_time) via psutil where it reports them."""
+        import psutil
+
+        collected = {}
+        for p in psutil.process_iter(['pid', 'name', 'create_time']):
+            try:
+                io = p.io_counters()
+            except (psutil.NoSuchProcess, psutil.AccessDenied, AttributeError, NotImplementedError):
+                continue
+            info = p.info
+            collected[(info['pid'], int((info['create_time'] or 0) * 1000))] = {
+                'pid': info['pid'],
+                'name': info['name'] or '?',
+                'read_bytes': io.read_bytes,
+                'write_bytes': io.write_bytes,
+            }
+        return collected
+
+
+if LINUX:
+    def read_process_states() -> Dict[int, Tuple[str, str]]:
+        """(name, status) per pid, status named as in PROCESS_STATES."""
+        states = {}
+        for pid_str in os.listdir(PROC):
+            if not pid_str.isdigit():
+                continue
+            try:
+                with open(f'{PROC}/{pid_str}/stat', 'r') as f:
+                    name, fields = parse_proc_stat(f.read())
+                states[int(pid_str)] = (name, PROCESS_STATES.get(fields[0], 'unknown'))
+            except (OSError, ValueError, IndexError) as e:
+                log.process_skipped(int(pid_str), f'{PROC}/{pid_str}/stat', e)
+                continue
+        return states
+
+else:
+    def read_process_states() -> Dict[int, Tuple[str, str]]:
+        """(name, status) per pid via psutil."""
+        import psutil
+
+        states = {}
+        for p in psutil.process_iter(['pid', 'name', 'status']):
+            info = p.info
+            states[info['pid']] = (info['name'] or '?', info['status'] or 'unknown')
+        return states
+
+
+# get_process_changes() defaults: percentage points of CPU and bytes of memory
+DEFAULT_CPU_EPSILON = 1.0
+DEFAULT_MEMORY_THRESHOLD = 1024 * 1024
+
+
+def process_table(rows: List[direct_os.ProcessRow],
+                  states: Dict[int, Tuple[str, str]]) -> Dict[int, Dict[str, Any]]:
+    """{pid: get_process_list() entry plus status} from process rows and read_process_states()."""
+    table = {}
+    for row in rows:
+        state = states.get(row[0])
+        table[row[0]] = {**direct_os.process_dict(row), 'status': state[1] if state else None}
+    return table
+
+
+def diff_processes(previous: Dict[int, Dict[str, Any]], current: Dict[int, Dict[str, Any]],
+                   cpu_epsilon: float, memory_threshold: int) -> Dict[str, List[Any]]:
+    """{started, exited, changed} between two process tables.
+
+    started and changed hold full entries, exited the pids that are gone. A
+    pid now naming another process counts as an exit and a start. An entry
+    changed when its cpu_percent moved by more than cpu_epsilon, its memory
+    by more than memory_threshold bytes, or its status at all.
+    """
+    started, changed = [], []
+    exited = [pid for pid, entry in previous.items()
+              if pid not in current or current[pid]['name'] != entry['name']]
+    for pid, entry in current.items():
+        before = previous.get(pid)
+        if before is None or before['name'] != entry['name']:
+            started.append(entry)
+        elif (abs(entry['cpu_percent'] - before['cpu_percent']) > cpu_epsilon
+              or abs(entry['memory_info'] - before['memory_info']) > memory_threshold
+              or entry['status'] != before['status']):
+            changed.append(entry)
+    return {'started': started, 'exited': sorted(exited), 'changed': changed}
+
+
+def get_process_changes(cpu_epsilon: float = DEFAULT_CPU_EPSILON,
+                        memory_threshold: int = DEFAULT_MEMORY_THRESHOLD) -> Dict[str, Any]:
+    """Get only what changed in the process table since the previous call.
+
+    Returns {snapshot_id, started, exited, changed}; entries are
+    get_process_list()'s plus "status", with cpu_percent measured since the
+    previous collection (0 for a process not seen before). Each process is
+    compared with how it was last reported, so slow drift shows up once it
+    adds up past cpu_epsilon or memory_threshold. The first call reports
+    every process as started. Pass snapshot_id to get_process_list() in the
+    same tick to get the full list from this collection without reading it
+    again.
+    """
+    validation.at_least('cpu_epsilon', cpu_epsilon, 0)
+    validation.at_least('memory_threshold', memory_threshold, 0)
+    state = active_state()
+    with state.lock('processes'):
+        snapshot_id, rows = direct_os.collect_process_rows()
+        current = process_table(rows, read_process_states())
+        previous = state.process_reported or {}
+        changes = diff_processes(previous, current, cpu_epsilon, memory_threshold)
+        reported = {entry['pid'] for entry in changes['started'] + changes['changed']}
+        state.process_reported = {pid: entry if pid in reported else previous[pid]
+                                  for pid, entry in current.items()}
+    return {'snapshot_id': snapshot_id, **changes}
+
+
+if LINUX:
+    def _read_namespaces(pid: int) -> Dict[str, Optional[int]]:
+        inodes: Dict[str, Optional[int]] = {}
+        for kind in NAMESPACE_KINDS:
+            try:
+                inodes[kind] = parse_ns_link(os.readlink(f'{PROC}/{pid}/ns/{kind}'))
+            except OSError as e:
+                # Other users' links need CAP_SYS_PTRACE
+                log.read_failed(f'{PROC}/{pid}/ns/{kind}', e)
+                inodes[kind] = None
+        return inodes
+
+    @requires('namespaces')
+    def get_process_namespaces(pid: int) -> Dict[str, Optional[int]]:
+        """Get the inode of each of a process's pid, net, mnt, and uts namespaces.
+
+        Processes sharing an inode share that namespace. A link the caller may
+        not read is None. Raises ProcessNotFound if the process doesn't exist.
+        """
+        validation.at_least('pid', pid, 1)
+        if not os.path.isdir(f'{PROC}/{pid}'):
+            raise ProcessNotFound.for_pid(pid)
+        return _read_namespaces(pid)
+
+    @requires('namespaces')
+    def group_processes_by_namespace(kind: str = 'pid') -> List[Dict[str, Any]]:
+        """Group every process by its kind namespace: {kind, inode, is_host, pids}.
+
+        is_host marks the kernel's initial namespace (for net and mnt, the
+        one pid 1 is in). Processes whose link can't be read are left out.
+        """
+        validation.one_of('kind', kind, NAMESPACE_KINDS)
+        namespaces = {int(name): _read_namespaces(int(name))
+                      for name in os.listdir(PROC) if name.isdigit()}
+        host = INIT_NAMESPACE_INODES.get(kind)
+        if host is None:
+            host = namespaces.get(1, {}).get(kind)
+        return group_by_namespace(namespaces, kind, host)
+
+else:
+    @requires('namespaces')
+    def get_process_namespaces(pid: int) -> Dict[str, Optional[int]]:
+        """Namespaces are Linux-only."""
+        raise Unsupported('get_process_namespaces is only supported on Linux')
+
+    @requires('namespaces')
+    def group_processes_by_namespace(kind: str = 'pid') -> List[Dict[str, Any]]:
+        """Namespaces are Linux-only."""
+        raise Unsupported('group_processes_by_namespace is only supported on Linux')
+
+
+# sched_getscheduler() policy numbers (include/uapi/linux/sched.h); 4 is unused
+SCHED_POLICIES = {0: 'other', 1: 'fifo', 2: 'rr', 3: 'batch', 5: 'idle', 6: 'deadline'}
+REALTIME_POLICIES = frozenset({'fifo', 'rr', 'deadline'})
+
+# ORed into the policy of a thread whose children drop back to "other"
+_SCHED_RESET_ON_FORK = 0x40000000
+
+# Windows priority classes -> (priority_class, the sched_policy closest to it).
+# The realtime class preempts everything else and round-robins its threads.
+WINDOWS_PRIORITY_CLASSES = {
+    0x40: ('idle', 'idle'),
+    0x4000: ('below_normal', 'other'),
+    0x20: ('normal', 'other'),
+    0x8000: ('above_normal', 'other'),
+    0x80: ('high', 'other'),
+    0x100: ('realtime', 'rr'),
+}
+
+
+def sched_entry(policy: Optional[int], rt_priority: Optional[int]) -> Dict[str, Any]:
+    """{sched_policy, rt_priority} from sched_getscheduler() and sched_getparam() values.
+
+    rt_priority is 1-99 under fifo and rr and 0 otherwise; either is None
+    when it couldn't be read.
+    """
+    if policy is not None:
+        policy &= ~_SCHED_RESET_ON_FORK
+    return {'sched_policy': SCHED_POLICIES.get(policy) if policy is not None else None,
+            'rt_priority': rt_priority}
+
+
+def windows_sched_entry(priority_class: Optional[int]) -> Dict[str, Any]:
+    """{sched_policy, rt_priority, priority_class} for a Windows priority class.
+
+    Windows has no per-thread realtime priority to report, so rt_priority is
+    always None.
+    """
+    names = WINDOWS_PRIORITY_CLASSES.get(priority_class) if priority_class is not None else None
+    return {'sched_policy': names[1] if names else None, 'rt_priority': None,
+            'priority_class': names[0] if names else None}
+
+
+if hasattr(os, 'sched_getscheduler'):
+    def read_sched(task_id: int) -> Dict[str, Any]:
+        """A process's or thread's {sched_policy, rt_priority}; None where refused.
+
+        Raises ProcessLookupError if the task doesn't exist.
+        """
+        try:
+            policy: Optional[int] = os.sched_getscheduler(task_id)
+        except PermissionError:
+            policy = None
+        try:
+            priority: Optional[int] = os.sched_getparam(task_id).sched_priority
+        except ProcessLookupError:
+            raise
+        except OSError:
+            # Refused, or a kernel that won't report it for a deadline task
+            priority = None
+        return sched_entry(policy, priority)
+
+elif WINDOWS:
+    def read_sched(task_id: int) -> Dict[str, Any]:
+        """A process's {sched_policy, rt_priority, priority_class} from its priority class."""
+        import psutil
+
+        try:
+            return windows_sched_entry(int(psutil.Process(task_id).nice()))
+        except psutil.NoSuchProcess:
+            raise ProcessLookupError(task_id) from None
+        except psutil.AccessDenied:
+            return windows_sched_entry(None)
+
+else:
+    def read_sched(task_id: int) -> Dict[str, Any]:
+        """macOS has no sched_getscheduler(); both fields are None."""
+        return sched_entry(None, None)
+
+
+def is_realtime(pid: int) -> Optional[Dict[str, Any]]:
+    """pid's read_sched() entry if it runs under a realtime policy, else None."""
+    try:
+        entry = read_sched(pid)
+    except ProcessLookupError:
+        return None
+    return entry if entry['sched_policy'] in REALTIME_POLICIES else None
+
+
+def get_process_scheduling(pid: int) -> Dict[str, Any]:
+    """Get a process's scheduling class and realtime priority: {pid, sched_policy, rt_priority}.
+
+    sched_policy is "other", "fifo", "rr", "batch", "idle", or "deadline";
+    rt_priority is 1-99 under fifo and rr. On Windows the policy is mapped
+    from the priority class, which is added as priority_class. A value the
+    caller may not read is None. Raises ProcessNotFound if the process
+    doesn't exist.
+    """
+    validation.at_least('pid', pid, 1)
+    try:
+        return {'pid': pid, **read_sched(pid)}
+    except ProcessLookupError:
+        raise ProcessNotFound.for_pid(pid) from None
+
+
+if LINUX:
+    def _thread_name(pid: int, tid: int) -> Optional[str]:
+        try:
+            with open(f'{PROC}/{pid}/task/{tid}/comm', 'r') as f:
+                return f.read().rstrip('\n')
+        except OSError:
+            return None
+
+    def get_process_threads(pid: int) -> List[Dict[str, Any]]:
+        """Get each of a process's threads: {tid, name, sched_policy, rt_priority}, by tid.
+
+        Policy and priority are per thread, so an audio callback thread can
+        run under fifo in an otherwise "other" process. Threads that exit
+        while being read are left out. Raises ProcessNotFound if the process
+        doesn't exist.
+        """
+        validation.at_least('pid', pid, 1)
+        try:
+            tids = sorted(int(name) for name in os.listdir(f'{PROC}/{pid}/task') if name.isdigit())
+        except FileNotFoundError:
+            raise ProcessNotFound.for_pid(pid) from None
+        except PermissionError:
+            raise PermissionDenied(f'Not allowed to list threads of pid {pid}') from None
+        threads = []
+        for tid in tids:
+            try:
+                threads.append({'tid': tid, 'name': _thread_name(pid, tid), **read_sched(tid)})
+            except ProcessLookupError:
+                continue
+        return threads
+
+else:
+    def get_process_threads(pid: int) -> List[Dict[str, Any]]:
+        """Get each of a process's thread ids via psutil; names and policies are None.
+
+        Per-thread policies are only read on Linux. Raises ProcessNotFound if
+        the process doesn't exist.
+        """
+        validation.at_least('pid', pid, 1)
+        import psutil
+
+        try:
+            threads = psutil.Process(pid).threads()
+        except psutil.NoSuchProcess:
+            raise ProcessNotFound.for_pid(pid) from None
+        except psutil.AccessDenied:
+            raise PermissionDenied(f'Not allowed to list threads of pid {pid}') from None
+        return [{'tid': t.id, 'name': None, **sched_entry(None, None)} for t in sorted(threads)]
+
+
+@requires('process_io')
+def get_io_hogs(n: int = 10) -> List[Dict[str, Any]]:
+    """Get the n processes doing the most disk I/O since the previous call.
+
+    Baselines are keyed by pid and start time so a reused pid never inherits
+    another process's counters. The first call only records baselines and
+    returns an empty list; processes without I/O in the interval are left out.
+    """
+    validation.at_least('n', n, 1)
+    state = active_state()
+    with state.lock('processes'):
+        current = _collect_process_io()
+        now = time.monotonic()
+        previous = state.process_io
+        elapsed = now - state.process_io_time
+        state.process_io = current
+        state.process_io_time = now
+
+    if previous is None or elapsed <= 0:
+        return []
+
+    hogs = []
+    for key, proc in current.items():
+        prev = previous.get(key)
+        if prev is None:
+            continue
+        read = counter_delta(proc['read_bytes'], prev['read_bytes'])
+        written = counter_delta(proc['write_bytes'], prev['write_bytes'])
+        if read == 0 and written == 0:
+            continue
+        hogs.append({
+            'pid': proc['pid'],
+            'name': proc['name'],
+            'read_rate': read / elapsed,
+            'write_rate': written / elapsed,
+            'read_bytes': proc['read_bytes'],
+            'write_bytes': proc['write_bytes'],
+        })
+
+    hogs.sort(key=lambda h: h['read_rate'] + h['write_rate'], reverse=True)
+    return hogs[:n]
+
+
+class ProcessIterator:
+    """Yields get_process_list() dicts one at a time from a single collection.
+
+    The processes are read once, when the iterator is created, and held as
+    compact tuples; each dict is only built as it is consumed. close(), or
+    simply dropping the iterator, frees the held rows.
+    """
+
+    __slots__ = ('_rows', '_index', '_fields')
+
+    def __init__(self, rows: List[direct_os.ProcessRow], fields: Optional[Tuple[str, ...]]):
+        self._rows: Optional[List[direct_os.ProcessRow]] = rows
+        self._index = 0
+        self._fields = fields
+
+    def __iter__(self) -> 'ProcessIterator':
+        return self
+
+    def __next__(self) -> Dict[str, Any]:
+        if self._rows is None or self._index >= len(self._rows):
+            self.close()
+            raise StopIteration
+        entry = direct_os.process_dict(self._rows[self._index])
+        self._index += 1
+        if self._fields is not None:
+            entry = {k: entry[k] for k in self._fields}
+        return compat.adapt('process', entry)
+
+    def __length_hint__(self) -> int:
+        return 0 if self._rows is None else len(self._rows) - self._index
+
+    def close(self) -> None:
+        """Release the collected rows; further iteration stops."""
+        self._rows = None
+
+
+def iter_processes(sort_by: Optional[str] = None, limit: Optional[int] = None,
+                   fields: Optional[Iterable[str]] = None) -> ProcessIterator:
+    """Stream the process list instead of building it all at once.
+
+    sort_by and limit are as for get_process_list(); fields trims each dict
+    to those keys. Raises ValueError for an unknown field.
+    """
+    if fields is not None:
+        fields = validation.all_of('fields', fields, direct_os.PROCESS_FIELDS)
+    return ProcessIterator(direct_os.get_process_rows(sort_by, limit), fields)
@@ -0,0 +1,108 @@
+"""
+Pulse Prometheus Export
+Renders a snapshot() in the Prometheus text exposition format (version 0.0.4).
+"""
+import re
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+
+DEFAULT_PREFIX = 'pulse'
+
+_METRIC_NAME = re.compile(r'^[a-zA-Z_:][a-zA-Z0-9_:]*$')
+
+# (snapshot dict key, metric suffix, HELP text) per section
+_MEMORY_GAUGES = (
+    ('total', 'memory_total_bytes', 'Total physical memory.'),
+    ('used', 'memory_used_bytes', 'Physical memory in use (total minus available).'),
+    ('available', 'memory_available_bytes', 'Memory available to new allocations without swapping.'),
+    ('percent', 'memory_usage_percent', 'Physical memory in use, as a percentage of total.'),
+    ('swap_total', 'swap_total_bytes', 'Total swap space.'),
+    ('swap_used', 'swap_used_bytes', 'Swap space in use.'),
+)
+
+_NETWORK_COUNTERS = (
+    ('bytes_recv', 'network_received_bytes_total', 'Bytes received on non-loopback interfaces.'),
+    ('bytes_sent', 'network_sent_bytes_total', 'Bytes sent on non-loopback interfaces.'),
+)
+
+_DISK_GAUGES = (
+    ('total', 'disk_total_bytes', 'Filesystem size.'),
+    ('used', 'disk_used_bytes', 'Filesystem space in use.'),
+    ('available', 'disk_available_bytes', 'Filesystem space available to unprivileged users.'),
+    ('percent', 'disk_usage_percent', 'Filesystem usage as df reports it.'),
+)
+
+_PROCESS_GAUGES = (
+    ('cpu_percent', 'process_cpu_percent', 'Process CPU usage, as a percentage of one core.'),
+    ('memory_info', 'process_memory_bytes', 'Process memory footprint.'),
+)
+
+Sample = Tuple[Dict[str, str], Any]
+
+
+def escape_label_value(value: Any) -> str:
+    """Escape a label value: backslash, double quote, and newline."""
+    return str(value).replace('\\', '\\\\').replace('"', '\\"').replace('\n', '\\n')
+
+
+def _escape_help(text: str) -> str:
+    return text.replace('\\', '\\\\').replace('\n', '\\n')
+
+
+def _format_value(value: Any) -> str:
+    if isinstance(value, bool):
+        return '1' if value else '0'
+    if isinstance(value, float):
+        if value != value:
+            return 'NaN'
+        if value in (float('inf'), float('-inf')):
+            return '+Inf' if value > 0 else '-Inf'
+        return repr(value)
+    return str(int(value))
+
+
+def _family(name: str, kind: str, help_text: str, samples: Iterable[Sample]) -> List[str]:
+    """HELP/TYPE header plus one line per sample; samples with a None value are dropped."""
+    lines = [f'# HELP {name} {_escape_help(help_text)}', f'# TYPE {name} {kind}']
+    for labels, value in samples:
+        if value is None:
+            continue
+        if labels:
+            rendered = ','.join(f'{k}="{escape_label_value(v)}"' for k, v in labels.items())
+            lines.append(f'{name}{{{rendered}}} {_format_value(value)}')
+        else:
+            lines.append(f'{name} {_format_value(value)}')
+    return lines
+
+
+def render(snap: Dict[str, Any], prefix: Optional[str] = None) -> str:
+    """Render the sections present in a snapshot() dict as exposition text.
+
+    Every metric name starts with prefix + "_" (default "pulse"). Network totals
+    are counters; everything else is a gauge. Raises ValueError for a prefix
+    that isn't a valid metric name.
+    """
+    prefix = DEFAULT_PREFIX if prefix is None else prefix
+    if not _METRIC_NAME.match(prefix):
+        raise ValueError(f'Invalid prefix {prefix!r}; expected a metric name matching {_METRIC_NAME.pattern}')
+
+    lines: List[str] = []
+    if 'cpu' in snap:
+        lines += _family(f'{prefix}_cpu_usage_percent', 'gauge', 'Per-core CPU usage since the previous sample.',
+                         (({'core': str(i)}, pct) for i, pct in enumerate(snap['cpu'])))
+    if 'memory' in snap:
+        for key, suffix, help_text in _MEMORY_GAUGES:
+            if key in snap['memory']:
+                lines += _family(f'{prefix}_{suffix}', 'gauge', help_text, [({}, snap['memory'][key])])
+    if 'network' in snap:
+        for key, suffix, help_text in _NETWORK_COUNTERS:
+            lines += _family(f'{prefix}_{suffix}', 'counter', help_text, [({}, snap['network'].get(key))])
+    if 'disks' in snap:
+        for key, suffix, help_text in _DISK_GAUGES:
+            lines += _family(f'{prefix}_{suffix}', 'gauge', help_text, (
+                ({'mount': d['mountpoint'], 'device': d['device'], 'fstype': d['fstype']}, d.get(key))
+                for d in snap['disks']))
+    if 'processes' in snap:
+        for key, suffix, help_text in _PROCESS_GAUGES:
+            lines += _family(f'{prefix}_{suffix}', 'gauge', help_text, (
+                ({'pid': str(p['pid']), 'name': p['name']}, p.get(key)) for p in snap['processes']))
+    return '\n'.join(lines) + '\n'
@@ -0,0 +1,120 @@
+"""
+Pulse Worker Protocol
+Framing for the requests and responses exchanged with an isolated worker.
+
+Each message is a 4-byte big-endian length followed by that many bytes of
+MessagePack, so bytes and integer dict keys survive the trip. Without the
+optional msgpack package the body is compact UTF-8 JSON instead, the same
+framing as a recording. Both ends must use the same codec; the parent
+names it on the worker's command line.
+"""
+import json
+import queue
+import struct
+import threading
+from typing import Any, BinaryIO, Dict, Optional
+
+from pulse import encoding
+from pulse.errors import Timeout
+
+try:
+    import msgpack
+except ImportError:
+    msgpack = None
+
+CODECS = ('msgpack', 'json')
+DEFAULT_CODEC = 'json' if msgpack is None else 'msgpack'
+
+# Larger lengths mean the stream is out of step, not a real message
+MAX_MESSAGE_BYTES = 256 * 1024 ** 2
+
+_LENGTH = struct.Struct('>I')
+
+
+def encode_body(message: Dict[str, Any], codec: str = DEFAULT_CODEC) -> bytes:
+    """message encoded as a frame body with codec."""
+    if codec == 'msgpack':
+        return msgpack.packb(message, use_bin_type=True)
+    return encoding.to_json(message).encode('utf-8')
+
+
+def decode_body(data: bytes, codec: str = DEFAULT_CODEC) -> Any:
+    """A frame body decoded with codec; raises ValueError if it doesn't decode."""
+    if codec == 'msgpack':
+        # Results keyed by pid or core number keep their integer keys
+        return msgpack.unpackb(data, raw=False, strict_map_key=False)
+    return json.loads(data.decode('utf-8'))
+
+
+def write_message(stream: BinaryIO, message: Dict[str, Any], codec: str = DEFAULT_CODEC) -> None:
+    """Frame message with codec and flush it to stream."""
+    data = encode_body(message, codec)
+    stream.write(_LENGTH.pack(len(data)) + data)
+    stream.flush()
+
+
+def _read_exactly(stream: BinaryIO, size: int) -> bytes:
+    data = b''
+    while len(data) < size:
+        chunk = stream.read(size - len(data))
+        if not chunk:
+            raise EOFError(f'Stream ended {size - len(data)} bytes into a {size}-byte read')
+        data += chunk
+    return data
+
+
+def read_message(stream: BinaryIO, codec: str = DEFAULT_CODEC) -> Optional[Dict[str, Any]]:
+    """The next message on stream, decoded with codec, or None if it closed between messages.
+
+    Raises EOFError for a stream that ends part-way through a message and
+    ValueError for a length or body that can't be a message.
+    """
+    header = stream.read(_LENGTH.size)
+    if not header:
+        return None
+    (length,) = _LENGTH.unpack(header + _read_exactly(stream, _LENGTH.size - len(header)))
+    if length > MAX_MESSAGE_BYTES:
+        raise ValueError(f'Message length {length} exceeds {MAX_MESSAGE_BYTES} bytes')
+    message = decode_body(_read_exactly(stream, length), codec)
+    if not isinstance(message, dict):
+        raise ValueError(f'Expected a message object, got {type(message).__name__}')
+    return message
+
+
+class Receiver:
+    """Reads messages from stream on a daemon thread so receive() can time out.
+
+    A plain pipe read can't be abandoned part-way on every platform, so the
+    thread does the blocking and receive() waits on its queue instead.
+    """
+
+    def __init__(self, stream: BinaryIO, name: str = 'pulse-receiver', codec: str = DEFAULT_CODEC):
+        self._messages: 'queue.Queue[Any]' = queue.Queue()
+        self._thread = threading.Thread(target=self._run, args=(stream, codec), name=name, daemon=True)
+        self._thread.start()
+
+    def _run(self, stream: BinaryIO, codec: str) -> None:
+        while True:
+            try:
+                message = read_message(stream, codec)
+            except (OSError, ValueError, EOFError) as e:
+                self._messages.put(e)
+                return
+            if message is None:
+                self._messages.put(EOFError('Stream closed'))
+                return
+            self._messages.put(message)
+
+    def receive(self, timeout: Optional[float] = None) -> Dict[str, Any]:
+        """The next message; raises Timeout after timeout seconds, EOFError once the stream ends."""
+        try:
+            item = self._messages.get(timeout=timeout)
+        except queue.Empty:
+            raise Timeout(f'No message within {timeout} s') from None
+        if isinstance(item, BaseException):
+            # Leave it queued so every later receive() sees the end as well
+            self._messages.put(item)
+            if isinstance(item, EOFError):
+                raise item
+            raise EOFError(f'Stream failed: {type(item).__name__}: {item}') from item
+        return item
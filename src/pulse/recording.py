@@ -0,0 +1,197 @@
+"""
+Pulse Recording
+Sampler snapshots written to a file, and read back later through a Replay.
+
+A recording starts with MAGIC, followed by one record per sample: a 4-byte
+big-endian length, then that many bytes of the snapshot as compact UTF-8 JSON.
+"""
+import json
+import struct
+import threading
+from typing import Any, BinaryIO, Dict, Iterable, List, Optional
+
+from pulse import encoding, validation
+from pulse.errors import CorruptRecording, PulseError
+
+MAGIC = b'PULSEREC\x01'
+
+_LENGTH = struct.Struct('>I')
+
+# Replay method -> the snapshot section it reads
+REPLAY_SECTIONS = {
+    'cpu_percents': 'cpu',
+    'memory_info': 'memory',
+    'process_list': 'processes',
+    'network_stats': 'network',
+    'disk_info': 'disks',
+}
+
+_lock = threading.Lock()
+_file: Optional[BinaryIO] = None
+_path: Optional[str] = None
+_written = 0
+_failure: Optional[str] = None
+# Whether start_recording() started the sampler, so stop_recording() stops it
+_owns_sampler = False
+
+
+def start_recording(path: str, interval_ms: float = 1000,
+                    sections: Optional[Iterable[str]] = None) -> None:
+    """Write every sampler snapshot to path until stop_recording().
+
+    Starts the sampler with interval_ms and sections (as for start_sampler)
+    unless it is already running, in which case its own samples are recorded.
+    path is overwritten. Raises RuntimeError while another recording is open.
+    """
+    from pulse import sampler
+
+    global _file, _path, _written, _failure, _owns_sampler
+    validation.not_empty('path', path)
+    validation.positive('interval_ms', interval_ms)
+    with _lock:
+        if _file is not None:
+            raise RuntimeError(f'Already recording to {_path}')
+        running = sampler.sampler_status()['running']
+        handle = open(path, 'wb')
+        handle.write(MAGIC)
+        handle.flush()
+        _file, _path, _written, _failure = handle, path, 0, None
+    if not running:
+        try:
+            sampler.start_sampler(interval_ms, sections=sections)
+        except ValueError:
+            stop_recording()
+            raise
+    with _lock:
+        _owns_sampler = not running
+
+
+def stop_recording(timeout: Optional[float] = None) -> int:
+    """Close the recording and return how many samples it holds.
+
+    Stops the sampler if start_recording() started it. Raises PulseError if
+    a write failed part-way; the file then holds the samples before it.
+    """
+    from pulse import sampler
+
+    global _file, _owns_sampler
+    with _lock:
+        owns_sampler, _owns_sampler = _owns_sampler, False
+    if owns_sampler:
+        sampler.stop_sampler(timeout)
+    with _lock:
+        if _file is not None:
+            _file.close()
+            _file = None
+        if _failure is not None:
+            raise PulseError(f'Recording to {_path} stopped after {_written} samples: {_failure}')
+        return _written
+
+
+def on_sample(sample: Dict[str, Any]) -> None:
+    """Append sample to the open recording; the sampler calls this after each snapshot."""
+    global _file, _written, _failure
+    with _lock:
+        if _file is None:
+            return
+        data = encoding.to_json(sample).encode('utf-8')
+        try:
+            _file.write(_LENGTH.pack(len(data)) + data)
+            _file.flush()
+        except OSError as e:
+            _failure = f'{type(e).__name__}: {e}'
+            _file.close()
+            _file = None
+            return
+        _written += 1
+
+
+def read_recording(path: str) -> List[Dict[str, Any]]:
+    """Every sample in the recording at path, oldest first.
+
+    Raises CorruptRecording, naming the byte offset, for a missing header,
+    a record cut short, or a record that isn't a snapshot.
+    """
+    with open(path, 'rb') as f:
+        data = f.read()
+    if data[:len(MAGIC)] != MAGIC:
+        raise CorruptRecording(path, 0, 'not a Pulse recording (bad header)')
+
+    samples = []
+    offset = len(MAGIC)
+    while offset < len(data):
+        if offset + _LENGTH.size > len(data):
+            raise CorruptRecording(path, offset, 'truncated record length')
+        (length,) = _LENGTH.unpack_from(data, offset)
+        body = offset + _LENGTH.size
+        if body + length > len(data):
+            raise CorruptRecording(path, offset, f'record of {length} bytes cut short '
+                                                 f'({len(data) - body} present)')
+        try:
+            sample = json.loads(data[body:body + length].decode('utf-8'))
+        except ValueError as e:
+            raise CorruptRecording(path, offset, f'undecodable record ({e})') from None
+        if not isinstance(sample, dict) or 'timestamp' not in sample:
+            raise CorruptRecording(path, offset, 'record is not a snapshot')
+        samples.append(sample)
+        offset = body + length
+    return samples
+
+
+class Replay:
+    """Recorded samples served through Monitor's method names.
+
+    Each method returns its section from the next sample on every call, with
+    one cursor per method, so a dashboard calling cpu_percents() and
+    memory_info() once per tick sees both advance together. A method raises
+    EOFError once its samples run out; rewind() starts them all over.
+    """
+
+    def __init__(self, samples: List[Dict[str, Any]]):
+        self.samples = samples
+        self._cursors: Dict[str, int] = {}
+
+    def __len__(self) -> int:
+        return len(self.samples)
+
+    def rewind(self) -> None:
+        self._cursors.clear()
+
+    def _next(self, name: str, as_json: bool, as_msgpack: bool) -> Any:
+        section = REPLAY_SECTIONS.get(name)
+        if section is not None and self.samples and section not in self.samples[0]:
+            raise ValueError(f'The recording has no "{section}" section')
+        position = self._cursors.get(name, 0)
+        if position >= len(self.samples):
+            raise EOFError(f'All {len(self.samples)} recorded samples have been replayed')
+        sample = self.samples[position]
+        data = sample if section is None else sample[section]
+        if as_json or as_msgpack:
+            data = encoding.timestamped(data, sample['timestamp'])
+        encoded = encoding.encode(data, as_json, as_msgpack)
+        self._cursors[name] = position + 1
+        return encoded
+
+    def snapshot(self, as_json: bool = False, as_msgpack: bool = False) -> Any:
+        """The next whole sample, timestamp included."""
+        return self._next('snapshot', as_json, as_msgpack)
+
+    def cpu_percents(self, as_json: bool = False, as_msgpack: bool = False) -> Any:
+        return self._next('cpu_percents', as_json, as_msgpack)
+
+    def memory_info(self, as_json: bool = False, as_msgpack: bool = False) -> Any:
+        return self._next('memory_info', as_json, as_msgpack)
+
+    def process_list(self, as_json: bool = False, as_msgpack: bool = False) -> Any:
+        return self._next('process_list', as_json, as_msgpack)
+
+    def network_stats(self, as_json: bool = False, as_msgpack: bool = False) -> Any:
+        return self._next('network_stats', as_json, as_msgpack)
+
+    def disk_info(self, as_json: bool = False, as_msgpack: bool = False) -> Any:
+        return self._next('disk_info', as_json, as_msgpack)
+
+
+def load_recording(path: str) -> Replay:
+    """Read the recording at path into a Replay (see read_recording for errors)."""
+    return Replay(read_recording(path))
@@ -0,0 +1,157 @@
+"""
+Pulse Session Reports
+One file summarizing a monitoring run: the sampler history plus what it adds up to.
+
+JSON holds {"summary": ..., "samples": [...]} with the samples as the
+sampler kept them. CSV is wide: one row per sample, a "timestamp" column and
+one column per anomalies.series_from_samples() series ("cpu_total",
+"memory_used", "disk_used:/", ...), empty where a sample lacks it, so
+pandas.read_csv() gives a frame indexed by time. The summary then goes to a
+second, long-format file beside it, "<path stem>.summary.csv", with
+section, name, statistic, and value columns.
+"""
+import csv
+import json
+import os
+from typing import Any, Dict, List, Optional, Tuple
+
+from pulse import sampler, validation
+from pulse.anomalies import series_from_samples
+from pulse.history import aggregate
+
+FORMATS = ('json', 'csv')
+
+# Processes listed in the summary's top_processes
+TOP_PROCESSES = 10
+
+SUMMARY_COLUMNS = ('section', 'name', 'statistic', 'value')
+
+
+def _counter_total(values: List[Optional[int]]) -> int:
+    """Growth of a cumulative counter over a run, skipping steps where it was reset."""
+    known = [v for v in values if v is not None]
+    return sum(max(0, after - before) for before, after in zip(known, known[1:]))
+
+
+def summarize(samples: List[Dict[str, Any]]) -> Dict[str, Any]:
+    """A run's derived statistics.
+
+    {samples, start, end, duration_secs, series: {name: {min, max, avg}},
+    top_processes: [{pid, name, cpu_secs}], network: {bytes_recv,
+    bytes_sent}, disk_growth: {mountpoint: bytes}}. cpu_secs adds up the
+    growth of each process's cpu_time (user plus system seconds, which the
+    sampler notes) between the samples it appears in, keyed by pid and
+    start_time so a reused pid is another process; one that started during
+    the run counts from zero. Network totals and disk growth are last minus
+    first, with counter resets skipped.
+    """
+    start = samples[0]['timestamp'] if samples else None
+    end = samples[-1]['timestamp'] if samples else None
+    series = {name: aggregate((v for _, v in readings), ('min', 'max', 'avg'))
+              for name, readings in sorted(series_from_samples(samples).items())}
+
+    cpu_secs: Dict[Tuple[int, float], float] = {}
+    names: Dict[Tuple[int, float], str] = {}
+    last: Dict[Tuple[int, float], float] = {}
+    for sample in samples:
+        for proc in sample.get('processes') or ():
+            if proc.get('cpu_time') is None:
+                continue
+            key = (proc['pid'], proc.get('start_time') or 0.0)
+            if key in last:
+                used = max(0.0, proc['cpu_time'] - last[key])
+            else:
+                used = proc['cpu_time'] if start is not None and key[1] >= start else 0.0
+            cpu_secs[key] = cpu_secs.get(key, 0.0) + used
+            names[key] = proc['name']
+            last[key] = proc['cpu_time']
+    top = sorted(cpu_secs.items(), key=lambda item: (-item[1], item[0]))[:TOP_PROCESSES]
+
+    network = [s['network'] for s in samples if s.get('network') is not None]
+    used: Dict[str, List[Optional[int]]] = {}
+    for sample in samples:
+        for disk in sample.get('disks') or ():
+            used.setdefault(disk['mountpoint'], []).append(disk.get('used'))
+
+    return {
+        'samples': len(samples),
+        'start': start,
+        'end': end,
+        'duration_secs': end - start if samples else 0.0,
+        'series': series,
+        'top_processes': [{'pid': key[0], 'name': names[key], 'cpu_secs': secs} for key, secs in top if secs > 0],
+        'network': {key: _counter_total([n.get(key) for n in network])
+                    for key in ('bytes_recv', 'bytes_sent')},
+        'disk_growth': {mount: None if None in (values[0], values[-1]) else values[-1] - values[0]
+                        for mount, values in sorted(used.items())},
+    }
+
+
+def summary_rows(summary: Dict[str, Any]) -> List[Tuple[str, str, str, Any]]:
+    """The summary flattened to (section, name, statistic, value) rows."""
+    rows: List[Tuple[str, str, str, Any]] = [
+        ('run', '', key, summary[key]) for key in ('samples', 'start', 'end', 'duration_secs')
+    ]
+    for name, stats in summary['series'].items():
+        rows += [('series', name, stat, value) for stat, value in stats.items()]
+    rows += [('top_processes', f"{p['name']} ({p['pid']})", 'cpu_secs', p['cpu_secs'])
+             for p in summary['top_processes']]
+    rows += [('network', '', key, value) for key, value in summary['network'].items()]
+    rows += [('disk_growth', mount, 'bytes', value) for mount, value in summary['disk_growth'].items()]
+    return rows
+
+
+def sample_table(samples: List[Dict[str, Any]]) -> Tuple[List[str], List[List[Any]]]:
+    """(header, rows) of the wide CSV: timestamp, then one column per series."""
+    series = series_from_samples(samples)
+    columns = sorted(series)
+    by_time: Dict[float, Dict[str, float]] = {}
+    for name, readings in series.items():
+        for timestamp, value in readings:
+            by_time.setdefault(timestamp, {})[name] = value
+    rows = [[s['timestamp'], *(by_time.get(s['timestamp'], {}).get(c, '') for c in columns)]
+            for s in samples]
+    return ['timestamp', *columns], rows
+
+
+def summary_path(path: str) -> str:
+    """Where a CSV report's summary goes: "run.csv" -> "run.summary.csv"."""
+    root, ext = os.path.splitext(path)
+    return f'{root}.summary{ext or ".csv"}'
+
+
+def _write_csv(path: str, header: List[str], rows: List[Any]) -> None:
+    with open(path, 'w', newline='', encoding='utf-8') as f:
+        writer = csv.writer(f)
+        writer.writerow(header)
+        writer.writerows(rows)
+
+
+def write_report(path: str, format: str = 'json',
+                 samples: Optional[List[Dict[str, Any]]] = None) -> None:
+    """Write a report of samples (default: the sampler's history) to path.
+
+    format is "json" or "csv"; see this module's docstring for both layouts.
+    Pass read_recording()'s samples to report on a recorded run. Raises
+    OSError naming the file when it can't be written.
+    """
+    validation.not_empty('path', path)
+    validation.one_of('format', format, FORMATS)
+    if samples is None:
+        samples = sampler.get_samples()
+    summary = summarize(samples)
+    target = path
+    try:
+        if format == 'json':
+            with open(path, 'w', encoding='utf-8') as f:
+                json.dump({'summary': summary, 'samples': samples}, f, separators=(',', ':'))
+        else:
+            header, rows = sample_table(samples)
+            _write_csv(path, header, rows)
+            target = summary_path(path)
+            _write_csv(target, list(SUMMARY_COLUMNS), summary_rows(summary))
+    except OSError as e:
+        # Errors raised mid-write (ENOSPC, EIO) carry no filename of their own
+        if e.filename is None:
+            raise OSError(e.errno, e.strerror, target) from e
+        raise
@@ -0,0 +1,137 @@
+"""
+Pulse Resource Control
+Last-level cache occupancy and memory bandwidth per workload from Intel RDT / AMD PQoS.
+
+With resctrl mounted (mount -t resctrl resctrl /sys/fs/resctrl) the kernel
+counts, per monitoring group and per L3 domain, the bytes of LLC a group's
+tasks occupy and the bytes they moved to and from memory. The root directory
+is the default group; every other directory is a control group, and
+mon_groups/<name> inside any of them is a monitoring-only subgroup.
+"""
+import os
+import time
+from typing import Any, Dict, List, Optional, Tuple
+
+from pulse import log
+from pulse.collect import counter_rate
+from pulse.state import active_state
+
+SYS_FS_RESCTRL = '/sys/fs/resctrl'
+
+# Event files in mon_data/mon_L3_<domain>/; mbm_* are cumulative byte counters
+EVENTS = ('llc_occupancy', 'mbm_total_bytes', 'mbm_local_bytes')
+_BANDWIDTH_EVENTS = ('mbm_total_bytes', 'mbm_local_bytes')
+
+# Top-level directories that aren't control groups
+_RESERVED = frozenset({'info', 'mon_data', 'mon_groups'})
+
+
+def _read(path: str) -> Optional[str]:
+    try:
+        with open(path, 'r') as f:
+            return f.read()
+    except OSError as e:
+        log.read_failed(path, e)
+        return None
+
+
+def parse_event_value(text: Optional[str]) -> Optional[int]:
+    """An event file's count; None for "Unavailable" (no RMID yet) or "Error"."""
+    value = (text or '').strip()
+    return int(value) if value.isdigit() else None
+
+
+def parse_tasks(text: str) -> List[int]:
+    """Pids listed in a group's tasks file."""
+    return sorted(int(p) for p in text.split() if p.isdigit())
+
+
+def find_groups(root: str) -> List[Tuple[str, str]]:
+    """(name, directory) of every monitoring group under a resctrl mount.
+
+    The default group is "/", a control group its directory name, and a
+    monitoring subgroup "<control>/mon_groups/<name>" ("mon_groups/<name>"
+    under the default group).
+    """
+    controls = [('/', root)]
+    try:
+        entries = sorted(os.listdir(root))
+    except OSError:
+        return []
+    controls += [(name, os.path.join(root, name)) for name in entries
+                 if name not in _RESERVED and os.path.isdir(os.path.join(root, name))]
+
+    groups = []
+    for name, directory in controls:
+        groups.append((name, directory))
+        mon_groups = os.path.join(directory, 'mon_groups')
+        try:
+            subgroups = sorted(os.listdir(mon_groups))
+        except OSError:
+            continue
+        prefix = '' if name == '/' else name + '/'
+        groups += [(f'{prefix}mon_groups/{sub}', os.path.join(mon_groups, sub)) for sub in subgroups]
+    return groups
+
+
+def read_domains(directory: str) -> Dict[str, Dict[str, Optional[int]]]:
+    """{domain: {event: value}} from a group's mon_data ("L3_00", "L3_01", ...)."""
+    mon_data = os.path.join(directory, 'mon_data')
+    try:
+        names = sorted(os.listdir(mon_data))
+    except OSError:
+        return {}
+    domains = {}
+    for name in names:
+        if not name.startswith('mon_'):
+            continue
+        domains[name[len('mon_'):]] = {
+            event: parse_event_value(_read(os.path.join(mon_data, name, event))) for event in EVENTS
+        }
+    return domains
+
+
+def group_entry(name: str, pids: List[int], domains: Dict[str, Dict[str, Optional[int]]],
+                previous: Optional[Tuple[float, Dict[str, Dict[str, Optional[int]]]]],
+                now: float) -> Dict[str, Any]:
+    """One group's totals across domains, plus bandwidth per second since previous.
+
+    A rate is None on the first reading and whenever either reading of a
+    domain was unavailable.
+    """
+    elapsed = now - previous[0] if previous is not None else 0.0
+    entry: Dict[str, Any] = {'name': name, 'pids': pids, 'domains': domains}
+    for event in EVENTS:
+        values = [d[event] for d in domains.values()]
+        entry[event] = None if not values or None in values else sum(values)
+    for event in _BANDWIDTH_EVENTS:
+        rates = []
+        for domain, counters in domains.items():
+            before = previous[1].get(domain, {}).get(event) if previous is not None else None
+            current = counters[event]
+            rates.append(None if before is None or current is None or elapsed <= 0
+                         else counter_rate(current, before, elapsed))
+        entry[event + '_per_sec'] = None if not rates or None in rates else sum(rates)
+    return entry
+
+
+def get_rdt_groups() -> List[Dict[str, Any]]:
+    """Get every resctrl monitoring group's LLC occupancy, memory bandwidth, and pids.
+
+    Each group has llc_occupancy (bytes), mbm_total_bytes and mbm_local_bytes
+    (cumulative) summed over L3 domains, their per-domain values under
+    "domains", and mbm_*_per_sec since the previous call (None on the first).
+    Needs resctrl mounted at /sys/fs/resctrl with monitoring support; empty
+    where it isn't mounted (get_capabilities() reports "rdt").
+    """
+    groups = find_groups(SYS_FS_RESCTRL)
+    readings = [(name, parse_tasks(_read(os.path.join(directory, 'tasks')) or ''),
+                 read_domains(directory)) for name, directory in groups]
+
+    state = active_state()
+    with state.lock('cgroups'):
+        now = time.monotonic()
+        previous = state.rdt_counters
+        state.rdt_counters = {name: (now, domains) for name, _, domains in readings}
+    return [group_entry(name, pids, domains, previous.get(name), now)
+            for name, pids, domains in readings]
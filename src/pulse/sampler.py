@@ -0,0 +1,196 @@
+"""
+Pulse Sampler
+Background snapshots of the whole system kept in a fixed-size history.
+
+The sampler thread collects with its own baselines, so callers polling the
+module-level getters (or their own Monitors) never see their CPU or I/O
+deltas reset by it. Callbacks registered with pulse.callbacks run on the
+same thread after each sample is recorded, pulse.recording writes each
+sample to its file there, and pulse.socket_publisher sends it to its clients.
+With the "processes" section, pulse.leaderboard also records each process's
+CPU time there, and with the "cpu" section pulse.thermal the package
+temperature and frequency. With the "network" section each sample's
+network entry also holds "interfaces", the byte counters per interface.
+"""
+import atexit
+import threading
+import time
+from array import array
+from collections import deque
+from typing import Any, Deque, Dict, Iterable, List, Optional, Tuple
+
+from pulse import backend, callbacks, leaderboard, recording, socket_publisher, thermal, units, validation
+from pulse.capabilities import supported
+from pulse.state import SysState, using
+
+# Samples kept by default; ten minutes at the default one-second interval
+DEFAULT_CAPACITY = 600
+
+_samples: Deque[Dict[str, Any]] = deque(maxlen=DEFAULT_CAPACITY)
+_samples_lock = threading.Lock()
+_thread: Optional[threading.Thread] = None
+_stop = threading.Event()
+# Serializes start_sampler/stop_sampler so two callers never both start a thread
+_control_lock = threading.Lock()
+_status: Dict[str, Any] = {
+    'interval_ms': None, 'capacity': DEFAULT_CAPACITY, 'sections': [],
+    'collected': 0, 'dropped': 0, 'last_error': None,
+}
+
+
+def _record(sample: Dict[str, Any]) -> None:
+    with _samples_lock:
+        if len(_samples) == _samples.maxlen:
+            _status['dropped'] += 1
+        _samples.append(sample)
+        _status['collected'] += 1
+
+
+def _record_interfaces(sample: Dict[str, Any]) -> None:
+    sample['network']['interfaces'] = {name: units.convert('network', counters)
+                                       for name, counters in backend.active().interface_counters().items()}
+
+
+def _run(interval_secs: float, sections: List[str], options: Dict[str, Dict[str, Any]]) -> None:
+    from pulse import core
+
+    with using(SysState()):
+        # The first CPU and rate readings only set a baseline
+        core.init(s for s in sections if s in core.INIT_SUBSYSTEMS)
+        delay = interval_secs
+        previous = None
+        track_cpu = 'processes' in sections
+        track_interfaces = 'network' in sections
+        track_thermal = 'cpu' in sections and supported('temperatures')
+        while not _stop.wait(delay):
+            started = time.monotonic()
+            try:
+                sample = core.snapshot(include=sections, options=options)
+            except Exception as e:
+                with _samples_lock:
+                    _status['last_error'] = f'{type(e).__name__}: {e}'
+            else:
+                if track_cpu:
+                    leaderboard.on_sample(sample)
+                if track_interfaces:
+                    _record_interfaces(sample)
+                _record(sample)
+                if track_thermal:
+                    thermal.on_sample(sample['timestamp'])
+                recording.on_sample(sample)
+                socket_publisher.on_sample(sample)
+                callbacks.on_sample(sample, previous)
+                previous = sample
+            # Keep the cadence steady however long the snapshot took
+            delay = max(0.0, interval_secs - (time.monotonic() - started))
+
+
+def start_sampler(interval_ms: float = 1000, capacity: int = DEFAULT_CAPACITY,
+                  sections: Optional[Iterable[str]] = None,
+                  options: Optional[Dict[str, Dict[str, Any]]] = None) -> None:
+    """Snapshot sections every interval_ms on a background thread; a no-op if running.
+
+    sections and options are as for snapshot() (default: every section).
+    Once capacity samples are held the oldest is overwritten, so memory stays
+    bounded however rarely get_samples() is called. Starting clears the
+    previous run's history, CPU leaderboard, and thermal readings. Raises
+    ValueError for bad arguments.
+    """
+    from pulse import core
+
+    global _thread, _samples
+    with _control_lock:
+        if _thread is not None and _thread.is_alive():
+            return
+        validation.positive('interval_ms', interval_ms)
+        validation.at_least('capacity', capacity, 1)
+        sections = list(core.SNAPSHOT_SECTIONS) if sections is None else list(sections)
+        options = options or {}
+        core.check_sections(sections, options)
+
+        with _samples_lock:
+            _samples = deque(maxlen=capacity)
+            leaderboard.BOARD.clear()
+            thermal.clear(capacity)
+            _status.update(interval_ms=interval_ms, capacity=capacity, sections=sections,
+                           collected=0, dropped=0, last_error=None)
+        _stop.clear()
+        _thread = threading.Thread(
+            target=_run, args=(interval_ms / 1000, sections, options),
+            name='pulse-sampler', daemon=True,
+        )
+        _thread.start()
+
+
+def stop_sampler(timeout: Optional[float] = None) -> None:
+    """Stop the sampler thread and wait for it to exit; the history is kept."""
+    global _thread
+    with _control_lock:
+        _stop.set()
+        if _thread is not None:
+            _thread.join(timeout)
+            _thread = None
+
+
+def get_samples(since_timestamp: Optional[float] = None) -> List[Dict[str, Any]]:
+    """Samples held in the history, oldest first.
+
+    With since_timestamp only samples taken after it are returned, so passing
+    the last timestamp seen pulls each sample exactly once.
+    """
+    with _samples_lock:
+        samples = list(_samples)
+    if since_timestamp is not None:
+        samples = [s for s in samples if s['timestamp'] > since_timestamp]
+    return samples
+
+
+def get_cpu_history_buffer(last: Optional[int] = None) -> Tuple[memoryview, memoryview]:
+    """The held CPU samples as contiguous arrays for plotting, oldest first.
+
+    Returns (cpu, timestamps): cpu is a C-contiguous float32 view shaped
+    [samples x cores], timestamps a float64 view with one entry per row.
+    Both support the buffer protocol, so numpy.asarray() wraps them without
+    copying. last keeps only the newest samples. Samples whose core count
+    differs from the newest one's are left out; with none held both views
+    are empty and one-dimensional.
+    """
+    validation.optional_at_least('last', last, 1)
+    cpu = array('f')
+    timestamps = array('d')
+    cores = 0
+    # Copied out under the lock so a frame never mixes two histories
+    with _samples_lock:
+        held = [s for s in _samples if 'cpu' in s]
+        if held:
+            cores = len(held[-1]['cpu'])
+            held = [s for s in held if len(s['cpu']) == cores]
+            for sample in held[-last:] if last is not None else held:
+                cpu.extend(sample['cpu'])
+                timestamps.append(sample['timestamp'])
+    if not timestamps or not cores:
+        return memoryview(array('f')), memoryview(array('d'))
+    return memoryview(cpu).cast('B').cast('f', (len(timestamps), cores)), memoryview(timestamps)
+
+
+def sampler_status() -> Dict[str, Any]:
+    """Whether the sampler runs, its configuration, and its sample counters.
+
+    "dropped" counts samples overwritten because the history was full;
+    "last_error" is the most recent snapshot failure, if any.
+    """
+    with _samples_lock:
+        status = dict(_status, sections=list(_status['sections']))
+        held = len(_samples)
+        oldest = _samples[0]['timestamp'] if _samples else None
+        newest = _samples[-1]['timestamp'] if _samples else None
+    return {
+        'running': _thread is not None and _thread.is_alive(),
+        **status,
+        'samples': held,
+        'oldest_timestamp': oldest,
+        'newest_timestamp': newest,
+    }
+
+
+atexit.register(stop_sampler)
@@ -0,0 +1,221 @@
+"""
+Pulse Disk Scanner
+Recursive directory sizing for "what's eating my disk" views.
+
+Walks use os.scandir, which drops the GIL inside its directory syscalls, so the
+per-child walks dispatched to the thread pool overlap their I/O.
+"""
+import heapq
+import os
+import threading
+import time
+from concurrent.futures import ThreadPoolExecutor
+from typing import Any, Callable, Dict, List, Optional, Set, Tuple
+
+from pulse import validation
+
+_MAX_WORKERS = min(32, (os.cpu_count() or 1) + 4)
+
+
+class _ScanContext:
+    """State shared by every worker of one scan."""
+
+    def __init__(self, follow_symlinks: bool, max_depth: Optional[int], timeout_secs: Optional[float],
+                 clock: Callable[[], float] = time.monotonic):
+        validation.optional_at_least('max_depth', max_depth, 0)
+        validation.optional_at_least('timeout_secs', timeout_secs, 0)
+        self.follow_symlinks = follow_symlinks
+        self.max_depth = max_depth
+        self.clock = clock
+        self.deadline = clock() + timeout_secs if timeout_secs is not None else None
+        self.timed_out = False
+        self._lock = threading.Lock()
+        self._seen_inodes: Set[Tuple[int, int]] = set()
+        self._seen_dirs: Set[Tuple[int, int]] = set()
+
+    def expired(self) -> bool:
+        # Reaching the deadline counts, so a timeout_secs of 0 never starts a walk
+        if not self.timed_out and self.deadline is not None and self.clock() >= self.deadline:
+            self.timed_out = True
+        return self.timed_out
+
+    def first_link(self, st: os.stat_result) -> bool:
+        """Whether this is the first sighting of a (possibly hard-linked) file."""
+        # Windows DirEntry stats report st_nlink == 0; treat them as single links
+        if st.st_nlink <= 1:
+            return True
+        key = (st.st_dev, st.st_ino)
+        with self._lock:
+            if key in self._seen_inodes:
+                return False
+            self._seen_inodes.add(key)
+            return True
+
+    def enter_dir(self, st: os.stat_result) -> bool:
+        """Guard against symlink cycles when following links."""
+        if not self.follow_symlinks or not st.st_ino:
+            return True
+        key = (st.st_dev, st.st_ino)
+        with self._lock:
+            if key in self._seen_dirs:
+                return False
+            self._seen_dirs.add(key)
+            return True
+
+
+def _walk(ctx: _ScanContext, root: str, depth: int) -> Dict[str, int]:
+    """Total a subtree iteratively; depth is how far root sits below the scan root."""
+    totals = {'size': 0, 'files': 0, 'dirs': 0, 'errors': 0}
+    stack = [(root, depth)]
+    while stack:
+        if ctx.expired():
+            break
+        path, level = stack.pop()
+        try:
+            with os.scandir(path) as it:
+                entries = list(it)
+        except OSError:
+            totals['errors'] += 1
+            continue
+
+        for entry in entries:
+            try:
+                st = entry.stat(follow_symlinks=ctx.follow_symlinks)
+                is_dir = entry.is_dir(follow_symlinks=ctx.follow_symlinks)
+            except OSError:
+                totals['errors'] += 1
+                continue
+
+            if is_dir:
+                totals['dirs'] += 1
+                if (ctx.max_depth is None or level < ctx.max_depth) and ctx.enter_dir(st):
+                    stack.append((entry.path, level + 1))
+            else:
+                totals['files'] += 1
+                if ctx.first_link(st):
+                    totals['size'] += st.st_size
+    return totals
+
+
+def scan_directory(path: str, max_depth: Optional[int] = None, follow_symlinks: bool = False,
+                   timeout_secs: Optional[float] = None) -> Dict[str, Any]:
+    """Get the recursive apparent size of a directory and of each immediate child.
+
+    Hard links are counted once, unreadable directories are skipped and tallied in
+    "errors", and max_depth limits how many directory levels below path are read
+    (0 counts only the files directly inside it). When timeout_secs elapses the
+    partial totals are returned with "timed_out" set.
+    """
+    validation.not_empty('path', path)
+    resolved = os.path.realpath(path)
+    if not os.path.isdir(resolved):
+        raise NotADirectoryError(f"Not a directory: '{path}'")
+
+    ctx = _ScanContext(follow_symlinks, max_depth, timeout_secs)
+    children: List[Dict[str, Any]] = []
+    totals = {'files': 0, 'dirs': 0, 'errors': 0}
+
+    with os.scandir(resolved) as it:
+        entries = list(it)
+
+    subdirs = []
+    for entry in entries:
+        try:
+            st = entry.stat(follow_symlinks=follow_symlinks)
+            is_dir = entry.is_dir(follow_symlinks=follow_symlinks)
+        except OSError:
+            totals['errors'] += 1
+            continue
+        if is_dir:
+            totals['dirs'] += 1
+            child = {'name': entry.name, 'path': entry.path, 'size': 0, 'is_dir': True}
+            if (max_depth is None or max_depth > 0) and ctx.enter_dir(st):
+                subdirs.append(child)
+        else:
+            totals['files'] += 1
+            size = st.st_size if ctx.first_link(st) else 0
+            child = {'name': entry.name, 'path': entry.path, 'size': size, 'is_dir': False}
+        children.append(child)
+
+    if subdirs:
+        with ThreadPoolExecutor(max_workers=min(_MAX_WORKERS, len(subdirs))) as pool:
+            results = pool.map(lambda child: _walk(ctx, child['path'], 1), subdirs)
+            for child, result in zip(subdirs, results):
+                child['size'] = result['size']
+                for key in totals:
+                    totals[key] += result[key]
+
+    children.sort(key=lambda c: c['size'], reverse=True)
+    return {
+        'path': resolved,
+        'total_size': sum(c['size'] for c in children),
+        'files': totals['files'],
+        'dirs': totals['dirs'],
+        'errors': totals['errors'],
+        'timed_out': ctx.timed_out,
+        'children': children,
+    }
+
+
+def find_largest_files(path: str, n: int = 20, min_size: Optional[int] = None,
+                       follow_symlinks: bool = False,
+                       timeout_secs: Optional[float] = None) -> Dict[str, Any]:
+    """Get the n largest files under path, largest first.
+
+    A bounded min-heap keeps memory at O(n) however big the tree is. Unreadable
+    directories are skipped and counted in "errors"; when timeout_secs elapses the
+    best files found so far are returned with "timed_out" set.
+    """
+    validation.at_least('n', n, 1)
+    validation.optional_at_least('min_size', min_size, 0)
+    validation.not_empty('path', path)
+    resolved = os.path.realpath(path)
+    if not os.path.isdir(resolved):
+        raise NotADirectoryError(f"Not a directory: '{path}'")
+
+    ctx = _ScanContext(follow_symlinks, None, timeout_secs)
+    threshold = min_size or 0
+    # (size, tiebreak, path, mtime); the smallest kept file sits at heap[0]
+    heap: List[Tuple[int, int, str, float]] = []
+    counter = 0
+    errors = 0
+    stack = [resolved]
+
+    while stack and n > 0:
+        if ctx.expired():
+            break
+        current = stack.pop()
+        try:
+            with os.scandir(current) as it:
+                entries = list(it)
+        except OSError:
+            errors += 1
+            continue
+
+        for entry in entries:
+            try:
+                if entry.is_dir(follow_symlinks=follow_symlinks):
+                    if ctx.enter_dir(entry.stat(follow_symlinks=True)):
+                        stack.append(entry.path)
+                    continue
+                if not entry.is_file(follow_symlinks=follow_symlinks):
+                    continue
+                st = entry.stat(follow_symlinks=follow_symlinks)
+            except OSError:
+                errors += 1
+                continue
+
+            if st.st_size < threshold:
+                continue
+            counter += 1
+            item = (st.st_size, counter, entry.path, st.st_mtime)
+            if len(heap) < n:
+                heapq.heappush(heap, item)
+            elif st.st_size > heap[0][0]:
+                heapq.heapreplace(heap, item)
+
+    files = [
+        {'path': file_path, 'size': size, 'modified': mtime}
+        for size, _, file_path, mtime in sorted(heap, key=lambda item: (-item[0], item[1]))
+    ]
+    return {'files': files, 'errors': errors, 'timed_out': ctx.timed_out}
@@ -0,0 +1,153 @@
+"""
+Pulse Scheduler Statistics
+Run-queue length and how long runnable tasks wait for a CPU.
+
+Load average smooths over minutes; these are the raw figures behind it.
+procs_running and procs_blocked are instantaneous, everything else is a
+cumulative counter reported together with its growth since the previous call.
+"""
+import os
+import re
+from typing import Any, Dict, List, Optional
+
+from pulse import log
+from pulse.collect import counter_delta
+from pulse.direct_os import LINUX
+from pulse.state import active_state
+
+PROC_STAT = '/proc/stat'
+PROC_SCHEDSTAT = '/proc/schedstat'
+PROC_SELF_SCHEDSTAT = '/proc/self/schedstat'
+# Where CONFIG_SCHED_DEBUG kernels dump the run queues: debugfs since 5.13, /proc before
+SCHED_DEBUG_PATHS = ('/sys/kernel/debug/sched/debug', '/proc/sched_debug')
+
+# /proc/stat lines -> keys; procs_* are current counts, the rest cumulative since boot
+_STAT_KEYS = {
+    'procs_running': 'procs_running',
+    'procs_blocked': 'procs_blocked',
+    'ctxt': 'context_switches',
+    'processes': 'forks',
+}
+
+_CPU_LINE = re.compile(r'^cpu\d+$')
+
+# Cumulative counters behind each CPU's (and a task's) schedstat
+SCHEDSTAT_KEYS = ('running_ns', 'waiting_ns', 'timeslices')
+
+
+def parse_stat_sched(text: str) -> Dict[str, int]:
+    """procs_running, procs_blocked, context_switches, and forks from /proc/stat."""
+    stats = {}
+    for line in text.splitlines():
+        name, _, value = line.partition(' ')
+        if name in _STAT_KEYS:
+            stats[_STAT_KEYS[name]] = int(value)
+    return stats
+
+
+def parse_schedstat(text: str) -> List[Dict[str, int]]:
+    """Per-CPU {cpu, running_ns, waiting_ns, timeslices} from /proc/schedstat.
+
+    Every schedstat version since 2.6.x ends a cpu line with the time its
+    tasks spent running, the time runnable tasks spent waiting on its run
+    queue, and the number of timeslices run; the fields before them vary.
+    """
+    cpus = []
+    for line in text.splitlines():
+        fields = line.split()
+        if len(fields) < 4 or not _CPU_LINE.match(fields[0]):
+            continue
+        cpus.append({'cpu': int(fields[0][3:]), **dict(zip(SCHEDSTAT_KEYS, map(int, fields[-3:])))})
+    return cpus
+
+
+def parse_sched_debug(text: str) -> Dict[int, int]:
+    """Each CPU's nr_running from a sched_debug dump, keyed by CPU number.
+
+    Only the ".nr_running" directly under a "cpu#N, ... MHz" header counts;
+    the cfs_rq and rt_rq sections that follow have counters of their own.
+    """
+    running = {}
+    cpu = None
+    for line in text.splitlines():
+        if line and not line[0].isspace():
+            header = line.split(',', 1)[0]
+            cpu = int(header[4:]) if header.startswith('cpu#') and header[4:].isdigit() else None
+            continue
+        name, sep, value = line.partition(':')
+        if cpu is not None and sep and name.strip() == '.nr_running' and cpu not in running:
+            try:
+                running[cpu] = int(value)
+            except ValueError:
+                continue
+    return running
+
+
+def parse_task_schedstat(text: str) -> Dict[str, int]:
+    """{running_ns, waiting_ns, timeslices} from /proc/<pid>/schedstat."""
+    return dict(zip(SCHEDSTAT_KEYS, map(int, text.split()[:3])))
+
+
+def _deltas(current: Dict[str, int], previous: Optional[Dict[str, int]], keys: Any) -> Dict[str, int]:
+    return {key: counter_delta(current[key], previous[key]) if previous else 0 for key in keys}
+
+
+def _read(path: str) -> Optional[str]:
+    try:
+        with open(path, 'r') as f:
+            return f.read()
+    except OSError as e:
+        log.read_failed(path, e)
+        return None
+
+
+if LINUX:
+    def get_scheduler_stats(include_self: bool = False) -> Dict[str, Any]:
+        """Get run-queue length, context switches, and per-CPU run-queue wait time.
+
+        procs_running and procs_blocked are tasks runnable and in
+        uninterruptible sleep right now. context_switches and forks, and each
+        CPU's running_ns, waiting_ns, and timeslices, are cumulative; every
+        group's "deltas" holds their growth since the previous call (zeros on
+        the first). A CPU's waiting_ns growing close to wall time means tasks
+        queue for it, and its nr_running is the length of its run queue right
+        now, read from sched_debug (None where that isn't readable, usually
+        for want of root or CONFIG_SCHED_DEBUG). "cpus" is absent on kernels
+        without CONFIG_SCHEDSTATS. include_self adds the calling process's own
+        schedstat as "self".
+        """
+        text = _read(PROC_STAT)
+        if text is None:
+            return {}
+        stats: Dict[str, Any] = parse_stat_sched(text)
+        schedstat = _read(PROC_SCHEDSTAT)
+        cpus = parse_schedstat(schedstat) if schedstat is not None else None
+        queues: Dict[int, int] = {}
+        if cpus is not None:
+            debug = next((_read(path) for path in SCHED_DEBUG_PATHS if os.path.exists(path)), None)
+            queues = parse_sched_debug(debug) if debug else {}
+        own = _read(PROC_SELF_SCHEDSTAT) if include_self else None
+        task = parse_task_schedstat(own) if own else None
+
+        state = active_state()
+        with state.lock('cpu'):
+            previous = state.sched_counters or {}
+            state.sched_counters = {
+                'stat': dict(stats), 'cpus': {c['cpu']: c for c in cpus or ()},
+                'self': task if task is not None else previous.get('self'),
+            }
+
+        stats['deltas'] = _deltas(stats, previous.get('stat'), ('context_switches', 'forks'))
+        if cpus is not None:
+            stats['cpus'] = [{**cpu, 'nr_running': queues.get(cpu['cpu']),
+                              'deltas': _deltas(cpu, previous.get('cpus', {}).get(cpu['cpu']), SCHEDSTAT_KEYS)}
+                             for cpu in cpus]
+        if task is not None:
+            stats['self'] = {'pid': os.getpid(), **task,
+                             'deltas': _deltas(task, previous.get('self'), SCHEDSTAT_KEYS)}
+        return stats
+
+else:
+    def get_scheduler_stats(include_self: bool = False) -> Dict[str, Any]:
+        """Scheduler statistics come from Linux's /proc; always empty elsewhere."""
+        return {}
@@ -0,0 +1,342 @@
+"""
+Pulse Sensors
+Fans, thermal zones, CPU throttling, and the hwmon traversal they share.
+
+Linux exposes every sensor chip as /sys/class/hwmon/hwmonN with channel files
+named <type><index>_<item> (fan1_input, temp2_label); the helpers here walk that
+layout once for all sensor types.
+"""
+import glob
+import os
+import re
+from typing import Any, Dict, List, Optional
+
+from pulse import environment, log
+from pulse.capabilities import requires
+from pulse.direct_os import LINUX, MACOS
+from pulse.errors import Unsupported
+from pulse.state import active_state
+
+SYS_CLASS_HWMON = '/sys/class/hwmon'
+SYS_CLASS_THERMAL = '/sys/class/thermal'
+SYS_DEVICES_CPU = '/sys/devices/system/cpu'
+
+# Cooling device types that throttle the CPU itself (cpufreq/intel_powerclamp)
+_CPU_COOLING_TYPES = ('Processor', 'intel_powerclamp', 'cpufreq')
+
+_CHANNEL_FILE = re.compile(r'^([a-z]+)(\d+)_([a-z_]+)$')
+
+
+def _read_attr(path: str) -> Optional[str]:
+    try:
+        with open(path, 'r') as f:
+            return f.read().strip() or None
+    except OSError as e:
+        log.read_failed(path, e)
+        return None
+
+
+def _read_number(path: str) -> Optional[int]:
+    value = _read_attr(path)
+    try:
+        return int(value) if value is not None else None
+    except ValueError:
+        return None
+
+
+def numbered_paths(pattern: str) -> List[str]:
+    """Paths matching a glob like hwmon*, sorted by their numeric suffix."""
+    return sorted(glob.glob(pattern), key=lambda p: int(re.sub(r'\D', '', os.path.basename(p)) or 0))
+
+
+def hwmon_chips() -> List[str]:
+    """Every hwmon chip directory, in index order."""
+    return numbered_paths(os.path.join(SYS_CLASS_HWMON, 'hwmon*'))
+
+
+def read_channels(chip: str, sensor_type: str) -> List[Dict[str, Any]]:
+    """Channels of one type ("fan", "temp", "in", ...) on a chip, by index.
+
+    Each entry has the channel index, its label (or None), and every numeric
+    item the driver exposes (input, min, max, crit, ...) as raw integers.
+    Channels without an _input file are skipped.
+    """
+    try:
+        names = os.listdir(chip)
+    except OSError:
+        return []
+
+    channels: Dict[int, Dict[str, Any]] = {}
+    for name in names:
+        match = _CHANNEL_FILE.match(name)
+        if not match or match.group(1) != sensor_type:
+            continue
+        index, item = int(match.group(2)), match.group(3)
+        channel = channels.setdefault(index, {'index': index, 'label': None})
+        path = os.path.join(chip, name)
+        if item == 'label':
+            channel['label'] = _read_attr(path)
+        else:
+            channel[item] = _read_number(path)
+    return [channels[i] for i in sorted(channels) if channels[i].get('input') is not None]
+
+
+if LINUX:
+    @requires('fans')
+    def get_fans() -> List[Dict[str, Any]]:
+        """Get every fan hwmon reports, with its label and RPM limits where known.
+
+        Unlabelled fans are named after their chip ("thinkpad fan1"). Under
+        WSL the list is empty: the VM's sensors don't measure the host.
+        """
+        if environment.running_under_wsl():
+            return []
+        fans = []
+        for chip in hwmon_chips():
+            chip_name = _read_attr(os.path.join(chip, 'name')) or os.path.basename(chip)
+            for channel in read_channels(chip, 'fan'):
+                fans.append({
+                    'label': channel['label'] or f"{chip_name} fan{channel['index']}",
+                    'rpm': channel['input'],
+                    'min_rpm': channel.get('min'),
+                    'max_rpm': channel.get('max'),
+                })
+        return fans
+
+elif MACOS:
+    import ctypes
+    import ctypes.util
+    import struct
+
+    _KERNEL_INDEX_SMC = 2
+    _SMC_CMD_READ_BYTES = 5
+    _SMC_CMD_READ_KEYINFO = 9
+
+    class _SMCVersion(ctypes.Structure):
+        _fields_ = [('major', ctypes.c_char), ('minor', ctypes.c_char), ('build', ctypes.c_char),
+                    ('reserved', ctypes.c_char), ('release', ctypes.c_uint16)]
+
+    class _SMCPLimitData(ctypes.Structure):
+        _fields_ = [('version', ctypes.c_uint16), ('length', ctypes.c_uint16),
+                    ('cpuPLimit', ctypes.c_uint32), ('gpuPLimit', ctypes.c_uint32),
+                    ('memPLimit', ctypes.c_uint32)]
+
+    class _SMCKeyInfo(ctypes.Structure):
+        _fields_ = [('dataSize', ctypes.c_uint32), ('dataType', ctypes.c_uint32),
+                    ('dataAttributes', ctypes.c_char)]
+
+    class _SMCKeyData(ctypes.Structure):
+        _fields_ = [('key', ctypes.c_uint32), ('vers', _SMCVersion), ('pLimitData', _SMCPLimitData),
+                    ('keyInfo', _SMCKeyInfo), ('result', ctypes.c_char), ('status', ctypes.c_char),
+                    ('data8', ctypes.c_char), ('data32', ctypes.c_uint32),
+                    ('bytes', ctypes.c_ubyte * 32)]
+
+    def _fourcc(code: str) -> int:
+        return struct.unpack('>I', code.encode('ascii'))[0]
+
+    def decode_smc_value(data_type: str, raw: bytes) -> Optional[float]:
+        """Decode the SMC numeric encodings fan keys use."""
+        if data_type == 'fpe2' and len(raw) >= 2:
+            # Unsigned big-endian fixed point with two fractional bits (Intel Macs)
+            return struct.unpack('>H', raw[:2])[0] / 4.0
+        if data_type == 'flt ' and len(raw) >= 4:
+            # Native little-endian float (Apple silicon)
+            return struct.unpack('<f', raw[:4])[0]
+        if data_type == 'ui8 ' and raw:
+            return float(raw[0])
+        return None
+
+    class _SMC:
+        """An open connection to the AppleSMC service."""
+
+        def __init__(self):
+            self._iokit = ctypes.cdll.LoadLibrary(ctypes.util.find_library('IOKit'))
+            self._iokit.IOServiceMatching.restype = ctypes.c_void_p
+            self._iokit.IOServiceGetMatchingService.argtypes = [ctypes.c_uint32, ctypes.c_void_p]
+            self._iokit.IOServiceGetMatchingService.restype = ctypes.c_uint32
+            libc = ctypes.CDLL(ctypes.util.find_library('c'))
+            service = self._iokit.IOServiceGetMatchingService(
+                0, self._iokit.IOServiceMatching(b'AppleSMC'))
+            if not service:
+                raise OSError('AppleSMC service not found')
+            self._conn = ctypes.c_uint32()
+            task = ctypes.c_uint32.in_dll(libc, 'mach_task_self_')
+            status = self._iokit.IOServiceOpen(service, task, 0, ctypes.byref(self._conn))
+            self._iokit.IOObjectRelease(service)
+            if status != 0:
+                raise OSError(f'IOServiceOpen failed ({status:#x})')
+
+        def _call(self, request: '_SMCKeyData') -> '_SMCKeyData':
+            response = _SMCKeyData()
+            size = ctypes.c_size_t(ctypes.sizeof(response))
+            status = self._iokit.IOConnectCallStructMethod(
+                self._conn, _KERNEL_INDEX_SMC, ctypes.byref(request), ctypes.sizeof(request),
+                ctypes.byref(response), ctypes.byref(size))
+            if status != 0 or response.result != b'\0':
+                raise OSError(f'SMC call failed ({status:#x})')
+            return response
+
+        def read(self, key: str) -> Optional[float]:
+            request = _SMCKeyData(key=_fourcc(key), data8=bytes([_SMC_CMD_READ_KEYINFO]))
+            try:
+                info = self._call(request).keyInfo
+                request.keyInfo.dataSize = info.dataSize
+                request.data8 = bytes([_SMC_CMD_READ_BYTES])
+                response = self._call(request)
+            except OSError:
+                return None
+            data_type = struct.pack('>I', info.dataType).decode('ascii', 'replace')
+            return decode_smc_value(data_type, bytes(response.bytes[:info.dataSize]))
+
+        def close(self) -> None:
+            self._iokit.IOServiceClose(self._conn)
+
+    @requires('fans')
+    def get_fans() -> List[Dict[str, Any]]:
+        """Get fan speeds from the SMC (F<n>Ac actual, F<n>Mn/F<n>Mx limits).
+
+        Returns an empty list when the SMC can't be opened, as in some sandboxes.
+        """
+        try:
+            smc = _SMC()
+        except OSError:
+            return []
+        try:
+            count = smc.read('FNum')
+            fans = []
+            for i in range(int(count or 0)):
+                rpm = smc.read(f'F{i}Ac')
+                if rpm is None:
+                    continue
+                low, high = smc.read(f'F{i}Mn'), smc.read(f'F{i}Mx')
+                fans.append({
+                    'label': f'Fan {i}',
+                    'rpm': int(rpm),
+                    'min_rpm': int(low) if low is not None else None,
+                    'max_rpm': int(high) if high is not None else None,
+                })
+            return fans
+        finally:
+            smc.close()
+
+else:
+    @requires('fans')
+    def get_fans() -> List[Dict[str, Any]]:
+        """Fan sensors aren't exposed on this platform."""
+        raise Unsupported('get_fans is only supported on Linux and macOS')
+
+
+def read_thermal_zones() -> List[Dict[str, Any]]:
+    """Every thermal zone's type and temperature (°C) from /sys/class/thermal."""
+    zones = []
+    for path in numbered_paths(os.path.join(SYS_CLASS_THERMAL, 'thermal_zone*')):
+        milli = _read_number(os.path.join(path, 'temp'))
+        zones.append({
+            'name': os.path.basename(path),
+            'type': _read_attr(os.path.join(path, 'type')),
+            # Disabled zones fail the temp read with EINVAL/ENODATA
+            'temperature_c': milli / 1000 if milli is not None else None,
+        })
+    return zones
+
+
+def read_cooling_devices() -> List[Dict[str, Any]]:
+    """Every cooling device's type and current/maximum state."""
+    devices = []
+    for path in numbered_paths(os.path.join(SYS_CLASS_THERMAL, 'cooling_device*')):
+        devices.append({
+            'name': os.path.basename(path),
+            'type': _read_attr(os.path.join(path, 'type')),
+            'cur_state': _read_number(os.path.join(path, 'cur_state')),
+            'max_state': _read_number(os.path.join(path, 'max_state')),
+        })
+    return devices
+
+
+def read_throttle_counts() -> Optional[Dict[str, int]]:
+    """Summed core/package throttle event counts across CPUs (x86 only).
+
+    Package counters repeat on every CPU of a package, so they are taken once
+    per physical package id. None when the kernel exposes no counters.
+    """
+    core_total = 0
+    packages: Dict[str, int] = {}
+    found = False
+    for cpu in glob.glob(os.path.join(SYS_DEVICES_CPU, 'cpu[0-9]*')):
+        base = os.path.join(cpu, 'thermal_throttle')
+        core = _read_number(os.path.join(base, 'core_throttle_count'))
+        if core is None:
+            continue
+        found = True
+        core_total += core
+        package = _read_number(os.path.join(base, 'package_throttle_count'))
+        if package is not None:
+            package_id = _read_attr(os.path.join(cpu, 'topology', 'physical_package_id')) or '0'
+            packages[package_id] = package
+    if not found:
+        return None
+    return {'core_throttle_count': core_total, 'package_throttle_count': sum(packages.values())}
+
+
+def _throttled_since(previous: Optional[Dict[str, int]], current: Optional[Dict[str, int]]) -> bool:
+    if previous is None or current is None:
+        return False
+    return any(current[key] > previous[key] for key in current)
+
+
+if LINUX:
+    @requires('temperatures')
+    def get_thermal_status() -> Dict[str, Any]:
+        """Get thermal zones, cooling devices, and whether the CPU is being throttled.
+
+        is_throttling is True when a CPU cooling device is engaged or the x86
+        throttle counters grew since the previous call; the first call can only
+        see the former. Counts are cumulative since boot, None off x86. Under
+        WSL there are no zones or counts: the VM's thermal class is synthetic.
+        """
+        if environment.running_under_wsl():
+            return {'zones': [], 'cooling_devices': [], 'core_throttle_count': None,
+                    'package_throttle_count': None, 'is_throttling': False}
+        cooling = read_cooling_devices()
+        state = active_state()
+        with state.lock('thermal'):
+            counts = read_throttle_counts()
+            previous = state.thermal_throttle
+            state.thermal_throttle = counts
+
+        cooling_engaged = any(
+            d['type'] in _CPU_COOLING_TYPES and d['cur_state'] for d in cooling
+        )
+        return {
+            'zones': read_thermal_zones(),
+            'cooling_devices': cooling,
+            'core_throttle_count': counts['core_throttle_count'] if counts else None,
+            'package_throttle_count': counts['package_throttle_count'] if counts else None,
+            'is_throttling': cooling_engaged or _throttled_since(previous, counts),
+        }
+
+else:
+    @requires('temperatures')
+    def get_thermal_status() -> Dict[str, Any]:
+        """Get whatever temperatures psutil reports; throttling isn't observable here."""
+        zones = []
+        try:
+            import psutil
+
+            readings = psutil.sensors_temperatures() if hasattr(psutil, 'sensors_temperatures') else {}
+        except (ImportError, OSError, RuntimeError):
+            readings = {}
+        for chip, entries in (readings or {}).items():
+            for entry in entries:
+                zones.append({
+                    'name': chip,
+                    'type': entry.label or chip,
+                    'temperature_c': entry.current,
+                })
+        return {
+            'zones': zones,
+            'cooling_devices': [],
+            'core_throttle_count': None,
+            'package_throttle_count': None,
+            'is_throttling': None,
+        }
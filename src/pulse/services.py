@@ -0,0 +1,355 @@
+"""
+Pulse Services
+systemd unit state joined with each unit's cgroup accounting, and Windows
+services from the Service Control Manager joined with the process table.
+
+get_boot_metrics() also asks systemd, where it is the init system, how long
+each phase of the last boot took.
+"""
+import ctypes
+import fnmatch
+import json
+import subprocess
+from typing import Any, Dict, List, Optional
+
+from pulse import cgroups, direct_os, system, validation
+from pulse.capabilities import requires, supported
+from pulse.direct_os import LINUX, WINDOWS
+from pulse.errors import PermissionDenied, PulseError, ServiceNotFound, Timeout, Unsupported
+
+# sd_booted(): systemd creates this directory only when it is PID 1
+RUN_SYSTEMD_SYSTEM = '/run/systemd/system'
+
+_SHOW_PROPERTIES = ('Id', 'LoadState', 'ActiveState', 'SubState', 'MainPID',
+                    'ControlGroup', 'MemoryCurrent', 'CPUUsageNSec', 'Description')
+
+# systemctl show prints this (2^64 - 1) for accounting that is switched off
+_UNSET = '18446744073709551615'
+
+_TIMEOUT_SECS = 5.0
+
+# The manager's boot timestamps, microseconds of CLOCK_MONOTONIC. Firmware and
+# loader count back from the kernel's start, which is 0 on this clock; a
+# phase that didn't happen (no initrd, no EFI loader reporting) reads 0.
+_BOOT_PROPERTIES = ('FirmwareTimestampMonotonic', 'LoaderTimestampMonotonic',
+                    'InitRDTimestampMonotonic', 'UserspaceTimestampMonotonic',
+                    'FinishTimestampMonotonic')
+
+
+def parse_systemctl_show(text: str) -> List[Dict[str, str]]:
+    """Parse `systemctl show` output; units are blank-line separated KEY=value blocks."""
+    units = []
+    current: Dict[str, str] = {}
+    for line in text.splitlines():
+        if not line.strip():
+            if current:
+                units.append(current)
+                current = {}
+            continue
+        key, sep, value = line.partition('=')
+        if sep:
+            current[key] = value
+    if current:
+        units.append(current)
+    return units
+
+
+def _show_int(value: Optional[str]) -> Optional[int]:
+    if not value or value == _UNSET:
+        return None
+    try:
+        return int(value)
+    except ValueError:
+        return None
+
+
+def service_entry(props: Dict[str, str]) -> Dict[str, Any]:
+    """One unit's state, main pid, and cgroup CPU/memory.
+
+    The cgroup files are read directly so memory.max is available too;
+    systemd's own counters fill in when the cgroup can't be read.
+    """
+    cgroup_path = props.get('ControlGroup') or None
+    usage = cgroups.read_usage(cgroups.cgroup_dir(cgroup_path)) if cgroup_path else {}
+    cpu_usec = usage.get('cpu_usage_usec')
+    if cpu_usec is None:
+        cpu_nsec = _show_int(props.get('CPUUsageNSec'))
+        cpu_usec = cpu_nsec // 1000 if cpu_nsec is not None else None
+    memory = usage.get('memory_current')
+    if memory is None:
+        memory = _show_int(props.get('MemoryCurrent'))
+    main_pid = _show_int(props.get('MainPID'))
+    return {
+        'name': props.get('Id'),
+        'description': props.get('Description') or None,
+        'load_state': props.get('LoadState'),
+        'active_state': props.get('ActiveState'),
+        'sub_state': props.get('SubState'),
+        # MainPID=0 means the unit has no running main process
+        'main_pid': main_pid or None,
+        'cgroup': cgroup_path,
+        'memory_current': memory,
+        'memory_max': usage.get('memory_max'),
+        'cpu_usage_usec': cpu_usec,
+        'cpu_percent': cgroups.cpu_percent(cgroup_path or props.get('Id', ''), cpu_usec),
+    }
+
+
+def _usecs(props: Dict[str, str], key: str) -> int:
+    return _show_int(props.get(key)) or 0
+
+
+def _secs(usecs: int) -> float:
+    return usecs / 1e6
+
+
+def boot_phases(props: Dict[str, str]) -> Dict[str, Any]:
+    """Seconds spent in firmware, loader, kernel, initrd, and userspace, as systemd-analyze splits them.
+
+    props are the manager's _BOOT_PROPERTIES from `systemctl show`. A phase
+    the machine didn't go through is None, and so are userspace_secs and
+    total_secs while boot hasn't finished (finished is False).
+    """
+    firmware, loader, initrd, userspace, finish = (_usecs(props, key) for key in _BOOT_PROPERTIES)
+    return {
+        'firmware_secs': _secs(firmware - loader) if firmware else None,
+        'loader_secs': _secs(loader) if loader else None,
+        # The kernel runs until it hands over to the initrd, or straight to userspace
+        'kernel_secs': _secs(initrd or userspace) if userspace else None,
+        'initrd_secs': _secs(userspace - initrd) if initrd and userspace else None,
+        'userspace_secs': _secs(finish - userspace) if finish and userspace else None,
+        'total_secs': _secs(firmware + finish) if finish else None,
+        'finished': finish > 0,
+    }
+
+
+def parse_activating_units(text: str) -> List[str]:
+    """Unit names from `systemctl list-units --state=activating --output=json`."""
+    return sorted(u['unit'] for u in json.loads(text or '[]'))
+
+
+def _unit_name(name: str) -> str:
+    return name if '.' in name else f'{name}.service'
+
+
+if LINUX:
+    def _systemctl(*args: str) -> str:
+        try:
+            result = subprocess.run(['systemctl', '--no-pager', *args],
+                                    capture_output=True, text=True, timeout=_TIMEOUT_SECS)
+        except subprocess.TimeoutExpired:
+            raise Timeout(f'systemctl {args[0]} took longer than {_TIMEOUT_SECS:g}s') from None
+        if result.returncode != 0:
+            raise PulseError(f"systemctl {args[0]} failed: {result.stderr.strip()}")
+        return result.stdout
+
+    def _show(units: List[str]) -> List[Dict[str, Any]]:
+        if not units:
+            return []
+        text = _systemctl('show', f"--property={','.join(_SHOW_PROPERTIES)}", '--', *units)
+        return [service_entry(props) for props in parse_systemctl_show(text)]
+
+    @requires('services')
+    def list_services(pattern: Optional[str] = None) -> List[Dict[str, Any]]:
+        """Get every loaded service unit, optionally filtered by a glob like "nginx*".
+
+        Raises Unsupported when systemd isn't the init system.
+        """
+        validation.not_empty('pattern', pattern)
+        listed = json.loads(_systemctl('list-units', '--type=service', '--all', '--output=json') or '[]')
+        names = [u['unit'] for u in listed if pattern is None or fnmatch.fnmatch(u['unit'], pattern)]
+        return _show(sorted(names))
+
+    @requires('services')
+    def get_service(name: str) -> Dict[str, Any]:
+        """Get one unit's state; "nginx" means "nginx.service".
+
+        Raises ServiceNotFound (a KeyError) for units systemd doesn't know
+        and Unsupported when systemd isn't the init system.
+        """
+        validation.not_empty('name', name)
+        entries = _show([_unit_name(name)])
+        if not entries or entries[0]['load_state'] == 'not-found':
+            raise ServiceNotFound(f'systemd has no unit {_unit_name(name)}')
+        return entries[0]
+
+    def _systemd_boot() -> Dict[str, Any]:
+        props = parse_systemctl_show(_systemctl('show', f"--property={','.join(_BOOT_PROPERTIES)}"))
+        activating = _systemctl('list-units', '--state=activating', '--all', '--output=json')
+        return {'systemd': boot_phases(props[0] if props else {}),
+                'activating_units': parse_activating_units(activating)}
+
+else:
+    @requires('services')
+    def list_services(pattern: Optional[str] = None) -> List[Dict[str, Any]]:
+        """systemd services only exist on Linux."""
+        raise Unsupported('Services require systemd as the init system')
+
+    @requires('services')
+    def get_service(name: str) -> Dict[str, Any]:
+        """systemd services only exist on Linux."""
+        raise Unsupported('Services require systemd as the init system')
+
+    def _systemd_boot() -> Dict[str, Any]:
+        raise Unsupported('Boot phases require systemd as the init system')
+
+
+def get_boot_metrics() -> Dict[str, Any]:
+    """Get when the system booted, how long ago, and what systemd spent on each phase.
+
+    boot_time is unix seconds and uptime_seconds the time since. Where systemd
+    is the init system, "systemd" holds boot_phases() (firmware, loader,
+    kernel, initrd, and userspace seconds and their total) and
+    "activating_units" the units still starting; elsewhere both are None.
+    """
+    info = system.get_system_info()
+    metrics: Dict[str, Any] = {'boot_time': info['boot_time'], 'uptime_seconds': info['uptime_seconds'],
+                               'systemd': None, 'activating_units': None}
+    if supported('services'):
+        metrics.update(_systemd_boot())
+    return metrics
+
+
+# SERVICE_STATUS.dwCurrentState
+_WINDOWS_STATES = {
+    1: 'stopped',
+    2: 'start_pending',
+    3: 'stop_pending',
+    4: 'running',
+    5: 'continue_pending',
+    6: 'pause_pending',
+    7: 'paused',
+}
+
+# QUERY_SERVICE_CONFIG.dwStartType
+_WINDOWS_START_TYPES = {0: 'boot', 1: 'system', 2: 'automatic', 3: 'manual', 4: 'disabled'}
+
+
+def windows_service_entry(name: str, display_name: str, state: int, start_type: Optional[int],
+                          pid: int, processes: Dict[int, Dict[str, Any]]) -> Dict[str, Any]:
+    """One SCM service joined with its hosting process's CPU and memory.
+
+    Services sharing an svchost.exe share its pid, so they report the same
+    process figures.
+    """
+    process = processes.get(pid) if pid else None
+    return {
+        'name': name,
+        'display_name': display_name or None,
+        'state': _WINDOWS_STATES.get(state, 'unknown'),
+        'start_type': _WINDOWS_START_TYPES.get(start_type) if start_type is not None else None,
+        'pid': pid or None,
+        'cpu_percent': process['cpu_percent'] if process else None,
+        'memory_info': process['memory_info'] if process else None,
+    }
+
+
+if WINDOWS:
+    from ctypes import wintypes
+
+    _SC_MANAGER_ENUMERATE_SERVICE = 0x0004
+    _SERVICE_QUERY_CONFIG = 0x0001
+    _SC_ENUM_PROCESS_INFO = 0
+    _SERVICE_WIN32 = 0x30
+    _SERVICE_STATE_ALL = 0x3
+    _ERROR_MORE_DATA = 234
+    _ERROR_INSUFFICIENT_BUFFER = 122
+
+    class _ServiceStatusProcess(ctypes.Structure):
+        _fields_ = [
+            ('dwServiceType', wintypes.DWORD),
+            ('dwCurrentState', wintypes.DWORD),
+            ('dwControlsAccepted', wintypes.DWORD),
+            ('dwWin32ExitCode', wintypes.DWORD),
+            ('dwServiceSpecificExitCode', wintypes.DWORD),
+            ('dwCheckPoint', wintypes.DWORD),
+            ('dwWaitHint', wintypes.DWORD),
+            ('dwProcessId', wintypes.DWORD),
+            ('dwServiceFlags', wintypes.DWORD),
+        ]
+
+    class _EnumServiceStatusProcess(ctypes.Structure):
+        _fields_ = [
+            ('lpServiceName', wintypes.LPWSTR),
+            ('lpDisplayName', wintypes.LPWSTR),
+            ('ServiceStatusProcess', _ServiceStatusProcess),
+        ]
+
+    class _QueryServiceConfig(ctypes.Structure):
+        _fields_ = [
+            ('dwServiceType', wintypes.DWORD),
+            ('dwStartType', wintypes.DWORD),
+            ('dwErrorControl', wintypes.DWORD),
+            ('lpBinaryPathName', wintypes.LPWSTR),
+            ('lpLoadOrderGroup', wintypes.LPWSTR),
+            ('dwTagId', wintypes.DWORD),
+            ('lpDependencies', wintypes.LPWSTR),
+            ('lpServiceStartName', wintypes.LPWSTR),
+            ('lpDisplayName', wintypes.LPWSTR),
+        ]
+
+    _advapi32 = ctypes.WinDLL('advapi32', use_last_error=True)
+    _advapi32.OpenSCManagerW.restype = wintypes.HANDLE
+    _advapi32.OpenServiceW.restype = wintypes.HANDLE
+
+    def _start_type(manager: int, name: str) -> Optional[int]:
+        # Some services' DACLs refuse SERVICE_QUERY_CONFIG to non-admins; report them without a start type
+        service = _advapi32.OpenServiceW(wintypes.HANDLE(manager), name, _SERVICE_QUERY_CONFIG)
+        if not service:
+            return None
+        try:
+            needed = wintypes.DWORD()
+            _advapi32.QueryServiceConfigW(wintypes.HANDLE(service), None, 0, ctypes.byref(needed))
+            if ctypes.get_last_error() != _ERROR_INSUFFICIENT_BUFFER or not needed.value:
+                return None
+            buffer = ctypes.create_string_buffer(needed.value)
+            if not _advapi32.QueryServiceConfigW(wintypes.HANDLE(service), buffer, needed.value,
+                                                 ctypes.byref(needed)):
+                return None
+            return _QueryServiceConfig.from_buffer(buffer).dwStartType
+        finally:
+            _advapi32.CloseServiceHandle(wintypes.HANDLE(service))
+
+    @requires('windows_services')
+    def list_windows_services() -> List[Dict[str, Any]]:
+        """Get every Win32 service's state, start type, and pid from the SCM.
+
+        Running services carry their process's cpu_percent and memory_info
+        from get_process_list(). Services whose configuration we may not
+        query are still listed, with start_type None. Raises PermissionDenied
+        only when the SCM itself refuses enumeration.
+        """
+        manager = _advapi32.OpenSCManagerW(None, None, _SC_MANAGER_ENUMERATE_SERVICE)
+        if not manager:
+            raise PermissionDenied(ctypes.get_last_error(), 'OpenSCManager refused enumeration')
+        try:
+            needed, count, resume = wintypes.DWORD(), wintypes.DWORD(), wintypes.DWORD()
+            raw: List[tuple] = []
+            buffer = ctypes.create_string_buffer(64 * 1024)
+            while True:
+                ok = _advapi32.EnumServicesStatusExW(
+                    wintypes.HANDLE(manager), _SC_ENUM_PROCESS_INFO, _SERVICE_WIN32, _SERVICE_STATE_ALL,
+                    buffer, len(buffer), ctypes.byref(needed), ctypes.byref(count), ctypes.byref(resume), None)
+                error = ctypes.get_last_error()
+                entries = ctypes.cast(buffer, ctypes.POINTER(_EnumServiceStatusProcess))
+                for i in range(count.value):
+                    status = entries[i].ServiceStatusProcess
+                    raw.append((entries[i].lpServiceName, entries[i].lpDisplayName,
+                                status.dwCurrentState, status.dwProcessId))
+                if ok or error != _ERROR_MORE_DATA:
+                    break
+                # resume carries on from where this batch stopped
+                if needed.value > len(buffer):
+                    buffer = ctypes.create_string_buffer(needed.value)
+
+            processes = {p['pid']: p for p in direct_os.get_process_list()}
+            return [windows_service_entry(name, display, state, _start_type(manager, name), pid, processes)
+                    for name, display, state, pid in raw]
+        finally:
+            _advapi32.CloseServiceHandle(wintypes.HANDLE(manager))
+
+else:
+    @requires('windows_services')
+    def list_windows_services() -> List[Dict[str, Any]]:
+        """The Service Control Manager only exists on Windows."""
+        raise Unsupported('list_windows_services is only supported on Windows')
@@ -0,0 +1,136 @@
+"""
+Pulse Snapshot Diffing
+Compact point-in-time captures and the changes between two of them.
+
+A Snapshot holds only tuples of numbers and strings, so keeping one per tick
+for change detection costs far less than a snapshot() dict.
+"""
+import time
+from typing import Any, Dict, List, Optional, Tuple
+
+from pulse import clock, direct_os, disks, netstat, processes, validation
+
+# Disks whose available space moved by less than this are left out of a diff
+DEFAULT_DISK_THRESHOLD_BYTES = 1024 ** 2
+
+
+class Snapshot:
+    """One capture of CPU, memory, interfaces, disks, and process states.
+
+    cpu is per-core percent; memory is (total, used, available); interfaces
+    are (name, bytes_recv, bytes_sent); disks are (mountpoint, total,
+    available); processes are (pid, name, status). timestamp is on the
+    set_clock() clock; monotonic, when given, times the diff interval instead.
+    """
+
+    __slots__ = ('timestamp', 'cpu', 'memory', 'interfaces', 'disks', 'processes', 'monotonic')
+
+    def __init__(self, timestamp: float, cpu: Tuple[float, ...], memory: Tuple[int, int, int],
+                 interfaces: Tuple[Tuple[str, int, int], ...],
+                 disks: Tuple[Tuple[str, Optional[int], Optional[int]], ...],
+                 processes: Tuple[Tuple[int, str, str], ...], monotonic: Optional[float] = None):
+        self.timestamp = timestamp
+        self.monotonic = monotonic
+        self.cpu = cpu
+        self.memory = memory
+        self.interfaces = interfaces
+        self.disks = disks
+        self.processes = processes
+
+    def __repr__(self) -> str:
+        return (f'<Snapshot at {self.timestamp:.3f}: {len(self.cpu)} cores, '
+                f'{len(self.interfaces)} interfaces, {len(self.disks)} disks, '
+                f'{len(self.processes)} processes>')
+
+
+def take_snapshot() -> Snapshot:
+    """Capture the current state for a later diff_snapshots().
+
+    CPU percent is measured since the previous CPU reading in the active
+    baselines, as get_cpu_percents() would report it.
+    """
+    timestamp, monotonic = clock.now(), time.monotonic()
+    memory = direct_os.get_memory_info()
+    return Snapshot(
+        timestamp,
+        tuple(direct_os.get_cpu_percents()),
+        (memory.get('total', 0), memory.get('used', 0), memory.get('available', 0)),
+        tuple((name, c['bytes_recv'], c['bytes_sent'])
+              for name, c in netstat.read_interface_counters().items()),
+        tuple((d['mountpoint'], d['total'], d['available']) for d in disks.get_disk_info()),
+        tuple((pid, name, status) for pid, (name, status) in processes.read_process_states().items()),
+        monotonic,
+    )
+
+
+def _average(values: Tuple[float, ...]) -> float:
+    return sum(values) / len(values) if values else 0.0
+
+
+def diff_snapshots(a: Snapshot, b: Snapshot,
+                   disk_threshold_bytes: int = DEFAULT_DISK_THRESHOLD_BYTES) -> Dict[str, Any]:
+    """What changed going from a to b; every delta is b minus a.
+
+    Diffing in the opposite order flips the signs and swaps started with
+    exited. Interfaces and disks present in only one snapshot are listed under
+    "added"/"removed"; a process counts as changed when its status differs.
+    """
+    validation.at_least('disk_threshold_bytes', disk_threshold_bytes, 0)
+    interfaces_a = {name: (recv, sent) for name, recv, sent in a.interfaces}
+    interfaces_b = {name: (recv, sent) for name, recv, sent in b.interfaces}
+    interfaces = {
+        name: {'bytes_recv_delta': recv - interfaces_a[name][0],
+               'bytes_sent_delta': sent - interfaces_a[name][1]}
+        for name, (recv, sent) in interfaces_b.items() if name in interfaces_a
+    }
+
+    disks_a = {mount: (total, available) for mount, total, available in a.disks}
+    disks_b = {mount: (total, available) for mount, total, available in b.disks}
+    changed_disks: List[Dict[str, Any]] = []
+    for mount, (total, available) in disks_b.items():
+        before = disks_a.get(mount)
+        if before is None or available is None or before[1] is None:
+            continue
+        delta = available - before[1]
+        if abs(delta) > disk_threshold_bytes:
+            changed_disks.append({'mountpoint': mount, 'available_delta': delta,
+                                  'total_delta': (total or 0) - (before[0] or 0)})
+
+    procs_a = {pid: (name, status) for pid, name, status in a.processes}
+    procs_b = {pid: (name, status) for pid, name, status in b.processes}
+    changed = [
+        {'pid': pid, 'name': name, 'from': procs_a[pid][1], 'to': status}
+        for pid, (name, status) in procs_b.items()
+        if pid in procs_a and procs_a[pid][1] != status
+    ]
+
+    if a.monotonic is not None and b.monotonic is not None:
+        interval = b.monotonic - a.monotonic
+    else:
+        interval = b.timestamp - a.timestamp
+    return {
+        'interval': interval,
+        'cpu_average_delta': _average(b.cpu) - _average(a.cpu),
+        'memory': {
+            'total_delta': b.memory[0] - a.memory[0],
+            'used_delta': b.memory[1] - a.memory[1],
+            'available_delta': b.memory[2] - a.memory[2],
+        },
+        'interfaces': {
+            'changed': interfaces,
+            'added': sorted(set(interfaces_b) - set(interfaces_a)),
+            'removed': sorted(set(interfaces_a) - set(interfaces_b)),
+        },
+        'disks': {
+            'changed': changed_disks,
+            'added': sorted(set(disks_b) - set(disks_a)),
+            'removed': sorted(set(disks_a) - set(disks_b)),
+        },
+        'processes': {
+            'started': [{'pid': pid, 'name': name, 'status': status}
+                        for pid, (name, status) in procs_b.items() if pid not in procs_a],
+            'exited': [{'pid': pid, 'name': name, 'status': status}
+                       for pid, (name, status) in procs_a.items() if pid not in procs_b],
+            'changed_state': changed,
+        },
+    }
@@ -0,0 +1,172 @@
+"""
+Pulse Socket Publisher
+Sampler snapshots streamed to local clients over a Unix domain socket.
+
+Each client receives one snapshot per sample as a line of compact JSON, the
+same document snapshot(as_json=True) returns, so a consumer in any language
+needs only a socket and a JSON parser. A client that can't take a whole line
+without blocking is disconnected: it would otherwise hold up the sampler
+thread, and a partial line would break its framing anyway.
+"""
+import errno
+import os
+import socket
+import stat
+import threading
+from typing import Any, Dict, Iterable, List, Optional
+
+from pulse import encoding, validation
+from pulse.errors import Unsupported
+
+_lock = threading.Lock()
+_server: Optional[socket.socket] = None
+_path: Optional[str] = None
+_clients: List[socket.socket] = []
+_thread: Optional[threading.Thread] = None
+_published = 0
+_dropped = 0
+# Whether start_socket_publisher() started the sampler, so stopping stops it
+_owns_sampler = False
+
+
+def _remove_stale_socket(path: str) -> None:
+    """Unlink a socket file left by a publisher that died; refuse to touch anything else."""
+    try:
+        if not stat.S_ISSOCK(os.stat(path).st_mode):
+            return
+    except FileNotFoundError:
+        return
+    probe = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+    try:
+        probe.connect(path)
+    except ConnectionRefusedError:
+        os.unlink(path)
+    except OSError:
+        pass
+    else:
+        raise OSError(errno.EADDRINUSE, f'Cannot publish on {path}: another process is listening', path)
+    finally:
+        probe.close()
+
+
+def _accept(server: socket.socket) -> None:
+    while True:
+        try:
+            client, _ = server.accept()
+        except OSError:
+            # The socket was closed by stop_socket_publisher()
+            return
+        client.setblocking(False)
+        with _lock:
+            if _server is not server:
+                client.close()
+                return
+            _clients.append(client)
+
+
+def start_socket_publisher(path: str, interval_ms: float = 1000,
+                           sections: Optional[Iterable[str]] = None) -> None:
+    """Stream every sampler snapshot as a JSON line to clients connecting on path.
+
+    Starts the sampler with interval_ms and sections (as for start_sampler)
+    unless it is already running, in which case its own samples are sent.
+    A socket file left behind by a publisher that died is replaced. Raises
+    OSError naming path when it can't be bound, RuntimeError while a
+    publisher is already running, and Unsupported where Unix sockets don't
+    exist.
+    """
+    from pulse import sampler
+
+    global _server, _path, _thread, _published, _dropped, _owns_sampler
+    validation.not_empty('path', path)
+    validation.positive('interval_ms', interval_ms)
+    if not hasattr(socket, 'AF_UNIX'):
+        raise Unsupported('start_socket_publisher needs Unix domain sockets')
+    with _lock:
+        if _server is not None:
+            raise RuntimeError(f'Already publishing on {_path}')
+        _remove_stale_socket(path)
+        server = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        try:
+            server.bind(path)
+            server.listen()
+        except OSError as e:
+            server.close()
+            raise OSError(e.errno, f'Cannot publish on {path}: {e.strerror or e}', path) from None
+        _server, _path, _published, _dropped = server, path, 0, 0
+        _thread = threading.Thread(target=_accept, args=(server,), name='pulse-socket', daemon=True)
+        _thread.start()
+    running = sampler.sampler_status()['running']
+    if not running:
+        try:
+            sampler.start_sampler(interval_ms, sections=sections)
+        except ValueError:
+            stop_socket_publisher()
+            raise
+    with _lock:
+        _owns_sampler = not running
+
+
+def stop_socket_publisher(timeout: Optional[float] = None) -> None:
+    """Disconnect every client, close the socket, and remove its file.
+
+    Stops the sampler if start_socket_publisher() started it; a no-op when
+    nothing is being published.
+    """
+    from pulse import sampler
+
+    global _server, _path, _thread, _owns_sampler
+    with _lock:
+        owns_sampler, _owns_sampler = _owns_sampler, False
+    if owns_sampler:
+        sampler.stop_sampler(timeout)
+    with _lock:
+        server, path, thread = _server, _path, _thread
+        _server = _path = _thread = None
+        clients = list(_clients)
+        _clients.clear()
+    if server is None:
+        return
+    # shutdown() wakes the accept() blocked in the other thread
+    try:
+        server.shutdown(socket.SHUT_RDWR)
+    except OSError:
+        pass
+    server.close()
+    for client in clients:
+        client.close()
+    if thread is not None:
+        thread.join(timeout)
+    try:
+        os.unlink(path)
+    except OSError:
+        pass
+
+
+def socket_publisher_status() -> Dict[str, Any]:
+    """{path, clients, published, dropped}: path is None when not publishing."""
+    with _lock:
+        return {'path': _path, 'clients': len(_clients), 'published': _published, 'dropped': _dropped}
+
+
+def on_sample(sample: Dict[str, Any]) -> None:
+    """Send sample to every client; the sampler calls this after each snapshot."""
+    global _published, _dropped
+    with _lock:
+        if _server is None:
+            return
+        line = (encoding.to_json(sample) + '\n').encode('utf-8')
+        kept = []
+        for client in _clients:
+            try:
+                sent = client.send(line)
+            except OSError:
+                # Would block, or the client hung up
+                sent = 0
+            if sent == len(line):
+                kept.append(client)
+            else:
+                client.close()
+                _dropped += 1
+        _clients[:] = kept
+        _published += 1
@@ -0,0 +1,95 @@
+"""
+Pulse Engine State
+Previous-sample baselines kept between calls by the delta/rate collectors.
+
+Collectors read the baselines through active_state(), which is the process-wide
+STATE unless a Monitor has made its own state active with using(). Each
+subsystem's baselines have their own lock, so a slow process scan never holds
+up a network or memory read running on another thread.
+"""
+import contextlib
+import contextvars
+import itertools
+import threading
+from typing import Any, Dict, Iterator, List, Optional, Tuple
+
+
+# Subsystems with a lock of their own in every SysState
+SUBSYSTEMS = ('cpu', 'memory', 'processes', 'network', 'disks', 'thermal', 'cgroups')
+
+_tokens = itertools.count(1)
+
+
+class SysState:
+    """Counter baselines shared by the collectors that report deltas."""
+
+    def __init__(self):
+        # Names this state to the isolation worker and in-flight bounded calls;
+        # unlike id(), never handed to a later state
+        self.token = next(_tokens)
+        # Held while a subsystem's baselines are read and replaced, and while a
+        # max_age_ms getter refreshes it. Re-entrant so a cached getter can call
+        # the collector that takes the same lock.
+        self.locks = {name: threading.RLock() for name in SUBSYSTEMS}
+        # Per-core {busy, total} CPU times behind the previous get_cpu_percents() call
+        self.cpu_times: Optional[List[Dict[str, float]]] = None
+        self.cpu_time = 0.0
+        self.cpu_percents: List[float] = []
+        # /proc/net/snmp Udp counters from the previous get_udp_stats() call
+        self.udp_counters: Optional[Dict[str, int]] = None
+        # get_disk_io() counters and the monotonic time they were sampled at
+        self.disk_io: Optional[Dict[str, Dict[str, Any]]] = None
+        self.disk_io_time = 0.0
+        # Per-process I/O counters keyed by (pid, start_time)
+        self.process_io: Optional[Dict[Tuple[int, int], Dict[str, Any]]] = None
+        self.process_io_time = 0.0
+        # Per-process utime + stime ticks and the cpu_percent they gave, keyed by
+        # (pid, start ticks), with the monotonic time of that process table read
+        self.process_cpu: Optional[Tuple[float, Dict[Tuple[int, int], Tuple[int, float]]]] = None
+        # The latest get_process_changes() collection as (snapshot id, rows), and
+        # each process as that call last reported it
+        self.process_rows: Optional[Tuple[int, List[Tuple[int, str, float, int]]]] = None
+        self.process_reported: Optional[Dict[int, Dict[str, Any]]] = None
+        # /proc/stat, /proc/schedstat, and own schedstat counters from the previous get_scheduler_stats()
+        self.sched_counters: Optional[Dict[str, Any]] = None
+        # resctrl mon_data readings and their monotonic sample time, keyed by group name
+        self.rdt_counters: Dict[str, Tuple[float, Dict[str, Dict[str, Optional[int]]]]] = {}
+        # Summed x86 thermal_throttle counters from the previous get_thermal_status()
+        self.thermal_throttle: Optional[Dict[str, int]] = None
+        # cgroup cpu.stat usage_usec and its monotonic sample time, keyed by cgroup
+        self.cgroup_cpu: Dict[str, Tuple[float, int]] = {}
+        # Per-interface /proc/<pid>/net/dev counters and their monotonic sample time,
+        # keyed by network namespace inode
+        self.namespace_network: Dict[int, Tuple[float, Dict[str, Dict[str, int]]]] = {}
+        # Cached getter results for max_age_ms: (subsystem, call args) -> (monotonic time, result)
+        self.cache: Dict[Tuple[str, str], Tuple[float, Any]] = {}
+
+    def lock(self, subsystem: str) -> threading.RLock:
+        """The lock guarding one of SUBSYSTEMS."""
+        return self.locks[subsystem]
+
+    def reset(self) -> None:
+        """Drop every baseline and cached result, as if newly constructed; the locks and token stay."""
+        locks, token = self.locks, self.token
+        self.__init__()
+        self.locks, self.token = locks, token
+
+
+STATE = SysState()
+
+_ACTIVE: contextvars.ContextVar[SysState] = contextvars.ContextVar('pulse_state', default=STATE)
+
+
+def active_state() -> SysState:
+    """The baselines collectors should read and update in this context."""
+    return _ACTIVE.get()
+
+
+@contextlib.contextmanager
+def using(state: SysState) -> Iterator[SysState]:
+    """Make state the active baselines for the current thread or task."""
+    token = _ACTIVE.set(state)
+    try:
+        yield state
+    finally:
+        _ACTIVE.reset(token)
@@ -0,0 +1,330 @@
+"""
+Pulse System Identity
+Host facts every report starts with: name, OS, kernel, architecture, boot time,
+and the DMI/SMBIOS description of the machine itself.
+"""
+import os
+import platform
+import socket
+import struct
+import time
+from typing import Any, Dict, Iterator, List, Optional, Tuple
+
+from pulse import log
+from pulse.capabilities import requires
+from pulse.direct_os import FREEBSD, LINUX, MACOS, WINDOWS
+
+PROC_UPTIME = '/proc/uptime'
+PROC_STAT = '/proc/stat'
+OS_RELEASE_PATHS = ('/etc/os-release', '/usr/lib/os-release')
+SYS_CLASS_DMI_ID = '/sys/class/dmi/id'
+
+# SMBIOS chassis types (DMTF DSP0134, table 17) grouped into form factors
+_CHASSIS_FORM_FACTORS = {
+    'laptop': {8, 9, 10, 14, 31, 32},
+    'desktop': {3, 4, 5, 6, 7, 13, 15, 16, 24, 34, 35, 36},
+    'server': {17, 23, 25, 28, 29},
+    'tablet': {11, 30},
+}
+
+# Substrings of DMI system vendor/product naming a hypervisor
+DMI_HYPERVISORS = (
+    ('QEMU', 'kvm'),
+    ('KVM', 'kvm'),
+    ('Amazon EC2', 'kvm'),
+    ('Google Compute Engine', 'kvm'),
+    ('VMware', 'vmware'),
+    ('innotek', 'virtualbox'),
+    ('VirtualBox', 'virtualbox'),
+    ('Xen', 'xen'),
+    ('Virtual Machine', 'hyperv'),
+    ('Parallels', 'parallels'),
+    ('Bochs', 'bochs'),
+)
+
+# /sys/class/dmi/id attribute -> get_hardware_info() key
+_DMI_FIELDS = {
+    'sys_vendor': 'manufacturer',
+    'product_name': 'product_name',
+    'product_serial': 'serial',
+    'board_vendor': 'board_vendor',
+    'board_name': 'board_name',
+    'bios_vendor': 'bios_vendor',
+    'bios_version': 'bios_version',
+    'bios_date': 'bios_date',
+}
+
+# Placeholders firmware vendors leave in unset DMI strings
+_DMI_PLACEHOLDERS = frozenset({
+    'to be filled by o.e.m.', 'default string', 'system product name',
+    'system manufacturer', 'not specified', 'none', 'o.e.m.', '0',
+})
+
+
+def parse_os_release(text: str) -> Dict[str, str]:
+    """Parse os-release KEY=value lines, unquoting values."""
+    fields = {}
+    for line in text.splitlines():
+        line = line.strip()
+        if not line or line.startswith('#') or '=' not in line:
+            continue
+        key, _, value = line.partition('=')
+        value = value.strip()
+        if len(value) >= 2 and value[0] == value[-1] and value[0] in '"\'':
+            value = value[1:-1].replace('\\"', '"').replace('\\\\', '\\')
+        fields[key.strip()] = value
+    return fields
+
+
+def parse_btime(text: str) -> Optional[int]:
+    """Boot time (unix seconds) from the "btime" line of /proc/stat."""
+    for line in text.splitlines():
+        if line.startswith('btime '):
+            return int(line.split()[1])
+    return None
+
+
+if LINUX:
+    def get_uptime() -> Optional[float]:
+        """Seconds since boot from /proc/uptime."""
+        try:
+            with open(PROC_UPTIME, 'r') as f:
+                return float(f.read().split()[0])
+        except (OSError, ValueError, IndexError) as e:
+            log.read_failed(PROC_UPTIME, e)
+            return None
+
+    def _boot_time() -> Optional[int]:
+        try:
+            with open(PROC_STAT, 'r') as f:
+                return parse_btime(f.read())
+        except OSError as e:
+            log.read_failed(PROC_STAT, e)
+            return None
+
+    def _os_name_version() -> Dict[str, Optional[str]]:
+        for path in OS_RELEASE_PATHS:
+            try:
+                with open(path, 'r') as f:
+                    release = parse_os_release(f.read())
+            except OSError:
+                continue
+            return {'os_name': release.get('NAME'), 'os_version': release.get('VERSION_ID')}
+        return {'os_name': 'Linux', 'os_version': None}
+
+elif WINDOWS:
+    import ctypes
+
+    def get_uptime() -> Optional[float]:
+        """Seconds since boot from GetTickCount64."""
+        kernel32 = ctypes.windll.kernel32
+        kernel32.GetTickCount64.restype = ctypes.c_ulonglong
+        return kernel32.GetTickCount64() / 1000.0
+
+    def _boot_time() -> Optional[int]:
+        uptime = get_uptime()
+        return int(time.time() - uptime) if uptime is not None else None
+
+    def _os_name_version() -> Dict[str, Optional[str]]:
+        return {'os_name': 'Windows', 'os_version': platform.version() or None}
+
+else:
+    def _boot_time() -> Optional[int]:
+        import psutil
+
+        try:
+            return int(psutil.boot_time())
+        except (OSError, RuntimeError) as e:
+            log.read_failed('psutil.boot_time()', e)
+            return None
+
+    def get_uptime() -> Optional[float]:
+        """Seconds since boot, derived from the kernel's boot timestamp."""
+        boot = _boot_time()
+        return time.time() - boot if boot is not None else None
+
+    def _os_name_version() -> Dict[str, Optional[str]]:
+        if MACOS:
+            return {'os_name': 'macOS', 'os_version': platform.mac_ver()[0] or None}
+        if FREEBSD:
+            # "14.1-RELEASE-p5": the version is the release before its branch
+            return {'os_name': 'FreeBSD', 'os_version': platform.release().split('-')[0] or None}
+        return {'os_name': platform.system() or None, 'os_version': None}
+
+
+def get_system_info() -> Dict[str, Any]:
+    """Get hostname, OS, kernel, architecture, boot time, and uptime.
+
+    Anything the platform can't report is None.
+    """
+    return {
+        'hostname': socket.gethostname() or None,
+        **_os_name_version(),
+        'kernel_version': platform.release() or None,
+        'architecture': platform.machine() or None,
+        'boot_time': _boot_time(),
+        'uptime_seconds': get_uptime(),
+    }
+
+
+@requires('load_average')
+def get_load_average() -> Dict[str, float]:
+    """Get the 1, 5, and 15 minute load averages (runnable plus waiting tasks)."""
+    one, five, fifteen = os.getloadavg()
+    return {'load_1m': one, 'load_5m': five, 'load_15m': fifteen}
+
+
+def dmi_hypervisor(manufacturer: Optional[str], product: Optional[str]) -> Optional[str]:
+    """Hypervisor named by DMI system strings, or None for physical hardware."""
+    text = f"{manufacturer or ''} {product or ''}"
+    for needle, hypervisor in DMI_HYPERVISORS:
+        if needle in text:
+            return hypervisor
+    return None
+
+
+def form_factor(chassis_type: Optional[int], manufacturer: Optional[str] = None,
+                product: Optional[str] = None) -> str:
+    """"laptop" | "desktop" | "server" | "tablet" | "vm" | "unknown"."""
+    if dmi_hypervisor(manufacturer, product) is not None:
+        return 'vm'
+    for name, types in _CHASSIS_FORM_FACTORS.items():
+        if chassis_type in types:
+            return name
+    return 'unknown'
+
+
+def clean_dmi_string(value: Optional[str]) -> Optional[str]:
+    """Strip a DMI string, mapping firmware placeholders to None."""
+    if value is None:
+        return None
+    value = value.strip()
+    if not value or value.lower() in _DMI_PLACEHOLDERS:
+        return None
+    return value
+
+
+def iter_smbios_structures(table: bytes) -> Iterator[Tuple[int, bytes, List[str]]]:
+    """Walk an SMBIOS structure table, yielding (type, formatted area, strings).
+
+    String references in the formatted area are 1-based indexes into strings.
+    """
+    offset = 0
+    while offset + 4 <= len(table):
+        kind, length = table[offset], table[offset + 1]
+        if length < 4:
+            break
+        formatted = table[offset:offset + length]
+        end = table.find(b'\0\0', offset + length)
+        if end < 0:
+            break
+        raw = table[offset + length:end]
+        strings = [part.decode('ascii', 'replace') for part in raw.split(b'\0')] if raw else []
+        yield kind, formatted, strings
+        # Type 127 marks the end of the table
+        if kind == 127:
+            break
+        offset = end + 2
+
+
+def _smbios_string(formatted: bytes, at: int, strings: List[str]) -> Optional[str]:
+    if at >= len(formatted) or not formatted[at] or formatted[at] > len(strings):
+        return None
+    return strings[formatted[at] - 1]
+
+
+def parse_smbios(table: bytes) -> Dict[str, Any]:
+    """The get_hardware_info() fields from a raw SMBIOS table."""
+    info: Dict[str, Any] = {name: None for name in _DMI_FIELDS.values()}
+    info['chassis_type'] = None
+    for kind, formatted, strings in iter_smbios_structures(table):
+        if kind == 0:
+            info['bios_vendor'] = _smbios_string(formatted, 4, strings)
+            info['bios_version'] = _smbios_string(formatted, 5, strings)
+            info['bios_date'] = _smbios_string(formatted, 8, strings)
+        elif kind == 1:
+            info['manufacturer'] = _smbios_string(formatted, 4, strings)
+            info['product_name'] = _smbios_string(formatted, 5, strings)
+            info['serial'] = _smbios_string(formatted, 7, strings)
+        elif kind == 2:
+            info['board_vendor'] = _smbios_string(formatted, 4, strings)
+            info['board_name'] = _smbios_string(formatted, 5, strings)
+        elif kind == 3 and len(formatted) > 5 and info['chassis_type'] is None:
+            # Bit 7 is the chassis lock flag
+            info['chassis_type'] = formatted[5] & 0x7F
+    return info
+
+
+if LINUX:
+    def _read_hardware_fields() -> Dict[str, Any]:
+        info: Dict[str, Any] = {}
+        for attr, key in _DMI_FIELDS.items():
+            path = os.path.join(SYS_CLASS_DMI_ID, attr)
+            try:
+                with open(path, 'r') as f:
+                    info[key] = f.read()
+            except OSError as e:
+                # Serials are root-only; ARM boards have no DMI at all
+                log.read_failed(path, e)
+                info[key] = None
+        path = os.path.join(SYS_CLASS_DMI_ID, 'chassis_type')
+        try:
+            with open(path, 'r') as f:
+                info['chassis_type'] = int(f.read())
+        except (OSError, ValueError) as e:
+            log.read_failed(path, e)
+            info['chassis_type'] = None
+        return info
+
+elif WINDOWS:
+    _RSMB = struct.unpack('>I', b'RSMB')[0]
+
+    def _read_hardware_fields() -> Dict[str, Any]:
+        kernel32 = ctypes.windll.kernel32
+        size = kernel32.GetSystemFirmwareTable(_RSMB, 0, None, 0)
+        if not size:
+            return parse_smbios(b'')
+        buffer = ctypes.create_string_buffer(size)
+        if kernel32.GetSystemFirmwareTable(_RSMB, 0, buffer, size) != size:
+            return parse_smbios(b'')
+        # RawSMBIOSData: 4 version bytes and a DWORD length precede the table
+        length = struct.unpack_from('<I', buffer.raw, 4)[0]
+        return parse_smbios(buffer.raw[8:8 + length])
+
+else:
+    def _read_hardware_fields() -> Dict[str, Any]:
+        info: Dict[str, Any] = {name: None for name in _DMI_FIELDS.values()}
+        info['chassis_type'] = None
+        if MACOS:
+            info['manufacturer'] = 'Apple Inc.'
+            model = sysctl(b'hw.model')
+            info['product_name'] = model.rstrip(b'\0').decode('utf-8', 'replace') if model else None
+        return info
+
+    def sysctl(name: bytes) -> Optional[bytes]:
+        """Raw value of a BSD/macOS sysctl by name, or None if it doesn't exist."""
+        import ctypes
+        import ctypes.util
+
+        libc = ctypes.CDLL(ctypes.util.find_library('c'))
+        size = ctypes.c_size_t(0)
+        if libc.sysctlbyname(name, None, ctypes.byref(size), None, 0) != 0 or not size.value:
+            return None
+        buffer = ctypes.create_string_buffer(size.value)
+        if libc.sysctlbyname(name, buffer, ctypes.byref(size), None, 0) != 0:
+            return None
+        return buffer.raw[:size.value]
+
+
+def get_hardware_info() -> Dict[str, Any]:
+    """Get manufacturer, product, board, BIOS, and serial from DMI/SMBIOS.
+
+    Unreadable or placeholder fields are None. form_factor is one of "laptop",
+    "desktop", "server", "tablet", "vm", or "unknown".
+    """
+    info = _read_hardware_fields()
+    hardware = {key: clean_dmi_string(info.get(key)) for key in _DMI_FIELDS.values()}
+    hardware['chassis_type'] = info.get('chassis_type')
+    hardware['form_factor'] = form_factor(
+        info.get('chassis_type'), hardware['manufacturer'], hardware['product_name'])
+    return hardware
@@ -0,0 +1,245 @@
+"""
+Pulse Tags
+Processes bucketed by rule ("database", "webserver", "build") for roll-up charts.
+
+set_process_tags() compiles its rules once. A process's tags are worked out
+the first time a collection sees it and kept under its pid, start time,
+and name, so a poll only reads the command line, executable, or owner of
+processes that started (or exec'd another program) since the last one, and
+only when some rule looks at that field; a reused pid is a new process even
+under the same name. Every full collection drops the processes that have
+exited.
+"""
+import copy
+import os
+import re
+import threading
+from typing import Any, Dict, List, NamedTuple, Optional, Pattern, Set, Tuple
+
+from pulse import backend, direct_os, log
+from pulse.direct_os import LINUX, ProcessRow
+
+MATCH_KEYS = ('name_regex', 'cmdline_regex', 'exe_prefix', 'username')
+
+# Per-process details each match key needs beyond the row's name
+_DETAIL_FIELDS = {'cmdline_regex': 'cmdline', 'exe_prefix': 'exe', 'username': 'username'}
+
+
+class Rule(NamedTuple):
+    """One compiled rule; every condition that is set must hold."""
+    tag: str
+    name: Optional[Pattern[str]]
+    cmdline: Optional[Pattern[str]]
+    exe_prefix: Optional[str]
+    username: Optional[str]
+
+
+_lock = threading.Lock()
+_given: List[Dict[str, Any]] = []
+_rules: List[Rule] = []
+_fields: Set[str] = set()
+# (pid, start time or None where the backend doesn't know it, name) -> tags
+_cache: Dict[Tuple[int, Optional[float], str], List[str]] = {}
+
+
+def _compile(index: int, key: str, pattern: str) -> Pattern[str]:
+    try:
+        return re.compile(pattern)
+    except re.error as e:
+        raise ValueError(f'rule {index}: invalid {key} {pattern!r}: {e}') from None
+
+
+def compile_rule(index: int, rule: Any) -> Rule:
+    """rule ({tag, match}) compiled; ValueError names index for anything malformed."""
+    if not isinstance(rule, dict) or set(rule) != {'tag', 'match'}:
+        raise ValueError(f'rule {index}: expected a dict with exactly "tag" and "match"')
+    tag, match = rule['tag'], rule['match']
+    if not isinstance(tag, str) or not tag.strip():
+        raise ValueError(f'rule {index}: tag must be a non-empty string')
+    if not isinstance(match, dict) or not match:
+        raise ValueError(f'rule {index}: match must be a non-empty dict of {", ".join(MATCH_KEYS)}')
+    unknown = sorted(set(match) - set(MATCH_KEYS))
+    if unknown:
+        raise ValueError(f'rule {index}: unknown match key {unknown[0]!r}; '
+                         f'expected one of {", ".join(MATCH_KEYS)}')
+    for key, value in match.items():
+        if not isinstance(value, str) or not value:
+            raise ValueError(f'rule {index}: {key} must be a non-empty string')
+    return Rule(
+        tag=tag,
+        name=_compile(index, 'name_regex', match['name_regex']) if 'name_regex' in match else None,
+        cmdline=(_compile(index, 'cmdline_regex', match['cmdline_regex'])
+                 if 'cmdline_regex' in match else None),
+        exe_prefix=match.get('exe_prefix'),
+        username=match.get('username'),
+    )
+
+
+def rule_matches(rule: Rule, name: str, details: Dict[str, Optional[str]]) -> bool:
+    """Whether a process with name and details (cmdline, exe, username) meets rule.
+
+    Regexes are searched, not anchored. A detail that couldn't be read
+    (None) fails the condition on it.
+    """
+    if rule.name is not None and not rule.name.search(name):
+        return False
+    if rule.cmdline is not None and (details.get('cmdline') is None
+                                     or not rule.cmdline.search(details['cmdline'])):
+        return False
+    if rule.exe_prefix is not None and not (details.get('exe') or '').startswith(rule.exe_prefix):
+        return False
+    if rule.username is not None and details.get('username') != rule.username:
+        return False
+    return True
+
+
+def match_tags(rules: List[Rule], name: str, details: Dict[str, Optional[str]]) -> List[str]:
+    """The tags of every rule a process meets, each once, in rule order."""
+    tags: List[str] = []
+    for rule in rules:
+        if rule.tag not in tags and rule_matches(rule, name, details):
+            tags.append(rule.tag)
+    return tags
+
+
+if LINUX:
+    import pwd
+
+    def read_details(pid: int, fields: Set[str]) -> Dict[str, Optional[str]]:
+        """The cmdline, exe, and username among fields for pid; None where unreadable."""
+        details: Dict[str, Optional[str]] = {}
+        if 'cmdline' in fields:
+            try:
+                with open(f'/proc/{pid}/cmdline', 'rb') as f:
+                    details['cmdline'] = os.fsdecode(f.read().rstrip(b'\0').replace(b'\0', b' '))
+            except OSError as e:
+                log.read_failed(f'/proc/{pid}/cmdline', e)
+                details['cmdline'] = None
+        if 'exe' in fields:
+            try:
+                details['exe'] = os.readlink(f'/proc/{pid}/exe')
+            except OSError:
+                # Other users' executables are hidden unless we are root
+                details['exe'] = None
+        if 'username' in fields:
+            try:
+                uid = os.stat(f'/proc/{pid}').st_uid
+            except OSError as e:
+                log.read_failed(f'/proc/{pid}', e)
+                details['username'] = None
+            else:
+                try:
+                    details['username'] = pwd.getpwuid(uid).pw_name
+                except KeyError:
+                    details['username'] = str(uid)
+        return details
+
+else:
+    def read_details(pid: int, fields: Set[str]) -> Dict[str, Optional[str]]:
+        """The cmdline, exe, and username among fields for pid via psutil; None where unreadable."""
+        import psutil
+
+        details: Dict[str, Optional[str]] = {}
+        try:
+            process = psutil.Process(pid)
+        except psutil.NoSuchProcess:
+            return {field: None for field in fields}
+        readers = {'cmdline': lambda: ' '.join(process.cmdline()), 'exe': process.exe,
+                   'username': process.username}
+        for field in fields:
+            try:
+                details[field] = readers[field]() or None
+            except (psutil.NoSuchProcess, psutil.AccessDenied, OSError):
+                details[field] = None
+        return details
+
+
+def tag_rows(rows: List[ProcessRow], complete: bool = False) -> Optional[List[List[str]]]:
+    """Each row's tags, in row order; None while no rules are set.
+
+    complete says rows is the whole process table, so processes missing
+    from it have exited and their cached tags can go.
+    """
+    with _lock:
+        rules, fields = _rules, _fields
+        if not rules:
+            return None
+        cache = _cache
+    starts = backend.active().process_start_times()
+    keys = [(row[0], starts.get(row[0]), row[1]) for row in rows]
+    with _lock:
+        known = [cache.get(key) for key in keys]
+    tagged = []
+    found = {}
+    for row, key, tags in zip(rows, keys, known):
+        if tags is None:
+            details = read_details(row[0], fields) if fields else {}
+            tags = found[key] = match_tags(rules, row[1], details)
+        tagged.append(tags)
+    with _lock:
+        # Rules replaced mid-collection leave these tags to the old rules
+        if _cache is cache:
+            if complete:
+                live = set(keys)
+                for key in [k for k in cache if k not in live]:
+                    del cache[key]
+            cache.update(found)
+    return [list(tags) for tags in tagged]
+
+
+def set_process_tags(rules: Optional[List[Dict[str, Any]]] = None) -> None:
+    """Tag processes by rule; None (or []) removes every rule.
+
+    Each rule is {"tag": str, "match": {...}} where match holds any of
+    "name_regex" and "cmdline_regex" (searched in the process name and
+    command line), "exe_prefix" (the start of the executable's path), and
+    "username", all of which must hold. A process gets the tag of every
+    rule it meets. While rules are set, get_process_list() entries carry a
+    "tags" list and get_tag_summary() totals them. Raises ValueError naming
+    the index of the first malformed rule or bad regex.
+    """
+    global _given, _rules, _fields, _cache
+    if rules is not None and not isinstance(rules, (list, tuple)):
+        raise ValueError('rules must be a list of {"tag", "match"} dicts')
+    compiled = [compile_rule(index, rule) for index, rule in enumerate(rules or [])]
+    fields = {_DETAIL_FIELDS[key] for rule in rules or [] for key in rule['match'] if key in _DETAIL_FIELDS}
+    with _lock:
+        _given = copy.deepcopy(list(rules or []))
+        _rules = compiled
+        _fields = fields
+        _cache = {}
+
+
+def get_process_tags() -> List[Dict[str, Any]]:
+    """The rules set_process_tags() last set, as given."""
+    with _lock:
+        return copy.deepcopy(_given)
+
+
+def summarize(rows: List[ProcessRow], tagged: List[List[str]], tags: List[str]) -> Dict[str, Dict[str, Any]]:
+    """{tag: {count, cpu_percent, memory_info}} over rows, with every tag in tags present."""
+    empty = {'count': 0, 'cpu_percent': 0.0, 'memory_info': 0}
+    summary = {tag: dict(empty) for tag in tags}
+    for row, row_tags in zip(rows, tagged):
+        for tag in row_tags:
+            entry = summary.setdefault(tag, dict(empty))
+            entry['count'] += 1
+            entry['cpu_percent'] += row[2]
+            entry['memory_info'] += row[3]
+    return summary
+
+
+def get_tag_summary() -> Dict[str, Dict[str, Any]]:
+    """Get the process count, summed cpu_percent, and summed memory_info (bytes) per tag.
+
+    Every tag the rules name is present, with zeros when no process has it,
+    so a chart's series stay stable. A process with several tags counts in
+    each. Empty while no rules are set.
+    """
+    rows = direct_os.get_process_rows()
+    tagged = tag_rows(rows, complete=True)
+    if tagged is None:
+        return {}
+    with _lock:
+        tags = list(dict.fromkeys(rule.tag for rule in _rules))
+    return summarize(rows, tagged, tags)
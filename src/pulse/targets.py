@@ -0,0 +1,179 @@
+"""
+Pulse Targets
+Resolve a service spec from agent config to the processes it names.
+
+A spec is "pid:1234", "pidfile:/run/nginx.pid", "name:nginx", or
+"cmdline:<text>" ("cmdline:~<regex>" for a regular expression searched in
+the command line). Every match carries its start_time, so a caller that
+stores the pid can tell later whether it was reused by another process.
+"""
+import os
+import re
+from typing import Any, Dict, List, Optional, Tuple
+
+from pulse import direct_os, log, validation
+from pulse.direct_os import LINUX
+from pulse.errors import PermissionDenied, ProcessNotFound
+from pulse.processes import PROC, parse_proc_stat
+
+SPEC_KINDS = ('pid', 'pidfile', 'name', 'cmdline')
+
+# Linux cuts comm, the name /proc reports, to this many characters
+COMM_LENGTH = 15
+
+
+def parse_spec(spec: str) -> Tuple[str, str]:
+    """(kind, value) from "kind:value"; raises ValueError for anything else."""
+    kind, sep, value = spec.partition(':')
+    if not sep:
+        raise ValueError(f"Invalid spec {spec!r}; expected one of "
+                         f"{', '.join(k + ':...' for k in SPEC_KINDS)}")
+    validation.one_of('spec kind', kind, SPEC_KINDS)
+    validation.not_empty(kind, value)
+    return kind, value
+
+
+def parse_pid(text: str) -> Optional[int]:
+    """The pid a pidfile (or "pid:" spec) holds; None unless it is one positive integer.
+
+    Surrounding whitespace, including the trailing newline daemons write, is ignored.
+    """
+    value = text.strip()
+    return int(value) if value.isdigit() and int(value) > 0 else None
+
+
+def name_matches(name: str, expected: str) -> bool:
+    """Whether a process name is expected, allowing for comm's truncation on Linux."""
+    return name == expected or (len(name) == COMM_LENGTH and expected.startswith(name))
+
+
+def cmdline_matcher(pattern: str) -> Any:
+    """A predicate over command lines: regex search for "~<regex>", else substring."""
+    if pattern.startswith('~'):
+        try:
+            regex = re.compile(pattern[1:])
+        except re.error as e:
+            raise ValueError(f'Invalid cmdline pattern {pattern[1:]!r}: {e}') from None
+        return lambda cmdline: regex.search(cmdline) is not None
+    return lambda cmdline: pattern in cmdline
+
+
+if LINUX:
+    _CLOCK_TICKS = os.sysconf('SC_CLK_TCK')
+
+    def _boot_time() -> float:
+        from pulse import system
+
+        with open(system.PROC_STAT, 'r') as f:
+            return float(system.parse_btime(f.read()) or 0)
+
+    def describe_process(pid: int, boot_time: Optional[float] = None) -> Optional[Dict[str, Any]]:
+        """{pid, name, start_time, cmdline} from /proc; None if no such process.
+
+        start_time is unix seconds. Raises PermissionDenied when /proc hides it.
+        """
+        try:
+            with open(f'{PROC}/{pid}/stat', 'r') as f:
+                name, fields = parse_proc_stat(f.read())
+            with open(f'{PROC}/{pid}/cmdline', 'rb') as f:
+                cmdline = f.read().rstrip(b'\0').replace(b'\0', b' ')
+        except (FileNotFoundError, ProcessLookupError):
+            return None
+        except PermissionError:
+            raise PermissionDenied(f'Permission denied reading process {pid}') from None
+        boot = _boot_time() if boot_time is None else boot_time
+        return {'pid': pid, 'name': name, 'start_time': boot + int(fields[19]) / _CLOCK_TICKS,
+                'cmdline': os.fsdecode(cmdline)}
+
+    def _describe_all(pids: List[int]) -> List[Dict[str, Any]]:
+        boot = _boot_time()
+        described = []
+        for pid in pids:
+            try:
+                process = describe_process(pid, boot)
+            except (PermissionDenied, ValueError, IndexError) as e:
+                log.process_skipped(pid, f'{PROC}/{pid}', e)
+                continue
+            if process is not None:
+                described.append(process)
+        return described
+
+else:
+    def describe_process(pid: int, boot_time: Optional[float] = None) -> Optional[Dict[str, Any]]:
+        """{pid, name, start_time, cmdline} via psutil; None if no such process."""
+        import psutil
+
+        try:
+            process = psutil.Process(pid)
+            with process.oneshot():
+                return {'pid': pid, 'name': process.name(), 'start_time': process.create_time(),
+                        'cmdline': ' '.join(process.cmdline())}
+        except psutil.NoSuchProcess:
+            return None
+        except psutil.AccessDenied:
+            raise PermissionDenied(f'Permission denied reading process {pid}') from None
+
+    def _describe_all(pids: List[int]) -> List[Dict[str, Any]]:
+        described = []
+        for pid in pids:
+            try:
+                process = describe_process(pid)
+            except PermissionDenied as e:
+                log.process_skipped(pid, str(pid), e)
+                continue
+            if process is not None:
+                described.append(process)
+        return described
+
+
+def _read_pidfile(path: str) -> int:
+    try:
+        with open(path, 'r') as f:
+            text = f.read()
+    except FileNotFoundError:
+        raise ProcessNotFound(f'No pidfile at {path}') from None
+    except PermissionError:
+        raise PermissionDenied(f'Permission denied reading {path}') from None
+    pid = parse_pid(text)
+    if pid is None:
+        raise ValueError(f'{path} holds no pid: {text.strip()[:40]!r}')
+    return pid
+
+
+def resolve_target(spec: str, expected_name: Optional[str] = None) -> List[Dict[str, Any]]:
+    """Find the processes a spec names: [{pid, name, start_time, cmdline}, ...] by pid.
+
+    "pid:" and "pidfile:" resolve to that one pid if it is running; a pidfile
+    may have trailing whitespace. "name:" matches the process name exactly
+    (Linux's 15-character comm matches a longer name it is the start of) and
+    "cmdline:" the command line. With expected_name, only processes of that
+    name count, which catches a stale pidfile whose pid now belongs to
+    something else. start_time is unix seconds. Raises ProcessNotFound when
+    nothing matches and ValueError for a malformed spec or pidfile.
+    """
+    kind, value = parse_spec(spec)
+    validation.not_empty('expected_name', expected_name)
+    if kind in ('pid', 'pidfile'):
+        pid = parse_pid(value) if kind == 'pid' else _read_pidfile(value)
+        if pid is None:
+            raise ValueError(f'Invalid pid {value!r} in spec {spec!r}')
+        process = describe_process(pid)
+        if process is None:
+            source = f' (from {value})' if kind == 'pidfile' else ''
+            raise ProcessNotFound.for_pid(pid, source)
+        if expected_name is not None and not name_matches(process['name'], expected_name):
+            raise ProcessNotFound(f"Process {pid} is {process['name']!r}, not {expected_name!r}"
+                                  + ('; stale pidfile?' if kind == 'pidfile' else ''))
+        return [process]
+
+    rows = direct_os.get_process_rows()
+    if kind == 'name':
+        processes = _describe_all([pid for pid, name, _, _ in rows if name_matches(name, value)])
+    else:
+        matches = cmdline_matcher(value)
+        processes = [p for p in _describe_all([row[0] for row in rows]) if matches(p['cmdline'])]
+    if expected_name is not None:
+        processes = [p for p in processes if name_matches(p['name'], expected_name)]
+    if not processes:
+        raise ProcessNotFound(f'No process matches {spec!r}')
+    return sorted(processes, key=lambda p: p['pid'])
@@ -0,0 +1,228 @@
+"""
+Pulse Thermal
+Package temperature and CPU frequency sampled alongside CPU usage, and how they move together.
+
+While the sampler runs with its "cpu" section on a host with temperature
+sensors, each sample also reads the CPU package temperature and the mean
+core frequency, kept under the sample's timestamp for as many samples as
+the sampler holds. get_thermal_correlation() pairs them with the sampled
+cpu_total series by timestamp; a reading taken for a sample lines up with
+it exactly, and one within half a sampling interval of a sample is taken
+as belonging to it.
+
+A CPU hitting its thermal limit lowers its clock, and usage measured
+against a slower clock reads higher, so throttling shows up as a hot
+package whose frequency dropped rather than in the usage series alone.
+"""
+import bisect
+import glob
+import math
+import os
+import threading
+from collections import deque
+from typing import Any, Deque, Dict, List, Optional, Sequence, Tuple
+
+from pulse import log, validation
+from pulse.capabilities import requires
+from pulse.direct_os import LINUX
+
+DEFAULT_THRESHOLD_C = 95.0
+
+# Thermal zones and hwmon chips that measure the CPU package as a whole
+PACKAGE_ZONE_TYPES = ('x86_pkg_temp', 'cpu-thermal', 'cpu_thermal', 'soc_thermal')
+PACKAGE_CHIPS = ('coretemp', 'k10temp', 'zenpower', 'cpu_thermal')
+PACKAGE_LABELS = ('Package id', 'Tctl', 'Tdie')
+
+# (timestamp, package temperature in °C, mean core frequency in MHz)
+Reading = Tuple[float, Optional[float], Optional[float]]
+
+# Sized to the sampler's capacity when it starts
+_readings: Deque[Reading] = deque(maxlen=600)
+_readings_lock = threading.Lock()
+
+
+def package_channel(chip_name: Optional[str], labels: Sequence[Optional[str]]) -> Optional[int]:
+    """Index in labels of a package chip's package sensor; the first one if none is labelled so."""
+    if chip_name not in PACKAGE_CHIPS or not labels:
+        return None
+    for index, label in enumerate(labels):
+        if (label or '').startswith(PACKAGE_LABELS):
+            return index
+    return 0
+
+
+if LINUX:
+    from pulse import sensors
+
+    def read_package_temperature() -> Optional[float]:
+        """The hottest CPU package's temperature (°C), or None with no package sensor."""
+        temperatures = [zone['temperature_c'] for zone in sensors.read_thermal_zones()
+                        if zone['type'] in PACKAGE_ZONE_TYPES and zone['temperature_c'] is not None]
+        if temperatures:
+            return max(temperatures)
+        for chip in sensors.hwmon_chips():
+            try:
+                with open(os.path.join(chip, 'name'), 'r') as f:
+                    name: Optional[str] = f.read().strip()
+            except OSError:
+                continue
+            channels = sensors.read_channels(chip, 'temp')
+            index = package_channel(name, [channel['label'] for channel in channels])
+            if index is not None:
+                temperatures.append(channels[index]['input'] / 1000)
+        return max(temperatures) if temperatures else None
+
+    def read_frequency_mhz() -> Optional[float]:
+        """The mean current frequency of the online cores (MHz), or None without cpufreq."""
+        frequencies = []
+        for path in glob.glob(os.path.join(sensors.SYS_DEVICES_CPU, 'cpu[0-9]*', 'cpufreq', 'scaling_cur_freq')):
+            try:
+                with open(path, 'r') as f:
+                    frequencies.append(int(f.read()) / 1000)
+            except (OSError, ValueError) as e:
+                log.read_failed(path, e)
+        return sum(frequencies) / len(frequencies) if frequencies else None
+
+else:
+    def read_package_temperature() -> Optional[float]:
+        """The hottest CPU package's temperature (°C) via psutil, or None with no package sensor."""
+        import psutil
+
+        try:
+            readings = psutil.sensors_temperatures() if hasattr(psutil, 'sensors_temperatures') else {}
+        except (OSError, RuntimeError):
+            readings = {}
+        temperatures = []
+        for chip, entries in (readings or {}).items():
+            index = package_channel(chip, [entry.label for entry in entries])
+            if index is not None and entries[index].current is not None:
+                temperatures.append(entries[index].current)
+        return max(temperatures) if temperatures else None
+
+    def read_frequency_mhz() -> Optional[float]:
+        """The current CPU frequency (MHz) via psutil, or None where it isn't reported."""
+        import psutil
+
+        try:
+            frequency = psutil.cpu_freq()
+        except (OSError, RuntimeError, NotImplementedError):
+            return None
+        return frequency.current if frequency and frequency.current else None
+
+
+def clear(capacity: int) -> None:
+    """Drop every reading and keep at most capacity from now on; the sampler calls this on start."""
+    global _readings
+    with _readings_lock:
+        _readings = deque(maxlen=capacity)
+
+
+def on_sample(timestamp: float) -> None:
+    """Record the package temperature and frequency under a sample's timestamp."""
+    reading = (timestamp, read_package_temperature(), read_frequency_mhz())
+    with _readings_lock:
+        _readings.append(reading)
+
+
+def recent_readings() -> List[Reading]:
+    """The held readings, oldest first."""
+    with _readings_lock:
+        return list(_readings)
+
+
+def align(series: List[Tuple[float, float]], readings: List[Reading],
+          tolerance_secs: float) -> List[Tuple[float, float, Reading]]:
+    """Pair each (timestamp, value) with the reading nearest in time, within tolerance_secs.
+
+    Both lists are oldest first. A reading pairs with one value at most;
+    values without a reading close enough are left out.
+    """
+    times = [reading[0] for reading in readings]
+    pairs = []
+    used = -1
+    for timestamp, value in series:
+        at = bisect.bisect_left(times, timestamp)
+        nearest = min((i for i in (at - 1, at) if used < i < len(times)),
+                      key=lambda i: abs(times[i] - timestamp), default=None)
+        if nearest is not None and abs(times[nearest] - timestamp) <= tolerance_secs:
+            pairs.append((timestamp, value, readings[nearest]))
+            used = nearest
+    return pairs
+
+
+def pearson(xs: Sequence[float], ys: Sequence[float]) -> Optional[float]:
+    """The Pearson correlation coefficient of two equal-length series.
+
+    None with fewer than two pairs or when either series is constant, where
+    the coefficient is undefined.
+    """
+    if len(xs) < 2:
+        return None
+    mean_x, mean_y = sum(xs) / len(xs), sum(ys) / len(ys)
+    dx = [x - mean_x for x in xs]
+    dy = [y - mean_y for y in ys]
+    spread = math.sqrt(sum(d * d for d in dx) * sum(d * d for d in dy))
+    if spread == 0:
+        return None
+    return max(-1.0, min(1.0, sum(a * b for a, b in zip(dx, dy)) / spread))
+
+
+def correlate(series: List[Tuple[float, float]], readings: List[Reading], tolerance_secs: float,
+              threshold_c: float) -> Dict[str, Any]:
+    """get_thermal_correlation()'s result for a cpu_total series and thermal readings.
+
+    An interval is the time between two consecutive paired samples; it is
+    hot when the later one's temperature exceeds threshold_c, and counts in
+    hot_frequency_drops when the frequency also fell across it.
+    """
+    pairs = [(timestamp, cpu, reading) for timestamp, cpu, reading in align(series, readings, tolerance_secs)
+             if reading[1] is not None]
+    samples = [{'timestamp': timestamp, 'cpu_percent': cpu, 'temperature_c': reading[1],
+                'frequency_mhz': reading[2]} for timestamp, cpu, reading in pairs]
+    sampled_frequency = any(sample['frequency_mhz'] is not None for sample in samples)
+    hot = drops = 0
+    for before, after in zip(samples, samples[1:]):
+        if after['temperature_c'] <= threshold_c:
+            continue
+        hot += 1
+        if (before['frequency_mhz'] is not None and after['frequency_mhz'] is not None
+                and after['frequency_mhz'] < before['frequency_mhz']):
+            drops += 1
+    return {
+        'samples': samples,
+        'correlation': pearson([s['cpu_percent'] for s in samples], [s['temperature_c'] for s in samples]),
+        'threshold_c': threshold_c,
+        'hot_intervals': hot,
+        'hot_frequency_drops': drops if sampled_frequency else None,
+    }
+
+
+@requires('temperatures')
+def get_thermal_correlation(window_secs: float = 300,
+                            threshold_c: float = DEFAULT_THRESHOLD_C) -> Dict[str, Any]:
+    """Get CPU usage against package temperature over the last window_secs of sampler history.
+
+    Returns {samples, correlation, threshold_c, hot_intervals,
+    hot_frequency_drops}: samples are the paired {timestamp, cpu_percent,
+    temperature_c, frequency_mhz} readings, oldest first (frequency_mhz
+    None where it isn't reported); correlation is the Pearson coefficient
+    of usage and temperature (None with fewer than two samples or a flat
+    series); hot_intervals counts the intervals between samples that ended
+    above threshold_c, and hot_frequency_drops those in which the frequency
+    also fell (None when it isn't sampled). Needs the sampler running with
+    its "cpu" section; empty until then. Raises ValueError for a window_secs
+    or threshold_c that isn't positive.
+    """
+    from pulse import sampler
+    from pulse.anomalies import series_from_samples
+
+    validation.positive('window_secs', window_secs)
+    validation.positive('threshold_c', threshold_c)
+    samples = sampler.get_samples()
+    series = series_from_samples(samples).get('cpu_total', [])
+    if not series:
+        return correlate([], [], 0.0, threshold_c)
+    since = samples[-1]['timestamp'] - window_secs
+    interval_ms = sampler.sampler_status()['interval_ms'] or 0
+    return correlate([point for point in series if point[0] >= since], recent_readings(),
+                     interval_ms / 2000, threshold_c)
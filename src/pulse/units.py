@@ -0,0 +1,92 @@
+"""
+Pulse Units
+Caller-chosen units for the byte fields of the memory, disk, and network getters.
+"""
+import contextlib
+import contextvars
+from typing import Any, Dict, Iterator, Optional
+
+from pulse import validation
+
+UNITS = ('bytes', 'kib', 'mib', 'auto_human')
+
+_SCALE = {'kib': 1024, 'mib': 1024 ** 2}
+
+_HUMAN_SUFFIXES = ('B', 'KiB', 'MiB', 'GiB', 'TiB', 'PiB', 'EiB')
+
+# Byte-valued keys of each getter's dicts; percents and counts stay as they are
+BYTE_FIELDS = {
+    'memory': ('total', 'available', 'used', 'free', 'buffers', 'cached', 'compressed',
+               'swap_total', 'swap_used', 'swap_free'),
+    'disk': ('total', 'used', 'free', 'available'),
+    'network': ('bytes_recv', 'bytes_sent'),
+}
+
+_units: Dict[str, str] = {kind: 'bytes' for kind in BYTE_FIELDS}
+
+# Set while collecting for consumers that need raw bytes regardless (Prometheus)
+_raw: contextvars.ContextVar[bool] = contextvars.ContextVar('pulse_raw_units', default=False)
+
+
+def humanize(n: Optional[int]) -> Optional[str]:
+    """Format a byte count 1024-based with one decimal, e.g. "5.2 GiB"; bytes stay whole."""
+    if n is None:
+        return None
+    if abs(n) < 1024:
+        return f'{n} B'
+    value = float(n)
+    for suffix in _HUMAN_SUFFIXES[1:-1]:
+        value /= 1024
+        # Move up a unit rather than print "1024.0 KiB"
+        if round(abs(value), 1) < 1024:
+            return f'{value:.1f} {suffix}'
+    return f'{value / 1024:.1f} {_HUMAN_SUFFIXES[-1]}'
+
+
+def set_units(memory: str = 'bytes', disk: str = 'bytes', network: str = 'bytes') -> None:
+    """Choose the units for get_memory_info, get_disk_info, and get_network_stats.
+
+    "bytes" (the default) leaves figures as integers; "kib" and "mib" divide
+    them into floats; "auto_human" keeps bytes and adds a "<field>_human"
+    string beside each ("5.2 GiB"). Raises ValueError for other names.
+    """
+    chosen = {'memory': memory, 'disk': disk, 'network': network}
+    for kind, unit in chosen.items():
+        validation.one_of(kind, unit, UNITS)
+    _units.update(chosen)
+
+
+def get_units() -> Dict[str, str]:
+    """The units currently set per getter kind."""
+    return dict(_units)
+
+
+def effective_units() -> Dict[str, str]:
+    """The units convert() applies here and now: all bytes inside raw_units()."""
+    return {kind: 'bytes' for kind in _units} if _raw.get() else dict(_units)
+
+
+@contextlib.contextmanager
+def raw_units() -> Iterator[None]:
+    """Report plain bytes inside the block whatever set_units() chose."""
+    token = _raw.set(True)
+    try:
+        yield
+    finally:
+        _raw.reset(token)
+
+
+def convert(kind: str, entry: Dict[str, Any]) -> Dict[str, Any]:
+    """Apply kind's unit to one result dict in place and return it."""
+    unit = _units[kind]
+    if unit == 'bytes' or _raw.get():
+        return entry
+    for field in BYTE_FIELDS[kind]:
+        if field not in entry:
+            continue
+        value = entry[field]
+        if unit == 'auto_human':
+            entry[f'{field}_human'] = humanize(value)
+        elif value is not None:
+            entry[field] = value / _SCALE[unit]
+    return entry
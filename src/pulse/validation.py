@@ -0,0 +1,75 @@
+"""
+Pulse Argument Validation
+The checks public functions run on their arguments.
+
+Every helper raises ValueError worded the same way, naming the parameter,
+what it accepts, and the value it got, so a typo in caller code fails
+loudly instead of being treated as "no filter" or "no limit".
+"""
+from typing import Any, Iterable, Optional, Tuple
+
+
+def one_of(param: str, value: Any, accepted: Iterable[str]) -> None:
+    """Raise unless value is one of accepted."""
+    accepted = tuple(accepted)
+    if value not in accepted:
+        raise ValueError(f"Invalid {param} {value!r}; expected one of {', '.join(accepted)}")
+
+
+def all_of(param: str, values: Iterable[str], accepted: Iterable[str]) -> Tuple[str, ...]:
+    """Raise unless every item of values is in accepted; returns values as a tuple.
+
+    A bare string is rejected rather than read as a sequence of letters.
+    """
+    accepted = tuple(accepted)
+    values = names(param, values)
+    unknown = sorted(set(values) - set(accepted))
+    if unknown:
+        raise ValueError(f"Invalid {param} {', '.join(map(repr, unknown))}; "
+                         f"expected any of {', '.join(accepted)}")
+    return values
+
+
+def names(param: str, values: Iterable[str]) -> Tuple[str, ...]:
+    """Raise for a bare string or an empty name among values; returns values as a tuple."""
+    if isinstance(values, str):
+        raise ValueError(f'{param} must be a list of names, got the string {values!r}')
+    values = tuple(values)
+    for value in values:
+        not_empty(param, value)
+    return values
+
+
+def positive(param: str, value: float) -> None:
+    """Raise unless value > 0 (intervals, durations)."""
+    if not value > 0:
+        raise ValueError(f'{param} must be > 0, got {value!r}')
+
+
+def at_least(param: str, value: float, minimum: float) -> None:
+    """Raise unless value >= minimum (counts, limits, ages)."""
+    if not value >= minimum:
+        raise ValueError(f'{param} must be >= {minimum}, got {value!r}')
+
+
+def optional_at_least(param: str, value: Optional[float], minimum: float) -> None:
+    """at_least(), with None meaning "no bound" and passing."""
+    if value is not None:
+        at_least(param, value, minimum)
+
+
+def between(param: str, value: float, low: float, high: float) -> None:
+    """Raise unless low <= value <= high."""
+    if not low <= value <= high:
+        raise ValueError(f'{param} must be between {low} and {high}, got {value!r}')
+
+
+def not_empty(param: str, value: Optional[str]) -> None:
+    """Raise for an empty or blank string; None (no filter) passes."""
+    if value is not None and not value.strip():
+        raise ValueError(f'{param} must not be empty, got {value!r}')
+
+
+def conflict(param: str, other: str) -> ValueError:
+    """The error for two arguments that can't be given together."""
+    return ValueError(f'{param} cannot be combined with {other}')
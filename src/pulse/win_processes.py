@@ -0,0 +1,174 @@
+"""
+Pulse Windows Process Metrics
+Per-process handle counts and GPU usage, as Task Manager's Details tab shows them.
+
+Handle counts come from GetProcessHandleCount; GPU figures from the PDH
+"GPU Engine" and "GPU Process Memory" counters, whose instance names carry
+the pid ("pid_1234_luid_0x0_0x1_phys_0_eng_0_engtype_3D").
+"""
+import ctypes
+import re
+import threading
+from typing import Any, Dict, List, Optional, Tuple
+
+from pulse import direct_os
+from pulse.capabilities import requires
+from pulse.direct_os import WINDOWS
+from pulse.errors import Unsupported
+
+GPU_ENGINE_COUNTER = r'\GPU Engine(*)\Utilization Percentage'
+GPU_MEMORY_COUNTER = r'\GPU Process Memory(*)\Dedicated Usage'
+
+_INSTANCE = re.compile(r'pid_(\d+)_.*?(?:_engtype_(.+))?$')
+
+
+def parse_gpu_instance(name: str) -> Optional[Tuple[int, Optional[str]]]:
+    """(pid, engine type) from a GPU counter instance name; engine type is None for memory."""
+    match = _INSTANCE.match(name)
+    if match is None:
+        return None
+    return int(match.group(1)), match.group(2)
+
+
+def gpu_percents(engines: Dict[str, float]) -> Dict[int, float]:
+    """Per-pid GPU percent from "GPU Engine" utilization keyed by instance name.
+
+    Like Task Manager: each engine type's instances are summed, and a process
+    reports its busiest engine type (3D, Copy, VideoDecode, ...).
+    """
+    by_type: Dict[Tuple[int, Optional[str]], float] = {}
+    for name, value in engines.items():
+        parsed = parse_gpu_instance(name)
+        if parsed is not None:
+            by_type[parsed] = by_type.get(parsed, 0.0) + value
+    percents: Dict[int, float] = {}
+    for (pid, _), value in by_type.items():
+        percents[pid] = min(100.0, max(percents.get(pid, 0.0), value))
+    return percents
+
+
+def gpu_memory(usage: Dict[str, float]) -> Dict[int, int]:
+    """Per-pid dedicated GPU memory bytes from "GPU Process Memory" keyed by instance name.
+
+    A process using several adapters has one instance per adapter; they add up.
+    """
+    memory: Dict[int, int] = {}
+    for name, value in usage.items():
+        parsed = parse_gpu_instance(name)
+        if parsed is not None:
+            memory[parsed[0]] = memory.get(parsed[0], 0) + int(value)
+    return memory
+
+
+def windows_process_entry(pid: int, name: str, handle_count: Optional[int],
+                          percents: Optional[Dict[int, float]],
+                          memory: Optional[Dict[int, int]]) -> Dict[str, Any]:
+    """One process's entry; a pid absent from the GPU counters used no GPU.
+
+    percents or memory is None when the counters couldn't be read at all,
+    which makes the GPU fields None rather than 0.
+    """
+    return {
+        'pid': pid,
+        'name': name,
+        'handle_count': handle_count,
+        'gpu_percent': None if percents is None else percents.get(pid, 0.0),
+        'gpu_memory': None if memory is None else memory.get(pid, 0),
+    }
+
+
+if WINDOWS:
+    from ctypes import wintypes
+
+    _PROCESS_QUERY_LIMITED_INFORMATION = 0x1000
+    _PDH_FMT_DOUBLE = 0x00000200
+    _PDH_FMT_NOCAP100 = 0x00008000
+    _PDH_MORE_DATA = 0x800007D2
+
+    class _PdhFmtCounterValue(ctypes.Structure):
+        _fields_ = [('CStatus', wintypes.DWORD), ('doubleValue', ctypes.c_double)]
+
+    class _PdhFmtCounterValueItem(ctypes.Structure):
+        _fields_ = [('szName', wintypes.LPWSTR), ('FmtValue', _PdhFmtCounterValue)]
+
+    _kernel32 = ctypes.WinDLL('kernel32', use_last_error=True)
+    _kernel32.OpenProcess.restype = wintypes.HANDLE
+    _pdh = ctypes.WinDLL('pdh')
+
+    # Utilization is a rate between two collections, so the query stays open
+    # and each call reports usage since the previous one
+    _query_lock = threading.Lock()
+    _query: Optional[Tuple[wintypes.HANDLE, wintypes.HANDLE, wintypes.HANDLE]] = None
+
+    def _handle_count(pid: int) -> Optional[int]:
+        # Protected and other users' processes refuse the query; they stay listed with None
+        process = _kernel32.OpenProcess(_PROCESS_QUERY_LIMITED_INFORMATION, False, pid)
+        if not process:
+            return None
+        try:
+            count = wintypes.DWORD()
+            if not _kernel32.GetProcessHandleCount(wintypes.HANDLE(process), ctypes.byref(count)):
+                return None
+            return count.value
+        finally:
+            _kernel32.CloseHandle(wintypes.HANDLE(process))
+
+    def _open_query() -> Optional[Tuple[wintypes.HANDLE, wintypes.HANDLE, wintypes.HANDLE]]:
+        query, engine, memory = wintypes.HANDLE(), wintypes.HANDLE(), wintypes.HANDLE()
+        if _pdh.PdhOpenQueryW(None, None, ctypes.byref(query)) != 0:
+            return None
+        # English names work whatever the display language
+        if (_pdh.PdhAddEnglishCounterW(query, GPU_ENGINE_COUNTER, None, ctypes.byref(engine)) != 0
+                or _pdh.PdhAddEnglishCounterW(query, GPU_MEMORY_COUNTER, None, ctypes.byref(memory)) != 0):
+            # No WDDM 2.x GPU driver, so no GPU counters
+            _pdh.PdhCloseQuery(query)
+            return None
+        return query, engine, memory
+
+    def _counter_values(counter: wintypes.HANDLE) -> Optional[Dict[str, float]]:
+        size, count = wintypes.DWORD(), wintypes.DWORD()
+        fmt = _PDH_FMT_DOUBLE | _PDH_FMT_NOCAP100
+        status = _pdh.PdhGetFormattedCounterArrayW(counter, fmt, ctypes.byref(size), ctypes.byref(count), None)
+        if status & 0xFFFFFFFF != _PDH_MORE_DATA:
+            return None
+        buffer = ctypes.create_string_buffer(size.value)
+        if _pdh.PdhGetFormattedCounterArrayW(counter, fmt, ctypes.byref(size), ctypes.byref(count), buffer) != 0:
+            return None
+        items = ctypes.cast(buffer, ctypes.POINTER(_PdhFmtCounterValueItem))
+        return {items[i].szName: items[i].FmtValue.doubleValue
+                for i in range(count.value) if items[i].FmtValue.CStatus == 0}
+
+    def _gpu_counters() -> Tuple[Optional[Dict[int, float]], Optional[Dict[int, int]]]:
+        global _query
+        with _query_lock:
+            if _query is None:
+                _query = _open_query()
+                if _query is None:
+                    return None, None
+                # First collection only sets the baseline for utilization
+                _pdh.PdhCollectQueryData(_query[0])
+            if _pdh.PdhCollectQueryData(_query[0]) != 0:
+                return None, None
+            engines = _counter_values(_query[1])
+            usage = _counter_values(_query[2])
+        return (None if engines is None else gpu_percents(engines),
+                None if usage is None else gpu_memory(usage))
+
+    @requires('windows_process_info')
+    def get_windows_process_info() -> List[Dict[str, Any]]:
+        """Get each process's handle count, GPU percent, and dedicated GPU memory bytes.
+
+        gpu_percent is usage since the previous call (0 on the first).
+        Processes that refuse PROCESS_QUERY_LIMITED_INFORMATION are listed
+        with handle_count None, and every GPU field is None when the GPU
+        performance counters are missing.
+        """
+        percents, memory = _gpu_counters()
+        return [windows_process_entry(pid, name, _handle_count(pid), percents, memory)
+                for pid, name, _, _ in direct_os.get_process_rows()]
+
+else:
+    @requires('windows_process_info')
+    def get_windows_process_info() -> List[Dict[str, Any]]:
+        """Handle counts and the GPU Engine counters only exist on Windows."""
+        raise Unsupported('get_windows_process_info is only supported on Windows')
@@ -0,0 +1,88 @@
+"""
+Pulse Worker
+The child process behind isolated mode: python -m pulse.worker [msgpack|json].
+
+It reads requests from stdin and answers on stdout, one at a time, framed
+by pulse.protocol with the codec named on its command line (the default
+one when none is). Shown as JSON:
+
+    {"id": 1, "function": "get_cpu_percents", "args": [], "kwargs": {},
+     "state": null, "released": [], "settings": {...}}
+    {"id": 1, "result": [3.0, 12.5]}  or  {"id": 1, "error": {"type": ..., "message": ...}}
+
+"function" names a pulse.core collector. "state" is the token of the
+Monitor's state the parent called from (null for the module-level
+functions), so each keeps its own baselines here too; "released" lists
+the tokens of states the parent has since dropped, whose baselines go
+here as well. "settings" carries the parent's output
+settings, applied before the call. Anything else the worker prints goes
+to stderr, keeping stdout for messages.
+"""
+import os
+import sys
+from typing import Any, BinaryIO, Dict
+
+from pulse import protocol
+
+
+def _apply(settings: Dict[str, Any]) -> None:
+    from pulse import clock, compat, core, privacy, tags, units
+
+    units.set_units(**settings['units'])
+    compat.set_compat_mode(settings['compat'])
+    clock.set_clock(settings['clock'])
+    core.set_default_max_age(settings['max_age_ms'])
+    privacy.set_privacy_mode({'drop_environ': settings['drop_environ']})
+    # Recompiling every call would throw away the tags already worked out
+    if settings['process_tags'] != tags.get_process_tags():
+        tags.set_process_tags(settings['process_tags'])
+
+
+def handle(request: Dict[str, Any], states: Dict[Any, Any]) -> Dict[str, Any]:
+    """Run one request against pulse.core and build its response."""
+    from pulse import core
+    from pulse.state import SysState, using
+
+    for token in request.get('released', ()):
+        states.pop(token, None)
+    try:
+        if request['function'] not in core._COLLECTORS:
+            raise ValueError(f"Invalid function {request['function']!r}; expected a collector")
+        _apply(request['settings'])
+        function = getattr(core, request['function'])
+        key = request.get('state')
+        if key is None:
+            result = function(*request['args'], **request['kwargs'])
+        else:
+            with using(states.setdefault(key, SysState())):
+                result = function(*request['args'], **request['kwargs'])
+    except Exception as e:
+        return {'id': request['id'], 'error': {'type': type(e).__name__, 'message': str(e)}}
+    return {'id': request['id'], 'result': result}
+
+
+def serve(requests: BinaryIO, responses: BinaryIO, codec: str = protocol.DEFAULT_CODEC) -> None:
+    """Answer requests until the parent closes the pipe."""
+    states: Dict[Any, Any] = {}
+    while True:
+        request = protocol.read_message(requests, codec)
+        if request is None:
+            return
+        protocol.write_message(responses, handle(request, states), codec)
+
+
+def main() -> None:
+    codec = sys.argv[1] if len(sys.argv) > 1 else protocol.DEFAULT_CODEC
+    if codec not in protocol.CODECS:
+        sys.exit(f'Invalid codec {codec!r}; expected one of {", ".join(protocol.CODECS)}')
+    if codec == 'msgpack' and protocol.msgpack is None:
+        sys.exit('The msgpack codec needs the msgpack package (pip install pulse-monitor[msgpack])')
+    # Messages get the real stdout; print() and stray writes go to stderr
+    responses = os.fdopen(os.dup(sys.stdout.fileno()), 'wb')
+    os.dup2(sys.stderr.fileno(), sys.stdout.fileno())
+    sys.stdout = sys.stderr
+    serve(sys.stdin.buffer, responses, codec)
+
+
+if __name__ == '__main__':
+    main()
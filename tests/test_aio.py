@@ -0,0 +1,100 @@
+import asyncio
+import os
+import subprocess
+import sys
+import time
+import pytest
+from pulse import aio
+from pulse.errors import Timeout
+from pulse.state import STATE
+
+
+def _sleeper(secs):
+    return subprocess.Popen([sys.executable, "-c", f"import time; time.sleep({secs})"])
+
+
+@pytest.fixture(params=["pidfd", "polling"])
+def wait_mode(request, monkeypatch):
+    if request.param == "pidfd" and not hasattr(os, "pidfd_open"):
+        pytest.skip("no pidfd_open on this platform")
+    if request.param == "polling" and hasattr(os, "pidfd_open"):
+        monkeypatch.delattr(os, "pidfd_open")
+    monkeypatch.setattr(aio, "POLL_INTERVAL_SECS", 0.01)
+    return request.param
+
+
+def test_async_cpu_percents_uses_own_baseline(monkeypatch):
+    monkeypatch.setattr(STATE, "cpu_times", None)
+    percents = asyncio.run(aio.async_cpu_percents(0.1))
+    assert len(percents) == len(aio.core.get_cpu_percents())
+    assert all(0.0 <= p <= 100.0 for p in percents)
+    with pytest.raises(ValueError):
+        asyncio.run(aio.async_cpu_percents(0))
+
+
+def test_async_cpu_percents_ignores_default_max_age(monkeypatch):
+    reads = []
+    monkeypatch.setattr(aio.core.direct_os, "get_cpu_percents", lambda: reads.append(1) or [float(len(reads))])
+    aio.core.set_default_max_age(60_000)
+    try:
+        assert asyncio.run(aio.async_cpu_percents(0.05)) == [2.0]
+    finally:
+        aio.core.set_default_max_age(0)
+    assert len(reads) == 2
+
+
+def test_wait_for_process_exit(wait_mode):
+    child = _sleeper(0.2)
+    try:
+        waited = asyncio.run(aio.async_wait_for_process_exit(child.pid, timeout=10))
+    finally:
+        child.wait()
+    assert 0.0 < waited < 10
+
+
+def test_wait_for_process_exit_timeout(wait_mode):
+    child = _sleeper(30)
+    try:
+        with pytest.raises(Timeout):
+            asyncio.run(aio.async_wait_for_process_exit(child.pid, timeout=0.1))
+    finally:
+        child.kill()
+        child.wait()
+
+
+def test_cancel_stops_the_wait(wait_mode, monkeypatch):
+    checks = []
+    real_pid_exists = aio._pid_exists
+    monkeypatch.setattr(aio, "_pid_exists", lambda pid: checks.append(pid) or real_pid_exists(pid))
+    child = _sleeper(30)
+
+    async def cancel_soon():
+        task = asyncio.create_task(aio.async_wait_for_process_exit(child.pid))
+        await asyncio.sleep(0.1)
+        task.cancel()
+        with pytest.raises(asyncio.CancelledError):
+            await task
+        count = len(checks)
+        await asyncio.sleep(0.1)
+        return count
+
+    try:
+        started = time.monotonic()
+        count = asyncio.run(cancel_soon())
+        assert time.monotonic() - started < 5
+        # No more polling once the task was cancelled
+        assert len(checks) == count
+    finally:
+        child.kill()
+        child.wait()
+
+
+def test_wait_for_missing_process_returns_immediately():
+    child = _sleeper(0)
+    child.wait()
+    assert asyncio.run(aio.async_wait_for_process_exit(child.pid, timeout=1)) < 1
+
+
+def test_async_snapshot():
+    snap = asyncio.run(aio.async_snapshot(include=["memory", "network"]))
+    assert set(snap) == {"timestamp", "memory", "network"}
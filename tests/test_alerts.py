@@ -0,0 +1,100 @@
+import pytest
+from pulse import alerts, core, sampler
+
+
+@pytest.fixture
+def readings(monkeypatch):
+    """Feed check_alerts() a scripted series of fresh snapshots, one per call."""
+    series = []
+
+    def fake_snapshot(include=None, options=None):
+        return series.pop(0)
+
+    monkeypatch.setattr(core, "snapshot", fake_snapshot)
+    monkeypatch.setattr(sampler, "sampler_status", lambda: {"running": False, "sections": []})
+    yield series
+    alerts.set_alerts([])
+
+
+def _cpu(timestamp, *percents):
+    return {"timestamp": timestamp, "cpu": list(percents)}
+
+
+def test_sustained_cpu_alert_fires_once_and_resolves(readings):
+    alerts.set_alerts([{"metric": "cpu_total", "operator": ">", "threshold": 90, "sustain_secs": 30}])
+    readings += [_cpu(0, 95, 95), _cpu(20, 99, 93), _cpu(30, 92, 96), _cpu(40, 100, 100), _cpu(50, 10, 20)]
+
+    assert alerts.check_alerts() == {"fired": [], "resolved": []}
+    assert alerts.check_alerts()["fired"] == []
+    fired = alerts.check_alerts()["fired"]
+    assert fired == [{"name": "cpu_total > 90", "metric": "cpu_total", "operator": ">", "threshold": 90.0,
+                      "value": 94.0, "timestamp": 30, "since": 0}]
+    # Still firing: not reported again
+    assert alerts.check_alerts() == {"fired": [], "resolved": []}
+    resolved = alerts.check_alerts()["resolved"]
+    assert [(a["name"], a["value"]) for a in resolved] == [("cpu_total > 90", 15.0)]
+
+
+def test_dip_below_threshold_restarts_sustain(readings):
+    alerts.set_alerts([{"metric": "cpu_total", "threshold": 90, "sustain_secs": 30}])
+    readings += [_cpu(0, 95), _cpu(20, 50), _cpu(40, 95), _cpu(60, 95), _cpu(70, 95)]
+    results = [alerts.check_alerts()["fired"] for _ in range(5)]
+    assert [bool(r) for r in results] == [False, False, False, False, True]
+    assert results[-1][0]["since"] == 40
+
+
+def test_clear_threshold_prevents_flapping(readings):
+    alerts.set_alerts([{"metric": "memory_percent", "threshold": 90, "clear_threshold": 80}])
+    for t, pct in enumerate([91, 85, 89, 91, 79]):
+        readings.append({"timestamp": t, "memory": {"percent": pct}})
+    results = [alerts.check_alerts() for _ in range(5)]
+    assert [len(r["fired"]) for r in results] == [1, 0, 0, 0, 0]
+    assert [len(r["resolved"]) for r in results] == [0, 0, 0, 0, 1]
+
+
+def test_disk_and_process_metrics(readings):
+    alerts.set_alerts([
+        {"metric": "disk_percent:/", "threshold": 95, "name": "root full"},
+        {"metric": "process_cpu:worker", "operator": ">=", "threshold": 150},
+        {"metric": "disk_percent:/missing", "threshold": 1},
+    ])
+    readings.append({
+        "timestamp": 5,
+        "disks": [{"mountpoint": "/", "percent": 97.5}, {"mountpoint": "/home", "percent": 99.0}],
+        "processes": [{"name": "worker", "cpu_percent": 80.0}, {"name": "worker", "cpu_percent": 70.0},
+                      {"name": "shell", "cpu_percent": 99.0}],
+    })
+    fired = alerts.check_alerts()["fired"]
+    assert [(a["name"], a["value"]) for a in fired] == [("root full", 97.5), ("process_cpu:worker >= 150", 150.0)]
+
+
+def test_sampler_history_is_walked(monkeypatch, readings):
+    """Every sample since the last check counts, so a sustained spike between checks fires."""
+    samples = [_cpu(t, 95) for t in range(0, 40, 10)] + [_cpu(40, 10)]
+    monkeypatch.setattr(sampler, "sampler_status", lambda: {"running": True, "sections": ["cpu", "memory"]})
+    monkeypatch.setattr(sampler, "get_samples",
+                        lambda since_timestamp=None: [s for s in samples
+                                                      if since_timestamp is None or s["timestamp"] > since_timestamp])
+    alerts.set_alerts([{"metric": "cpu_total", "threshold": 90, "sustain_secs": 30}])
+    result = alerts.check_alerts()
+    assert [a["timestamp"] for a in result["fired"]] == [30]
+    assert [a["timestamp"] for a in result["resolved"]] == [40]
+    # Nothing new in the history: nothing re-evaluated
+    assert alerts.check_alerts() == {"fired": [], "resolved": []}
+
+
+@pytest.mark.parametrize("rule, message", [
+    ({"metric": "gpu_total", "threshold": 1}, "unknown metric"),
+    ({"metric": "disk_percent", "threshold": 1}, "needs a target"),
+    ({"metric": "cpu_total:0", "threshold": 1}, "takes no target"),
+    ({"metric": "cpu_total", "operator": "!=", "threshold": 1}, "unknown operator"),
+    ({"metric": "cpu_total"}, "threshold"),
+    ({"metric": "cpu_total", "threshold": "high"}, "must be numbers"),
+    ({"metric": "cpu_total", "threshold": 90, "clear_threshold": 95}, "at or below"),
+    ({"metric": "cpu_total", "operator": "<", "threshold": 10, "clear_threshold": 5}, "at or above"),
+    ({"metric": "cpu_total", "threshold": 90, "sustain": 5}, "unknown keys"),
+])
+def test_invalid_rules_name_the_rule(rule, message):
+    with pytest.raises(ValueError, match=message) as excinfo:
+        alerts.set_alerts([{"metric": "memory_percent", "threshold": 50}, rule])
+    assert str(excinfo.value).startswith("Alert rule 1 ")
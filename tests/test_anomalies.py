@@ -0,0 +1,100 @@
+import math
+import pytest
+from pulse import anomalies, sampler
+
+
+def _sample(timestamp, cpu=None, memory=None, network=None, disks=None):
+    sample = {"timestamp": timestamp}
+    for section, value in (("cpu", cpu), ("memory", memory), ("network", network), ("disks", disks)):
+        if value is not None:
+            sample[section] = value
+    return sample
+
+
+def test_baseline_and_zscore():
+    mean, stddev = anomalies.baseline([2, 4, 4, 4, 5, 5, 7, 9])
+    assert (mean, stddev) == (5.0, 2.0)
+    assert anomalies.zscore(11, mean, stddev) == 3.0
+    assert anomalies.zscore(11, 5.0, 0.0) is None
+
+
+def test_series_from_samples_rates_and_resets():
+    samples = [
+        _sample(0, cpu=[10, 30], memory={"percent": 40.0}, network={"bytes_recv": 0, "bytes_sent": 0},
+                disks=[{"mountpoint": "/", "percent": 50.0}]),
+        _sample(2, cpu=[20, 20], network={"bytes_recv": 2000, "bytes_sent": 100}),
+        # Counters went backwards: no rate for this step
+        _sample(4, network={"bytes_recv": 500, "bytes_sent": 50}),
+    ]
+    series = anomalies.series_from_samples(samples)
+    assert series["cpu_total"] == [(0, 20.0), (2, 20.0)]
+    assert series["memory_percent"] == [(0, 40.0)]
+    assert series["disk_percent:/"] == [(0, 50.0)]
+    assert series["net_recv_per_sec"] == [(2, 1000.0)]
+    assert series["net_sent_per_sec"] == [(2, 50.0)]
+
+
+def test_series_from_samples_per_interface_rates():
+    samples = [
+        _sample(0, network={"bytes_recv": 0, "bytes_sent": 0, "interfaces": {
+            "eth0": {"bytes_recv": 0, "bytes_sent": 0}}}),
+        _sample(2, network={"bytes_recv": 0, "bytes_sent": 0, "interfaces": {
+            "eth0": {"bytes_recv": 4000, "bytes_sent": 200}, "wg0": {"bytes_recv": 10, "bytes_sent": 10}}}),
+        # eth0 went away and came back with fresh counters
+        _sample(4, network={"bytes_recv": 0, "bytes_sent": 0, "interfaces": {
+            "eth0": {"bytes_recv": 100, "bytes_sent": 300}, "wg0": {"bytes_recv": 30, "bytes_sent": 10}}}),
+    ]
+    series = anomalies.series_from_samples(samples)
+    assert series["net_rx_rate:eth0"] == [(2, 2000.0)]
+    assert series["net_tx_rate:eth0"] == [(2, 100.0), (4, 50.0)]
+    # wg0 first appears at 2, so its first rate is at 4
+    assert series["net_rx_rate:wg0"] == [(4, 10.0)]
+    assert series["net_tx_rate:wg0"] == [(4, 0.0)]
+
+
+def test_interface_rate_spike_is_flagged(monkeypatch):
+    samples = [_sample(t, network={"bytes_recv": 0, "bytes_sent": 0, "interfaces": {
+                   "eth0": {"bytes_recv": 1000 * t, "bytes_sent": 0}}}) for t in range(10)]
+    samples.append(_sample(10, network={"bytes_recv": 0, "bytes_sent": 0, "interfaces": {
+        "eth0": {"bytes_recv": 9000 + 50 * 1024 * 1024, "bytes_sent": 0}}}))
+    monkeypatch.setattr(sampler, "get_samples", lambda since_timestamp=None: samples)
+    # A flat 1000 B/s baseline, so only the absolute change counts
+    assert [(a["series"], a["score"]) for a in anomalies.get_anomalies()] == [("net_rx_rate:eth0", None)]
+
+
+def test_find_anomalies_flags_outliers_only():
+    steady = [(t, 50.0 + (t % 2)) for t in range(10)]
+    series = {
+        "cpu_total": steady + [(10, 95.0)],
+        "memory_percent": steady + [(10, 51.0)],
+        # Too short for a baseline
+        "disk_percent:/": [(0, 10.0), (1, 99.0)],
+    }
+    found = anomalies.find_anomalies(series, 3.0)
+    assert [a["series"] for a in found] == ["cpu_total"]
+    assert found[0]["mean"] == 50.5 and found[0]["stddev"] == 0.5
+    assert found[0]["score"] == 89.0
+    # A drop is as anomalous as a spike
+    assert anomalies.find_anomalies({"cpu_total": steady + [(10, 0.0)]}, 3.0)[0]["score"] < 0
+
+
+def test_flat_series_needs_a_large_absolute_change():
+    flat = [(t, 30.0) for t in range(10)]
+    assert anomalies.find_anomalies({"memory_percent": flat + [(10, 30.5)]}, 3.0) == []
+    jump = anomalies.find_anomalies({"memory_percent": flat + [(10, 45.0)],
+                                     "cpu_total": [(t, 50.0 + t % 2) for t in range(10)] + [(10, 60.0)]},
+                                    3.0)
+    assert [(a["series"], a["score"]) for a in jump] == [("memory_percent", None), ("cpu_total", 19.0)]
+    assert not any(isinstance(a["score"], float) and math.isinf(a["score"]) for a in jump)
+
+
+def test_get_anomalies_uses_only_the_window(monkeypatch):
+    # An old spike outside the window must not inflate the baseline
+    samples = [_sample(0, cpu=[100.0])] + [_sample(t, cpu=[10.0 + t % 2]) for t in range(100, 110)]
+    samples.append(_sample(110, cpu=[90.0]))
+    monkeypatch.setattr(sampler, "get_samples", lambda since_timestamp=None: samples)
+    found = anomalies.get_anomalies(window_secs=30)
+    assert [a["series"] for a in found] == ["cpu_total"]
+    assert found[0]["mean"] == 10.5
+    monkeypatch.setattr(sampler, "get_samples", lambda since_timestamp=None: [])
+    assert anomalies.get_anomalies() == []
@@ -0,0 +1,63 @@
+import pytest
+from pulse import audio
+
+PACTL_VOLUME = (
+    "Volume: front-left: 42597 /  65% / -11.23 dB,   front-right: 45875 /  70% / -9.29 dB\n"
+    "        balance 0.07\n"
+)
+
+AMIXER_MASTER = """Simple mixer control 'Master',0
+  Capabilities: pvolume pswitch pswitch-joined
+  Playback channels: Front Left - Front Right
+  Limits: Playback 0 - 87
+  Mono:
+  Front Left: Playback 57 [66%] [-22.50dB] [off]
+  Front Right: Playback 57 [66%] [-22.50dB] [off]
+"""
+
+def test_parse_pactl_volume_averages_channels():
+    assert audio.parse_pactl_volume(PACTL_VOLUME) == 67.5
+    assert audio.parse_pactl_volume("Volume: mono: 65536 / 100% / 0.00 dB\n") == 100.0
+
+def test_parse_pactl_mute():
+    assert audio.parse_pactl_mute("Mute: yes\n") is True
+    assert audio.parse_pactl_mute("Mute: no\n") is False
+
+def test_parse_amixer():
+    assert audio.parse_amixer(AMIXER_MASTER) == {"volume_percent": 66.0, "muted": True}
+    unmuted = AMIXER_MASTER.replace("[off]", "[on]")
+    assert audio.parse_amixer(unmuted)["muted"] is False
+
+@pytest.mark.skipif(not audio.LINUX, reason="pactl/amixer backends are Linux-only")
+def test_get_audio_state_pactl(monkeypatch):
+    outputs = {
+        "get-default-sink": "alsa_output.pci-0000_00_1f.3.analog-stereo\n",
+        "get-sink-volume": PACTL_VOLUME,
+        "get-sink-mute": "Mute: no\n",
+    }
+    monkeypatch.setattr(audio.shutil, "which", lambda name: f"/usr/bin/{name}")
+    monkeypatch.setattr(audio, "_run", lambda args: outputs.get(args[1]) if args[0] == "pactl" else None)
+    assert audio.get_audio_state() == {
+        "sink": "alsa_output.pci-0000_00_1f.3.analog-stereo",
+        "volume_percent": 67.5, "muted": False, "backend": "pulseaudio",
+    }
+
+def test_tools_run_untranslated(monkeypatch):
+    seen = {}
+
+    def run(args, **kwargs):
+        seen.update(kwargs["env"])
+        return audio.subprocess.CompletedProcess(args, 0, stdout="Mute: yes\n")
+
+    monkeypatch.setenv("LANG", "de_DE.UTF-8")
+    monkeypatch.setattr(audio.shutil, "which", lambda name: f"/usr/bin/{name}")
+    monkeypatch.setattr(audio.subprocess, "run", run)
+    assert audio._run(["pactl", "get-sink-mute", "@DEFAULT_SINK@"]) == "Mute: yes\n"
+    assert seen["LC_ALL"] == "C" and seen["LANG"] == "de_DE.UTF-8"
+
+@pytest.mark.skipif(not audio.LINUX, reason="pactl/amixer backends are Linux-only")
+def test_get_audio_state_unavailable(monkeypatch):
+    monkeypatch.setattr(audio.shutil, "which", lambda name: f"/usr/bin/{name}")
+    monkeypatch.setattr(audio, "_run", lambda args: None)
+    with pytest.raises(NotImplementedError):
+        audio.get_audio_state()
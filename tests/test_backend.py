@@ -0,0 +1,107 @@
+import time
+import pytest
+from pulse import backend, core
+from pulse.state import STATE
+
+GIB = 1 << 30
+
+PROCESSES = [
+    {"pid": 10, "name": "idle", "cpu_percent": 0.5, "memory_info": 5 << 20},
+    {"pid": 20, "name": "build", "cpu_percent": 97.0, "memory_info": 300 << 20},
+    {"pid": 30, "name": "browser", "cpu_percent": 12.0, "memory_info": 900 << 20},
+]
+
+DISKS = [
+    {"device": "/dev/sda1", "mountpoint": "/", "total": 100 * GIB, "used": 75 * GIB,
+     "free": 25 * GIB, "available": 25 * GIB},
+    {"device": "tmpfs", "mountpoint": "/run", "fstype": "tmpfs", "total": GIB, "used": 0,
+     "free": GIB, "available": GIB},
+]
+
+
+@pytest.fixture
+def mock():
+    core.set_backend("mock")
+    core.mock_clear()
+    yield
+    core.mock_clear()
+    core.set_backend("live")
+
+
+def test_switching_backends_drops_baselines(mock):
+    assert core.get_backend() == "mock"
+    STATE.cpu_times = [{"busy": 1, "total": 2}]
+    core.set_backend("live")
+    assert core.get_backend() == "live"
+    assert STATE.cpu_times is None
+
+
+def test_unset_sections_read_empty(mock):
+    assert core.get_cpu_percents() == []
+    assert core.get_process_list() == []
+    assert core.get_network_stats() == {"bytes_recv": 0, "bytes_sent": 0}
+    assert core.get_memory_info()["total"] == 0
+    assert core.get_disk_info() == []
+    assert core.get_disk_io() == {}
+
+
+def test_memory_is_returned_exactly(mock):
+    memory = {"total": 16 * GIB, "available": 4 * GIB, "used": 12 * GIB, "percent": 75.0,
+              "swap_total": 2 * GIB, "swap_used": 0}
+    core.mock_set_data("memory", memory)
+    assert core.get_memory_info() == memory
+    # Results are copies: editing one leaves the injected data alone
+    core.get_memory_info()["total"] = 1
+    assert core.get_memory_info()["total"] == 16 * GIB
+
+
+def test_cpu_percents_come_from_counter_deltas(mock):
+    core.mock_set_data("cpu", [{"busy": 100, "total": 400}, {"busy": 0, "total": 400}])
+    assert core.get_cpu_percents() == [0.0, 0.0]
+    core.mock_set_data("cpu", [{"busy": 300, "total": 600}, {"busy": 50, "total": 600}])
+    time.sleep(0.06)
+    assert core.get_cpu_percents() == [100.0, 25.0]
+
+
+def test_process_sorting_and_limits_run_over_mock_rows(mock):
+    core.mock_set_data("processes", PROCESSES)
+    assert [p["pid"] for p in core.get_process_list(sort_by="cpu")] == [20, 30, 10]
+    assert [p["name"] for p in core.get_process_list(sort_by="mem", limit=1)] == ["browser"]
+
+
+def test_disk_filters_and_usage(mock):
+    core.mock_set_data("disks", DISKS)
+    disks = core.get_disk_info()
+    # tmpfs is a pseudo filesystem, hidden unless physical_only=False
+    assert [d["mountpoint"] for d in disks] == ["/"]
+    assert disks[0]["percent"] == 75.0 and disks[0]["used_space"] == disks[0]["used"]
+    assert disks[0]["fstype"] == "ext4" and disks[0]["is_read_only"] is False
+    assert len(core.get_disk_info(physical_only=False)) == 2
+    usage = core.get_path_usage("/")
+    assert usage["mountpoint"] == "/" and usage["used"] == 75 * GIB
+
+
+def test_disk_io_rates_over_mock_counters(mock):
+    counters = {"read_count": 0, "write_count": 0, "read_bytes": 0, "write_bytes": 0,
+                "read_time_ms": 0, "write_time_ms": 0}
+    core.mock_set_data("disk_io", {"sda": counters})
+    assert core.get_disk_io_rates()["sda"]["read_bytes_per_sec"] == 0.0
+    core.mock_set_data("disk_io", {"sda": dict(counters, read_bytes=10 << 20)})
+    time.sleep(0.05)
+    rate = core.get_disk_io_rates()["sda"]["read_bytes_per_sec"]
+    assert 0 < rate < (10 << 20) / 0.05
+
+
+def test_monitor_reads_the_mock(mock):
+    core.mock_set_data("network", {"bytes_recv": 1000, "bytes_sent": 10})
+    assert core.Monitor().network_stats() == {"bytes_recv": 1000, "bytes_sent": 10}
+
+
+def test_bad_payloads_are_rejected():
+    with pytest.raises(ValueError, match="processes payload"):
+        core.mock_set_data("processes", [{"pid": 1}])
+    with pytest.raises(ValueError, match="cpu payload"):
+        core.mock_set_data("cpu", [50.0])
+    with pytest.raises(ValueError, match="disks payload"):
+        core.mock_set_data("disks", [{"mountpoint": "/"}])
+    assert backend.MOCK.cpu_times() == []
@@ -0,0 +1,117 @@
+import logging
+import time
+import pytest
+from pulse import baselines, core, log
+from pulse.state import SysState, using
+
+COUNTERS = {"read_count": 0, "write_count": 0, "read_bytes": 0, "write_bytes": 0,
+            "read_time_ms": 0, "write_time_ms": 0}
+
+
+class _Records(logging.Handler):
+    def __init__(self):
+        super().__init__(logging.DEBUG)
+        self.records = []
+
+    def emit(self, record):
+        self.records.append(record)
+
+
+@pytest.fixture
+def warnings():
+    handler = _Records()
+    log.logger.addHandler(handler)
+    core.enable_logging("warning")
+    yield handler.records
+    log.logger.removeHandler(handler)
+    core.enable_logging("off")
+
+
+@pytest.fixture
+def mock():
+    core.set_backend("mock")
+    yield
+    core.mock_clear()
+    core.set_backend("live")
+
+
+def test_values_round_trip_exactly():
+    value = {
+        (12, 3456): {"read_bytes": 1, "pids": [1, 2]},
+        7: (1.5, {"eth0": {"bytes_recv": 3}}),
+        "__items__": None,
+        "cpus": {0: {"running_ns": 9}},
+    }
+    assert baselines.decode_value(baselines.encode_value(value)) == value
+
+
+def test_state_round_trips_between_monitors(tmp_path):
+    saved = SysState()
+    saved.process_io = {(101, 5000): {"read_bytes": 10, "write_bytes": 20}}
+    saved.process_io_time = time.monotonic()
+    saved.namespace_network = {4026531992: (12.5, {"eth0": {"bytes_recv": 1, "bytes_sent": 2}})}
+    saved.cgroup_cpu = {"/system.slice": (3.0, 900)}
+    saved.cache = {("memory", "()"): (1.0, {})}
+    with using(saved):
+        core.save_state(str(tmp_path / "state"))
+
+    restored = SysState()
+    with using(restored):
+        assert core.load_state(str(tmp_path / "state")) is True
+    for name in baselines.PERSISTED:
+        assert getattr(restored, name) == getattr(saved, name), name
+    assert restored.cache == {}
+
+
+def test_first_rate_after_restart_uses_saved_baseline(tmp_path, mock):
+    core.mock_set_data("disk_io", {"sda": COUNTERS})
+    with using(SysState()):
+        core.get_disk_io_rates()
+        core.save_state(str(tmp_path / "state"))
+
+    core.mock_set_data("disk_io", {"sda": dict(COUNTERS, read_bytes=1 << 20)})
+    time.sleep(0.02)
+    with using(SysState()):
+        assert core.load_state(str(tmp_path / "state"))
+        assert core.get_disk_io_rates()["sda"]["read_bytes_per_sec"] > 0
+    # Without the saved baseline the first reading is only a baseline
+    with using(SysState()):
+        assert core.get_disk_io_rates()["sda"]["read_bytes_per_sec"] == 0.0
+
+
+def test_boot_time_changed_discards_baselines(tmp_path, monkeypatch, warnings):
+    saved = SysState()
+    saved.udp_counters = {"InDatagrams": 5}
+    with using(saved):
+        core.save_state(str(tmp_path / "state"))
+    boot = baselines._boot_time()
+    monkeypatch.setattr(baselines, "_boot_time", lambda: boot + 3600)
+
+    restored = SysState()
+    with using(restored):
+        assert core.load_state(str(tmp_path / "state")) is False
+    assert restored.udp_counters is None
+    [record] = warnings
+    assert "rebooted" in record.getMessage()
+    assert record.path == str(tmp_path / "state")
+
+
+def test_corrupt_and_mismatched_files_are_ignored(tmp_path, warnings):
+    path = tmp_path / "state"
+    with using(SysState()):
+        core.save_state(str(path))
+    data = path.read_bytes()
+
+    for broken in (b"garbage", data[:len(baselines.MAGIC)], data[:-5],
+                   baselines.MAGIC + bytes([baselines.VERSION + 1]) + data[len(baselines.MAGIC) + 1:],
+                   baselines.MAGIC + bytes([baselines.VERSION]) + b'{"baselines": 3}'):
+        path.write_bytes(broken)
+        with using(SysState()):
+            assert core.load_state(str(path)) is False
+    messages = [r.getMessage() for r in warnings]
+    assert len(messages) == 5
+    assert any("version 2, expected 1" in m for m in messages)
+
+
+def test_missing_file_is_not_an_error(tmp_path):
+    assert core.load_state(str(tmp_path / "absent")) is False
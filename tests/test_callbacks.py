@@ -0,0 +1,84 @@
+import time
+import pytest
+from pulse import alerts, callbacks, sampler
+
+
+@pytest.fixture
+def clean_callbacks(monkeypatch):
+    monkeypatch.setattr(callbacks, "_callbacks", {event: [] for event in callbacks.EVENTS})
+    callbacks.get_callback_errors()
+    yield
+    sampler.stop_sampler()
+    callbacks.get_callback_errors()
+
+
+def test_sample_callback_fires_once_per_sample(clean_callbacks):
+    seen = []
+    callbacks.register_callback("sample", seen.append)
+    sampler.start_sampler(interval_ms=10, sections=["memory"])
+    deadline = time.monotonic() + 3
+    while len(seen) < 5 and time.monotonic() < deadline:
+        time.sleep(0.01)
+    sampler.stop_sampler()
+
+    assert len(seen) == sampler.sampler_status()["collected"] >= 5
+    assert [s["timestamp"] for s in seen] == [s["timestamp"] for s in sampler.get_samples()]
+
+    callbacks.unregister_callback("sample", seen.append)
+    count = len(seen)
+    callbacks.on_sample({"timestamp": 1.0}, None)
+    assert len(seen) == count
+
+
+def test_failing_callback_is_recorded_and_others_still_run(clean_callbacks):
+    def broken(sample):
+        raise RuntimeError("widget gone")
+    after = []
+    callbacks.register_callback("sample", broken)
+    callbacks.register_callback("sample", after.append)
+
+    callbacks.on_sample({"timestamp": 1.0}, None)
+    callbacks.on_sample({"timestamp": 2.0}, None)
+    errors = callbacks.get_callback_errors()
+    assert len(after) == 2
+    assert [e["error"] for e in errors] == ["RuntimeError: widget gone"] * 2
+    assert errors[0]["event"] == "sample" and "broken" in errors[0]["callback"]
+    assert callbacks.get_callback_errors() == []
+
+
+def test_process_events(clean_callbacks):
+    started, exited = [], []
+    callbacks.register_callback("process_started", started.append)
+    callbacks.register_callback("process_exited", exited.append)
+    first = {"timestamp": 1.0, "processes": [{"pid": 1, "name": "init"}, {"pid": 50, "name": "old"}]}
+    second = {"timestamp": 2.0, "processes": [{"pid": 1, "name": "init"}, {"pid": 60, "name": "new"}]}
+
+    callbacks.on_sample(first, None)
+    assert started == exited == []
+    callbacks.on_sample(second, first)
+    assert started == [{"pid": 60, "name": "new"}]
+    assert exited == [{"pid": 50, "name": "old"}]
+
+
+def test_alert_callbacks(clean_callbacks, monkeypatch):
+    fired = []
+    callbacks.register_callback("alert_fired", fired.append)
+    monkeypatch.setattr(alerts, "check_alerts", lambda: {"fired": [{"name": "hot"}], "resolved": []})
+    callbacks.on_sample({"timestamp": 1.0}, None)
+    assert fired == [{"name": "hot"}]
+
+
+def test_no_callbacks_while_finalizing(clean_callbacks, monkeypatch):
+    seen = []
+    callbacks.register_callback("sample", seen.append)
+    monkeypatch.setattr(callbacks.sys, "is_finalizing", lambda: True)
+    callbacks.on_sample({"timestamp": 1.0}, None)
+    assert seen == []
+
+
+def test_registration_validation(clean_callbacks):
+    with pytest.raises(ValueError):
+        callbacks.register_callback("cpu_spike", print)
+    with pytest.raises(TypeError):
+        callbacks.register_callback("sample", "not callable")
+    callbacks.unregister_callback("sample", print)
@@ -0,0 +1,46 @@
+import pytest
+from pulse import capabilities, core
+from pulse.errors import Unsupported
+
+
+def test_every_feature_is_reported():
+    caps = core.get_capabilities()
+    assert list(caps) == list(capabilities.FEATURES)
+    assert all(isinstance(value, bool) for value in caps.values())
+    assert caps["load_average"] == hasattr(__import__("os"), "getloadavg")
+
+
+@pytest.mark.parametrize("feature", list(capabilities.FEATURES))
+def test_getter_agrees_with_capability(feature):
+    getter = getattr(core, capabilities.FEATURES[feature][0])
+    if core.get_capabilities()[feature]:
+        try:
+            getter()
+        except Unsupported as e:
+            pytest.fail(f"{feature} is reported but its getter raised: {e}")
+    elif feature in capabilities.EMPTY_WHEN_MISSING:
+        assert getter() == []
+    else:
+        with pytest.raises(Unsupported):
+            getter()
+
+
+@pytest.mark.parametrize("feature", [f for f in capabilities.FEATURES if f not in capabilities.EMPTY_WHEN_MISSING])
+def test_getter_consults_the_table(feature, monkeypatch):
+    getter_name, _ = capabilities.FEATURES[feature]
+    monkeypatch.setitem(capabilities.FEATURES, feature, (getter_name, lambda: False))
+    assert core.get_capabilities()[feature] is False
+    with pytest.raises(Unsupported, match=feature):
+        getattr(core, getter_name)()
+
+
+def test_init_skips_unsupported_udp_stats(monkeypatch):
+    monkeypatch.setitem(capabilities.FEATURES, "udp_stats", ("get_udp_stats", lambda: False))
+    core.Monitor(refresh=["network"])
+
+
+def test_explain_names_each_requirement():
+    explained = core.get_capabilities(explain=True)
+    assert set(explained) == set(capabilities.FEATURES) == set(capabilities.REQUIREMENTS)
+    assert {feature: entry["supported"] for feature, entry in explained.items()} == core.get_capabilities()
+    assert "CAP_NET_ADMIN" in explained["exit_accounting"]["requires"]
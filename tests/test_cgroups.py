@@ -0,0 +1,34 @@
+import pytest
+from pulse import cgroups
+from pulse.state import STATE
+
+def test_parse_flat_keyed():
+    text = "usage_usec 5000\nuser_usec 3000\nsystem_usec 2000\nnr_periods 0\n"
+    assert cgroups.parse_flat_keyed(text)["usage_usec"] == 5000
+
+def test_parse_limit():
+    assert cgroups.parse_limit("max\n") is None
+    assert cgroups.parse_limit("536870912\n") == 536870912
+
+def test_read_usage(tmp_path):
+    (tmp_path / "cpu.stat").write_text("usage_usec 5000\nuser_usec 3000\n")
+    (tmp_path / "memory.current").write_text("1048576\n")
+    (tmp_path / "memory.max").write_text("max\n")
+    (tmp_path / "cgroup.procs").write_text("812\n813\n")
+    assert cgroups.read_usage(str(tmp_path)) == {
+        "cpu_usage_usec": 5000, "memory_current": 1048576, "memory_max": None,
+    }
+    assert cgroups.read_pids(str(tmp_path)) == [812, 813]
+
+def test_read_usage_without_controllers(tmp_path):
+    assert cgroups.read_usage(str(tmp_path)) == {
+        "cpu_usage_usec": None, "memory_current": None, "memory_max": None,
+    }
+
+def test_cpu_percent_needs_a_baseline(monkeypatch):
+    monkeypatch.setattr(STATE, "cgroup_cpu", {})
+    clock = iter([100.0, 102.0])
+    monkeypatch.setattr(cgroups.time, "monotonic", lambda: next(clock))
+    assert cgroups.cpu_percent("/system.slice/a.service", 1_000_000) is None
+    # 1 s of CPU over 2 s of wall time
+    assert cgroups.cpu_percent("/system.slice/a.service", 2_000_000) == pytest.approx(50.0)
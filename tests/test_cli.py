@@ -0,0 +1,57 @@
+import json
+import os
+import subprocess
+import sys
+import pytest
+import pulse
+from pulse import cli, direct_os
+
+SRC = os.path.dirname(os.path.dirname(pulse.__file__))
+
+
+def _run(*args):
+    env = dict(os.environ, PYTHONPATH=SRC)
+    result = subprocess.run([sys.executable, "-m", "pulse.cli", *args], capture_output=True,
+                            text=True, timeout=60, env=env)
+    assert result.returncode == 0, result.stderr
+    return result.stdout
+
+
+def test_snapshot_json_matches_the_api_schema():
+    snap = json.loads(_run("snapshot", "--json"))
+    assert set(snap) == {"timestamp", "cpu", "memory", "processes", "network", "disks"}
+    assert set(snap["processes"][0]) == set(direct_os.PROCESS_FIELDS)
+    assert {"total", "used", "available"} <= set(snap["memory"])
+
+
+def test_watch_emits_json_lines():
+    lines = _run("watch", "--interval", "0.05", "--count", "3", "--include", "cpu,memory").splitlines()
+    samples = [json.loads(line) for line in lines]
+    assert len(samples) == 3
+    assert all(set(s) == {"timestamp", "cpu", "memory"} for s in samples)
+    assert samples[0]["timestamp"] < samples[2]["timestamp"]
+
+
+def test_top(capsys):
+    assert cli.main(["top", "--sort", "mem", "--limit", "3", "--json"]) == 0
+    rows = json.loads(capsys.readouterr().out)
+    assert len(rows) == 3
+    assert [r["memory_info"] for r in rows] == sorted((r["memory_info"] for r in rows), reverse=True)
+
+    assert cli.main(["top", "--limit", "2"]) == 0
+    table = capsys.readouterr().out.splitlines()
+    assert table[0].split() == ["PID", "NAME", "CPU%", "MEMORY"]
+    assert len(table) == 3
+
+
+@pytest.mark.parametrize("argv, message", [
+    (["top", "--limit", "0"], "must be >= 1"),
+    (["top", "--sort", "name"], "invalid choice"),
+    (["watch", "--interval", "-1"], "must be > 0"),
+    (["snapshot", "--include", "gpu"], "Invalid include 'gpu'"),
+])
+def test_bad_arguments_exit_with_usage(argv, message, capsys):
+    with pytest.raises(SystemExit) as info:
+        cli.main(argv)
+    assert info.value.code == 2
+    assert message in capsys.readouterr().err
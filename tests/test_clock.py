@@ -0,0 +1,117 @@
+import time
+import pytest
+from pulse import clock
+
+W32TM_SYNCED = """Leap Indicator: 0(no warning)
+Stratum: 4 (secondary reference - syncd by (S)NTP)
+Precision: -23 (119.209ns per tick)
+Root Delay: 0.0312500s
+Root Dispersion: 7.8034418s
+ReferenceId: 0x28774EB3 (source IP:  40.119.6.228)
+Last Successful Sync Time: 14/10/2026 09:12:41
+Source: time.windows.com,0x9
+Poll Interval: 10 (1024s)
+Phase Offset: -0.0012345s
+"""
+
+W32TM_UNSYNCED = """Leap Indicator: 3(not synchronized)
+Stratum: 0 (unspecified)
+Source: Local CMOS Clock
+"""
+
+def test_synchronized_timex():
+    status = clock.clock_from_timex(0, 0x2001, 1500000, 655360, 16000, 8000)
+    assert status == {
+        "synchronized": True,
+        "clock_state": "ok",
+        "offset_secs": pytest.approx(0.0015),  # STA_NANO: offset in ns
+        "estimated_error_secs": pytest.approx(0.008),
+        "max_error_secs": pytest.approx(0.016),
+        "frequency_ppm": 10.0,
+    }
+
+def test_unsynchronized_timex():
+    status = clock.clock_from_timex(5, 0x0041, 250, 0, 16000000, 16000000)
+    assert status["synchronized"] is False
+    assert status["clock_state"] == "unsynchronized"
+    assert status["offset_secs"] == pytest.approx(0.00025)
+
+def test_zone_from_localtime_link():
+    assert clock.zone_from_localtime_link("/usr/share/zoneinfo/Europe/Berlin") == "Europe/Berlin"
+    assert clock.zone_from_localtime_link("../usr/share/zoneinfo/Etc/UTC") == "Etc/UTC"
+    assert clock.zone_from_localtime_link("/etc/custom-zone") is None
+
+def test_parse_adjtime():
+    assert clock.parse_adjtime("0.000000 1700000000 0.000000\n1700000000\nUTC\n") is True
+    assert clock.parse_adjtime("0.0 0 0.0\n0\nLOCAL\n") is False
+    assert clock.parse_adjtime("0.0 0 0.0\n") is None
+
+def test_parse_w32tm_status():
+    assert clock.parse_w32tm_status(W32TM_SYNCED) == {
+        "synchronized": True, "source": "time.windows.com,0x9", "offset_secs": -0.0012345,
+    }
+    assert clock.parse_w32tm_status(W32TM_UNSYNCED) == {
+        "synchronized": False, "source": None, "offset_secs": None,
+    }
+
+@pytest.mark.skipif(not clock.LINUX, reason="adjtimex is Linux-only")
+def test_get_clock_status_reads_zone_and_rtc(tmp_path, monkeypatch):
+    (tmp_path / "localtime").symlink_to("/usr/share/zoneinfo/America/New_York")
+    (tmp_path / "adjtime").write_text("0.0 0 0.0\n0\nLOCAL\n")
+    monkeypatch.setattr(clock, "ETC_LOCALTIME", str(tmp_path / "localtime"))
+    monkeypatch.setattr(clock, "ETC_ADJTIME", str(tmp_path / "adjtime"))
+    status = clock.get_clock_status()
+    assert status["timezone"] == "America/New_York"
+    assert status["rtc_in_utc"] is False
+    assert status["synchronized"] in (True, False)
+
+
+@pytest.fixture
+def wall_clock():
+    yield
+    clock.set_clock("wall")
+
+
+def test_set_clock_switches_output_timestamps(wall_clock):
+    from pulse import core
+
+    assert abs(core.snapshot(include=["memory"])["timestamp"] - time.time()) < 5
+    core.set_clock("monotonic")
+    assert core.get_clock() == "monotonic"
+    assert abs(core.snapshot(include=["memory"])["timestamp"] - time.monotonic()) < 5
+    with pytest.raises(ValueError, match="Invalid source 'utc'"):
+        core.set_clock("utc")
+    assert core.get_clock() == "monotonic"
+
+
+def test_clock_info_converts_monotonic_to_wall(wall_clock):
+    info = clock.get_clock_info()
+    assert info["source"] == "wall"
+    assert info["wall"] == pytest.approx(info["monotonic"] + info["monotonic_offset"])
+    assert time.monotonic() + info["monotonic_offset"] == pytest.approx(time.time(), abs=1)
+    if hasattr(time, "CLOCK_MONOTONIC_RAW"):
+        clock.set_clock("monotonic_raw")
+        assert clock.get_clock_info()["monotonic_raw_offset"] is not None
+
+
+def test_rates_survive_a_wall_clock_step(monkeypatch):
+    from pulse import core, disks, snapshot_diff
+
+    # Every read of the wall clock lands an hour before the previous one
+    real_time, steps = time.time, []
+    def stepping_back():
+        steps.append(None)
+        return real_time() - 3600 * len(steps)
+    monkeypatch.setattr(time, "time", stepping_back)
+
+    before = snapshot_diff.take_snapshot()
+    core.get_cpu_percents()
+    disks.get_disk_io_rates()
+    time.sleep(0.05)
+    after = snapshot_diff.take_snapshot()
+    assert after.timestamp < before.timestamp
+    assert 0 < snapshot_diff.diff_snapshots(before, after)["interval"] < 5
+    assert all(0 <= percent <= 100 for percent in core.get_cpu_percents())
+    for rates in disks.get_disk_io_rates().values():
+        assert all(value >= 0 for value in rates.values() if isinstance(value, (int, float)))
+    assert steps
@@ -0,0 +1,75 @@
+import pytest
+from pulse import collect, core, direct_os
+
+PROC_STAT = """cpu  10132153 290696 3084719 46828483 16683 0 25195 0 0 0
+cpu0 100 10 50 800 40 0 0 0 0 0
+cpu1 300 0 100 600
+intr 199292 0 9 0
+"""
+
+MEMINFO = """MemTotal:       16000000 kB
+MemFree:         2000000 kB
+MemAvailable:    6000000 kB
+Buffers:          500000 kB
+Cached:          3000000 kB
+SwapTotal:       4000000 kB
+SwapFree:        3000000 kB
+HugePages_Total:       0
+"""
+
+NET_DEV = """Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo: 9000      90    0    0    0     0          0         0     9000      90    0    0    0     0       0          0
+  eth0: 1000      10    0    0    0     0          0         0      400       4    0    0    0     0       0          0
+wlan0:200 2 0 0 0 0 0 0 100 1 0 0 0 0 0 0
+"""
+
+
+def test_parse_proc_stat_skips_the_aggregate_line():
+    assert collect.parse_proc_stat(PROC_STAT) == [
+        {"busy": 160, "total": 1000},
+        {"busy": 400, "total": 1000},
+    ]
+
+
+def test_parse_meminfo():
+    mem = collect.parse_meminfo(MEMINFO)
+    assert mem["total"] == 16000000 * 1024
+    assert mem["used"] == 10000000 * 1024
+    assert mem["swap_used"] == 1000000 * 1024
+    assert mem["percent"] == pytest.approx(62.5)
+    assert collect.parse_meminfo("") == {"used": 0, "swap_used": 0, "percent": 0}
+
+
+def test_parse_net_dev_skips_loopback():
+    assert collect.parse_net_dev(NET_DEV) == {"bytes_recv": 1200, "bytes_sent": 500}
+
+
+def test_parse_pid_stat_with_awkward_comm():
+    stat = b"4242 (a) b (c)) S 1 4242 4242 0 -1 4194560 100 0 0 0 5 3 0 0 20 0 1 0 100 123456 300"
+    assert collect.parse_pid_stat(4242, stat) == (4242, "a) b (c)", 0, 123456)
+    with pytest.raises((ValueError, IndexError)):
+        collect.parse_pid_stat(1, b"1 (init")
+    # utime 5 + stime 3, starttime 100
+    assert collect.parse_pid_stat_ticks(4242, stat)[1:] == (8, 100)
+    assert collect.process_cpu_percent(100, 150, 0.5, 100) == 100.0
+    assert collect.process_cpu_percent(150, 100, 0.5, 100) == 0.0
+    assert collect.process_cpu_percent(100, 150, 0.0, 100) == 0.0
+
+
+def test_rate_math():
+    assert collect.counter_delta(150, 100) == 50
+    assert collect.counter_delta(10, 100) == 0
+    assert collect.counter_rate(300, 100, 2.0) == 100.0
+    assert collect.counter_rate(300, 100, 0.0) == 0.0
+    assert collect.cpu_percent({"busy": 100, "total": 400}, {"busy": 150, "total": 500}) == 50.0
+    assert collect.cpu_percent({"busy": 100, "total": 400}, {"busy": 300, "total": 500}) == 100.0
+    assert collect.cpu_percent({"busy": 100, "total": 400}, {"busy": 100, "total": 400}) == 0.0
+
+
+@pytest.mark.skipif(not direct_os.LINUX, reason="/proc/meminfo is Linux-only")
+@pytest.mark.parametrize("wsl", [True, False])
+def test_memory_is_dynamic_under_wsl(monkeypatch, wsl):
+    from pulse import environment
+    monkeypatch.setattr(environment, "running_under_wsl", lambda: wsl)
+    assert direct_os.get_memory_info()["is_dynamic"] is wsl
@@ -0,0 +1,15 @@
+from pulse import collect, collector, core
+
+
+def test_collector_matches_core():
+    reader = collector.Collector()
+    reader.cpu()
+    cores = reader.cpu()
+    assert [sample.core for sample in cores] == list(range(len(core.get_cpu_percents())))
+    assert reader.memory().total == core.get_memory_info()["total"]
+    heaviest = reader.processes(sort_by="mem", limit=5)
+    assert 0 < len(heaviest) <= 5
+    assert [p.memory_info for p in heaviest] == sorted((p.memory_info for p in heaviest), reverse=True)
+    disk = reader.disks()[0]
+    assert collect.DiskEntry.from_dict(disk.to_dict()) == disk
+    assert set(reader.network().to_dict()) == {"bytes_recv", "bytes_sent"}
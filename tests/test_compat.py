@@ -0,0 +1,93 @@
+import pytest
+from pulse import compat, core, disks
+
+# Field names psutil 5.9 returned on a Linux host, recorded from
+# virtual_memory()._fields, net_io_counters()._fields, and so on
+PSUTIL_VIRTUAL_MEMORY = {"total", "available", "percent", "used", "free", "active", "inactive",
+                         "buffers", "cached", "shared", "slab"}
+PSUTIL_NET_IO = {"bytes_sent", "bytes_recv", "packets_sent", "packets_recv", "errin", "errout", "dropin", "dropout"}
+PSUTIL_PMEM = {"rss", "vms", "shared", "text", "lib", "data", "dirty"}
+PSUTIL_PARTITION_AND_USAGE = {"device", "mountpoint", "fstype", "opts", "maxfile", "maxpath",
+                              "total", "used", "free", "percent"}
+PSUTIL_SCONN = {"fd", "family", "type", "laddr", "raddr", "status", "pid"}
+PSUTIL_BATTERY = {"percent", "secsleft", "power_plugged"}
+
+CONNECTIONS = [
+    {"protocol": "tcp", "local_address": "10.0.0.2", "local_port": 51000, "remote_address": "1.1.1.1",
+     "remote_port": 443, "tx_queue": 0, "rx_queue": 0, "uid": 1000, "inode": 7, "status": "ESTABLISHED"},
+    {"protocol": "tcp", "local_address": "0.0.0.0", "local_port": 22, "remote_address": "0.0.0.0",
+     "remote_port": 0, "tx_queue": 0, "rx_queue": 0, "uid": 0, "inode": 8, "status": "LISTEN"},
+]
+
+BATTERIES = [
+    {"name": "BAT0", "percent": 55.0, "state": "discharging", "time_to_empty_secs": 3600,
+     "time_to_full_secs": None, "cycle_count": 10, "health_percent": 90.0},
+    {"name": "BAT1", "percent": 80.0, "state": "charging", "time_to_empty_secs": None,
+     "time_to_full_secs": 600, "cycle_count": 3, "health_percent": 99.0},
+]
+
+
+@pytest.fixture
+def psutil_mode():
+    compat.set_compat_mode("psutil")
+    yield
+    compat.set_compat_mode("pulse")
+
+
+def test_default_mode():
+    assert core.get_compat_mode() == "pulse"
+    assert "memory_info" in core.get_process_list(limit=1)[0]
+    with pytest.raises(ValueError):
+        core.set_compat_mode("procps")
+
+
+def test_memory_and_network_names_match_psutil(psutil_mode):
+    memory = core.get_memory_info()
+    assert {"total", "available", "percent", "used"} <= set(memory)
+    assert set(memory) - {"swap_total", "swap_used", "swap_free", "is_dynamic"} <= PSUTIL_VIRTUAL_MEMORY
+    assert set(core.get_network_stats()) <= PSUTIL_NET_IO
+
+
+def test_process_memory_info_is_a_pmem_mapping(psutil_mode):
+    proc = core.get_process_list(sort_by="mem", limit=1)[0]
+    assert {"pid", "name", "cpu_percent", "memory_info"} == set(proc)
+    assert len(proc["memory_info"]) == 1
+    assert set(proc["memory_info"]) <= PSUTIL_PMEM
+
+
+def test_disk_options_become_opts(psutil_mode, monkeypatch):
+    monkeypatch.setattr(disks, "get_disk_info", lambda **kwargs: [{
+        "device": "/dev/sda1", "mountpoint": "/", "fstype": "ext4", "total": 100, "used": 40, "free": 60,
+        "available": 55, "percent": 42.1, "options": "rw,relatime", "is_read_only": False,
+    }])
+    disk = core.get_disk_info()[0]
+    assert disk["opts"] == "rw,relatime" and "options" not in disk
+    assert set(disk) & PSUTIL_PARTITION_AND_USAGE == PSUTIL_PARTITION_AND_USAGE - {"maxfile", "maxpath"}
+
+
+def test_connections_use_address_tuples(psutil_mode):
+    established, listening = compat.adapt("connection", CONNECTIONS)
+    assert established["laddr"] == ("10.0.0.2", 51000)
+    assert established["raddr"] == ("1.1.1.1", 443)
+    assert listening["raddr"] == ()
+    assert {"laddr", "raddr", "status"} <= PSUTIL_SCONN
+    assert "local_address" not in established and established["status"] == "ESTABLISHED"
+
+
+def test_battery_conventions(psutil_mode):
+    discharging, charging = compat.adapt("battery", BATTERIES)
+    assert PSUTIL_BATTERY <= set(discharging)
+    assert (discharging["secsleft"], discharging["power_plugged"]) == (3600, False)
+    assert (charging["secsleft"], charging["power_plugged"]) == (compat.POWER_TIME_UNLIMITED, True)
+    # The input dicts are left untouched
+    assert "time_to_empty_secs" in BATTERIES[0]
+
+
+def test_connection_and_battery_getters_are_adapted(psutil_mode):
+    assert all("laddr" in c for c in core.get_connections("inet"))
+    assert all("secsleft" in b for b in core.get_battery_info())
+
+
+def test_prometheus_reads_native_names(psutil_mode):
+    text = core.get_prometheus_metrics(include_processes=True, process_limit=1)
+    assert "pulse_process_memory_bytes{" in text
@@ -0,0 +1,141 @@
+import json
+import types
+
+import pytest
+from pulse import cgroups, containers
+from pulse.state import STATE
+
+DOCKER_ID = "4f1c" + "a" * 60
+PODMAN_ID = "9b2e" + "b" * 60
+
+NET_DEV = """Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:     500       5    0    0    0     0          0         0      500       5    0    0    0     0       0          0
+  eth0:   12000      80    0    0    0     0          0         0     3400      40    0    0    0     0       0          0
+"""
+
+def test_match_container_cgroup():
+    assert containers.match_container_cgroup(f"/system.slice/docker-{DOCKER_ID}.scope") == {
+        "id": DOCKER_ID, "runtime": "docker"}
+    assert containers.match_container_cgroup(f"/machine.slice/libpod-{PODMAN_ID}.scope")["runtime"] == "podman"
+    assert containers.match_container_cgroup(f"/docker/{DOCKER_ID}")["runtime"] == "docker"
+    assert containers.match_container_cgroup(
+        f"/kubepods.slice/kubepods-burstable.slice/cri-containerd-{DOCKER_ID}.scope")["runtime"] == "containerd"
+    assert containers.match_container_cgroup("/system.slice/ssh.service") is None
+    assert containers.match_container_cgroup(f"/machine.slice/libpod-conmon-{PODMAN_ID}.scope") is None
+
+def test_parse_proc_cgroup():
+    assert containers.parse_proc_cgroup("0::/system.slice/ssh.service\n") == "/system.slice/ssh.service"
+    assert containers.parse_proc_cgroup("4:memory:/user.slice\n") is None
+
+def test_names():
+    assert containers.docker_name(json.dumps({"ID": DOCKER_ID, "Name": "/web"})) == "web"
+    assert containers.docker_name("not json") is None
+    listing = json.dumps([{"id": PODMAN_ID, "names": ["db"]}, {"id": "x", "names": []}])
+    assert containers.podman_names(listing) == {PODMAN_ID: "db"}
+
+@pytest.fixture
+def fake_host(tmp_path, monkeypatch):
+    cgroup_root = tmp_path / "cgroup"
+    docker_scope = cgroup_root / "system.slice" / f"docker-{DOCKER_ID}.scope"
+    podman_scope = cgroup_root / "machine.slice" / f"libpod-{PODMAN_ID}.scope"
+    (docker_scope / "init").mkdir(parents=True)
+    podman_scope.mkdir(parents=True)
+    (cgroup_root / "system.slice" / "ssh.service").mkdir()
+    (docker_scope / "cpu.stat").write_text("usage_usec 1000000\n")
+    (docker_scope / "memory.current").write_text("104857600\n")
+    (docker_scope / "memory.max").write_text("209715200\n")
+    (podman_scope / "cpu.stat").write_text("usage_usec 5000\n")
+    (podman_scope / "memory.current").write_text("1048576\n")
+    (podman_scope / "memory.max").write_text("max\n")
+
+    proc = tmp_path / "proc"
+    for pid, group in ((100, f"/system.slice/docker-{DOCKER_ID}.scope/init"),
+                       (101, f"/system.slice/docker-{DOCKER_ID}.scope"),
+                       (200, f"/machine.slice/libpod-{PODMAN_ID}.scope"),
+                       (300, "/system.slice/ssh.service")):
+        (proc / str(pid) / "net").mkdir(parents=True)
+        (proc / str(pid) / "cgroup").write_text(f"0::{group}\n")
+        (proc / str(pid) / "net" / "dev").write_text(NET_DEV)
+
+    docker_state = tmp_path / "docker"
+    (docker_state / DOCKER_ID).mkdir(parents=True)
+    (docker_state / DOCKER_ID / "config.v2.json").write_text(json.dumps({"Name": "/web"}))
+    podman_json = tmp_path / "containers.json"
+    podman_json.write_text(json.dumps([{"id": PODMAN_ID, "names": ["db"]}]))
+
+    monkeypatch.setattr(cgroups, "SYS_FS_CGROUP", str(cgroup_root))
+    monkeypatch.setattr(containers, "PROC", str(proc))
+    monkeypatch.setattr(containers, "DOCKER_CONTAINERS", str(docker_state))
+    monkeypatch.setattr(containers, "PODMAN_CONTAINERS_JSON", str(podman_json))
+    monkeypatch.setattr(STATE, "cgroup_cpu", {})
+    monkeypatch.setattr(STATE, "namespace_network", {})
+    return tmp_path
+
+@pytest.mark.skipif(not containers.LINUX, reason="cgroup discovery is Linux-only")
+def test_list_containers(fake_host):
+    podman, docker = containers.list_containers()
+    assert docker["id"] == DOCKER_ID
+    assert docker["name"] == "web"
+    assert docker["runtime"] == "docker"
+    assert docker["pids"] == [100, 101]
+    assert docker["memory_percent"] == 50.0
+    assert docker["cpu_percent"] is None
+    assert docker["net_bytes_recv"] == 12000
+    assert docker["net_bytes_sent"] == 3400
+    assert podman["name"] == "db"
+    assert podman["memory_max"] is None
+    assert podman["memory_percent"] is None
+    assert podman["pids"] == [200]
+
+@pytest.mark.skipif(not containers.LINUX, reason="cgroup discovery is Linux-only")
+def test_no_containers(tmp_path, monkeypatch):
+    monkeypatch.setattr(cgroups, "SYS_FS_CGROUP", str(tmp_path))
+    assert containers.list_containers() == []
+
+@pytest.fixture
+def namespaces(fake_host, monkeypatch):
+    """Docker's pids share net namespace 4026532500; pid 200's link is unreadable."""
+    proc = fake_host / "proc"
+    for pid in (100, 101):
+        (proc / str(pid) / "ns").mkdir()
+        (proc / str(pid) / "ns" / "net").symlink_to("net:[4026532500]")
+    clock = iter([100.0, 102.0, 104.0])
+    monkeypatch.setattr(containers, "time", types.SimpleNamespace(monotonic=lambda: next(clock)))
+    return proc
+
+def _grow(proc, pid, recv, sent):
+    (proc / str(pid) / "net" / "dev").write_text(NET_DEV.replace("12000", str(recv)).replace("3400", str(sent)))
+
+@pytest.mark.skipif(not containers.LINUX, reason="network namespaces are Linux-only")
+def test_namespace_network_rates_are_keyed_by_namespace(namespaces):
+    first = containers.get_namespace_network(100)
+    assert first["namespace"] == 4026532500
+    assert first["interfaces"]["eth0"] == {"bytes_recv": 12000, "bytes_sent": 3400,
+                                           "bytes_recv_per_sec": None, "bytes_sent_per_sec": None}
+    # Another member of the same namespace continues the baseline
+    _grow(namespaces, 101, 16000, 4400)
+    second = containers.get_namespace_network(101)
+    assert second["interfaces"]["eth0"]["bytes_recv_per_sec"] == 2000.0
+    assert second["interfaces"]["eth0"]["bytes_sent_per_sec"] == 500.0
+
+@pytest.mark.skipif(not containers.LINUX, reason="network namespaces are Linux-only")
+def test_namespace_network_degrades_without_permission(namespaces):
+    unlinked = containers.get_namespace_network(200)
+    assert unlinked["namespace"] is None
+    assert unlinked["interfaces"]["eth0"]["bytes_recv_per_sec"] is None
+    (namespaces / "300" / "net" / "dev").unlink()
+    assert containers.get_namespace_network(300) is None
+    with pytest.raises(ProcessLookupError):
+        containers.get_namespace_network(999)
+
+@pytest.mark.skipif(not containers.LINUX, reason="cgroup discovery is Linux-only")
+def test_list_containers_with_network_rates(namespaces):
+    podman, docker = containers.list_containers(include_network_rates=True)
+    assert docker["net_recv_per_sec"] is None and docker["net_bytes_recv"] == 12000
+    _grow(namespaces, 100, 13000, 3600)
+    podman, docker = containers.list_containers(include_network_rates=True)
+    assert docker["net_recv_per_sec"] == 500.0
+    assert docker["net_sent_per_sec"] == 100.0
+    assert podman["net_recv_per_sec"] is None
+    assert "net_recv_per_sec" not in containers.list_containers()[0]
@@ -1,7 +1,11 @@
 import pytest
+import json
 import sys
+import threading
+import time
 from unittest.mock import MagicMock, patch
 from pulse import core
+from pulse.state import STATE, active_state
 
 def test_get_memory_info():
     """Test memory info retrieval."""
@@ -48,4 +52,331 @@ def test_get_disk_info():
         assert "mountpoint" in disk
         assert "total" in disk
         assert "percent" in disk
+        assert "available" in disk
+        assert isinstance(disk["is_read_only"], bool)
+        assert isinstance(disk["mount_options"], list)
+        assert disk["used"] <= disk["total"]
+        assert disk["used_space"] == disk["used"]
 
+def test_disk_percent_uses_available_space():
+    """Percent matches df: reserved blocks are excluded, empty mounts are 0."""
+    from pulse.disks import disk_percent
+    # 100 blocks total, 60 used, 30 available to users, 10 reserved for root
+    assert disk_percent(60, 30) == pytest.approx(66.666, rel=1e-3)
+    assert disk_percent(0, 0) == 0.0
+
+
+class FakeHost:
+    """One CPU whose load steps from 25% to 75% at t=2.5 s, on a fake clock."""
+
+    def __init__(self):
+        self.now = 0.0
+
+    def cpu_times(self):
+        busy = 25 * min(self.now, 2.5) + 75 * max(0.0, self.now - 2.5)
+        return [{"busy": busy, "total": 100 * self.now}]
+
+@pytest.fixture
+def fake_host(monkeypatch):
+    host = FakeHost()
+    monkeypatch.setattr(core.direct_os, "read_cpu_times", host.cpu_times)
+    monkeypatch.setattr(core.direct_os.time, "monotonic", lambda: host.now)
+    return host
+
+def test_monitors_keep_independent_cpu_baselines(fake_host):
+    """A 0.5 s and a 5 s poller each see usage over their own interval."""
+    fast, slow = core.Monitor(), core.Monitor()
+
+    fake_host.now = 0.5
+    assert fast.cpu_percents() == [pytest.approx(25.0)]
+    fake_host.now = 5.0
+    assert slow.cpu_percents() == [pytest.approx(50.0)]
+    # The slow sample at 5.0 didn't move the fast monitor's baseline (0.5)
+    fake_host.now = 5.5
+    assert fast.cpu_percents() == [pytest.approx(55.0)]
+
+def test_monitor_does_not_touch_module_state(fake_host, monkeypatch):
+    monkeypatch.setattr(STATE, "cpu_times", None)
+    core.Monitor().cpu_percents()
+    assert STATE.cpu_times is None
+
+def test_monitor_mirrors_collectors():
+    monitor = core.Monitor()
+    assert isinstance(monitor.memory_info(), dict)
+    assert isinstance(monitor.disk_info(), list)
+    assert monitor.process_list.__doc__ == core.get_process_list.__doc__
+
+def test_snapshot_sections_share_one_timestamp():
+    snap = core.snapshot(options={"processes": {"sort_by": "mem", "limit": 3, "fields": ["pid", "name"]}})
+    assert set(snap) == {"timestamp", "cpu", "memory", "processes", "network", "disks"}
+    assert isinstance(snap["timestamp"], float)
+    assert len(snap["processes"]) <= 3
+    assert all(set(p) == {"pid", "name"} for p in snap["processes"])
+
+def test_snapshot_include_and_validation():
+    snap = core.snapshot(include=["memory", "network"])
+    assert set(snap) == {"timestamp", "memory", "network"}
+    with pytest.raises(ValueError):
+        core.snapshot(include=["gpu"])
+    with pytest.raises(ValueError):
+        core.snapshot(options={"swap": {}})
+
+def test_monitor_snapshot_uses_monitor_baselines(fake_host):
+    monitor = core.Monitor()
+    fake_host.now = 1.0
+    assert monitor.snapshot(include=["cpu"])["cpu"] == [pytest.approx(25.0)]
+
+@pytest.fixture
+def primed(monkeypatch):
+    calls = []
+    for name in ("get_cpu_percents", "get_process_list", "get_udp_stats", "get_disk_io_rates", "get_memory_info"):
+        monkeypatch.setattr(core, name, lambda name=name: calls.append(name))
+    return calls
+
+def test_init_primes_everything_by_default(primed):
+    core.init()
+    expected = {"get_cpu_percents", "get_udp_stats", "get_disk_io_rates"}
+    if not sys.platform.startswith("linux"):
+        expected.add("get_process_list")
+    assert set(primed) == expected
+
+def test_memory_only_init_skips_other_subsystems(primed):
+    core.init(["memory"])
+    assert primed == []
+    with pytest.raises(ValueError):
+        core.init(["gpu"])
+
+def test_init_subsystems_are_snapshot_sections():
+    # The sampler primes the sections it collects by these names
+    assert set(core.INIT_SUBSYSTEMS) == set(core.SNAPSHOT_SECTIONS)
+
+def test_memory_only_init_is_an_order_of_magnitude_faster():
+    def median_secs(refresh):
+        times = []
+        for _ in range(15):
+            with core.using(core.SysState()):
+                start = time.perf_counter()
+                core.init(refresh)
+                times.append(time.perf_counter() - start)
+        return sorted(times)[len(times) // 2]
+
+    assert median_secs(["memory"]) * 10 <= median_secs(None)
+
+def test_monitor_init_respects_refresh(primed):
+    core.Monitor(refresh=["cpu"])
+    assert primed == ["get_cpu_percents"]
+
+@pytest.fixture
+def counting_network(monkeypatch):
+    counter = {"calls": 0}
+
+    def network_stats():
+        counter["calls"] += 1
+        return {"bytes_recv": 1000 * counter["calls"], "bytes_sent": 10 * counter["calls"]}
+
+    monkeypatch.setattr(core.direct_os, "get_network_stats", network_stats)
+    monkeypatch.setattr(STATE, "cache", {})
+    return counter
+
+def test_max_age_reuses_recent_results(counting_network):
+    first = core.get_network_stats(max_age_ms=60_000)
+    second = core.get_network_stats(max_age_ms=60_000)
+    assert first == second
+    assert counting_network["calls"] == 1
+
+def test_max_age_zero_always_refreshes(counting_network):
+    assert core.get_network_stats() != core.get_network_stats()
+    assert counting_network["calls"] == 2
+
+def test_expired_cache_refreshes(counting_network, monkeypatch):
+    clock = iter([100.0, 100.5])
+    monkeypatch.setattr(core.time, "monotonic", lambda: next(clock))
+    first = core.get_network_stats(max_age_ms=100)
+    assert core.get_network_stats(max_age_ms=100) != first
+
+def test_default_max_age(counting_network, monkeypatch):
+    monkeypatch.setattr(core, "_default_max_age_ms", 0.0)
+    core.set_default_max_age(60_000)
+    assert core.get_network_stats() == core.get_network_stats()
+    # An explicit 0 still forces a refresh
+    assert core.get_network_stats(max_age_ms=0)["bytes_recv"] == 2000
+    with pytest.raises(ValueError):
+        core.set_default_max_age(-1)
+
+def test_min_refresh_interval_clamps_tight_loops(counting_network, monkeypatch):
+    monkeypatch.setattr(core, "_min_refresh_ms", {})
+    before = core.get_refresh_stats()["network"]
+    core.set_min_refresh_interval("network", 60_000)
+    for _ in range(500):
+        # Neither the default nor an explicit 0 gets past the floor
+        core.get_network_stats()
+        core.get_network_stats(max_age_ms=0)
+    stats = core.get_refresh_stats()["network"]
+    assert stats["calls"] - before["calls"] == 1000
+    assert stats["refreshed"] - before["refreshed"] == 1 == counting_network["calls"]
+    assert stats["min_refresh_interval_ms"] == 60_000
+    core.set_min_refresh_interval("network", 0)
+    core.get_network_stats()
+    assert counting_network["calls"] == 2
+    assert core.get_refresh_stats()["network"]["min_refresh_interval_ms"] == 0
+
+def test_timeout_leaves_the_collection_running(monkeypatch):
+    release = threading.Event()
+    calls = []
+
+    def hung_disk_info(**kwargs):
+        # Holds the subsystem lock the way a blocked statvfs() would
+        with active_state().lock("disks"):
+            calls.append(kwargs)
+            release.wait(10)
+        return [{"mountpoint": "/", "percent": 1.0}]
+
+    monkeypatch.setattr(core.disks, "get_disk_info", hung_disk_info)
+    with pytest.raises(core.Timeout):
+        core.get_disk_info(timeout_ms=50)
+    # A second caller joins the same in-flight collection instead of starting another
+    with pytest.raises(TimeoutError):
+        core.get_disk_info(timeout_ms=50)
+    assert len(calls) == 1
+
+    release.set()
+    assert core.get_disk_info(timeout_ms=5000)[0]["mountpoint"] == "/"
+    # The worker released the lock on its way out
+    lock = STATE.lock("disks")
+    assert lock.acquire(timeout=1)
+    lock.release()
+
+def test_default_call_timeout(monkeypatch):
+    monkeypatch.setattr(core.deadlines, "_default_timeout_ms", 0.0)
+    core.set_call_timeout(20)
+    try:
+        assert core.get_call_timeout() == 20
+        monkeypatch.setattr(core.direct_os, "get_memory_info", lambda: time.sleep(0.5) or {})
+        with pytest.raises(core.Timeout, match="get_memory_info did not finish within 20 ms"):
+            core.get_memory_info()
+    finally:
+        core.set_call_timeout(0)
+
+def test_cached_results_are_copies(counting_network):
+    core.get_network_stats(max_age_ms=60_000)["bytes_recv"] = -1
+    assert core.get_network_stats(max_age_ms=60_000)["bytes_recv"] == 1000
+
+def test_json_matches_dict_output(counting_network, monkeypatch):
+    monkeypatch.setattr(core.time, "time", lambda: 1760000000.5)
+    as_dict = core.get_network_stats(max_age_ms=60_000)
+    as_json = core.get_network_stats(max_age_ms=60_000, as_json=True)
+    assert isinstance(as_json, str)
+    assert json.loads(as_json) == {**as_dict, "timestamp": 1760000000.5}
+
+def test_json_of_a_list_is_wrapped_with_its_timestamp(monkeypatch):
+    monkeypatch.setattr(core.time, "time", lambda: 1760000000.5)
+    document = json.loads(core.get_cpu_percents(as_json=True))
+    assert set(document) == {"timestamp", "data"}
+    assert document["timestamp"] == 1760000000.5
+    assert len(document["data"]) == len(core.get_cpu_percents())
+
+def test_snapshot_json_has_same_keys_and_timestamp(counting_network, monkeypatch):
+    monkeypatch.setattr(core.time, "time", lambda: 1760000000.5)
+    core.set_default_max_age(60_000)
+    try:
+        as_dict = core.snapshot(include=["memory", "network", "disks"])
+        as_json = core.snapshot(include=["memory", "network", "disks"], as_json=True)
+    finally:
+        core.set_default_max_age(0)
+    assert json.loads(as_json) == as_dict
+    assert json.loads(as_json)["timestamp"] == 1760000000.5
+
+def test_monitor_methods_take_as_json():
+    assert json.loads(core.Monitor().memory_info(as_json=True))["total"] > 0
+
+def test_concurrent_getters_do_not_deadlock():
+    """Threads hammering different (and the same) getters all finish with sane values."""
+    import threading
+    calls = [
+        lambda: core.get_memory_info(max_age_ms=5),
+        lambda: core.get_cpu_percents(),
+        lambda: core.get_network_stats(max_age_ms=5),
+        lambda: core.get_disk_info(max_age_ms=5),
+        lambda: core.get_disk_io_rates(),
+        lambda: core.get_udp_stats(),
+        lambda: core.snapshot(include=["cpu", "memory"]),
+    ]
+    errors = []
+
+    def worker(call):
+        try:
+            for _ in range(20):
+                result = call()
+                assert result is not None
+                if isinstance(result, list) and result and isinstance(result[0], float):
+                    assert all(0.0 <= pct <= 100.0 for pct in result)
+        except Exception as e:
+            errors.append(e)
+
+    threads = [threading.Thread(target=worker, args=(call,), daemon=True) for call in calls * 3]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join(timeout=30)
+    assert not any(thread.is_alive() for thread in threads), "getter threads deadlocked"
+    assert errors == []
+
+def test_slow_refresh_does_not_block_other_subsystems(monkeypatch):
+    import threading
+    started, release = threading.Event(), threading.Event()
+
+    def slow_process_list(**kwargs):
+        started.set()
+        release.wait(5)
+        return []
+
+    monkeypatch.setattr(core.direct_os, "get_process_list", slow_process_list)
+    monkeypatch.setattr(STATE, "cache", {})
+    scan = threading.Thread(target=core.get_process_list, kwargs={"max_age_ms": 1000}, daemon=True)
+    scan.start()
+    try:
+        assert started.wait(5)
+        # The process lock is held, but the network and memory locks are free
+        assert isinstance(core.get_network_stats(max_age_ms=1000), dict)
+        assert isinstance(core.get_memory_info(max_age_ms=1000), dict)
+        assert scan.is_alive()
+    finally:
+        release.set()
+        scan.join(5)
+
+@pytest.mark.skipif(not sys.platform.startswith("linux"), reason="reads /proc")
+def test_process_list_matches_comm_and_statm():
+    """The single /proc/<pid>/stat read reports what comm and statm say."""
+    import os
+    import subprocess
+    child = subprocess.Popen(["sleep", "30"])
+    try:
+        entry = next(p for p in core.direct_os.get_process_list() if p["pid"] == child.pid)
+        with open(f"/proc/{child.pid}/comm") as f:
+            name = f.read().strip()
+        with open(f"/proc/{child.pid}/statm") as f:
+            size = int(f.read().split()[0]) * os.sysconf("SC_PAGE_SIZE")
+    finally:
+        child.kill()
+        child.wait()
+    assert entry == {"pid": child.pid, "name": name, "cpu_percent": 0, "memory_info": size}
+
+def test_shutdown_stops_threads_and_resets_state():
+    import threading
+    core.start_sampler(interval_ms=10, sections=["cpu"])
+    core.start_mount_watcher(0.05)
+    core.register_callback("sample", print)
+    core.get_cpu_percents()
+    assert STATE.cpu_times is not None
+
+    core.shutdown()
+    names = {thread.name for thread in threading.enumerate()}
+    assert not names & {"pulse-sampler", "pulse-mount-watcher"}
+    assert core.sampler_status()["running"] is False
+    assert STATE.cpu_times is None and STATE.cache == {}
+    assert core.callbacks._listeners("sample") == []
+
+    # The next call rebuilds its baseline as on first import
+    assert len(core.get_cpu_percents()) > 0
+    assert STATE.cpu_times is not None
+    core.shutdown()
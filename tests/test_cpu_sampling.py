@@ -0,0 +1,100 @@
+import pytest
+from pulse import core, cpu_sampling
+
+
+class FakeClock:
+    """time.monotonic and time.sleep over a virtual clock; each counter read costs read_cost."""
+
+    def __init__(self, read_cost=0.0):
+        self.now = 100.0
+        self.read_cost = read_cost
+        self.wakeups = []
+
+    def monotonic(self):
+        return self.now
+
+    def sleep(self, secs):
+        self.now += secs
+        self.wakeups.append(self.now)
+
+
+@pytest.fixture
+def fake(monkeypatch):
+    clock = FakeClock(read_cost=0.03)
+    readings = iter([
+        [{"busy": 0, "total": 0}, {"busy": 0, "total": 0}],
+        [{"busy": 50, "total": 100}, {"busy": 0, "total": 100}],
+        [{"busy": 150, "total": 200}, {"busy": 50, "total": 200}],
+        [{"busy": 160, "total": 300}, {"busy": 150, "total": 300}],
+    ])
+
+    def cpu_times():
+        clock.now += clock.read_cost
+        return next(readings)
+
+    monkeypatch.setattr(cpu_sampling.time, "monotonic", clock.monotonic)
+    monkeypatch.setattr(cpu_sampling.time, "sleep", clock.sleep)
+    monkeypatch.setattr(cpu_sampling.backend.active(), "cpu_times", cpu_times)
+    return clock
+
+
+def test_summarize():
+    assert cpu_sampling.summarize([2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]) == {
+        "min": 2.0, "max": 9.0, "mean": 5.0, "stddev": 2.0}
+    assert cpu_sampling.summarize([]) == {"min": None, "max": None, "mean": None, "stddev": None}
+
+
+def test_series_and_summaries(fake):
+    result = core.sample_cpu(duration_secs=0.3, interval_secs=0.1)
+    assert result["samples"] == 3
+    assert result["interrupted"] is False
+    assert result["cores"][0]["series"] == [50.0, 100.0, 10.0]
+    assert result["cores"][1]["series"] == [0.0, 50.0, 100.0]
+    assert result["total"]["series"] == pytest.approx([25.0, 75.0, 55.0])
+    assert result["total"]["mean"] == pytest.approx(155 / 3)
+    assert result["cores"][0]["max"] == 100.0 and result["cores"][1]["min"] == 0.0
+
+
+def test_deadlines_are_absolute(fake):
+    result = core.sample_cpu(duration_secs=0.3, interval_secs=0.1)
+    # Reads cost 30 ms each, yet every wakeup lands on start + k * interval
+    assert fake.wakeups == pytest.approx([100.1, 100.2, 100.3])
+    assert result["offsets"] == pytest.approx([0.13, 0.23, 0.33])
+
+
+def test_overrun_deadlines_are_skipped(fake, monkeypatch):
+    costs = iter([0.0, 0.35, 0.0])
+    readings = iter([[{"busy": 0, "total": 0}], [{"busy": 50, "total": 100}], [{"busy": 60, "total": 200}]])
+
+    def cpu_times():
+        fake.now += next(costs)
+        return next(readings)
+
+    monkeypatch.setattr(cpu_sampling.backend.active(), "cpu_times", cpu_times)
+    result = core.sample_cpu(duration_secs=0.5, interval_secs=0.1)
+    # The slow first read ran past the deadlines at 0.2, 0.3 and 0.4; the next read waits for 0.5
+    assert fake.wakeups == pytest.approx([100.1, 100.5])
+    assert result["samples"] == 2
+    assert result["total"]["series"] == [50.0, 10.0]
+
+
+def test_interrupt_returns_partial_results(fake, monkeypatch):
+    sleeps = iter([None, KeyboardInterrupt])
+
+    def sleep(secs):
+        if next(sleeps) is KeyboardInterrupt:
+            raise KeyboardInterrupt
+        fake.sleep(secs)
+
+    monkeypatch.setattr(cpu_sampling.time, "sleep", sleep)
+    result = core.sample_cpu(duration_secs=1.0, interval_secs=0.1)
+    assert result["interrupted"] is True
+    assert result["samples"] == 1
+    assert result["total"]["series"] == [25.0]
+
+
+def test_real_host_sampling():
+    result = core.sample_cpu(duration_secs=0.05, interval_secs=0.01)
+    assert result["samples"] == 5
+    assert all(0.0 <= v <= 100.0 for v in result["total"]["series"])
+    assert len(result["cores"]) >= 1
@@ -0,0 +1,107 @@
+import os
+import subprocess
+import sys
+import time
+import pytest
+from pulse import detectors
+
+
+def _proc(pid, start, cpu=0.0, name="worker", cmdline="/usr/bin/worker --serve"):
+    return (pid, start, name, cmdline, cpu)
+
+
+def test_crash_loop_counts_new_starts_after_the_first_reading():
+    detector = detectors.CrashLoopDetector()
+    # Already running when first seen: four workers, none of them a restart
+    detector.observe([_proc(p, 100) for p in (10, 11, 12, 13)], now=0)
+    assert detector.report(count=2, window_secs=60, now=0) == []
+    # The same command comes back under a new pid every few seconds
+    for i, now in enumerate((5, 10, 15), start=1):
+        detector.observe([_proc(10, 100), _proc(20 + i, 200 + i)], now=now)
+    loops = detector.report(count=2, window_secs=60, now=15)
+    assert [(l["name"], l["starts"], l["pids"]) for l in loops] == [("worker", 3, [10, 23])]
+    assert loops[0]["cmdline_hash"] == detectors.cmdline_hash("/usr/bin/worker --serve")
+    # Outside a shorter window only the latest start counts
+    assert detector.report(count=2, window_secs=6, now=15) == []
+
+
+def test_crash_loop_keys_by_pid_and_start_time():
+    detector = detectors.CrashLoopDetector()
+    detector.observe([_proc(10, 100)], now=0)
+    # Same pid, new start time: the pid was recycled for a new instance
+    detector.observe([_proc(10, 150)], now=5)
+    detector.observe([_proc(10, 150)], now=10)
+    assert detector.report(count=0, window_secs=60, now=10)[0]["starts"] == 1
+    # A different cmdline is a different command
+    detector.observe([_proc(11, 160, cmdline="/usr/bin/worker --once")], now=12)
+    assert {l["cmdline"] for l in detector.report(count=0, window_secs=60, now=12)} == {
+        "/usr/bin/worker --serve", "/usr/bin/worker --once",
+    }
+
+
+def test_crash_loop_state_expires():
+    detector = detectors.CrashLoopDetector()
+    detector.observe([], now=0)
+    detector.observe([_proc(10, 100)], now=1)
+    detector.observe([], now=1 + detectors.STALE_SECS + 1)
+    assert detector.last_seen == {}
+    detector.observe([], now=detectors.MAX_WINDOW_SECS + 10)
+    assert detector.commands == {}
+
+
+def test_runaway_needs_sustained_usage():
+    detector = detectors.RunawayDetector()
+    for now, cpu in ((0, 95.0), (30, 99.0), (60, 97.0)):
+        detector.observe([_proc(10, 100, cpu=cpu), _proc(11, 100, cpu=50.0)], 90.0, now)
+    assert detector.report(sustain_secs=60, now=60) == [
+        {"pid": 10, "name": "worker", "cpu_percent": 97.0, "above_secs": 60},
+    ]
+    # One reading below the threshold restarts the clock
+    detector.observe([_proc(10, 100, cpu=20.0)], 90.0, 90)
+    detector.observe([_proc(10, 100, cpu=99.0)], 90.0, 120)
+    assert detector.report(sustain_secs=60, now=120) == []
+
+
+def test_runaway_survives_pid_reuse_and_threshold_change():
+    detector = detectors.RunawayDetector()
+    detector.observe([_proc(10, 100, cpu=99.0)], 90.0, 0)
+    # pid 10 exited and was reused: the new process starts from scratch
+    detector.observe([_proc(10, 300, cpu=99.0)], 90.0, 60)
+    assert detector.report(sustain_secs=30, now=60) == []
+    detector.observe([_proc(10, 300, cpu=99.0)], 80.0, 90)
+    assert detector.report(sustain_secs=0, now=90)[0]["above_secs"] == 0
+    detector.observe([], 80.0, 90 + detectors.STALE_SECS + 1)
+    assert detector.processes == {}
+
+
+@pytest.mark.skipif(not detectors.LINUX, reason="/proc reader is Linux-only")
+def test_read_processes_includes_self():
+    observations = {o[0]: o for o in detectors.read_processes()}
+    pid, start, name, cmdline, cpu = observations[os.getpid()]
+    assert start > 0 and "python" in cmdline
+
+
+@pytest.mark.skipif(not detectors.LINUX, reason="/proc reader is Linux-only")
+def test_busy_child_is_reported_as_runaway():
+    child = subprocess.Popen([sys.executable, "-c", "while True: pass"])
+    try:
+        detectors.get_runaway_processes(cpu_threshold=50, sustain_secs=0)
+        deadline = time.monotonic() + 5
+        runaways = []
+        while time.monotonic() < deadline and not runaways:
+            time.sleep(0.5)
+            runaways = [r for r in detectors.get_runaway_processes(cpu_threshold=50, sustain_secs=0)
+                        if r["pid"] == child.pid]
+        assert runaways and runaways[0]["cpu_percent"] > 50
+    finally:
+        child.kill()
+        child.wait()
+
+
+@pytest.mark.skipif(not detectors.LINUX, reason="/proc reader is Linux-only")
+def test_reading_leaves_the_shared_cpu_baseline_alone():
+    from pulse.state import STATE
+
+    before = STATE.process_cpu
+    detectors.read_processes()
+    assert STATE.process_cpu is before
@@ -0,0 +1,68 @@
+import pytest
+from pulse import core, diagnostics
+
+
+@pytest.fixture(autouse=True)
+def fresh_diagnostics():
+    core.reset_diagnostics()
+    yield
+    core.reset_diagnostics()
+
+
+def test_calls_advance_counters():
+    core.get_memory_info()
+    core.get_memory_info(as_json=True)
+    core.Monitor().memory_info()
+    core.get_network_stats()
+    collectors = core.get_diagnostics()["collectors"]
+    assert collectors["get_memory_info"]["calls"] == 3
+    assert collectors["get_network_stats"]["calls"] == 1
+    for stats in collectors.values():
+        assert stats["last_ms"] >= 0 and stats["avg_ms"] >= 0
+    assert "get_disk_info" not in collectors
+
+
+def test_failing_calls_are_timed():
+    with pytest.raises(ValueError):
+        core.snapshot(include=["gpu"])
+    assert core.get_diagnostics()["collectors"]["snapshot"]["calls"] == 1
+
+
+def test_rolling_average_window():
+    for ms in [1000.0] + [10.0] * diagnostics.WINDOW:
+        diagnostics._record("get_fans", ms)
+    stats = core.get_diagnostics()["collectors"]["get_fans"]
+    assert stats == {"calls": diagnostics.WINDOW + 1, "internal_errors": 0, "last_ms": 10.0,
+                     "avg_ms": pytest.approx(10.0)}
+
+
+def test_reset_and_host_details():
+    core.get_uptime()
+    core.reset_diagnostics()
+    diag = core.get_diagnostics()
+    assert diag["collectors"] == {}
+    assert diag["counts"]["processes"] > 0
+    assert set(diag["counts"]) == {"processes", "interfaces", "disks"}
+    assert diag["version"] and diag["python"] and diag["platform"]
+
+
+def test_collector_bug_becomes_internal_error():
+    core.get_cpu_percents()
+    with pytest.raises(core.PulseInternalError, match="_fail_for_testing failed unexpectedly: IndexError") as info:
+        core._fail_for_testing()
+    assert isinstance(info.value.__cause__, IndexError)
+    # The half-written baseline was dropped, so the next reading starts clean
+    assert core.STATE.cpu_times is None
+    assert all(0 <= percent <= 100 for percent in core.get_cpu_percents())
+    assert core.get_diagnostics()["collectors"]["_fail_for_testing"]["internal_errors"] == 1
+
+
+def test_documented_errors_pass_through(monkeypatch):
+    def broken():
+        raise KeyError("cpu0")
+    monkeypatch.setattr(core.direct_os, "get_memory_info", broken)
+    with pytest.raises(core.PulseInternalError, match="get_memory_info failed unexpectedly: KeyError"):
+        core.get_memory_info()
+    with pytest.raises(ValueError, match="sort_by"):
+        core.get_process_list(sort_by="size")
+    assert core.get_diagnostics()["collectors"]["get_process_list"]["internal_errors"] == 0
@@ -0,0 +1,70 @@
+import random
+import time
+import pytest
+from pulse import disk_history, disks
+
+DAY = 86400
+GIB = 1024 ** 3
+
+def _series(rate_per_day, days=7, start_used=50 * GIB, total=100 * GIB, noise=0):
+    rng = random.Random(7)
+    samples = []
+    for hour in range(days * 24):
+        t = hour * 3600.0
+        used = int(start_used + rate_per_day * t / DAY + rng.uniform(-noise, noise))
+        samples.append((t, used, total - used))
+    return samples
+
+def test_linear_fit_exact_line():
+    slope, intercept = disk_history.linear_fit([(0, 3), (1, 5), (2, 7)])
+    assert slope == pytest.approx(2)
+    assert intercept == pytest.approx(3)
+
+def test_linear_fit_degenerate():
+    assert disk_history.linear_fit([(1, 5)]) is None
+    assert disk_history.linear_fit([(1, 5), (1, 9)]) is None
+
+def test_projection_steady_growth():
+    samples = _series(GIB)
+    projection = disk_history.project(samples)
+    assert projection["fill_rate_bytes_per_day"] == pytest.approx(GIB, rel=1e-3)
+    assert projection["time_to_full_secs"] == pytest.approx(samples[-1][2] / GIB * DAY, rel=1e-3)
+
+def test_projection_noisy_growth():
+    """Half a GiB of jitter per sample still averages out to the real trend."""
+    projection = disk_history.project(_series(2 * GIB, noise=GIB // 2))
+    assert projection["fill_rate_bytes_per_day"] == pytest.approx(2 * GIB, rel=0.05)
+    assert projection["time_to_full_secs"] > 0
+
+def test_projection_shrinking_and_flat():
+    shrinking = disk_history.project(_series(-GIB))
+    assert shrinking["fill_rate_bytes_per_day"] < 0
+    assert shrinking["time_to_full_secs"] is None
+    assert disk_history.project(_series(0))["time_to_full_secs"] is None
+
+def test_projection_too_few_samples():
+    assert disk_history.project([(0.0, 10, 90)]) == {
+        "samples": 1, "fill_rate_bytes_per_day": None, "time_to_full_secs": None,
+    }
+
+def test_sampler_records_history(monkeypatch):
+    used = [10, 20, 30, 40]
+    def fake_disk_info():
+        value = used.pop(0) if len(used) > 1 else used[0]
+        return [{"mountpoint": "/var", "used": value, "available": 100 - value}]
+    monkeypatch.setattr(disks, "get_disk_info", fake_disk_info)
+    monkeypatch.setattr(disk_history, "_history", {})
+
+    disk_history.start_disk_sampler(interval_secs=0.01)
+    try:
+        deadline = time.monotonic() + 2
+        while len(disk_history._history.get("/var", ())) < 4 and time.monotonic() < deadline:
+            time.sleep(0.01)
+    finally:
+        disk_history.stop_disk_sampler()
+
+    projection = disk_history.get_disk_projection("/var")
+    assert projection["mount_point"] == "/var"
+    assert projection["fill_rate_bytes_per_day"] > 0
+    with pytest.raises(KeyError):
+        disk_history.get_disk_projection("/nonexistent")
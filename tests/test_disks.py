@@ -0,0 +1,458 @@
+import os
+import pytest
+from pulse import disks
+from pulse.state import STATE
+
+# Pre-4.18 kernels: 14 columns
+DISKSTATS_14 = """   8       0 sda 1200 30 48000 900 800 20 16000 700 0 1500 1600
+   8       1 sda1 1100 30 44000 850 790 20 15800 690 0 1400 1540
+"""
+
+# 5.5+ kernels: 20 columns (discard and flush groups)
+DISKSTATS_20 = """ 259       0 nvme0n1 5000 10 200000 3000 4000 5 100000 2500 2 6000 5600 10 0 2048 3 50 7
+ 259       1 nvme0n1p1 90 0 4096 40 0 0 0 0 0 50 40 0 0 0 0 0 0
+   7       0 loop0 12 0 96 2 0 0 0 0 0 4 2 0 0 0 0 0 0
+"""
+
+def test_parse_diskstats_14_fields():
+    stats = disks.parse_diskstats(DISKSTATS_14)
+    sda = stats["sda"]
+    assert sda["read_count"] == 1200
+    assert sda["read_bytes"] == 48000 * 512
+    assert sda["write_bytes"] == 16000 * 512
+    assert sda["io_time_ms"] == 1500
+    assert sda["weighted_io_time_ms"] == 1600
+    assert "discard_count" not in sda
+
+def test_parse_diskstats_extended_fields():
+    stats = disks.parse_diskstats(DISKSTATS_20)
+    nvme = stats["nvme0n1"]
+    assert nvme["write_count"] == 4000
+    assert nvme["discard_bytes"] == 2048 * 512
+    assert nvme["flush_count"] == 50
+    assert nvme["flush_time_ms"] == 7
+
+@pytest.fixture
+def fake_block(tmp_path, monkeypatch):
+    """A fake /proc/diskstats plus a sysfs tree where nvme0n1p1 is a partition."""
+    (tmp_path / "block" / "nvme0n1").mkdir(parents=True)
+    (tmp_path / "block" / "nvme0n1p1").mkdir()
+    (tmp_path / "block" / "nvme0n1p1" / "partition").write_text("1\n")
+    diskstats = tmp_path / "diskstats"
+    diskstats.write_text(DISKSTATS_20)
+    monkeypatch.setattr(disks, "SYS_CLASS_BLOCK", str(tmp_path / "block"))
+    monkeypatch.setattr(disks, "PROC_DISKSTATS", str(diskstats))
+    mounts = tmp_path / "mounts"
+    mounts.write_text(
+        "/dev/nvme0n1p1 /boot/efi vfat rw,relatime 0 0\n"
+        "/dev/nvme0n1p1 /srv/chroot/boot/efi vfat rw,relatime 0 0\n"
+        "tmpfs /run tmpfs rw,nosuid 0 0\n"
+    )
+    monkeypatch.setattr(disks, "PROC_MOUNTS", str(mounts))
+    monkeypatch.setattr(STATE, "disk_io", None)
+    return diskstats
+
+@pytest.mark.skipif(not disks.LINUX, reason="diskstats collector is Linux-only")
+def test_disk_io_flags_partitions(fake_block):
+    io = disks.get_disk_io()
+    assert io["nvme0n1"]["is_partition"] is False
+    assert io["nvme0n1p1"]["is_partition"] is True
+    assert "loop0" not in io
+
+@pytest.mark.skipif(not disks.LINUX, reason="diskstats collector is Linux-only")
+def test_disk_io_partition_mount_points(fake_block):
+    """A bind-mounted partition lists every mount point, shortest first."""
+    io = disks.get_disk_io()
+    assert io["nvme0n1p1"]["mount_point"] == "/boot/efi"
+    assert io["nvme0n1p1"]["mount_points"] == ["/boot/efi", "/srv/chroot/boot/efi"]
+    assert io["nvme0n1"]["mount_point"] is None
+    assert io["nvme0n1"]["mount_points"] == []
+    assert disks.get_disk_io_rates()["nvme0n1p1"]["mount_point"] == "/boot/efi"
+
+@pytest.mark.skipif(not disks.LINUX, reason="diskstats collector is Linux-only")
+def test_disk_io_rates_clamp_resets(fake_block, monkeypatch):
+    clock = iter([100.0, 102.0])
+    monkeypatch.setattr(disks.time, "monotonic", lambda: next(clock))
+
+    first = disks.get_disk_io_rates()
+    assert first["nvme0n1"]["read_bytes_per_sec"] == 0.0
+
+    # Two seconds later: 1 MiB more read, and the write counter was reset
+    fake_block.write_text(DISKSTATS_20.replace("200000 3000 4000 5 100000", "202048 3000 10 5 100"))
+    second = disks.get_disk_io_rates()
+    assert second["nvme0n1"]["read_bytes_per_sec"] == 2048 * 512 / 2
+    assert second["nvme0n1"]["write_bytes_per_sec"] == 0.0
+    assert second["nvme0n1"]["write_ops_per_sec"] == 0.0
+    assert second["nvme0n1p1"]["is_partition"] is True
+
+def _mount(device, mountpoint, fstype, options="rw,relatime"):
+    return {"device": device, "mountpoint": mountpoint, "fstype": fstype, "options": options}
+
+# A trimmed /proc/mounts from an Ubuntu desktop with snaps and Docker installed
+UBUNTU_MOUNTS = [
+    _mount("sysfs", "/sys", "sysfs"),
+    _mount("proc", "/proc", "proc"),
+    _mount("udev", "/dev", "devtmpfs"),
+    _mount("tmpfs", "/run", "tmpfs"),
+    _mount("/dev/nvme0n1p2", "/", "ext4"),
+    _mount("tmpfs", "/dev/shm", "tmpfs"),
+    _mount("cgroup2", "/sys/fs/cgroup", "cgroup2"),
+    _mount("/dev/loop0", "/snap/core20/2015", "squashfs", "ro,nodev"),
+    _mount("/dev/loop1", "/snap/firefox/3600", "squashfs", "ro,nodev"),
+    _mount("/dev/loop2", "/snap/gnome-42-2204/141", "squashfs", "ro,nodev"),
+    _mount("/dev/nvme0n1p1", "/boot/efi", "vfat"),
+    _mount("/dev/sda1", "/home", "ext4"),
+    _mount("/dev/nvme0n1p2", "/var/snap/firefox/common/host-hunspell", "ext4", "ro"),
+    _mount("overlay", "/var/lib/docker/overlay2/abc/merged", "overlay"),
+    _mount("/dev/sda1", "/srv/home-bind", "ext4"),
+]
+
+def test_filter_mounts_snap_heavy_ubuntu():
+    kept = disks.filter_mounts(UBUNTU_MOUNTS)
+    assert [m["mountpoint"] for m in kept] == ["/", "/boot/efi", "/home"]
+
+def test_filter_mounts_overrides():
+    # Everything, untouched
+    assert disks.filter_mounts(UBUNTU_MOUNTS, physical_only=False) == UBUNTU_MOUNTS
+
+    # A custom exclusion list replaces the default one
+    with_snaps = disks.filter_mounts(UBUNTU_MOUNTS, exclude_fs=["vfat"])
+    assert "/snap/core20/2015" in [m["mountpoint"] for m in with_snaps]
+    assert "/boot/efi" not in [m["mountpoint"] for m in with_snaps]
+
+    # include_fs whitelists types, even pseudo ones
+    tmpfs = disks.filter_mounts(UBUNTU_MOUNTS, include_fs=["tmpfs"])
+    assert [m["mountpoint"] for m in tmpfs] == ["/run", "/dev/shm"]
+
+@pytest.mark.parametrize("name, parent", [
+    ("nvme0n1p2", "nvme0n1"),
+    ("nvme0n1", "nvme0n1"),
+    ("sda1", "sda"),
+    ("sda", "sda"),
+    ("sdab12", "sdab"),
+    ("mmcblk0p1", "mmcblk0"),
+    ("vdb3", "vdb"),
+    ("xvda1", "xvda"),
+    ("md0", "md0"),
+    ("dm-0", "dm-0"),
+])
+def test_parent_block_device_by_name(name, parent, tmp_path, monkeypatch):
+    monkeypatch.setattr(disks, "SYS_CLASS_BLOCK", str(tmp_path))
+    assert disks.parent_block_device(name) == parent
+
+def test_parent_block_device_prefers_sysfs(tmp_path, monkeypatch):
+    """sysfs nests partitions under their disk, which beats name heuristics."""
+    devices = tmp_path / "devices" / "bcache0"
+    (devices / "weirdname7").mkdir(parents=True)
+    (devices / "weirdname7" / "partition").write_text("7\n")
+    block = tmp_path / "block"
+    block.mkdir()
+    (block / "weirdname7").symlink_to(devices / "weirdname7")
+    monkeypatch.setattr(disks, "SYS_CLASS_BLOCK", str(block))
+    assert disks.parent_block_device("weirdname7") == "bcache0"
+
+@pytest.mark.skipif(not disks.LINUX, reason="sysfs identity is Linux-only")
+def test_device_identity_from_sysfs(tmp_path, monkeypatch):
+    nvme = tmp_path / "nvme0n1"
+    (nvme / "queue").mkdir(parents=True)
+    (nvme / "device").mkdir()
+    (nvme / "queue" / "rotational").write_text("0\n")
+    (nvme / "device" / "model").write_text("Samsung SSD 970 EVO 1TB                 \n")
+    (nvme / "device" / "serial").write_text("S467NX0M123456\n")
+    (tmp_path / "sdb").mkdir()  # nothing readable
+    monkeypatch.setattr(disks, "SYS_CLASS_BLOCK", str(tmp_path))
+
+    identity = disks.get_device_identity("/dev/nvme0n1p2")
+    assert identity == {"kind": "ssd", "model": "Samsung SSD 970 EVO 1TB", "serial": "S467NX0M123456"}
+    assert disks.get_device_identity("/dev/sdb1") == {"kind": "unknown", "model": None, "serial": None}
+
+def test_inode_usage_without_inode_table():
+    assert disks.inode_usage(0, 0) == {
+        "inodes_total": None, "inodes_used": None, "inodes_free": None, "inodes_percent": None,
+    }
+    assert disks.inode_usage(1000, 250)["inodes_percent"] == 75.0
+
+@pytest.mark.skipif(disks.WINDOWS, reason="Windows volumes have no inode counts")
+def test_root_filesystem_inodes_are_consistent():
+    usage = disks.disk_usage("/")
+    assert "inodes_total" in usage
+    if usage["inodes_total"] is not None:
+        assert usage["inodes_used"] == usage["inodes_total"] - usage["inodes_free"]
+        assert 0 <= usage["inodes_percent"] <= 100
+
+@pytest.mark.skipif(disks.WINDOWS, reason="POSIX mount paths")
+def test_mount_for_path_longest_prefix():
+    mounts = [
+        _mount("/dev/sda2", "/", "ext4"),
+        _mount("/dev/sda3", "/home", "ext4"),
+        _mount("/dev/sdb1", "/home/user/data", "xfs"),
+        _mount("/dev/sdc1", "/home2", "ext4"),
+    ]
+    assert disks.mount_for_path("/home/user/data/projects", mounts)["device"] == "/dev/sdb1"
+    assert disks.mount_for_path("/home/user/Documents", mounts)["device"] == "/dev/sda3"
+    assert disks.mount_for_path("/home2/x", mounts)["device"] == "/dev/sdc1"
+    assert disks.mount_for_path("/home", mounts)["device"] == "/dev/sda3"
+    assert disks.mount_for_path("/var/log", mounts)["device"] == "/dev/sda2"
+
+def test_path_usage_follows_symlinks(tmp_path):
+    target = tmp_path / "data"
+    target.mkdir()
+    link = tmp_path / "link"
+    try:
+        link.symlink_to(target)
+    except OSError:
+        pytest.skip("symlinks not permitted")
+    usage = disks.get_path_usage(str(link))
+    assert usage["path"] == os.path.realpath(target)
+    assert usage["total"] > 0
+    assert 0 <= usage["percent"] <= 100
+
+def test_path_usage_missing_path(tmp_path):
+    with pytest.raises(FileNotFoundError):
+        disks.get_path_usage(str(tmp_path / "nope"))
+
+def _smart_log(critical_warning=0, kelvin=310, percentage_used=3, power_on_hours=1234):
+    log = bytearray(512)
+    log[0] = critical_warning
+    log[1:3] = kelvin.to_bytes(2, "little")
+    log[3] = 100
+    log[5] = percentage_used
+    log[128:144] = power_on_hours.to_bytes(16, "little")
+    return bytes(log)
+
+def test_parse_nvme_smart_log():
+    smart = disks.parse_nvme_smart_log(_smart_log())
+    assert smart["temperature_c"] == pytest.approx(36.85)
+    assert smart["percentage_used"] == 3
+    assert smart["power_on_hours"] == 1234
+    assert smart["critical_warning"] == 0
+    with pytest.raises(ValueError):
+        disks.parse_nvme_smart_log(b"\0" * 64)
+
+@pytest.mark.skipif(not disks.LINUX, reason="sysfs health is Linux-only")
+def test_disk_health_summary(tmp_path, monkeypatch):
+    hwmon = tmp_path / "nvme0n1" / "device" / "hwmon2"
+    hwmon.mkdir(parents=True)
+    (hwmon / "temp1_input").write_text("41850\n")
+    drivetemp = tmp_path / "sda" / "device" / "hwmon" / "hwmon4"
+    drivetemp.mkdir(parents=True)
+    (drivetemp / "temp1_input").write_text("61000\n")
+    (drivetemp / "temp1_crit").write_text("60000\n")
+    (tmp_path / "sdb").mkdir()
+    monkeypatch.setattr(disks, "SYS_CLASS_BLOCK", str(tmp_path))
+    monkeypatch.setattr(disks, "read_nvme_smart_log", lambda dev: _smart_log() if dev.endswith("nvme0") else None)
+
+    health = {d["name"]: d for d in disks.get_disk_health()}
+    assert health["nvme0n1"]["temperature_c"] == 41.85
+    assert health["nvme0n1"]["power_on_hours"] == 1234
+    assert health["nvme0n1"]["health"] == "ok"
+    assert health["sda"]["health"] == "warning"  # above its own crit threshold
+    assert health["sdb"] == {
+        "name": "sdb", "temperature_c": None, "power_on_hours": None,
+        "percentage_used": None, "health": "unknown",
+    }
+
+def test_filter_mounts_keeps_network_shares():
+    mounts = UBUNTU_MOUNTS + [
+        _mount("nas:/export/media", "/mnt/media", "nfs4"),
+        _mount("//files/team", "/mnt/team", "cifs"),
+    ]
+    kept = [m["mountpoint"] for m in disks.filter_mounts(mounts)]
+    assert kept[-2:] == ["/mnt/media", "/mnt/team"]
+
+WSL_MOUNTS = [
+    _mount("/dev/sdc", "/", "ext4"),
+    _mount("drivers", "/usr/lib/wsl/drivers", "9p", "ro,aname=drivers;fmask=222;dfmask=222"),
+    _mount("C:\\", "/mnt/c", "9p", "rw,aname=drvfs;path=C:\\;uid=1000;gid=1000"),
+    _mount("D:", "/mnt/d", "drvfs"),
+]
+
+def test_filter_mounts_keeps_wsl_windows_drives():
+    kept = [m["mountpoint"] for m in disks.filter_mounts(WSL_MOUNTS)]
+    assert kept == ["/", "/mnt/c", "/mnt/d"]
+
+@pytest.mark.parametrize("wsl", [True, False])
+def test_disk_info_tags_windows_drives_under_wsl(monkeypatch, wsl):
+    monkeypatch.setattr(disks.environment, "running_under_wsl", lambda: wsl)
+    monkeypatch.setattr(disks, "list_mounts", lambda: WSL_MOUNTS)
+    monkeypatch.setattr(disks, "disk_usage", lambda mountpoint: {
+        "total": 100, "used": 40, "free": 60, "available": 60, "percent": 40.0,
+        **disks.inode_usage(None, None)})
+    monkeypatch.setattr(disks, "get_device_identity", lambda device: {"kind": "unknown", "model": None, "serial": None})
+    info = {d["mountpoint"]: d["is_windows_drive"] for d in disks.get_disk_info()}
+    assert info == {"/": False, "/mnt/c": wsl, "/mnt/d": wsl}
+
+def test_disk_info_hanging_network_mount(monkeypatch):
+    """A dead NFS server yields a stale entry within the timeout instead of a hang."""
+    import threading
+    import time
+    release = threading.Event()
+
+    def fake_usage(mountpoint):
+        if mountpoint == "/mnt/dead":
+            release.wait(10)
+        return {"total": 100, "used": 40, "free": 60, "available": 50, "percent": 44.4,
+                **disks.inode_usage(None, None)}
+
+    monkeypatch.setattr(disks, "list_mounts", lambda: [
+        _mount("/dev/sda1", "/", "ext4"),
+        _mount("nas:/export", "/mnt/dead", "nfs"),
+    ])
+    monkeypatch.setattr(disks, "disk_usage", fake_usage)
+    monkeypatch.setattr(disks, "get_device_identity", lambda device: {"kind": "ssd", "model": None, "serial": None})
+    try:
+        started = time.monotonic()
+        info = {d["mountpoint"]: d for d in disks.get_disk_info(network_timeout=0.1)}
+        assert time.monotonic() - started < 2
+        assert info["/"]["stale"] is False and info["/"]["is_network"] is False
+        assert info["/mnt/dead"]["is_network"] is True
+        assert info["/mnt/dead"]["stale"] is True
+        assert info["/mnt/dead"]["available"] is None
+
+        # The hung query is reused, not duplicated, and recovers once the server answers
+        disks.get_disk_info(network_timeout=0.05)
+        assert len(disks._usage_jobs) == 1
+        release.set()
+        recovered = {d["mountpoint"]: d for d in disks.get_disk_info(network_timeout=1)}
+        assert recovered["/mnt/dead"]["stale"] is False
+        assert recovered["/mnt/dead"]["available"] == 50
+    finally:
+        release.set()
+
+@pytest.mark.parametrize("encoded, label", [
+    ("Backup\\x20Drive", "Backup Drive"),
+    ("EFI", "EFI"),
+    ("a\\x2fb", "a/b"),
+    ("Fotos\\x20Ma\\xc3\\xb1ana", "Fotos Mañana"),
+    ("café", "café"),
+])
+def test_decode_udev_string(encoded, label):
+    assert disks.decode_udev_string(encoded) == label
+
+@pytest.mark.skipif(not disks.LINUX, reason="by-label links are Linux-only")
+def test_read_labels_from_by_label_links(tmp_path, monkeypatch):
+    (tmp_path / "sdb1").write_text("")
+    by_label = tmp_path / "by-label"
+    by_label.mkdir()
+    (by_label / "Backup\\x20Drive").symlink_to("../sdb1")
+    monkeypatch.setattr(disks, "DEV_DISK_BY_LABEL", str(by_label))
+    assert disks.get_volume_label(str(tmp_path / "sdb1")) == "Backup Drive"
+    assert disks.get_volume_label(str(tmp_path / "sdc1")) is None
+
+def test_saturation_stats():
+    prev = disks.parse_diskstats(DISKSTATS_14)["sda"]
+    curr = dict(prev)
+    # Over 2 s: 100 ops taking 400 ms in total, busy for 500 ms, 1 s of queued time
+    curr.update(read_count=prev["read_count"] + 60, write_count=prev["write_count"] + 40,
+                read_time_ms=prev["read_time_ms"] + 250, write_time_ms=prev["write_time_ms"] + 150,
+                io_time_ms=prev["io_time_ms"] + 500, weighted_io_time_ms=prev["weighted_io_time_ms"] + 1000)
+    stats = disks.saturation_stats(prev, curr, 2.0)
+    assert stats == {"util_percent": 25.0, "avg_latency_ms": 4.0, "queue_depth": 0.5}
+
+def test_saturation_util_is_clamped():
+    """io_ticks landing slightly past the sample window never reports >100%."""
+    prev = disks.parse_diskstats(DISKSTATS_20)["nvme0n1"]
+    curr = dict(prev, io_time_ms=prev["io_time_ms"] + 1030, weighted_io_time_ms=prev["weighted_io_time_ms"] + 32000)
+    stats = disks.saturation_stats(prev, curr, 1.0)
+    assert stats["util_percent"] == 100.0
+    assert stats["queue_depth"] == 32.0
+    assert stats["avg_latency_ms"] == 0.0  # no completed requests
+
+@pytest.mark.skipif(not disks.LINUX, reason="block device enumeration is Linux-only")
+def test_block_devices_include_unmounted(tmp_path, monkeypatch):
+    """A blank second disk shows up alongside the mounted system disk."""
+    block = tmp_path / "block"
+    (block / "sda" / "sda1").mkdir(parents=True)
+    (block / "sda" / "sda1" / "partition").write_text("1\n")
+    (block / "sda" / "sda1" / "size").write_text("2048\n")
+    (block / "sda1").symlink_to(block / "sda" / "sda1")
+    (block / "sda" / "size").write_text("4096\n")
+    (block / "sda" / "queue").mkdir()
+    (block / "sda" / "queue" / "rotational").write_text("1\n")
+    (block / "sdb" / "device").mkdir(parents=True)
+    (block / "sdb" / "size").write_text("3907029168\n")
+    (block / "sdb" / "removable").write_text("1\n")
+    (block / "sdb" / "device" / "model").write_text("Ultra Fit      \n")
+    (block / "loop0").mkdir()
+    mounts = tmp_path / "mounts"
+    mounts.write_text("/dev/sda1 / ext4 rw,relatime 0 0\n")
+    monkeypatch.setattr(disks, "SYS_CLASS_BLOCK", str(block))
+    monkeypatch.setattr(disks, "PROC_MOUNTS", str(mounts))
+    monkeypatch.setattr(disks, "get_disk_info", lambda physical_only=True: [{
+        "mountpoint": "/", "fstype": "ext4", "label": "root",
+        "used": 600, "available": 400, "percent": 60.0,
+    }])
+
+    devices = {d["name"]: d for d in disks.get_block_devices()}
+    assert sorted(devices) == ["sda", "sda1", "sdb"]
+    assert devices["sda1"]["parent"] == "sda"
+    assert devices["sda1"]["size"] == 2048 * 512
+    assert devices["sda1"]["mount_point"] == "/"
+    assert devices["sda1"]["label"] == "root"
+    assert devices["sda"]["kind"] == "hdd"
+    assert devices["sda"]["is_mounted"] is False
+    assert devices["sdb"]["kind"] == "removable"
+    assert devices["sdb"]["model"] == "Ultra Fit"
+    assert devices["sdb"]["fstype"] is None
+    assert "loop0" in {d["name"] for d in disks.get_block_devices(include_virtual=True)}
+
+@pytest.mark.skipif(not disks.LINUX, reason="sysfs device hierarchy is Linux-only")
+def test_removable_media_usb_stick(tmp_path, monkeypatch):
+    """Vendor and product come from the USB device several levels above the disk."""
+    usb = tmp_path / "devices" / "usb2" / "2-1"
+    scsi = usb / "2-1:1.0" / "host6" / "target6:0:0" / "6:0:0:0"
+    scsi.mkdir(parents=True)
+    (usb / "idVendor").write_text("0781\n")
+    (usb / "manufacturer").write_text("SanDisk\n")
+    (usb / "product").write_text("Ultra\n")
+    (usb / "speed").write_text("5000\n")
+    block = tmp_path / "block"
+    (block / "sdc" / "sdc1").mkdir(parents=True)
+    (block / "sdc" / "sdc1" / "partition").write_text("1\n")
+    (block / "sdc1").symlink_to(block / "sdc" / "sdc1")
+    (block / "sdc" / "device").symlink_to(scsi)
+    (block / "sdc" / "size").write_text("60063744\n")
+    (block / "sdc" / "removable").write_text("0\n")
+    mounts = tmp_path / "mounts"
+    mounts.write_text("/dev/sdc1 /media/usb vfat rw 0 0\n")
+    monkeypatch.setattr(disks, "SYS_CLASS_BLOCK", str(block))
+    monkeypatch.setattr(disks, "PROC_MOUNTS", str(mounts))
+    monkeypatch.setattr(disks, "get_disk_info", lambda physical_only=True: [])
+
+    assert disks.get_removable_media() == [{
+        "name": "sdc", "size": 60063744 * 512, "vendor": "SanDisk", "product": "Ultra",
+        "bus": "USB 3.0", "is_mounted": True, "mount_points": ["/media/usb"],
+    }]
+
+@pytest.mark.skipif(not disks.LINUX, reason="sysfs device hierarchy is Linux-only")
+def test_bus_info_vanished_device(tmp_path, monkeypatch):
+    monkeypatch.setattr(disks, "SYS_CLASS_BLOCK", str(tmp_path))
+    assert disks.get_bus_info("sdz") == {"vendor": None, "product": None, "bus": None}
+
+# Escaped whitespace in paths, '=' in values, and a quoted SELinux context
+TRICKY_MOUNTS = r"""/dev/sdb1 /media/alex/My\040Passport vfat rw,nosuid,uid=1000,gid=1000,fmask=0022 0 0
+/dev/sda2 /srv/back\134slash\011tab ext4 rw,noatime,discard,usrquota 0 0
+/dev/sda3 /var/lib/data xfs rw,context="system_u:object_r:var_t:s0:c1,c2",attr2 0 0
+"""
+
+def test_parse_mount_options():
+    assert disks.parse_mount_options("rw,nosuid,uid=1000,subvol=/@home") == [
+        "rw", "nosuid", "uid=1000", "subvol=/@home",
+    ]
+    assert disks.parse_mount_options('ro,context="u:r:t:s0:c1,c2",attr2') == [
+        "ro", 'context="u:r:t:s0:c1,c2"', "attr2",
+    ]
+    assert disks.parse_mount_options("") == []
+
+@pytest.mark.skipif(not disks.LINUX, reason="/proc/mounts reader is Linux-only")
+def test_list_mounts_unescapes_fields(tmp_path, monkeypatch):
+    mounts = tmp_path / "mounts"
+    mounts.write_text(TRICKY_MOUNTS)
+    monkeypatch.setattr(disks, "PROC_MOUNTS", str(mounts))
+    listed = disks.list_mounts()
+    assert [m["mountpoint"] for m in listed] == [
+        "/media/alex/My Passport", "/srv/back\\slash\ttab", "/var/lib/data",
+    ]
+    assert "uid=1000" in disks.parse_mount_options(listed[0]["options"])
+    assert "discard" in disks.parse_mount_options(listed[1]["options"])
+    assert len(disks.parse_mount_options(listed[2]["options"])) == 3
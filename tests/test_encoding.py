@@ -0,0 +1,45 @@
+import json
+import sys
+import pytest
+from pulse import core, encoding
+from pulse.errors import Unsupported
+
+
+def test_snapshot_msgpack_matches_json(monkeypatch):
+    msgpack = pytest.importorskip("msgpack")
+    include = ["cpu", "memory", "processes", "network"]
+    monkeypatch.setattr(core.time, "time", lambda: 1760000000.5)
+    # Cached readings, so both forms encode the same collection
+    core.set_default_max_age(60_000)
+    try:
+        monitor = core.Monitor()
+        packed = monitor.snapshot_msgpack(include=include)
+        as_json = monitor.snapshot(include=include, as_json=True)
+    finally:
+        core.set_default_max_age(0)
+    assert isinstance(packed, bytes)
+    decoded = msgpack.unpackb(packed, raw=False)
+    assert set(decoded) == {"timestamp", "cpu", "memory", "processes", "network"}
+    assert decoded == json.loads(as_json)
+
+    snap = core.snapshot(include=["memory"])
+    assert msgpack.unpackb(encoding.to_msgpack(snap), raw=False) == json.loads(encoding.to_json(snap))
+
+
+def test_every_collector_takes_as_msgpack():
+    msgpack = pytest.importorskip("msgpack")
+    memory = msgpack.unpackb(core.get_memory_info(as_msgpack=True), raw=False)
+    assert memory.keys() == core.get_memory_info().keys() | {"timestamp"}
+    cpu = msgpack.unpackb(core.Monitor().cpu_percents(as_msgpack=True), raw=False)
+    assert len(cpu["data"]) == len(core.get_cpu_percents())
+
+
+def test_json_and_msgpack_conflict():
+    with pytest.raises(ValueError, match="as_msgpack cannot be combined with as_json"):
+        core.get_memory_info(as_json=True, as_msgpack=True)
+
+
+def test_missing_msgpack_is_unsupported(monkeypatch):
+    monkeypatch.setitem(sys.modules, "msgpack", None)
+    with pytest.raises(Unsupported, match=r"pulse-monitor\[msgpack\]"):
+        core.get_snapshot_msgpack(include=["memory"])
@@ -0,0 +1,31 @@
+import errno
+import pytest
+from pulse import entropy
+
+def _raising(code):
+    def getrandom(size, flags):
+        raise OSError(code, "getrandom")
+    return getrandom
+
+def test_probe_crng():
+    calls = []
+    assert entropy.probe_crng(lambda size, flags: calls.append((size, flags)) or b"x", 1) is True
+    assert calls == [(1, 1)]
+    assert entropy.probe_crng(_raising(errno.EAGAIN), 1) is False
+    assert entropy.probe_crng(_raising(errno.ENOSYS), 1) is None
+    assert entropy.probe_crng(None) is None
+
+def test_entropy_status():
+    assert entropy.entropy_status(64, 256, False) == {
+        "entropy_avail": 64, "poolsize": 256, "percent": 25.0, "crng_ready": False,
+    }
+    assert entropy.entropy_status(None, 4096, True)["percent"] is None
+
+@pytest.mark.skipif(not entropy.LINUX, reason="entropy counters are Linux-only")
+def test_get_entropy_reads_proc(tmp_path, monkeypatch):
+    (tmp_path / "entropy_avail").write_text("3011\n")
+    (tmp_path / "poolsize").write_text("4096\n")
+    monkeypatch.setattr(entropy, "PROC_SYS_KERNEL_RANDOM", str(tmp_path))
+    monkeypatch.setattr(entropy, "probe_crng", lambda: True)
+    status = entropy.get_entropy()
+    assert (status["entropy_avail"], status["poolsize"], status["crng_ready"]) == (3011, 4096, True)
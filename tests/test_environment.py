@@ -0,0 +1,106 @@
+import pytest
+from pulse import environment
+
+@pytest.fixture(autouse=True)
+def fresh_wsl_check():
+    # running_under_wsl() is cached; tests that fake osrelease mustn't see or leave a stale answer
+    environment.running_under_wsl.cache_clear()
+    yield
+    environment.running_under_wsl.cache_clear()
+
+@pytest.mark.parametrize("cpuinfo, expected", [
+    ("processor\t: 0\nflags\t\t: fpu vme de pse tsc msr hypervisor lahf_lm\n", True),
+    ("processor\t: 0\nflags\t\t: fpu vme de pse tsc msr lahf_lm\n", False),
+    ("Processor\t: AArch64 Processor\nFeatures\t: fp asimd\n", False),
+])
+def test_cpuinfo_hypervisor_flag(cpuinfo, expected):
+    assert environment.cpuinfo_hypervisor_flag(cpuinfo) is expected
+
+@pytest.mark.parametrize("available, expected", [
+    ("tsc kvm-clock acpi_pm \n", "kvm"),
+    ("xen tsc hpet acpi_pm\n", "xen"),
+    ("hyperv_clocksource_tsc_page acpi_pm\n", "hyperv"),
+    ("tsc hpet acpi_pm\n", None),
+])
+def test_hypervisor_from_clocksource(available, expected):
+    assert environment.hypervisor_from_clocksource(available) == expected
+
+@pytest.mark.parametrize("cgroup, expected", [
+    ("0::/kubepods/besteffort/pod1234/abcdef\n", "kubernetes"),
+    ("12:pids:/docker/3f2a9c\n0::/docker/3f2a9c\n", "docker"),
+    ("0::/machine.slice/libpod-5e6f.scope/container\n", "podman"),
+    ("2:cpu:/lxc/web01\n", "lxc"),
+    ("0::/init.scope\n", None),
+    ("0::/\n", None),
+])
+def test_container_from_cgroup(cgroup, expected):
+    assert environment.container_from_cgroup(cgroup) == expected
+
+@pytest.mark.parametrize("environ, expected", [
+    ({"KUBERNETES_SERVICE_HOST": "10.0.0.1", "container": "docker"}, "kubernetes"),
+    ({"container": "podman"}, "podman"),
+    ({"container": "lxc"}, "lxc"),
+    ({"container": "systemd-nspawn"}, None),
+    ({}, None),
+])
+def test_container_from_environ(environ, expected):
+    assert environment.container_from_environ(environ) == expected
+
+@pytest.mark.parametrize("dockerenv, containerenv, expected", [
+    (True, False, "docker"),
+    (False, True, "podman"),
+    (False, False, None),
+])
+def test_container_from_marker_files(dockerenv, containerenv, expected):
+    assert environment.container_from_marker_files(dockerenv, containerenv) == expected
+
+@pytest.mark.parametrize("osrelease, expected", [
+    ("5.15.153.1-microsoft-standard-WSL2\n", True),
+    ("4.4.0-19041-Microsoft\n", True),
+    ("6.8.0-45-generic\n", False),
+])
+def test_is_wsl(osrelease, expected):
+    assert environment.is_wsl(osrelease) is expected
+
+def test_parse_environ_block():
+    assert environment.parse_environ_block("container=podman\0HOME=/\0TERM\0") == {
+        "container": "podman", "HOME": "/",
+    }
+
+@pytest.mark.skipif(not environment.LINUX, reason="proc-based detection is Linux-only")
+def test_detect_environment_bare_metal(tmp_path, monkeypatch):
+    (tmp_path / "cpuinfo").write_text("flags\t\t: fpu vme de pse\n")
+    (tmp_path / "cgroup").write_text("0::/init.scope\n")
+    (tmp_path / "osrelease").write_text("6.8.0-45-generic\n")
+    for name, path in (("PROC_CPUINFO", "cpuinfo"), ("PROC_1_CGROUP", "cgroup"),
+                       ("PROC_OSRELEASE", "osrelease"), ("PROC_1_ENVIRON", "missing"),
+                       ("SYS_HYPERVISOR_TYPE", "missing"), ("AVAILABLE_CLOCKSOURCE", "missing"),
+                       ("DOCKERENV", "missing"), ("CONTAINERENV", "missing")):
+        monkeypatch.setattr(environment, name, str(tmp_path / path))
+    monkeypatch.setattr(environment.system, "get_hardware_info",
+                        lambda: {"manufacturer": "Dell Inc.", "product_name": "XPS 13 9310"})
+    monkeypatch.delenv("KUBERNETES_SERVICE_HOST", raising=False)
+    monkeypatch.delenv("container", raising=False)
+    assert environment.detect_environment() == {
+        "virtualization": "none", "container": "none", "wsl": False,
+    }
+
+@pytest.mark.skipif(not environment.LINUX, reason="proc-based detection is Linux-only")
+def test_running_under_wsl(tmp_path, monkeypatch):
+    osrelease = tmp_path / "osrelease"
+    monkeypatch.setattr(environment, "PROC_OSRELEASE", str(osrelease))
+    assert environment.running_under_wsl() is False
+    osrelease.write_text("5.15.153.1-microsoft-standard-WSL2\n")
+    assert environment.running_under_wsl() is False
+    environment.running_under_wsl.cache_clear()
+    assert environment.running_under_wsl() is True
+
+@pytest.mark.parametrize("vm_guest, expected", [
+    ("none\0", "none"),
+    ("bhyve\0", "bhyve"),
+    ("hv\0", "hyperv"),
+    ("generic\0", "unknown"),
+    ("something-new", "unknown"),
+])
+def test_hypervisor_from_vm_guest(vm_guest, expected):
+    assert environment.hypervisor_from_vm_guest(vm_guest) == expected
@@ -0,0 +1,50 @@
+import os
+import subprocess
+import sys
+
+import pytest
+from pulse import core, errors, processes
+
+def test_hierarchy_keeps_builtin_bases():
+    assert issubclass(errors.ProcessNotFound, errors.PulseError)
+    assert issubclass(errors.ProcessNotFound, ProcessLookupError)
+    assert issubclass(errors.PermissionDenied, PermissionError)
+    assert issubclass(errors.Unsupported, NotImplementedError)
+    assert issubclass(errors.Timeout, TimeoutError)
+    assert issubclass(errors.CorruptRecording, ValueError)
+    assert issubclass(errors.WorkerRestarted, RuntimeWarning)
+    assert issubclass(errors.PulseInternalError, RuntimeError)
+    assert issubclass(errors.ServiceNotFound, KeyError)
+    assert core.PulseError is errors.PulseError
+
+def _limits_or_none(pid):
+    try:
+        return core.get_process_limits(pid)
+    except core.ProcessNotFound:
+        return None
+
+@pytest.mark.skipif(not processes.LINUX, reason="reads /proc fixtures")
+def test_catch_process_not_found_only(tmp_path, monkeypatch):
+    monkeypatch.setattr(processes, "PROC", str(tmp_path))
+    assert _limits_or_none(424242) is None
+
+    # Other failures aren't swallowed by an except ProcessNotFound
+    (tmp_path / "7").mkdir()
+    (tmp_path / "7" / "limits").mkdir()
+    with pytest.raises(IsADirectoryError):
+        _limits_or_none(7)
+
+@pytest.mark.skipif(not processes.LINUX or os.geteuid() == 0, reason="needs an unprivileged user")
+def test_kill_other_users_process_is_permission_denied():
+    with pytest.raises(core.PermissionDenied):
+        core.kill_process(1)
+    with pytest.raises(core.PermissionDenied):
+        core.renice_process(1, 0)
+
+def test_signalling_a_missing_process_is_process_not_found():
+    child = subprocess.Popen([sys.executable, "-c", "pass"])
+    child.wait()
+    with pytest.raises(core.ProcessNotFound, match=f"^No such process: {child.pid}$"):
+        core.kill_process(child.pid)
+    with pytest.raises(core.ProcessNotFound, match=f"^No such process: {child.pid}$"):
+        core.renice_process(child.pid, 5)
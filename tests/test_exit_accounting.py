@@ -0,0 +1,160 @@
+import struct
+import subprocess
+import sys
+import time
+import pytest
+from pulse import capabilities, core, exit_accounting
+from pulse.errors import Unsupported
+
+
+def taskstats(pid, tgid, name="cc1", utime=250_000, stime=50_000, rss_kib=2048, read_bytes=4096,
+              write_bytes=8192, status=0, version=16):
+    data = bytearray(400)
+    struct.pack_into("=H", data, 0, version)
+    struct.pack_into("=I", data, 4, status)
+    data[80:80 + len(name)] = name.encode()
+    struct.pack_into("=I", data, 120, 1000)
+    struct.pack_into("=III", data, 128, pid, 1, 1_700_000_000)
+    struct.pack_into("=QQQ", data, 144, 1_500_000, utime, stime)
+    struct.pack_into("=Q", data, 200, rss_kib)
+    struct.pack_into("=QQ", data, 248, read_bytes, write_bytes)
+    struct.pack_into("=I", data, 368, tgid)
+    return bytes(data)
+
+
+def exit_message(*stats, group=None):
+    attrs = b"".join(exit_accounting._attribute(exit_accounting.TASKSTATS_TYPE_AGGR_PID,
+                                                exit_accounting._attribute(1, struct.pack("=I", 7))
+                                                + exit_accounting._attribute(exit_accounting.TASKSTATS_TYPE_STATS, s))
+                     for s in stats)
+    if group is not None:
+        attrs += exit_accounting._attribute(
+            exit_accounting.TASKSTATS_TYPE_AGGR_TGID,
+            exit_accounting._attribute(exit_accounting.TASKSTATS_TYPE_TGID, struct.pack("=I", group))
+            + exit_accounting._attribute(exit_accounting.TASKSTATS_TYPE_STATS, taskstats(0, 0, name="")))
+    return struct.pack("=BBH", exit_accounting.TASKSTATS_CMD_NEW, 1, 0) + attrs
+
+
+def test_parse_taskstats():
+    record = exit_accounting.parse_taskstats(taskstats(42, 42, status=9))
+    assert record == {
+        "pid": 42, "tgid": 42, "ppid": 1, "name": "cc1", "uid": 1000, "exit_code": 0, "signal": 9,
+        "start_time": 1_700_000_000, "elapsed_secs": 1.5, "cpu_user_secs": 0.25, "cpu_system_secs": 0.05,
+        "peak_rss_bytes": 2048 * 1024, "read_bytes": 4096, "write_bytes": 8192,
+    }
+    assert exit_accounting.parse_taskstats(taskstats(42, 42, status=3 << 8))["exit_code"] == 3
+    # Before version 11 there is no thread group id to fold threads by
+    assert exit_accounting.parse_taskstats(taskstats(42, 42, version=10))["tgid"] is None
+
+
+def test_parse_exit_keeps_thread_group_aggregates_in_order():
+    entries = exit_accounting.parse_exit(exit_message(taskstats(43, 42), group=42))
+    assert [(kind, value if kind == 5 else value["pid"]) for kind, value in entries] == [(4, 43), (5, 42)]
+    assert [value["pid"] for _, value in exit_accounting.parse_exit(exit_message(taskstats(42, 42)))] == [42]
+
+
+def test_threads_fold_into_their_process():
+    folder = exit_accounting.ThreadFolder(live_threads=lambda tgid: 1)
+    parse = exit_accounting.parse_taskstats
+    assert folder.add(parse(taskstats(43, 42, name="worker", utime=100_000, rss_kib=4096))) is None
+    assert folder.add(parse(taskstats(44, 42, name="worker", write_bytes=1))) is None
+    assert folder.add(parse(taskstats(42, 42))) is None
+    process = folder.finish(42)
+    assert process["pid"] == 42 and process["name"] == "cc1" and process["threads"] == 3
+    assert process["cpu_user_secs"] == pytest.approx(0.6)
+    assert process["write_bytes"] == 8192 * 2 + 1
+    assert process["peak_rss_bytes"] == 4096 * 1024
+    assert "tgid" not in process
+    assert folder.finish(42) is None
+    # A process that only ever ran one thread gets no aggregate
+    assert folder.add(parse(taskstats(50, 50))) is None
+    assert [process["threads"] for process in folder.settle()] == [1]
+    assert folder.settle() == []
+    # Without a thread group id every task stands alone
+    assert folder.add(parse(taskstats(45, 42, version=10)))["threads"] == 1
+
+
+def test_main_thread_exiting_first_waits_for_the_rest():
+    folder = exit_accounting.ThreadFolder(live_threads=lambda tgid: 3)
+    parse = exit_accounting.parse_taskstats
+    assert folder.add(parse(taskstats(42, 42))) is None
+    assert folder.add(parse(taskstats(43, 42, name="worker"))) is None
+    assert folder.add(parse(taskstats(44, 42, name="worker"))) is None
+    assert folder.settle() == []
+    process = folder.finish(42)
+    assert process["name"] == "cc1" and process["threads"] == 3
+    assert process["cpu_user_secs"] == pytest.approx(0.75)
+
+
+def test_main_thread_seen_alone_still_takes_threads_received_before_settling():
+    # The other threads exited before live_threads looked, but their records were already queued
+    folder = exit_accounting.ThreadFolder(live_threads=lambda tgid: 1)
+    parse = exit_accounting.parse_taskstats
+    assert folder.add(parse(taskstats(42, 42))) is None
+    assert folder.add(parse(taskstats(43, 42, name="worker"))) is None
+    assert folder.settle() == []
+    assert folder.finish(42)["threads"] == 2
+
+
+def test_pending_thread_groups_are_bounded():
+    folder = exit_accounting.ThreadFolder(max_groups=2, live_threads=lambda tgid: 1)
+    for tgid in (10, 20, 30):
+        folder.add(exit_accounting.parse_taskstats(taskstats(tgid + 1, tgid)))
+    assert folder.add(exit_accounting.parse_taskstats(taskstats(10, 10))) is None
+    assert folder.add(exit_accounting.parse_taskstats(taskstats(30, 30))) is None
+    assert [process["threads"] for process in folder.settle()] == [1]
+    assert folder.finish(30)["threads"] == 2
+
+
+def test_cap_effective():
+    assert exit_accounting.cap_effective("Name:\tx\nCapEff:\t0000000000001000\n") == 1 << 12
+    assert exit_accounting.cap_effective("Name:\tx\n") == 0
+
+
+def test_unsupported_host_says_what_is_missing(monkeypatch):
+    monkeypatch.setitem(capabilities.FEATURES, "exit_accounting", ("get_exited_processes", lambda: False))
+    with pytest.raises(Unsupported, match="CAP_NET_ADMIN"):
+        core.enable_exit_accounting()
+    with pytest.raises(Unsupported, match="CAP_NET_ADMIN"):
+        core.get_exited_processes()
+
+
+@pytest.mark.skipif(not capabilities.supported("exit_accounting"), reason="needs CAP_NET_ADMIN and taskstats")
+def test_short_lived_child_is_recorded():
+    core.enable_exit_accounting()
+    try:
+        core.get_exited_processes()
+        child = subprocess.run(["sh", "-c", "exit 3"])
+        found = []
+        deadline = time.monotonic() + 5
+        while not found and time.monotonic() < deadline:
+            time.sleep(0.05)
+            found = [r for r in core.get_exited_processes() if r["exit_code"] == 3 and r["name"] == "sh"]
+        assert found, f"no exit record for {child}"
+        assert found[0]["threads"] == 1 and found[0]["signal"] is None
+    finally:
+        core.disable_exit_accounting()
+
+
+@pytest.mark.skipif(not capabilities.supported("exit_accounting"), reason="needs CAP_NET_ADMIN and taskstats")
+@pytest.mark.parametrize("main_exits", ["first", "last"])
+def test_multi_threaded_child_is_recorded_once(main_exits):
+    # Four threads block; the main thread either ends them all with exit 4 or waits for them to return
+    script = ("import os, threading, time\n"
+              "threads = [threading.Thread(target=time.sleep, args=(0.2,)) for _ in range(4)]\n"
+              "[t.start() for t in threads]\n"
+              + ("os._exit(4)\n" if main_exits == "first" else "[t.join() for t in threads]\nos._exit(4)\n"))
+    core.enable_exit_accounting()
+    try:
+        core.get_exited_processes()
+        child = subprocess.Popen([sys.executable, "-c", script])
+        child.wait()
+        found = []
+        deadline = time.monotonic() + 5
+        while not found and time.monotonic() < deadline:
+            time.sleep(0.05)
+            found = [r for r in core.get_exited_processes() if r["pid"] == child.pid]
+        assert len(found) == 1
+        assert found[0]["exit_code"] == 4 and found[0]["threads"] == 5
+    finally:
+        core.disable_exit_accounting()
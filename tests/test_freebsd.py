@@ -0,0 +1,40 @@
+import struct
+import pytest
+from pulse import freebsd
+
+def _longs(*values):
+    return struct.pack(f"<{len(values)}q", *values)
+
+def test_parse_uint_any_width():
+    assert freebsd.parse_uint(struct.pack("<I", 4096), "little") == 4096
+    assert freebsd.parse_uint(struct.pack("<Q", 17179869184), "little") == 17179869184
+
+def test_parse_cp_times():
+    # Two CPUs: user, nice, system, interrupt, idle
+    data = _longs(100, 5, 40, 5, 850, 300, 0, 100, 0, 600)
+    assert freebsd.parse_cp_times(data, long_size=8, byteorder="little") == [
+        {"busy": 150, "total": 1000},
+        {"busy": 400, "total": 1000},
+    ]
+
+def test_parse_cp_times_32_bit_longs_and_trailing_bytes():
+    data = struct.pack("<5i", 1, 2, 3, 4, 90) + b"\0\0"
+    assert freebsd.parse_cp_times(data, long_size=4, byteorder="little") == [{"busy": 10, "total": 100}]
+
+def test_parse_xswdev():
+    data = struct.pack("=I4xQiii4x", 1, 0x5a, 0, 524288, 1024)
+    assert freebsd.parse_xswdev(data) == (524288, 1024)
+    with pytest.raises(ValueError):
+        freebsd.parse_xswdev(data[:20])
+
+def test_memory_from_counters():
+    pages = {"v_free_count": 1000, "v_active_count": 3000, "v_inactive_count": 2000,
+             "v_laundry_count": 1000, "v_wire_count": 1000}
+    mem = freebsd.memory_from_counters(8 * 4096 * 1000, 4096, pages, [(500, 100), (500, 0)])
+    assert mem == {
+        "total": 32768000, "available": 16384000, "free": 4096000, "used": 16384000,
+        "percent": 50.0, "swap_total": 4096000, "swap_used": 409600, "swap_free": 3686400,
+    }
+
+def test_memory_from_counters_without_physmem():
+    assert freebsd.memory_from_counters(0, 4096, {}, [])["percent"] == 0
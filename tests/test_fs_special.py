@@ -0,0 +1,67 @@
+import pytest
+from pulse import disks, fs_special
+
+GIB = 1024 ** 3
+MIB = 1024 ** 2
+
+@pytest.fixture
+def btrfs_raid1(tmp_path, monkeypatch):
+    """/sys/fs/btrfs for a RAID1 filesystem mirrored across two 10 GiB disks."""
+    fs_dir = tmp_path / "6a3c5b7e-0000-4000-8000-000000000001"
+    allocation = {
+        "data": (4 * GIB, 3 * GIB, 8 * GIB),
+        "metadata": (GIB, 256 * MIB, 2 * GIB),
+        "system": (32 * MIB, 16 * 1024, 64 * MIB),
+    }
+    for group, (total, used, disk_total) in allocation.items():
+        base = fs_dir / "allocation" / group
+        base.mkdir(parents=True)
+        (base / "total_bytes").write_text(f"{total}\n")
+        (base / "bytes_used").write_text(f"{used}\n")
+        (base / "disk_total").write_text(f"{disk_total}\n")
+    for device in ("sda", "sdb"):
+        (fs_dir / "devices" / device).mkdir(parents=True)
+        (fs_dir / "devices" / device / "size").write_text(f"{10 * GIB // 512}\n")
+    monkeypatch.setattr(fs_special, "SYS_FS_BTRFS", str(tmp_path))
+    return fs_dir
+
+def test_btrfs_free_space_accounts_for_raid1(btrfs_raid1):
+    space = fs_special.parse_btrfs_allocation(str(btrfs_raid1))
+    unallocated = 20 * GIB - (8 * GIB + 2 * GIB + 64 * MIB)
+    assert space["data_ratio"] == 2.0
+    assert space["used"] == 3 * GIB + 256 * MIB + 16 * 1024
+    # Slack in allocated data chunks plus half the unallocated raw space
+    assert space["available"] == GIB + unallocated // 2
+    assert space["total"] == space["used"] + space["available"]
+
+def test_btrfs_incomplete_sysfs(btrfs_raid1):
+    (btrfs_raid1 / "allocation" / "metadata" / "disk_total").unlink()
+    assert fs_special.parse_btrfs_allocation(str(btrfs_raid1)) is None
+
+def test_find_btrfs_filesystem_by_member_device(btrfs_raid1):
+    assert fs_special.find_btrfs_filesystem("sdb") == str(btrfs_raid1)
+    assert fs_special.find_btrfs_filesystem("nvme0n1p2") is None
+
+def test_zfs_space_is_flagged(tmp_path, monkeypatch):
+    (tmp_path / "tank").mkdir()
+    (tmp_path / "tank" / "state").write_text("DEGRADED\n")
+    monkeypatch.setattr(fs_special, "PROC_SPL_KSTAT_ZFS", str(tmp_path))
+    usage = {"total": 100, "used": 40, "free": 60, "available": 60, "percent": 40.0}
+    space = fs_special.zfs_space("tank/home", usage)
+    assert space["may_be_inaccurate"] is True
+    assert space["pool"] == "tank"
+    assert space["pool_state"] == "DEGRADED"
+    assert space["used"] == 40
+
+def test_special_usage_leaves_ext4_alone(monkeypatch):
+    monkeypatch.setattr(fs_special, "btrfs_space", lambda device: pytest.fail("queried sysfs"))
+    usage = {"total": 100, "used": 40, "free": 60, "available": 50, "percent": 44.4}
+    mount = {"device": "/dev/sda1", "mountpoint": "/", "fstype": "ext4", "options": "rw"}
+    assert disks._special_usage(mount, usage) is usage
+
+def test_filter_mounts_keeps_zfs_datasets():
+    mounts = [
+        {"device": "tank/home", "mountpoint": "/home", "fstype": "zfs", "options": "rw,xattr"},
+        {"device": "none", "mountpoint": "/weird", "fstype": "ext4", "options": "rw"},
+    ]
+    assert [m["mountpoint"] for m in disks.filter_mounts(mounts)] == ["/home"]
@@ -0,0 +1,71 @@
+import os
+import pytest
+from pulse import gpu
+
+@pytest.fixture
+def drm(tmp_path, monkeypatch):
+    """An amdgpu dGPU, an i915 iGPU with only identity attributes, and a connector."""
+    drivers = tmp_path / "drivers"
+    for name in ("amdgpu", "i915"):
+        (drivers / name).mkdir(parents=True)
+
+    amd = tmp_path / "drm" / "card1" / "device"
+    (amd / "hwmon" / "hwmon4").mkdir(parents=True)
+    (amd / "vendor").write_text("0x1002\n")
+    (amd / "gpu_busy_percent").write_text("37\n")
+    (amd / "mem_info_vram_used").write_text("1073741824\n")
+    (amd / "mem_info_vram_total").write_text("8589934592\n")
+    (amd / "hwmon" / "hwmon4" / "temp1_input").write_text("54000\n")
+    (amd / "hwmon" / "hwmon4" / "temp1_label").write_text("edge\n")
+    os.symlink(drivers / "amdgpu", amd / "driver")
+
+    intel = tmp_path / "drm" / "card0" / "device"
+    intel.mkdir(parents=True)
+    (intel / "vendor").write_text("0x8086\n")
+    os.symlink(drivers / "i915", intel / "driver")
+
+    (tmp_path / "drm" / "card0-eDP-1").mkdir()
+    monkeypatch.setattr(gpu, "SYS_CLASS_DRM", str(tmp_path / "drm"))
+    monkeypatch.setattr(gpu, "read_nvml_devices", lambda: None)
+    return tmp_path / "drm"
+
+def test_read_drm_cards(drm):
+    intel, amd = gpu.read_drm_cards()
+    assert amd == {
+        "index": 1, "name": None, "vendor": "amd", "driver": "amdgpu",
+        "utilization_percent": 37, "memory_used": 1073741824,
+        "memory_total": 8589934592, "temperature_c": 54.0,
+    }
+    assert intel["vendor"] == "intel"
+    assert intel["driver"] == "i915"
+    assert intel["utilization_percent"] is None
+    assert intel["memory_total"] is None
+    assert intel["temperature_c"] is None
+
+@pytest.mark.skipif(not gpu.LINUX, reason="DRM sysfs is Linux-only")
+def test_nvml_cards_replace_drm_duplicates(drm, monkeypatch):
+    nvidia = drm / "card2" / "device"
+    nvidia.mkdir(parents=True)
+    (nvidia / "vendor").write_text("0x10de\n")
+    assert [g["vendor"] for g in gpu.get_gpu_info()] == ["intel", "amd", "nvidia"]
+
+    from_nvml = {"index": 0, "name": "NVIDIA GeForce RTX 4070", "vendor": "nvidia"}
+    monkeypatch.setattr(gpu, "read_nvml_devices", lambda: [from_nvml])
+    assert [g["vendor"] for g in gpu.get_gpu_info()] == ["nvidia", "intel", "amd"]
+
+def test_nvml_library_is_looked_up_once(monkeypatch):
+    attempts = []
+
+    def missing(name):
+        attempts.append(name)
+        raise OSError(f"{name}: cannot open shared object file")
+
+    monkeypatch.setattr(gpu.ctypes, "CDLL", missing)
+    gpu._load_nvml.cache_clear()
+    try:
+        assert gpu.read_nvml_devices() is None
+        tried = len(attempts)
+        assert gpu.read_nvml_devices() is None
+        assert len(attempts) == tried
+    finally:
+        gpu._load_nvml.cache_clear()
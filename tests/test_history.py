@@ -0,0 +1,53 @@
+import pytest
+from pulse import history, sampler
+
+# Worked examples of the nearest-rank method
+FIVE = [15, 20, 35, 40, 50]
+TEN = [3, 6, 7, 8, 8, 10, 13, 15, 16, 20]
+
+@pytest.mark.parametrize("values, percent, expected", [
+    (FIVE, 5, 15), (FIVE, 30, 20), (FIVE, 40, 20), (FIVE, 50, 35), (FIVE, 100, 50),
+    (TEN, 25, 7), (TEN, 50, 8), (TEN, 75, 15), (TEN, 100, 20),
+    ([42], 0, 42), ([42], 99, 42),
+])
+def test_nearest_rank(values, percent, expected):
+    assert history.nearest_rank(values, percent) == expected
+
+def test_aggregate():
+    assert history.aggregate(reversed(range(1, 101)), history.AGGREGATES) == {
+        "min": 1, "max": 100, "avg": 50.5, "p50": 50, "p95": 95, "p99": 99,
+    }
+    assert history.aggregate([], ["max", "p95"]) == {"max": None, "p95": None}
+
+@pytest.fixture
+def samples(monkeypatch):
+    held = [{"timestamp": t, "cpu": [float(t)], "memory": {"percent": 50.0, "used": 1000 + t}}
+            for t in range(0, 100, 10)]
+    monkeypatch.setattr(sampler, "get_samples", lambda since_timestamp=None: held)
+    return held
+
+def test_query_history_window(samples):
+    # Window ends at the newest sample (90): readings at 60, 70, 80, 90
+    assert history.query_history("cpu_total", 30, ["min", "max", "avg"]) == {
+        "min": 60.0, "max": 90.0, "avg": 75.0, "samples": 4,
+    }
+    assert history.query_history("memory_used", 1000)["p50"] == 1040.0
+
+def test_query_history_per_interface_rate(monkeypatch):
+    held = [{"timestamp": t, "network": {"bytes_recv": 0, "bytes_sent": 0, "interfaces": {
+                "eth0": {"bytes_recv": 100 * t * t, "bytes_sent": 0},
+                "lo": {"bytes_recv": 50 * t, "bytes_sent": 50 * t}}}}
+            for t in range(5)]
+    monkeypatch.setattr(sampler, "get_samples", lambda since_timestamp=None: held)
+    # eth0 received 100, 300, 500, 700 bytes over each second
+    assert history.query_history("net_rx_rate:eth0", 60, ["min", "max", "p50"]) == {
+        "min": 100.0, "max": 700.0, "p50": 300.0, "samples": 4,
+    }
+    assert history.query_history("net_tx_rate:lo", 60, ["avg"]) == {"avg": 50.0, "samples": 4}
+
+def test_query_history_unknown_metric_lists_tracked(samples, monkeypatch):
+    with pytest.raises(ValueError, match=r"tracked: cpu_total, memory_percent, memory_used"):
+        history.query_history("gpu_total")
+    monkeypatch.setattr(sampler, "get_samples", lambda since_timestamp=None: [])
+    with pytest.raises(ValueError, match="sampler running"):
+        history.query_history("cpu_total")
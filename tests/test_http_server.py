@@ -0,0 +1,92 @@
+import json
+import socket
+import threading
+import urllib.error
+import urllib.request
+import pytest
+from pulse import core, http_server
+
+
+@pytest.fixture
+def served():
+    addr = core.start_http_server("127.0.0.1:0")
+    yield f"http://{addr}"
+    core.stop_http_server()
+
+
+def _get(url):
+    with urllib.request.urlopen(url, timeout=10) as response:
+        return response.headers["Content-Type"], response.read().decode()
+
+
+def test_routes(served):
+    content_type, body = _get(served + "/metrics")
+    assert content_type.startswith("text/plain; version=0.0.4")
+    assert "pulse_memory_used_bytes" in body
+    content_type, body = _get(served + "/snapshot")
+    assert content_type == "application/json"
+    assert {"timestamp", "cpu", "memory"} <= set(json.loads(body))
+    assert _get(served + "/healthz")[1] == "ok\n"
+    with pytest.raises(urllib.error.HTTPError) as info:
+        _get(served + "/admin")
+    assert info.value.code == 404
+
+
+def test_bind_failure_names_the_address(served):
+    with pytest.raises(RuntimeError, match="Already serving"):
+        core.start_http_server("127.0.0.1:0")
+    port = served.rsplit(":", 1)[1]
+    core.stop_http_server()
+    with socket.socket() as taken:
+        taken.bind(("127.0.0.1", int(port)))
+        taken.listen()
+        with pytest.raises(OSError, match=f"Cannot serve on 127.0.0.1:{port}"):
+            core.start_http_server(f"127.0.0.1:{port}")
+    core.stop_http_server()
+
+
+def test_slow_client_does_not_block_collectors(served, monkeypatch):
+    writing, release = threading.Event(), threading.Event()
+    end_headers = http_server._Handler.end_headers
+    def stall(handler):
+        writing.set()
+        release.wait(10)
+        end_headers(handler)
+    monkeypatch.setattr(http_server._Handler, "end_headers", stall)
+
+    client = threading.Thread(target=_get, args=(served + "/snapshot",))
+    client.start()
+    try:
+        assert writing.wait(10)
+        collector = threading.Thread(target=core.snapshot)
+        collector.start()
+        collector.join(10)
+        assert not collector.is_alive()
+    finally:
+        release.set()
+        client.join(10)
+
+
+def _ipv6_loopback():
+    try:
+        with socket.socket(socket.AF_INET6) as probe:
+            probe.bind(("::1", 0))
+    except OSError:
+        return False
+    return True
+
+
+@pytest.mark.skipif(not _ipv6_loopback(), reason="needs IPv6 loopback")
+def test_serves_on_ipv6():
+    addr = core.start_http_server("[::1]:0")
+    try:
+        assert addr.startswith("[::1]:")
+        assert _get(f"http://{addr}/healthz")[1] == "ok\n"
+    finally:
+        core.stop_http_server()
+
+
+def test_parse_address():
+    assert http_server.parse_address("0.0.0.0:9184") == ("0.0.0.0", 9184)
+    assert http_server.parse_address("[::1]:80") == ("::1", 80)
+    assert http_server.parse_address(":0") == ("", 0)
@@ -0,0 +1,124 @@
+import os
+import pytest
+from pulse import backend, core, interfaces
+
+COUNTERS = {
+    "lo": {"bytes_recv": 5000, "bytes_sent": 5000},
+    "eth0": {"bytes_recv": 600, "bytes_sent": 60},
+    "eth1": {"bytes_recv": 400, "bytes_sent": 40},
+    "bond0": {"bytes_recv": 1000, "bytes_sent": 100},
+    "br0": {"bytes_recv": 1000, "bytes_sent": 100},
+    "veth1": {"bytes_recv": 30, "bytes_sent": 3},
+    "docker0": {"bytes_recv": 30, "bytes_sent": 3},
+    "wg0": {"bytes_recv": 7, "bytes_sent": 7},
+}
+
+
+def make_interface(root, name, device=False, bond_slaves=None, bridge_ports=None):
+    directory = root / name
+    directory.mkdir()
+    if device:
+        (directory / "device").mkdir()
+    if bond_slaves is not None:
+        (directory / "bonding").mkdir()
+        (directory / "bonding" / "slaves").write_text(" ".join(bond_slaves) + "\n")
+    if bridge_ports is not None:
+        (directory / "brif").mkdir()
+        for port in bridge_ports:
+            (directory / "brif" / port).mkdir()
+
+
+@pytest.fixture
+def sysfs(tmp_path):
+    """A bridge over a bond of two NICs, a Docker bridge over a veth, a tunnel, and loopback."""
+    make_interface(tmp_path, "lo")
+    make_interface(tmp_path, "eth0", device=True)
+    make_interface(tmp_path, "eth1", device=True)
+    make_interface(tmp_path, "bond0", bond_slaves=["eth1", "eth0"])
+    make_interface(tmp_path, "br0", bridge_ports=["bond0"])
+    make_interface(tmp_path, "veth1")
+    make_interface(tmp_path, "docker0", bridge_ports=["veth1"])
+    make_interface(tmp_path, "wg0")
+    return tmp_path
+
+
+def test_read_topology_nested_bridge_over_bond(sysfs):
+    topology = interfaces.read_topology(str(sysfs))
+    assert topology["bond0"] == {"kind": "bond", "master": "br0", "slaves": ["eth0", "eth1"]}
+    assert topology["br0"] == {"kind": "bridge", "master": None, "slaves": ["bond0"]}
+    assert topology["eth0"] == {"kind": "physical", "master": "bond0", "slaves": []}
+    assert topology["veth1"] == {"kind": "virtual", "master": "docker0", "slaves": []}
+    assert topology["lo"]["kind"] == "loopback"
+    assert topology["wg0"] == {"kind": "virtual", "master": None, "slaves": []}
+
+
+def test_top_interfaces_count_each_stack_once(sysfs):
+    topology = interfaces.read_topology(str(sysfs))
+    assert interfaces.top_interfaces(topology) == ["br0", "docker0", "wg0"]
+    assert interfaces.top_interfaces(topology, physical_only=True) == ["br0"]
+    assert interfaces.sum_counters(COUNTERS, interfaces.top_interfaces(topology)) == {
+        "bytes_recv": 1037, "bytes_sent": 110}
+
+
+def test_plain_bond_and_standalone_nics():
+    topology = {
+        "eth0": {"kind": "physical", "master": "bond0", "slaves": []},
+        "eth1": {"kind": "physical", "master": "bond0", "slaves": []},
+        "bond0": {"kind": "bond", "master": None, "slaves": ["eth0", "eth1"]},
+        "eth2": {"kind": "physical", "master": None, "slaves": []},
+    }
+    assert interfaces.top_interfaces(topology, physical_only=True) == ["bond0", "eth2"]
+    # A bond with no slaves yet carries nothing physical
+    topology["bond1"] = {"kind": "bond", "master": None, "slaves": []}
+    assert "bond1" not in interfaces.top_interfaces(topology, physical_only=True)
+
+
+def test_slave_cycle_does_not_recurse_forever():
+    topology = {
+        "a": {"kind": "bridge", "master": None, "slaves": ["b"]},
+        "b": {"kind": "bridge", "master": "a", "slaves": ["a"]},
+    }
+    assert interfaces.top_interfaces(topology, physical_only=True) == []
+
+
+def test_topology_refreshes_when_interfaces_change(sysfs, monkeypatch):
+    monkeypatch.setattr(interfaces, "SYS_CLASS_NET", str(sysfs))
+    monkeypatch.setattr(interfaces, "_topology", None)
+    reads = []
+    read_topology = interfaces.read_topology
+    monkeypatch.setattr(interfaces, "read_topology", lambda root: reads.append(root) or read_topology(root))
+    assert "bond1" not in interfaces.current_topology()
+    interfaces.current_topology()
+    assert len(reads) == 1
+    make_interface(sysfs, "eth2", device=True)
+    make_interface(sysfs, "bond1", bond_slaves=["eth2"])
+    assert interfaces.current_topology()["eth2"]["master"] == "bond1"
+    assert len(reads) == 2
+
+
+def test_deduplicated_network_stats(sysfs, monkeypatch):
+    monkeypatch.setattr(interfaces, "SYS_CLASS_NET", str(sysfs))
+    monkeypatch.setattr(interfaces, "_topology", None)
+    monkeypatch.setattr(interfaces, "supported", lambda feature: True)
+    monkeypatch.setattr(backend.LIVE, "interface_counters", lambda: dict(COUNTERS))
+    assert core.get_network_stats(dedupe_bonded=True) == {"bytes_recv": 1037, "bytes_sent": 110}
+    assert core.get_network_stats(physical_only=True) == {"bytes_recv": 1000, "bytes_sent": 100}
+
+
+def test_mock_backend_keeps_its_totals():
+    core.set_backend("mock")
+    try:
+        core.mock_set_data("network", {"bytes_recv": 10, "bytes_sent": 20})
+        assert backend.active().interface_counters() == {}
+        assert core.get_network_stats(dedupe_bonded=True) == {"bytes_recv": 10, "bytes_sent": 20}
+    finally:
+        core.mock_clear()
+        core.set_backend("live")
+
+
+@pytest.mark.skipif(not os.path.isdir(interfaces.SYS_CLASS_NET), reason="needs /sys/class/net")
+def test_interface_topology_of_this_host():
+    topology = core.get_interface_topology()
+    assert all(entry["kind"] in interfaces.KINDS for entry in topology.values())
+    if "lo" in topology:
+        assert topology["lo"]["kind"] == "loopback"
@@ -0,0 +1,200 @@
+import gc
+import os
+import sys
+import threading
+import time
+import pytest
+from pulse import capabilities, core, isolation, leaderboard, protocol, sampler, thermal, worker
+from pulse.errors import Timeout, WorkerRestarted
+
+# Stands in for the worker once: reads the start of a request, then crashes
+# or hangs. Later starts (the marker exists) run the real worker with the codec.
+FAKE_WORKER = """
+import os, runpy, sys, time
+marker, mode = sys.argv[1], sys.argv[2]
+if os.path.exists(marker):
+    del sys.argv[1:3]
+    runpy.run_module("pulse.worker", run_name="__main__")
+else:
+    open(marker, "w").close()
+    sys.stdin.buffer.read(4)
+    if mode == "crash":
+        os._exit(3)
+    time.sleep(60)
+"""
+
+
+@pytest.fixture
+def isolated():
+    yield
+    core.stop_worker()
+    core.set_units()
+
+
+@pytest.fixture
+def fake_worker(tmp_path, monkeypatch):
+    script = tmp_path / "fake_worker.py"
+    script.write_text(FAKE_WORKER)
+    def use(mode):
+        monkeypatch.setattr(isolation, "WORKER_COMMAND", (sys.executable, str(script), str(tmp_path / "started"), mode))
+    return use
+
+
+def test_collectors_run_in_the_worker(isolated):
+    core.spawn_worker()
+    status = core.worker_status()
+    assert status["enabled"] and status["pid"] not in (None, os.getpid())
+
+    assert core.get_memory_info()["total"] > 0
+    assert len(core.Monitor().cpu_percents()) == len(core.get_cpu_percents())
+    core.set_units(memory="mib")
+    assert isinstance(core.get_memory_info()["total"], float)
+    with pytest.raises(ValueError, match="sort_by 'size'"):
+        core.get_process_list(sort_by="size")
+    # The sampler's history lives here, not in the worker
+    assert core.get_samples() == []
+
+    core.stop_worker()
+    assert core.worker_status()["pid"] is None
+    assert core.get_memory_info()["total"] > 0
+
+
+def test_dropped_monitor_states_are_released_in_the_worker(isolated, monkeypatch):
+    core.spawn_worker()
+    sent = []
+    real_call = isolation.call
+    monkeypatch.setattr(isolation, "call", lambda *args: sent.append(args[3]) or real_call(*args))
+    monitor = core.Monitor()
+    monitor.cpu_percents()
+    token = monitor.state.token
+    assert sent[-1] == token
+    del monitor
+    gc.collect()
+    assert isolation._released == [token]
+    core.get_uptime()
+    assert isolation._released == [] and token not in isolation._tracked
+    # A new state never reuses a dropped one's key, even at the same address
+    assert core.Monitor().state.token != token
+
+
+def test_worker_drops_released_states():
+    states = {}
+    request = {"id": 1, "function": "get_cpu_percents", "args": [], "kwargs": {},
+               "settings": isolation._settings(), "state": 7, "released": []}
+    assert "result" in worker.handle(request, states)
+    assert list(states) == [7]
+    worker.handle(dict(request, id=2, state=None, released=[7]), states)
+    assert states == {}
+
+
+# Sampler history as this process holds it, ending in a CPU spike; the worker has none
+SAMPLES = [{"timestamp": 1000.0 + i, "cpu": [10.0 + i % 3, 20.0]} for i in range(29)]
+SAMPLES.append({"timestamp": 1029.0, "cpu": [100.0, 100.0]})
+
+# Readers of this process's background state: (name, call, check)
+LOCAL_READERS = [
+    ("query_history", lambda: core.query_history("cpu_total"), lambda r: r["samples"] == 30),
+    ("get_anomalies", lambda: core.get_anomalies(), lambda r: [a["series"] for a in r] == ["cpu_total"]),
+    ("get_cpu_leaderboard", lambda: core.get_cpu_leaderboard(), lambda r: [e["name"] for e in r] == ["cc1"]),
+    ("get_thermal_correlation", lambda: core.get_thermal_correlation(), lambda r: len(r["samples"]) == 30),
+]
+
+
+def _board():
+    board = leaderboard.Leaderboard()
+    board.update({(1, 0.0): ("cc1", 0.0)}, now=1000.0)
+    board.update({(1, 0.0): ("cc1", 2.0)}, now=1001.0)
+    return board
+
+
+@pytest.mark.parametrize("name, call, check", LOCAL_READERS, ids=[r[0] for r in LOCAL_READERS])
+def test_background_state_readers_stay_local(isolated, monkeypatch, name, call, check):
+    monkeypatch.setattr(sampler, "get_samples", lambda since_timestamp=None: list(SAMPLES))
+    monkeypatch.setattr(leaderboard, "BOARD", _board())
+    monkeypatch.setitem(capabilities.FEATURES, "temperatures", ("get_thermal_status", lambda: True))
+    monkeypatch.setattr(thermal, "recent_readings", lambda: [(s["timestamp"], 60.0, 3000.0) for s in SAMPLES])
+    core.spawn_worker()
+    assert name in isolation.LOCAL_ONLY
+    assert check(call())
+
+
+def test_crash_mid_request_restarts_once(isolated, fake_worker):
+    fake_worker("crash")
+    core.spawn_worker()
+    restarts = core.worker_status()["restarts"]
+    with pytest.raises(WorkerRestarted, match="died during get_memory_info"):
+        core.get_memory_info()
+    assert core.get_memory_info()["total"] > 0
+    assert core.worker_status()["restarts"] == restarts + 1
+
+
+def test_killed_mid_request(isolated, fake_worker):
+    fake_worker("hang")
+    core.spawn_worker()
+    pid = core.worker_status()["pid"]
+    killer = threading.Timer(0.5, os.kill, (pid, 9))
+    killer.start()
+    with pytest.raises(WorkerRestarted):
+        core.get_cpu_percents()
+    killer.join()
+    assert core.worker_status()["pid"] not in (None, pid)
+    assert core.get_uptime()
+
+
+def test_worker_dead_between_calls(isolated):
+    core.spawn_worker()
+    pid = core.worker_status()["pid"]
+    os.kill(pid, 9)
+    time.sleep(0.2)
+    with pytest.raises(WorkerRestarted, match=f"pid {pid}"):
+        core.get_memory_info()
+    assert core.get_memory_info()["total"] > 0
+
+
+def test_timeout_kills_a_hung_worker(isolated, fake_worker):
+    fake_worker("hang")
+    core.spawn_worker(timeout=0.5)
+    restarts = core.worker_status()["restarts"]
+    with pytest.raises(Timeout, match="within 0.5 s"):
+        core.get_memory_info()
+    # The caller has already been told, so the fresh worker starts quietly
+    assert core.get_memory_info()["total"] > 0
+    assert core.worker_status()["restarts"] == restarts
+
+
+@pytest.mark.parametrize("codec", [c for c in protocol.CODECS if c != "msgpack" or protocol.msgpack])
+def test_framing(tmp_path, codec):
+    path = tmp_path / "stream"
+    with open(path, "wb") as f:
+        protocol.write_message(f, {"id": 1, "result": [1, 2]}, codec)
+        protocol.write_message(f, {"id": 2}, codec)
+    data = path.read_bytes()
+    with open(path, "rb") as f:
+        assert protocol.read_message(f, codec) == {"id": 1, "result": [1, 2]}
+        assert protocol.read_message(f, codec) == {"id": 2}
+        assert protocol.read_message(f, codec) is None
+    path.write_bytes(data[:-2])
+    with open(path, "rb") as f:
+        protocol.read_message(f, codec)
+        with pytest.raises(EOFError):
+            protocol.read_message(f, codec)
+    with open(path, "rb") as f:
+        receiver = protocol.Receiver(f, codec=codec)
+        assert receiver.receive(5)["id"] == 1
+        with pytest.raises(EOFError):
+            receiver.receive(5)
+
+
+@pytest.mark.skipif(protocol.msgpack is None, reason="needs the msgpack package")
+def test_msgpack_keeps_bytes_and_integer_keys():
+    message = {"id": 1, "result": {"raw": b"\x00\xff", "by_pid": {42: "init"}}}
+    assert protocol.decode_body(protocol.encode_body(message, "msgpack"), "msgpack") == message
+    # JSON can carry neither
+    with pytest.raises(TypeError):
+        protocol.encode_body(message, "json")
+
+
+def test_worker_speaks_json_without_msgpack(isolated, monkeypatch):
+    monkeypatch.setattr(isolation, "_codec", "json")
+    core.spawn_worker()
+    assert core.get_memory_info()["total"] > 0
@@ -0,0 +1,71 @@
+import os
+import pytest
+from pulse import kmsg
+
+USB_RESET = (
+    "6,1234,5678901234,-;usb 1-2: reset high-speed USB device number 3 using xhci_hcd\n"
+    " SUBSYSTEM=usb\n"
+    " DEVICE=c189:2\n"
+)
+
+OOM_KILL = "3,2001,98765000000,-,caller=T812;Out of memory: Killed process 4242 (chrome)\n"
+
+ESCAPED = "4,77,1000,c;name with\\x20space and \\x5cbackslash\n"
+
+def test_parse_record_with_properties():
+    entry = kmsg.parse_kmsg_record(USB_RESET)
+    assert entry["seq"] == 1234
+    assert entry["timestamp"] == pytest.approx(5678.901234)
+    assert entry["level"] == "info"
+    assert entry["facility"] == 0
+    assert entry["message"].startswith("usb 1-2: reset")
+    assert entry["properties"] == {"SUBSYSTEM": "usb", "DEVICE": "c189:2"}
+    assert entry["continuation"] is False
+
+def test_parse_record_extra_header_fields():
+    """Newer kernels append fields like caller= after the flags."""
+    entry = kmsg.parse_kmsg_record(OOM_KILL)
+    assert entry["level"] == "err"
+    assert entry["message"] == "Out of memory: Killed process 4242 (chrome)"
+
+def test_parse_record_escapes_and_fragments():
+    entry = kmsg.parse_kmsg_record(ESCAPED)
+    assert entry["message"] == "name with space and \\backslash"
+    assert entry["continuation"] is True
+    assert entry["level"] == "warning"
+
+def test_parse_record_userspace_facility():
+    # <14> is facility 1 (user), level 6 (info), as written by systemd via /dev/kmsg
+    assert kmsg.parse_kmsg_record("14,9,100,-;systemd[1]: Started foo\n")["facility"] == 1
+
+def test_parse_malformed_record():
+    assert kmsg.parse_kmsg_record("garbage without separator") is None
+    assert kmsg.parse_kmsg_record("x,y,z,-;message") is None
+
+def test_unknown_level_rejected():
+    with pytest.raises(ValueError):
+        kmsg.get_kernel_messages(level="loud")
+
+def test_negative_since_seq_rejected_before_reading(monkeypatch):
+    monkeypatch.setattr(kmsg.os, "open", lambda path, flags: pytest.fail("read /dev/kmsg"))
+    with pytest.raises(ValueError, match="since_seq"):
+        kmsg.get_kernel_messages(since_seq=-1, level="err")
+
+@pytest.mark.skipif(not kmsg.LINUX, reason="/dev/kmsg is Linux-only")
+def test_unreadable_kmsg_raises_permission_error(monkeypatch):
+    """dmesg_restrict denials surface as PermissionError rather than an empty list."""
+    def denied(path, flags):
+        raise PermissionError(13, "Operation not permitted", path)
+    monkeypatch.setattr(kmsg.os, "open", denied)
+    with pytest.raises(PermissionError):
+        kmsg.get_kernel_messages()
+
+@pytest.mark.skipif(not (kmsg.LINUX and os.access(kmsg.DEV_KMSG, os.R_OK)),
+                    reason="needs a readable /dev/kmsg")
+def test_incremental_poll_live():
+    messages = kmsg.get_kernel_messages()
+    assert messages, "the boot banner is always in the ring buffer"
+    last = messages[-1]["seq"]
+    assert all(m["seq"] > last for m in kmsg.get_kernel_messages(since_seq=last))
+    assert all(m["level"] in ("emerg", "alert", "crit", "err")
+               for m in kmsg.get_kernel_messages(level="err"))
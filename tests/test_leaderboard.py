@@ -0,0 +1,110 @@
+import os
+import time
+import pytest
+from pulse import core, leaderboard, report, sampler
+
+
+def board(**kwargs):
+    return leaderboard.Leaderboard(bucket_secs=kwargs.pop("bucket_secs", 1.0), **kwargs)
+
+
+def test_accumulates_growth_including_bursts_and_exits():
+    b = board()
+    b.update({(10, 500.0): ("postgres", 10.0), (20, 600.0): ("nginx", 5.0)}, now=1000.0)
+    assert b.top(3600, 10) == []
+    # A short-lived compiler starts and uses CPU between two samples
+    b.update({(10, 500.0): ("postgres", 12.0), (20, 600.0): ("nginx", 5.5),
+              (30, 1000.5): ("cc1", 0.75)}, now=1001.0)
+    b.update({(20, 600.0): ("nginx", 6.5)}, now=1002.0)
+    assert b.top(3600, 10) == [
+        {"pid": 10, "name": "postgres", "start_time": 500.0, "cpu_seconds": 2.0, "exited": True},
+        {"pid": 20, "name": "nginx", "start_time": 600.0, "cpu_seconds": 1.5, "exited": False},
+        {"pid": 30, "name": "cc1", "start_time": 1000.5, "cpu_seconds": 0.75, "exited": True},
+    ]
+    assert [e["pid"] for e in b.top(3600, 2)] == [10, 20]
+
+
+def test_newcomer_that_predates_tracking_only_sets_a_baseline():
+    b = board()
+    b.update({}, now=1000.0)
+    # Evicted earlier or missed: started long before the previous update
+    b.update({(40, 10.0): ("java", 900.0)}, now=1001.0)
+    assert b.top(3600, 10) == []
+    b.update({(40, 10.0): ("java", 901.0)}, now=1002.0)
+    assert b.top(3600, 10)[0]["cpu_seconds"] == 1.0
+
+
+def test_window_counts_only_recent_growth():
+    b = board()
+    b.update({(1, 0.0): ("a", 0.0)}, now=1000.0)
+    b.update({(1, 0.0): ("a", 5.0)}, now=1010.0)
+    b.update({(1, 0.0): ("a", 6.0)}, now=1070.0)
+    assert b.top(3600, 1)[0]["cpu_seconds"] == 6.0
+    assert b.top(30, 1)[0]["cpu_seconds"] == 1.0
+
+
+def test_group_by_name_sums_processes():
+    b = board()
+    b.update({(1, 0.0): ("worker", 0.0), (2, 0.0): ("worker", 0.0), (3, 0.0): ("web", 0.0)}, now=1000.0)
+    b.update({(1, 0.0): ("worker", 1.0), (2, 0.0): ("worker", 2.0), (3, 0.0): ("web", 2.5)}, now=1001.0)
+    b.update({(2, 0.0): ("worker", 2.0)}, now=1002.0)
+    assert b.top(3600, 10, group_by="name") == [
+        {"name": "worker", "cpu_seconds": 3.0, "processes": 2, "exited": False},
+        {"name": "web", "cpu_seconds": 2.5, "processes": 1, "exited": True},
+    ]
+
+
+def test_reused_pid_is_a_new_entry():
+    b = board()
+    b.update({(7, 100.0): ("old", 1.0)}, now=1000.0)
+    b.update({(7, 100.0): ("old", 3.0)}, now=1001.0)
+    b.update({(7, 1001.5): ("new", 0.5)}, now=1002.0)
+    assert [(e["name"], e["cpu_seconds"], e["exited"]) for e in b.top(3600, 10)] == [
+        ("old", 2.0, True), ("new", 0.5, False)]
+
+
+def test_eviction_drops_least_consuming():
+    b = board(max_entries=2)
+    b.update({(1, 0.0): ("a", 0.0), (2, 0.0): ("b", 0.0)}, now=1000.0)
+    b.update({(1, 0.0): ("a", 4.0), (2, 0.0): ("b", 1.0), (3, 1000.5): ("c", 2.0)}, now=1001.0)
+    assert len(b) == 2
+    assert [e["name"] for e in b.top(3600, 10)] == ["a", "c"]
+
+
+def test_exited_processes_age_out():
+    b = board(retention_secs=60)
+    b.update({(1, 0.0): ("a", 0.0)}, now=1000.0)
+    b.update({(1, 0.0): ("a", 1.0)}, now=1001.0)
+    b.update({}, now=1030.0)
+    assert len(b) == 1
+    b.update({}, now=1062.0)
+    assert len(b) == 0
+
+
+def test_growth_is_bucketed():
+    b = board(bucket_secs=5.0)
+    b.update({(1, 0.0): ("a", 0.0)}, now=1000.0)
+    for second in range(1, 11):
+        b.update({(1, 0.0): ("a", float(second))}, now=1000.0 + second)
+    assert len(b._entries[(1, 0.0)]["buckets"]) == 2
+
+
+@pytest.mark.skipif(not leaderboard.LINUX, reason="reads /proc")
+def test_sampler_feeds_the_leaderboard(monkeypatch):
+    sampler.start_sampler(interval_ms=20, sections=["processes"])
+    try:
+        deadline = time.monotonic() + 3
+        while time.monotonic() < deadline and not any(
+                e["pid"] == os.getpid() for e in core.get_cpu_leaderboard(n=1000)):
+            sum(range(10000))
+    finally:
+        sampler.stop_sampler()
+    ours = [e for e in core.get_cpu_leaderboard(n=1000) if e["pid"] == os.getpid()]
+    assert ours and ours[0]["cpu_seconds"] > 0 and ours[0]["exited"] is False
+    # The samples carry the same CPU time, so a report of the run sees it too
+    recorded = [p for s in sampler.get_samples() for p in s["processes"] if p["pid"] == os.getpid()]
+    assert recorded and all(p["start_time"] == ours[0]["start_time"] for p in recorded)
+    assert recorded[-1]["cpu_time"] > recorded[0]["cpu_time"]
+    monkeypatch.setattr(report, "TOP_PROCESSES", 100000)
+    top = report.summarize(sampler.get_samples())["top_processes"]
+    assert any(p["pid"] == os.getpid() and p["cpu_secs"] > 0 for p in top)
@@ -0,0 +1,66 @@
+import os
+import pytest
+from pulse import limits
+
+INOTIFY_FDINFO = """pos:	0
+flags:	02004000
+mnt_id:	15
+ino:	1057
+inotify wd:3 ino:2b1 sdev:800001 mask:fc6 ignored_mask:0 fhandle-bytes:8 fhandle-type:1 f_handle:b1020000
+inotify wd:2 ino:10 sdev:800001 mask:fc6 ignored_mask:0 fhandle-bytes:8 fhandle-type:1 f_handle:10000000
+inotify wd:1 ino:2 sdev:800001 mask:fc6 ignored_mask:0 fhandle-bytes:8 fhandle-type:1 f_handle:02000000
+"""
+
+EPOLL_FDINFO = """pos:	0
+flags:	02000002
+mnt_id:	15
+ino:	1057
+tfd:        5 events:       19 data:                5  pos:0 ino:3f2 sdev:e
+tfd:        9 events:       19 data:                9  pos:0 ino:3f6 sdev:e
+"""
+
+def test_parse_file_nr():
+    assert limits.parse_file_nr("9344\t0\t9223372036854775807\n") == (9344, 0, 9223372036854775807)
+
+def test_count_fdinfo_entries():
+    assert limits.count_fdinfo_entries(INOTIFY_FDINFO, "inotify wd:") == 3
+    assert limits.count_fdinfo_entries(EPOLL_FDINFO, "tfd:") == 2
+    assert limits.count_fdinfo_entries(EPOLL_FDINFO, "inotify wd:") == 0
+
+def test_limit_entry():
+    assert limits.limit_entry(250, 1000) == {"current": 250, "max": 1000, "percent": 25.0}
+    assert limits.limit_entry(3, None)["percent"] is None
+
+@pytest.mark.skipif(not limits.LINUX, reason="/proc fd walker is Linux-only")
+def test_scan_user_watches(tmp_path, monkeypatch):
+    """fd links and fdinfo are read per process; vanished and foreign pids are skipped."""
+    proc = tmp_path / "proc"
+    pid = proc / "100"
+    (pid / "fd").mkdir(parents=True)
+    (pid / "fdinfo").mkdir()
+    for fd, target, info in (("3", "anon_inode:inotify", INOTIFY_FDINFO),
+                             ("4", "anon_inode:[eventpoll]", EPOLL_FDINFO),
+                             ("5", "/var/log/syslog", "")):
+        os.symlink(target, pid / "fd" / fd)
+        (pid / "fdinfo" / fd).write_text(info)
+    # Link present but fdinfo gone: the fd closed between the two reads
+    os.symlink("anon_inode:inotify", pid / "fd" / "6")
+    (proc / "200").mkdir()  # no fd dir: exited mid-walk
+    (proc / "self").mkdir()
+    monkeypatch.setattr(limits, "PROC", str(proc))
+
+    assert limits.scan_user_watches(os.getuid()) == {
+        "inotify_instances": 1, "inotify_watches": 3, "epoll_watches": 2,
+    }
+    assert limits.scan_user_watches(os.getuid() + 1)["inotify_watches"] == 0
+
+@pytest.mark.skipif(not limits.LINUX, reason="/proc/sys/fs reader is Linux-only")
+def test_get_kernel_limits_live():
+    kernel = limits.get_kernel_limits()
+    assert set(kernel) == {
+        "open_files", "threads", "pids", "inotify_watches", "inotify_instances", "epoll_watches",
+    }
+    assert 0 < kernel["open_files"]["current"] <= kernel["open_files"]["max"]
+
+def test_parse_loadavg_tasks():
+    assert limits.parse_loadavg_tasks("0.52 0.58 0.59 3/1203 42317\n") == 1203
@@ -0,0 +1,63 @@
+import errno
+import logging
+import pytest
+from pulse import core, log, power, sched, system
+
+
+class _Records(logging.Handler):
+    def __init__(self):
+        super().__init__(logging.DEBUG)
+        self.records = []
+
+    def emit(self, record):
+        self.records.append(record)
+
+
+@pytest.fixture
+def records():
+    handler = _Records()
+    log.logger.addHandler(handler)
+    yield handler.records
+    log.logger.removeHandler(handler)
+    core.enable_logging("off")
+
+
+@pytest.mark.skipif(not sched.LINUX, reason="scheduler statistics are Linux-only")
+def test_degraded_read_is_logged_with_path_and_errno(records, tmp_path, monkeypatch):
+    missing = str(tmp_path / "schedstat")
+    monkeypatch.setattr(sched, "PROC_SCHEDSTAT", missing)
+    core.enable_logging("debug")
+    assert "cpus" not in sched.get_scheduler_stats()
+    [record] = [r for r in records if getattr(r, "path", None) == missing]
+    assert record.name == "pulse_core"
+    assert record.levelno == logging.DEBUG
+    assert record.errno == errno.ENOENT
+    assert missing in record.getMessage()
+
+
+@pytest.mark.skipif(not sched.LINUX, reason="sysfs and procfs readers")
+def test_power_and_system_reads_are_logged(records, tmp_path, monkeypatch):
+    (tmp_path / "BAT0").mkdir()
+    monkeypatch.setattr(power, "SYS_CLASS_POWER_SUPPLY", str(tmp_path))
+    monkeypatch.setattr(system, "PROC_UPTIME", str(tmp_path / "uptime"))
+    core.enable_logging("debug")
+    assert power.get_battery_info.__wrapped__() == []
+    assert system.get_uptime() is None
+    assert {r.path for r in records} >= {str(tmp_path / "BAT0" / "uevent"), str(tmp_path / "uptime")}
+
+
+def test_quiet_until_enabled(records):
+    log.read_failed("/sys/class/hwmon/hwmon0/temp1_input", PermissionError(errno.EACCES, "Permission denied"))
+    assert records == []
+    core.enable_logging("warning")
+    log.read_failed("/sys/class/hwmon/hwmon0/temp1_input", PermissionError(errno.EACCES, "Permission denied"))
+    # Missing files stay below warning
+    log.read_failed("/sys/class/hwmon/hwmon0/fan1_input", FileNotFoundError(errno.ENOENT, "No such file"))
+    assert [(r.levelname, r.errno) for r in records] == [("WARNING", errno.EACCES)]
+    assert records[0].getMessage() == "Cannot read /sys/class/hwmon/hwmon0/temp1_input: Permission denied"
+
+
+def test_process_skipped_carries_the_pid(records):
+    core.enable_logging("debug")
+    log.process_skipped(4242, "/proc/4242/io", PermissionError(errno.EACCES, "Permission denied"))
+    assert [(r.pid, r.path, r.errno) for r in records] == [(4242, "/proc/4242/io", errno.EACCES)]
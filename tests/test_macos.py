@@ -0,0 +1,52 @@
+import struct
+import pytest
+from pulse import macos
+
+# A 16 GB Apple Silicon Mac (16 KiB pages) under moderate load
+STATS = {
+    "free_count": 20000, "active_count": 300000, "inactive_count": 290000, "wire_count": 120000,
+    "zero_fill_count": 1, "reactivations": 2, "pageins": 3, "pageouts": 4, "faults": 5,
+    "cow_faults": 6, "lookups": 7, "hits": 8, "purges": 9,
+    "purgeable_count": 10000, "speculative_count": 15000,
+    "decompressions": 10, "compressions": 11, "swapins": 12, "swapouts": 13,
+    "compressor_page_count": 50000, "throttled_count": 0, "external_page_count": 260000,
+    "internal_page_count": 340000, "total_uncompressed_pages_in_compressor": 150000,
+}
+PAGE = 16384
+
+def _vm_statistics64(stats):
+    return struct.pack("<4I9Q2I4Q4IQ", *(stats[name] for name in macos.VM_STATISTICS64_FIELDS))
+
+def test_parse_vm_statistics64_round_trip():
+    data = _vm_statistics64(STATS)
+    assert len(data) == 152  # HOST_VM_INFO64_COUNT natural_t words
+    assert macos.parse_vm_statistics64(data) == STATS
+    with pytest.raises(ValueError):
+        macos.parse_vm_statistics64(data[:100])
+
+def test_memory_matches_activity_monitor():
+    total = 16 * 1024 ** 3
+    memory = macos.memory_from_vm_statistics(STATS, PAGE, total)
+    # App memory (internal - purgeable) + wired + compressed
+    used = (330000 + 120000 + 50000) * PAGE
+    assert memory == {
+        "total": total, "available": total - used, "used": used, "free": 20000 * PAGE,
+        "compressed": 50000 * PAGE, "percent": used / total * 100,
+    }
+
+def test_parse_xsw_usage():
+    data = struct.pack("<3QIi", 2 * 1024 ** 3, 1536 * 1024 ** 2, 512 * 1024 ** 2, PAGE, 1)
+    assert macos.parse_xsw_usage(data) == {"swap_total": 2 * 1024 ** 3, "swap_used": 512 * 1024 ** 2}
+
+@pytest.mark.parametrize("value, level", [(1, "normal"), (2, "warn"), (4, "critical"), (0, None), (None, None)])
+def test_pressure_level(value, level):
+    assert macos.pressure_level(value) == level
+
+def test_usage_by_core_type():
+    # M1: four efficiency cores (cpu0-3), then four performance cores
+    usage = macos.usage_by_core_type([10.0, 20.0, 30.0, 40.0, 90.0, 70.0, 0.0, 0.0], 4)
+    assert usage == {
+        "performance": {"logical_cpus": 4, "percent": 40.0},
+        "efficiency": {"logical_cpus": 4, "percent": 25.0},
+    }
+    assert macos.usage_by_core_type([50.0], 0)["efficiency"] == {"logical_cpus": 0, "percent": None}
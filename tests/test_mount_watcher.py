@@ -0,0 +1,37 @@
+import time
+from pulse import disks, mount_watcher
+
+ROOT = {"device": "/dev/sda1", "mountpoint": "/", "fstype": "ext4", "options": "rw"}
+USB = {"device": "/dev/sdb1", "mountpoint": "/media/usb", "fstype": "vfat", "options": "rw"}
+
+def test_diff_mounts():
+    mounted = mount_watcher.diff_mounts([ROOT], [ROOT, USB], 42.0)
+    assert mounted == [{
+        "action": "mounted", "mount_point": "/media/usb", "device": "/dev/sdb1",
+        "fs_type": "vfat", "timestamp": 42.0,
+    }]
+    unmounted = mount_watcher.diff_mounts([ROOT, USB], [ROOT], 43.0)
+    assert [e["action"] for e in unmounted] == ["unmounted"]
+    assert mount_watcher.diff_mounts([ROOT], [ROOT], 44.0) == []
+
+def test_snapshot_fallback_watcher(monkeypatch):
+    """Without a kernel event source the thread diffs periodic snapshots."""
+    tables = [[ROOT], [ROOT, USB], [ROOT]]
+    monkeypatch.setattr(mount_watcher, "LINUX", False)
+    monkeypatch.setattr(disks, "list_mounts", lambda: tables[0] if len(tables) == 1 else tables.pop(0))
+    mount_watcher.get_mount_events()
+
+    mount_watcher.start_mount_watcher(interval_secs=0.01)
+    try:
+        deadline = time.monotonic() + 2
+        events = []
+        while len(events) < 2 and time.monotonic() < deadline:
+            events += mount_watcher.get_mount_events()
+            time.sleep(0.01)
+    finally:
+        mount_watcher.stop_mount_watcher()
+
+    assert [(e["action"], e["mount_point"]) for e in events] == [
+        ("mounted", "/media/usb"), ("unmounted", "/media/usb"),
+    ]
+    assert mount_watcher._thread is None
@@ -0,0 +1,82 @@
+import sys
+import pytest
+from pulse import netstat
+from pulse.errors import Unsupported
+from pulse.state import STATE
+
+TCP_LINE = "   2: 0100007F:BC8F 0100007F:8584 01 0000002A:00000010 00:00000000 00000000 65534        0 1134 1 00000000e82ee6e5 20 4 14 18 -1"
+UDP_LINE = "  512: 00000000:14E9 00000000:0000 07 00000000:00000C00 00:00000000 00000000   101        0 20488 2 0000000000000000 37"
+UDP6_LINE = "  301: 00000000000000000000000001000000:0035 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 1820 2 0000000000000000 0"
+
+SNMP = """Ip: Forwarding DefaultTTL
+Ip: 1 64
+Udp: InDatagrams NoPorts InErrors OutDatagrams RcvbufErrors SndbufErrors InCsumErrors IgnoredMulti MemErrors
+Udp: 100 3 7 90 5 0 0 0 0
+"""
+
+little_endian = pytest.mark.skipif(sys.byteorder != "little", reason="fixtures are little-endian")
+
+@little_endian
+def test_parse_tcp_line():
+    """TCP rows decode addresses, state names, and hex queue depths."""
+    entry = netstat.parse_socket_line(TCP_LINE, "tcp")
+    assert entry["local_address"] == "127.0.0.1"
+    assert entry["local_port"] == 0xBC8F
+    assert entry["remote_port"] == 0x8584
+    assert entry["status"] == "ESTABLISHED"
+    assert entry["tx_queue"] == 42
+    assert entry["rx_queue"] == 16
+    assert entry["uid"] == 65534
+    assert entry["inode"] == 1134
+
+@little_endian
+def test_parse_udp_lines():
+    """UDP rows share the TCP parser but report bound sockets and drops."""
+    entry = netstat.parse_socket_line(UDP_LINE, "udp")
+    assert entry["local_address"] == "0.0.0.0"
+    assert entry["local_port"] == 5353
+    assert entry["status"] == "NONE"
+    assert entry["rx_queue"] == 3072
+    assert entry["drops"] == 37
+
+    entry6 = netstat.parse_socket_line(UDP6_LINE, "udp6")
+    assert entry6["local_address"] == "::1"
+    assert entry6["local_port"] == 53
+
+def test_parse_header_line_is_skipped():
+    header = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode"
+    assert netstat.parse_socket_line(header, "tcp") is None
+
+def test_udp_stats_deltas(tmp_path, monkeypatch):
+    """Deltas are relative to the previous call and clamp counter resets."""
+    monkeypatch.setattr(netstat, "PROC_NET", str(tmp_path))
+    monkeypatch.setattr(STATE, "udp_counters", None)
+    snmp = tmp_path / "snmp"
+
+    snmp.write_text(SNMP)
+    first = netstat.get_udp_stats()
+    assert first["in_errors"] == 7
+    assert first["rcvbuf_errors"] == 5
+    assert first["deltas"]["in_errors"] == 0
+
+    snmp.write_text(SNMP.replace("100 3 7 90 5", "150 3 9 80 5"))
+    second = netstat.get_udp_stats()
+    assert second["deltas"]["in_datagrams"] == 50
+    assert second["deltas"]["in_errors"] == 2
+    assert second["deltas"]["out_datagrams"] == 0  # reset clamped
+
+def test_udp_stats_missing_file(tmp_path, monkeypatch):
+    monkeypatch.setattr(netstat, "PROC_NET", str(tmp_path))
+    with pytest.raises(Unsupported):
+        netstat.get_udp_stats()
+
+def test_parse_net_dev_per_interface():
+    text = """Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:     500       5    0    0    0     0          0         0      500       5    0    0    0     0       0          0
+  eth0:   12000      80    0    0    0     0          0         0     3400      40    0    0    0     0       0          0
+"""
+    assert netstat.parse_net_dev_per_interface(text) == {
+        "lo": {"bytes_recv": 500, "bytes_sent": 500},
+        "eth0": {"bytes_recv": 12000, "bytes_sent": 3400},
+    }
@@ -0,0 +1,103 @@
+import os
+import socket
+import pytest
+from pulse import core, open_files
+from pulse.errors import ProcessNotFound
+
+FDINFO = """pos:	4096
+flags:	0100002
+mnt_id:	29
+ino:	1311563
+"""
+
+
+@pytest.mark.parametrize("target, expected", [
+    ("/var/log/syslog", {"type": "file", "path": "/var/log/syslog", "inode": None}),
+    ("socket:[58231]", {"type": "socket", "path": "socket:[58231]", "inode": 58231}),
+    ("pipe:[913]", {"type": "pipe", "path": "pipe:[913]", "inode": 913}),
+    ("anon_inode:[eventfd]", {"type": "anon", "path": "anon_inode:[eventfd]", "inode": None}),
+    ("net:[4026531840]", {"type": "anon", "path": "net:[4026531840]", "inode": None}),
+])
+def test_classify_target(target, expected):
+    assert open_files.classify_target(target) == expected
+
+
+def test_parse_fdinfo():
+    assert open_files.parse_fdinfo(FDINFO) == {"pos": 4096, "flags": 0o100002, "mode": "rw"}
+    assert open_files.parse_fdinfo("pos:\t0\nflags:\t02000000\n")["mode"] == "r"
+    assert open_files.parse_fdinfo("") == {"pos": None, "flags": None, "mode": None}
+
+
+@pytest.mark.skipif(not open_files.LINUX, reason="reads /proc/<pid>/fd")
+def test_open_files_of_this_process(tmp_path):
+    path = tmp_path / "data.bin"
+    path.write_bytes(b"x" * 100)
+    read_end, write_end = os.pipe()
+    listener = socket.socket(socket.AF_INET, socket.SOCK_STREAM)
+    listener.bind(("127.0.0.1", 0))
+    listener.listen()
+    port, sock_fd = listener.getsockname()[1], listener.fileno()
+    with open(path, "rb") as f:
+        f.seek(10)
+        file_fd = f.fileno()
+        try:
+            files = {entry["fd"]: entry for entry in core.get_open_files(os.getpid())}
+            connections = core.get_connections("tcp4")
+        finally:
+            os.close(read_end)
+            os.close(write_end)
+            listener.close()
+
+    assert files[file_fd] == {"fd": file_fd, "type": "file", "path": str(path), "inode": None,
+                              "pos": 10, "flags": files[file_fd]["flags"], "mode": "r"}
+    assert files[read_end]["type"] == "pipe" and files[read_end]["inode"] == files[write_end]["inode"]
+    assert files[write_end]["mode"] == "w"
+    sock = files[sock_fd]
+    assert sock["type"] == "socket"
+    # The socket's inode joins it to its row in get_connections()
+    assert [c["local_port"] for c in connections if c["inode"] == sock["inode"]] == [port]
+
+
+@pytest.mark.skipif(not open_files.LINUX, reason="reads /proc/<pid>/fd")
+def test_open_files_limit_and_missing_process():
+    assert [entry["fd"] for entry in core.get_open_files(os.getpid(), limit=2)] == [0, 1]
+    with pytest.raises(ProcessNotFound):
+        core.get_open_files(2 ** 22 + 1)
+
+
+def test_who_has_open_finds_this_process(tmp_path):
+    path = tmp_path / "locked"
+    path.write_text("busy")
+    os.symlink(path, tmp_path / "alias")
+    with open(path) as f:
+        fd = f.fileno()
+        found = core.who_has_open(str(tmp_path / "alias"))
+    assert found["path"] == str(path)
+    assert found["timed_out"] is False
+    ours = [p for p in found["processes"] if p["pid"] == os.getpid()]
+    assert ours and fd in ours[0]["fds"]
+    assert core.who_has_open(str(path))["processes"] == []
+
+
+def test_who_has_open_counts_skipped_and_times_out(tmp_path, monkeypatch):
+    path = tmp_path / "f"
+    path.write_text("")
+    monkeypatch.setattr(open_files, "_pids", lambda: [1, 2, 3])
+
+    def holders(pid, wanted):
+        if pid == 2:
+            raise PermissionError(13, "Permission denied")
+        if pid == 3:
+            raise FileNotFoundError(2, "exited")
+        return [4]
+
+    monkeypatch.setattr(open_files, "_holders", holders)
+    monkeypatch.setattr(open_files, "_process_name", lambda pid: "editor")
+    assert core.who_has_open(str(path)) == {
+        "path": str(path), "processes": [{"pid": 1, "name": "editor", "fds": [4]}],
+        "skipped": 1, "timed_out": False}
+
+    ticks = iter([0.0, 0.1, 10.0])
+    monkeypatch.setattr(open_files.time, "monotonic", lambda: next(ticks))
+    partial = core.who_has_open(str(path), timeout_secs=1)
+    assert partial["timed_out"] is True and [p["pid"] for p in partial["processes"]] == [1]
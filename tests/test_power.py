@@ -0,0 +1,130 @@
+import pytest
+from pulse import power
+from pulse.errors import Unsupported
+
+# ThinkPad-style battery reporting energy in µWh and power in µW
+ENERGY_UEVENT = """POWER_SUPPLY_NAME=BAT0
+POWER_SUPPLY_TYPE=Battery
+POWER_SUPPLY_STATUS=Discharging
+POWER_SUPPLY_PRESENT=1
+POWER_SUPPLY_CYCLE_COUNT=312
+POWER_SUPPLY_POWER_NOW=10000000
+POWER_SUPPLY_ENERGY_FULL_DESIGN=57000000
+POWER_SUPPLY_ENERGY_FULL=51300000
+POWER_SUPPLY_ENERGY_NOW=25000000
+POWER_SUPPLY_CAPACITY=48
+"""
+
+# Battery reporting charge in µAh and current in µA, no cycle tracking
+CHARGE_UEVENT = """POWER_SUPPLY_NAME=BAT1
+POWER_SUPPLY_TYPE=Battery
+POWER_SUPPLY_STATUS=Charging
+POWER_SUPPLY_PRESENT=1
+POWER_SUPPLY_CYCLE_COUNT=0
+POWER_SUPPLY_CURRENT_NOW=1500000
+POWER_SUPPLY_CHARGE_FULL_DESIGN=4000000
+POWER_SUPPLY_CHARGE_FULL=3800000
+POWER_SUPPLY_CHARGE_NOW=2300000
+"""
+
+MOUSE_UEVENT = """POWER_SUPPLY_NAME=hidpp_battery_0
+POWER_SUPPLY_TYPE=Battery
+POWER_SUPPLY_SCOPE=Device
+POWER_SUPPLY_STATUS=Discharging
+POWER_SUPPLY_CAPACITY=70
+"""
+
+AC_UEVENT = """POWER_SUPPLY_NAME=AC
+POWER_SUPPLY_TYPE=Mains
+POWER_SUPPLY_ONLINE=0
+"""
+
+def _battery(text):
+    fields = power.parse_power_supply_uevent(text)
+    return power.battery_from_uevent(fields["NAME"], fields)
+
+def test_energy_reporting_battery():
+    bat = _battery(ENERGY_UEVENT)
+    assert bat["percent"] == 48.0
+    assert bat["state"] == "discharging"
+    assert bat["time_to_empty_secs"] == 9000  # 25 Wh at 10 W
+    assert bat["time_to_full_secs"] is None
+    assert bat["cycle_count"] == 312
+    assert bat["health_percent"] == pytest.approx(90.0)
+
+def test_charge_reporting_battery():
+    bat = _battery(CHARGE_UEVENT)
+    assert bat["percent"] == pytest.approx(2300000 / 3800000 * 100)
+    assert bat["state"] == "charging"
+    assert bat["time_to_full_secs"] == 3600  # 1.5 Ah left at 1.5 A
+    assert bat["time_to_empty_secs"] is None
+    assert bat["cycle_count"] is None
+    assert bat["health_percent"] == pytest.approx(95.0)
+
+def test_idle_rate_has_no_estimate():
+    bat = _battery(ENERGY_UEVENT.replace("POWER_NOW=10000000", "POWER_NOW=0"))
+    assert bat["time_to_empty_secs"] is None
+
+@pytest.mark.skipif(not power.LINUX, reason="power_supply reader is Linux-only")
+def test_get_battery_info_skips_non_system_supplies(tmp_path, monkeypatch):
+    for name, text in (("BAT0", ENERGY_UEVENT), ("BAT1", CHARGE_UEVENT),
+                       ("hidpp_battery_0", MOUSE_UEVENT), ("AC", AC_UEVENT)):
+        (tmp_path / name).mkdir()
+        (tmp_path / name / "uevent").write_text(text)
+    monkeypatch.setattr(power, "SYS_CLASS_POWER_SUPPLY", str(tmp_path))
+    assert [b["name"] for b in power.get_battery_info()] == ["BAT0", "BAT1"]
+
+@pytest.mark.skipif(not power.LINUX, reason="power_supply reader is Linux-only")
+def test_desktop_has_no_batteries(tmp_path, monkeypatch):
+    monkeypatch.setattr(power, "SYS_CLASS_POWER_SUPPLY", str(tmp_path))
+    assert power.get_battery_info() == []
+    monkeypatch.setattr(power, "SYS_CLASS_POWER_SUPPLY", str(tmp_path / "missing"))
+    with pytest.raises(Unsupported):
+        power.get_battery_info()
+
+@pytest.mark.skipif(not power.LINUX, reason="power_supply reader is Linux-only")
+def test_no_batteries_under_wsl(tmp_path, monkeypatch):
+    (tmp_path / "BAT1").mkdir()
+    (tmp_path / "BAT1" / "uevent").write_text(CHARGE_UEVENT)
+    monkeypatch.setattr(power, "SYS_CLASS_POWER_SUPPLY", str(tmp_path))
+    monkeypatch.setattr(power.environment, "running_under_wsl", lambda: True)
+    assert power.get_battery_info() == []
+
+def _backlight(root, name, brightness, max_brightness, backlight_type="raw"):
+    device = root / name
+    device.mkdir()
+    (device / "brightness").write_text(f"{brightness}\n")
+    (device / "actual_brightness").write_text(f"{brightness}\n")
+    (device / "max_brightness").write_text(f"{max_brightness}\n")
+    (device / "type").write_text(f"{backlight_type}\n")
+
+@pytest.mark.skipif(not power.LINUX, reason="backlight reader is Linux-only")
+def test_get_brightness_lists_every_backlight(tmp_path, monkeypatch):
+    _backlight(tmp_path, "intel_backlight", 9600, 19200)
+    _backlight(tmp_path, "ddcci3", 75, 100)
+    monkeypatch.setattr(power, "SYS_CLASS_BACKLIGHT", str(tmp_path))
+    assert power.get_brightness() == [
+        {"name": "ddcci3", "type": "raw", "percent": 75.0, "brightness": 75, "max_brightness": 100},
+        {"name": "intel_backlight", "type": "raw", "percent": 50.0, "brightness": 9600, "max_brightness": 19200},
+    ]
+
+@pytest.mark.skipif(not power.LINUX, reason="backlight reader is Linux-only")
+def test_no_backlight_interface(tmp_path, monkeypatch):
+    monkeypatch.setattr(power, "SYS_CLASS_BACKLIGHT", str(tmp_path))
+    assert power.get_brightness() == []
+    monkeypatch.setattr(power, "SYS_CLASS_BACKLIGHT", str(tmp_path / "missing"))
+    with pytest.raises(Unsupported):
+        power.get_brightness()
+
+@pytest.mark.skipif(not power.LINUX, reason="backlight writer is Linux-only")
+def test_set_brightness(tmp_path, monkeypatch):
+    _backlight(tmp_path, "intel_backlight", 9600, 19200)
+    _backlight(tmp_path, "ddcci3", 75, 100)
+    monkeypatch.setattr(power, "SYS_CLASS_BACKLIGHT", str(tmp_path))
+    power.set_brightness(25, "intel_backlight")
+    assert (tmp_path / "intel_backlight" / "brightness").read_text() == "4800"
+    assert (tmp_path / "ddcci3" / "brightness").read_text() == "75\n"
+    with pytest.raises(ValueError):
+        power.set_brightness(120)
+    with pytest.raises(FileNotFoundError):
+        power.set_brightness(50, "acpi_video0")
@@ -0,0 +1,103 @@
+import os
+import time
+import pytest
+from pulse import core, environment, privacy, recording
+
+KEY = "export-key"
+
+PROCESSES = [
+    {"pid": 4242, "name": "gunicorn", "cpu_percent": 1.0, "memory_info": 1024,
+     "username": "alice", "cmdline": "/home/alice/venv/bin/gunicorn --secret-token=hunter2 app:main",
+     "environ": {"AWS_SECRET_ACCESS_KEY": "abc123"}},
+]
+RAW = ("alice", "hunter2", "AWS_SECRET_ACCESS_KEY", "abc123", "/home/")
+
+
+@pytest.fixture
+def private(monkeypatch):
+    monkeypatch.setattr(core.direct_os, "get_process_list", lambda **kwargs: [dict(p) for p in PROCESSES])
+    core.set_privacy_mode({"hash_usernames": True, "key": KEY, "cmdline_executable_only": True,
+                           "drop_environ": True})
+    yield
+    core.set_privacy_mode(None)
+
+
+def test_same_username_hashes_identically():
+    first = privacy.hash_username("alice", KEY.encode())
+    assert first == privacy.hash_username("alice", KEY.encode())
+    assert len(first) == privacy.HASH_LENGTH
+    assert first != privacy.hash_username("bob", KEY.encode())
+    assert first != privacy.hash_username("alice", b"other-key")
+
+
+def test_executable_only():
+    assert privacy.executable_only("/usr/bin/python3 -m http.server") == "python3"
+    assert privacy.executable_only(r"C:\Windows\System32\svchost.exe -k netsvcs") == "svchost.exe"
+    assert privacy.executable_only("nginx: worker process") == "nginx:"
+    assert privacy.executable_only("") == ""
+
+
+def test_process_entries_are_scrubbed(private):
+    [first] = core.get_process_list()
+    [second] = core.get_process_list()
+    assert first["username"] == second["username"] == privacy.hash_username("alice", KEY.encode())
+    assert first["cmdline"] == "gunicorn"
+    assert "environ" not in first
+    assert first["pid"] == 4242 and first["memory_info"] == 1024
+
+
+def test_raw_values_never_appear_in_any_output(private, tmp_path):
+    outputs = [
+        repr(core.get_process_list()),
+        core.get_process_list(as_json=True),
+        core.get_process_list(as_msgpack=True).decode("latin-1"),
+        repr(core.snapshot(include=["processes"])),
+        core.snapshot(include=["processes"], as_json=True),
+        core.get_prometheus_metrics(include_processes=True),
+        repr(core.Monitor().process_list()),
+    ]
+    path = str(tmp_path / "private.pulse")
+    core.start_recording(path, interval_ms=10, sections=["processes"])
+    try:
+        deadline = time.monotonic() + 3
+        while recording._written < 2 and time.monotonic() < deadline:
+            time.sleep(0.01)
+    finally:
+        core.stop_recording()
+    with open(path, "rb") as f:
+        outputs.append(f.read().decode("latin-1"))
+    for output in outputs:
+        for raw in RAW:
+            assert raw not in output
+
+
+def test_identity_fields_survive_with_the_mode_off(monkeypatch):
+    monkeypatch.setattr(core.direct_os, "get_process_list", lambda **kwargs: [dict(p) for p in PROCESSES])
+    [entry] = core.get_process_list()
+    assert entry["username"] == "alice" and "hunter2" in entry["cmdline"]
+
+
+def test_resolve_target_cmdline_is_cut(private):
+    [me] = core.resolve_target(f"pid:{os.getpid()}")
+    assert " " not in me["cmdline"] and "/" not in me["cmdline"]
+
+
+def test_drop_environ_skips_reading_environ(private, monkeypatch):
+    read = []
+    monkeypatch.setattr(environment, "_read", lambda path: read.append(path))
+    environment.detect_container()
+    assert environment.PROC_1_ENVIRON not in read
+
+
+def test_get_privacy_mode_never_reports_the_key(private):
+    mode = core.get_privacy_mode()
+    assert mode == {"hash_usernames": True, "cmdline_executable_only": True, "drop_environ": True,
+                    "has_key": True}
+    core.set_privacy_mode({})
+    assert not any(core.get_privacy_mode().values())
+
+
+def test_hashing_needs_a_key():
+    with pytest.raises(ValueError, match="needs a non-empty key"):
+        core.set_privacy_mode({"hash_usernames": True})
+    assert core.get_privacy_mode()["hash_usernames"] is False
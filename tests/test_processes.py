@@ -0,0 +1,340 @@
+import os
+import subprocess
+import sys
+import time
+import pytest
+from pulse import processes
+from pulse.state import STATE
+
+def test_parse_proc_stat_handles_awkward_names():
+    text = "4242 (tmux: server) (x)) S 1 4242 4242 0 -1 4194560 " + " ".join(["0"] * 8) + " 20 0 1 0 987654 0 0"
+    name, fields = processes.parse_proc_stat(text)
+    assert name == "tmux: server) (x)"
+    assert fields[0] == "S"
+    assert fields[19] == "987654"
+
+def test_parse_proc_io():
+    io = processes.parse_proc_io("rchar: 3980\nread_bytes: 4096\nwrite_bytes: 8192\n")
+    assert io == {"rchar": 3980, "read_bytes": 4096, "write_bytes": 8192}
+
+@pytest.fixture
+def io_samples(monkeypatch):
+    """Feed get_io_hogs a scripted sequence of collections, one second apart."""
+    samples = []
+    clock = iter(range(100, 200))
+    monkeypatch.setattr(processes, "_collect_process_io", lambda: samples.pop(0))
+    monkeypatch.setattr(processes.time, "monotonic", lambda: float(next(clock)))
+    monkeypatch.setattr(STATE, "process_io", None)
+    return samples
+
+def _proc(pid, name, read, write):
+    return {"pid": pid, "name": name, "read_bytes": read, "write_bytes": write}
+
+def test_io_hogs_first_call_is_empty(io_samples):
+    io_samples.append({(10, 1): _proc(10, "dd", 10**9, 0)})
+    assert processes.get_io_hogs() == []
+
+def test_io_hogs_rates_and_ordering(io_samples):
+    io_samples.append({
+        (10, 1): _proc(10, "dd", 1000, 0),
+        (11, 1): _proc(11, "idle", 500, 500),
+        (12, 1): _proc(12, "rsync", 0, 0),
+    })
+    io_samples.append({
+        (10, 1): _proc(10, "dd", 5000, 0),
+        (11, 1): _proc(11, "idle", 500, 500),
+        (12, 1): _proc(12, "rsync", 100, 20000),
+    })
+    processes.get_io_hogs()
+    hogs = processes.get_io_hogs()
+    assert [h["name"] for h in hogs] == ["rsync", "dd"]  # idle had no I/O
+    assert hogs[0]["write_rate"] == 20000.0
+    assert hogs[1]["read_rate"] == 4000.0
+    assert hogs[1]["read_bytes"] == 5000
+
+def test_io_hogs_survive_pid_reuse(io_samples):
+    io_samples.append({(10, 1): _proc(10, "old", 10**6, 0)})
+    io_samples.append({(10, 2): _proc(10, "new", 10**6 + 50, 0)})
+    processes.get_io_hogs()
+    assert processes.get_io_hogs() == []
+
+# 2.6.32 (RHEL 6): no units on the priority rows, narrower columns
+LIMITS_2_6 = """Limit                     Soft Limit           Hard Limit           Units     
+Max cpu time              unlimited            unlimited            seconds   
+Max processes             1024                 127431               processes 
+Max open files            1024                 4096                 files     
+Max address space         unlimited            unlimited            bytes     
+Max nice priority         0                    0                    
+Max realtime priority     0                    0                    
+"""
+
+# 6.x: realtime timeout row, values wide enough to shift the columns
+LIMITS_6_X = """Limit                     Soft Limit           Hard Limit           Units     
+Max stack size            8388608              unlimited            bytes     
+Max processes             126991               126991               processes 
+Max open files            1048576              1048576              files     
+Max locked memory         8388608              8388608              bytes     
+Max address space         unlimited            unlimited            bytes     
+Max pending signals       126991               126991               signals   
+Max realtime timeout      unlimited            unlimited            us        
+"""
+
+def test_parse_proc_limits_old_kernel():
+    limits = processes.parse_proc_limits(LIMITS_2_6)
+    assert limits["open_files"] == {"soft": 1024, "hard": 4096, "unit": "files"}
+    assert limits["address_space"] == {"soft": None, "hard": None, "unit": "bytes"}
+    assert limits["nice_priority"] == {"soft": 0, "hard": 0, "unit": None}
+    assert "realtime_timeout" not in limits
+
+def test_parse_proc_limits_new_kernel():
+    limits = processes.parse_proc_limits(LIMITS_6_X)
+    assert limits["stack_size"] == {"soft": 8388608, "hard": None, "unit": "bytes"}
+    assert limits["open_files"]["soft"] == 1048576
+    assert limits["realtime_timeout"]["unit"] == "us"
+    assert "Limit" not in limits
+
+@pytest.mark.skipif(not processes.LINUX, reason="/proc limits reader is Linux-only")
+def test_get_process_limits_self_and_missing():
+    limits = processes.get_process_limits(os.getpid())
+    assert "open_files" in limits and "address_space" in limits
+    with pytest.raises(ProcessLookupError):
+        processes.get_process_limits(2 ** 22 + 1)
+
+STATUS = """Name:	postgres
+Uid:	1000	1000	1000	1000
+VmSize:	  220344 kB
+Threads:	4
+"""
+
+def test_parse_status_usage():
+    assert processes.parse_status_usage(STATUS) == {"uid": 1000, "vm_size": 220344 * 1024, "threads": 4}
+    assert processes.parse_status_usage("Name:\tkthreadd\nUid:\t0\t0\t0\t0\nThreads:\t1\n") == {
+        "uid": 0, "threads": 1,
+    }
+
+def test_limit_headroom_flags_and_unlimited():
+    limits = processes.parse_proc_limits(LIMITS_6_X)
+    usage = {"open_files": 917504, "address_space": 1 << 30, "file_size": 10, "processes": None}
+    headroom = processes.limit_headroom(7, limits, usage, 80.0)
+    assert headroom["limits"]["open_files"] == {
+        "usage": 917504, "limit": 1048576, "percent": 87.5, "warning": True,
+    }
+    # Unlimited limits and unreadable usage have no percent and never warn
+    assert headroom["limits"]["address_space"]["percent"] is None
+    assert headroom["limits"]["processes"]["warning"] is False
+    assert headroom["warnings"] == ["open_files"]
+    # No "Max file size" row, so no file_size entry
+    assert set(headroom["limits"]) == {"open_files", "address_space", "processes"}
+
+@pytest.mark.skipif(not processes.LINUX, reason="/proc readers are Linux-only")
+def test_get_limit_headroom_self():
+    headroom = processes.get_limit_headroom(os.getpid(), warn_percent=100)
+    assert headroom["limits"]["open_files"]["usage"] >= 3
+    assert headroom["limits"]["processes"]["usage"] >= 1
+    with pytest.raises(ProcessLookupError):
+        processes.get_limit_headroom(2 ** 22 + 1)
+    # Vanished pids drop out of the batch form
+    assert [h["pid"] for h in processes.list_limit_headroom([os.getpid(), 2 ** 22 + 1])] == [os.getpid()]
+
+@pytest.mark.parametrize("target, inode", [
+    ("net:[4026531840]", 4026531840),
+    ("pid:[4026532201]", 4026532201),
+    ("net:[]", None),
+    ("/dev/null", None),
+])
+def test_parse_ns_link(target, inode):
+    assert processes.parse_ns_link(target) == inode
+
+@pytest.fixture
+def fake_ns(tmp_path, monkeypatch):
+    """Host pids 1 and 7, a container with pids 40 and 41, and pid 50 whose links are unreadable."""
+    host_net, container_net = 4026531840, 4026532300
+    for pid, net, pid_ns in ((1, host_net, 0xEFFFFFFC), (7, host_net, 0xEFFFFFFC),
+                             (40, container_net, 4026532301), (41, container_net, 4026532301)):
+        ns = tmp_path / str(pid) / "ns"
+        ns.mkdir(parents=True)
+        (ns / "net").symlink_to(f"net:[{net}]")
+        (ns / "pid").symlink_to(f"pid:[{pid_ns}]")
+    (tmp_path / "50").mkdir()
+    (tmp_path / "self").mkdir()
+    (tmp_path / "self" / "ns").mkdir()
+    monkeypatch.setattr(processes, "PROC", str(tmp_path))
+    return tmp_path
+
+@pytest.mark.skipif(not processes.LINUX, reason="namespaces are Linux-only")
+def test_get_process_namespaces(fake_ns):
+    assert processes.get_process_namespaces(40) == {"pid": 4026532301, "net": 4026532300, "mnt": None, "uts": None}
+    assert processes.get_process_namespaces(50) == {"pid": None, "net": None, "mnt": None, "uts": None}
+    with pytest.raises(ProcessLookupError):
+        processes.get_process_namespaces(99)
+
+@pytest.mark.skipif(not processes.LINUX, reason="namespaces are Linux-only")
+@pytest.mark.parametrize("kind", ["net", "pid"])
+def test_group_processes_by_namespace(fake_ns, kind):
+    groups = processes.group_processes_by_namespace(kind)
+    assert [(g["is_host"], g["pids"]) for g in groups] == [(True, [1, 7]), (False, [40, 41])]
+    assert all(g["kind"] == kind for g in groups)
+    assert processes.group_processes_by_namespace("mnt") == []
+
+def test_group_by_namespace_orders_host_then_size():
+    namespaces = {1: {"net": 10}, 2: {"net": 30}, 3: {"net": 30}, 4: {"net": 20}, 5: {"net": None}}
+    groups = processes.group_by_namespace(namespaces, "net", 10)
+    assert [(g["inode"], g["pids"]) for g in groups] == [(10, [1]), (30, [2, 3]), (20, [4])]
+
+ROWS = [(1, "init", 0.5, 4096), (20, "db", 40.0, 8192), (30, "web", 10.0, 1024)]
+
+@pytest.fixture
+def fake_rows(monkeypatch):
+    calls = []
+    monkeypatch.setattr(processes.direct_os, "read_process_rows", lambda: calls.append(1) or list(ROWS))
+    return calls
+
+def test_iter_processes_matches_process_list(fake_rows):
+    assert list(processes.iter_processes(sort_by="cpu")) == processes.direct_os.get_process_list(sort_by="cpu")
+    assert [p["pid"] for p in processes.iter_processes(sort_by="mem", limit=2)] == [20, 1]
+
+def test_iter_processes_collects_once(fake_rows):
+    it = processes.iter_processes(fields=["pid", "name"])
+    assert len(fake_rows) == 1
+    assert next(it) == {"pid": 1, "name": "init"}
+    assert it.__length_hint__() == 2
+    assert [p["pid"] for p in it] == [20, 30]
+    assert len(fake_rows) == 1
+    with pytest.raises(StopIteration):
+        next(it)
+
+def test_iter_processes_close_frees_rows(fake_rows):
+    it = processes.iter_processes()
+    next(it)
+    it.close()
+    assert it._rows is None
+    assert list(it) == []
+    with pytest.raises(ValueError):
+        processes.iter_processes(fields=["pid", "rss"])
+
+@pytest.fixture
+def collections(monkeypatch):
+    """Feed get_process_changes scripted (rows, statuses) collections in order."""
+    from pulse.state import SysState, using
+    scripted = []
+    reads = []
+    def rows():
+        reads.append(1)
+        return list(scripted[0][0])
+    monkeypatch.setattr(processes.direct_os, "read_process_rows", rows)
+    monkeypatch.setattr(processes, "read_process_states",
+                        lambda: {pid: ("?", status) for pid, status in scripted.pop(0)[1].items()})
+    with using(SysState()):
+        yield scripted, reads
+
+def test_process_changes_report_only_differences(collections):
+    scripted, _ = collections
+    scripted.append(([(1, "init", 0.0, 4096), (20, "db", 40.0, 8 << 20)], {1: "sleeping", 20: "running"}))
+    first = processes.get_process_changes()
+    assert [e["pid"] for e in first["started"]] == [1, 20]
+    assert first["exited"] == [] and first["changed"] == []
+
+    # db's CPU moves 0.5 (under epsilon), its memory 2 MiB; init stops; web starts
+    scripted.append(([(20, "db", 40.5, 10 << 20), (30, "web", 5.0, 1024)], {20: "running", 30: "sleeping"}))
+    second = processes.get_process_changes(cpu_epsilon=1.0, memory_threshold=1 << 20)
+    assert [e["pid"] for e in second["started"]] == [30]
+    assert second["exited"] == [1]
+    assert [(e["pid"], e["memory_info"]) for e in second["changed"]] == [(20, 10 << 20)]
+    assert second["snapshot_id"] > first["snapshot_id"]
+
+    # Status changes always count
+    scripted.append(([(20, "db", 40.9, 10 << 20), (30, "web", 5.0, 1024)], {20: "running", 30: "zombie"}))
+    third = processes.get_process_changes()
+    assert [(e["pid"], e["status"]) for e in third["changed"]] == [(30, "zombie")]
+
+    # 0.7 since the previous collection, but 1.1 since db was last reported at 40.5
+    scripted.append(([(20, "db", 41.6, 10 << 20), (30, "web", 5.0, 1024)], {20: "running", 30: "zombie"}))
+    fourth = processes.get_process_changes()
+    assert [(e["pid"], e["cpu_percent"]) for e in fourth["changed"]] == [(20, 41.6)]
+
+def test_reused_pid_is_an_exit_and_a_start(collections):
+    scripted, _ = collections
+    scripted.append(([(7, "cron", 0.0, 100)], {7: "sleeping"}))
+    processes.get_process_changes()
+    scripted.append(([(7, "bash", 0.0, 100)], {7: "sleeping"}))
+    changes = processes.get_process_changes()
+    assert changes["exited"] == [7]
+    assert [e["name"] for e in changes["started"]] == ["bash"]
+
+def test_process_list_shares_the_changes_collection(collections):
+    scripted, reads = collections
+    scripted.append(([(1, "init", 0.5, 4096), (20, "db", 40.0, 8192)], {1: "sleeping", 20: "running"}))
+    snapshot_id = processes.get_process_changes()["snapshot_id"]
+    listed = processes.direct_os.get_process_list(sort_by="cpu", snapshot_id=snapshot_id)
+    assert [p["pid"] for p in listed] == [20, 1]
+    assert len(reads) == 1
+    with pytest.raises(ValueError, match=f"snapshot_id {snapshot_id - 1} is no longer held"):
+        processes.direct_os.get_process_list(snapshot_id=snapshot_id - 1)
+
+@pytest.mark.skipif(not processes.LINUX, reason="/proc reader is Linux-only")
+def test_busy_child_changes_past_cpu_epsilon():
+    from pulse.state import SysState, using
+    child = subprocess.Popen([sys.executable, "-c", "while True: pass"])
+    try:
+        with using(SysState()):
+            # Seen for the first time, the child has no CPU delta to report yet
+            processes.get_process_changes(cpu_epsilon=10)
+            deadline = time.monotonic() + 5
+            changed = []
+            while time.monotonic() < deadline and not changed:
+                time.sleep(0.3)
+                changed = [e for e in processes.get_process_changes(cpu_epsilon=10)["changed"]
+                           if e["pid"] == child.pid]
+        assert changed and changed[0]["cpu_percent"] > 10
+    finally:
+        child.kill()
+        child.wait()
+
+@pytest.mark.parametrize("policy, priority, expected", [
+    (0, 0, {"sched_policy": "other", "rt_priority": 0}),
+    (1, 80, {"sched_policy": "fifo", "rt_priority": 80}),
+    (2 | 0x40000000, 10, {"sched_policy": "rr", "rt_priority": 10}),
+    (6, None, {"sched_policy": "deadline", "rt_priority": None}),
+    (None, None, {"sched_policy": None, "rt_priority": None}),
+])
+def test_sched_entry(policy, priority, expected):
+    assert processes.sched_entry(policy, priority) == expected
+
+def test_windows_priority_class_mapping():
+    assert processes.windows_sched_entry(0x100) == {
+        "sched_policy": "rr", "rt_priority": None, "priority_class": "realtime"}
+    assert processes.windows_sched_entry(0x4000)["sched_policy"] == "other"
+    assert processes.windows_sched_entry(None)["priority_class"] is None
+
+@pytest.mark.skipif(not processes.LINUX, reason="reads this process's policy")
+def test_process_scheduling_self_and_missing():
+    assert processes.get_process_scheduling(os.getpid()) == {
+        "pid": os.getpid(), "sched_policy": "other", "rt_priority": 0}
+    with pytest.raises(ProcessLookupError):
+        processes.get_process_scheduling(2 ** 22 + 1)
+
+@pytest.mark.skipif(not processes.LINUX, reason="per-thread policies are read on Linux")
+def test_process_threads_include_each_thread():
+    import threading
+
+    started, stop = threading.Event(), threading.Event()
+    thread = threading.Thread(target=lambda: started.set() or stop.wait())
+    thread.start()
+    started.wait()
+    try:
+        threads = processes.get_process_threads(os.getpid())
+    finally:
+        stop.set()
+        thread.join()
+    assert threads[0]["tid"] == os.getpid()
+    entry = next(t for t in threads if t["tid"] == thread.native_id)
+    assert (entry["sched_policy"], entry["rt_priority"]) == ("other", 0)
+
+def test_realtime_only_filters_before_limit(fake_rows, monkeypatch):
+    policies = {1: processes.sched_entry(0, 0), 20: processes.sched_entry(1, 50),
+                30: processes.sched_entry(2, 10)}
+    monkeypatch.setattr(processes, "read_sched", lambda pid: policies[pid])
+    realtime = processes.direct_os.get_process_list(sort_by="mem", realtime_only=True)
+    assert [(p["pid"], p["sched_policy"], p["rt_priority"]) for p in realtime] == [(20, "fifo", 50),
+                                                                                  (30, "rr", 10)]
+    assert [p["pid"] for p in processes.direct_os.get_process_list(realtime_only=True, limit=1)] == [20]
@@ -0,0 +1,86 @@
+import re
+import pytest
+from pulse import core, prometheus
+
+SNAP = {
+    "timestamp": 1760000000.0,
+    "cpu": [12.5, 80.0],
+    "memory": {"total": 8000, "available": 3000, "used": 5000, "percent": 62.5, "swap_total": 0, "swap_used": 0},
+    "network": {"bytes_recv": 123456, "bytes_sent": 654},
+    "disks": [
+        {"device": "/dev/sda1", "mountpoint": "/", "fstype": "ext4", "total": 1000, "used": 400,
+         "available": 500, "percent": 44.4},
+        {"device": "server:/share", "mountpoint": '/mnt/odd "name"\\x', "fstype": "nfs", "total": None,
+         "used": None, "available": None, "percent": None},
+    ],
+    "processes": [{"pid": 42, "name": "evil\nname", "cpu_percent": 3.5, "memory_info": 2048}],
+}
+
+SAMPLE = re.compile(r'^([a-zA-Z_:][a-zA-Z0-9_:]*)(?:\{(.*)\})? (\S+)$')
+LABEL = re.compile(r'([a-zA-Z_][a-zA-Z0-9_]*)="((?:[^"\\]|\\[\\"n])*)"(,|$)')
+
+
+def parse(text):
+    """Parse exposition text into {name: {"type", "help", "samples": [(labels, value)]}}, checking the grammar."""
+    assert text.endswith("\n")
+    families = {}
+    for line in text.splitlines():
+        if line.startswith("# HELP "):
+            name, help_text = line[7:].split(" ", 1)
+            assert name not in families, f"duplicate family {name}"
+            families[name] = {"help": help_text, "samples": []}
+        elif line.startswith("# TYPE "):
+            name, kind = line[7:].split(" ")
+            assert kind in ("counter", "gauge")
+            families[name]["type"] = kind
+        else:
+            match = SAMPLE.match(line)
+            assert match, f"bad sample line {line!r}"
+            name, raw_labels, value = match.groups()
+            labels, pos = {}, 0
+            while raw_labels and pos < len(raw_labels):
+                label = LABEL.match(raw_labels, pos)
+                assert label, f"bad labels in {line!r}"
+                value_text = re.sub(r'\\(.)', lambda m: "\n" if m.group(1) == "n" else m.group(1), label.group(2))
+                labels[label.group(1)] = value_text
+                pos = label.end()
+            assert name in families and "type" in families[name], f"{name} sampled before its TYPE"
+            families[name]["samples"].append((labels, float(value)))
+    return families
+
+
+def test_render_families_and_types():
+    families = parse(prometheus.render(SNAP))
+    assert families["pulse_cpu_usage_percent"]["samples"] == [({"core": "0"}, 12.5), ({"core": "1"}, 80.0)]
+    assert families["pulse_memory_used_bytes"]["type"] == "gauge"
+    assert families["pulse_memory_used_bytes"]["samples"] == [({}, 5000.0)]
+    assert families["pulse_network_received_bytes_total"]["type"] == "counter"
+    assert families["pulse_network_received_bytes_total"]["samples"] == [({}, 123456.0)]
+    assert families["pulse_process_memory_bytes"]["type"] == "gauge"
+
+
+def test_render_escapes_labels_and_skips_stale_disks():
+    families = parse(prometheus.render(SNAP))
+    disk = families["pulse_disk_total_bytes"]["samples"]
+    # The stale NFS mount has no figures, so only "/" is reported
+    assert disk == [({"mount": "/", "device": "/dev/sda1", "fstype": "ext4"}, 1000.0)]
+    assert families["pulse_process_cpu_percent"]["samples"] == [({"pid": "42", "name": "evil\nname"}, 3.5)]
+    assert prometheus.escape_label_value('a"b\\c\nd') == 'a\\"b\\\\c\\nd'
+
+
+def test_render_prefix_and_sections():
+    text = prometheus.render({"network": SNAP["network"]}, prefix="host")
+    assert set(parse(text)) == {"host_network_received_bytes_total", "host_network_sent_bytes_total"}
+    with pytest.raises(ValueError):
+        prometheus.render(SNAP, prefix="bad-prefix")
+
+
+def test_get_prometheus_metrics_processes_opt_in():
+    families = parse(core.get_prometheus_metrics())
+    assert "pulse_memory_total_bytes" in families
+    assert not any(name.startswith("pulse_process_") for name in families)
+
+    families = parse(core.get_prometheus_metrics(prefix="node", include_processes=True, process_limit=2))
+    assert len(families["node_process_memory_bytes"]["samples"]) <= 2
+    with pytest.raises(ValueError):
+        core.get_prometheus_metrics(process_limit=0)
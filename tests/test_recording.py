@@ -0,0 +1,116 @@
+import json
+import time
+import pytest
+from pulse import core, recording, sampler
+from pulse.errors import CorruptRecording
+
+
+def _wait_for(condition, timeout=3.0):
+    deadline = time.monotonic() + timeout
+    while not condition() and time.monotonic() < deadline:
+        time.sleep(0.01)
+
+
+@pytest.fixture
+def recorded(tmp_path):
+    path = str(tmp_path / "incident.pulse")
+    core.start_recording(path, interval_ms=10, sections=["cpu", "memory", "processes"])
+    try:
+        _wait_for(lambda: recording._written >= 4)
+    finally:
+        count = core.stop_recording()
+    return path, count
+
+
+def test_round_trip(recorded):
+    path, count = recorded
+    assert count >= 4
+    assert sampler.sampler_status()["running"] is False
+    live = sampler.get_samples()
+
+    replay = core.load_recording(path)
+    assert len(replay) == count
+    assert replay.samples == live[-count:]
+    for sample in replay.samples:
+        assert replay.memory_info() == sample["memory"]
+        assert replay.cpu_percents() == sample["cpu"]
+    assert json.loads(replay.process_list(as_json=True)) == {"timestamp": replay.samples[0]["timestamp"],
+                                                            "data": replay.samples[0]["processes"]}
+    with pytest.raises(EOFError):
+        replay.memory_info()
+
+    replay.rewind()
+    assert replay.snapshot() == replay.samples[0]
+    with pytest.raises(ValueError, match="network"):
+        replay.network_stats()
+
+
+def test_recording_a_running_sampler_leaves_it_running(tmp_path):
+    sampler.start_sampler(interval_ms=10, sections=["memory"])
+    try:
+        core.start_recording(str(tmp_path / "a.pulse"))
+        with pytest.raises(RuntimeError):
+            core.start_recording(str(tmp_path / "b.pulse"))
+        _wait_for(lambda: recording._written >= 2)
+        assert core.stop_recording() >= 2
+        assert sampler.sampler_status()["running"] is True
+    finally:
+        sampler.stop_sampler()
+    assert all(set(s) == {"timestamp", "memory"} for s in core.load_recording(str(tmp_path / "a.pulse")).samples)
+
+
+def _records(path):
+    with open(path, "rb") as f:
+        data = f.read()
+    offsets, offset = [], len(recording.MAGIC)
+    while offset < len(data):
+        offsets.append(offset)
+        offset += 4 + int.from_bytes(data[offset:offset + 4], "big")
+    return data, offsets
+
+
+def test_truncated_file_names_the_offset(recorded, tmp_path):
+    path, count = recorded
+    data, offsets = _records(path)
+    assert len(offsets) == count
+
+    cut = tmp_path / "cut.pulse"
+    cut.write_bytes(data[:-3])
+    with pytest.raises(CorruptRecording, match=f"offset {offsets[-1]}") as info:
+        core.load_recording(str(cut))
+    assert info.value.offset == offsets[-1]
+
+    cut.write_bytes(data[:offsets[1] + 2])
+    with pytest.raises(CorruptRecording, match="truncated record length") as info:
+        core.load_recording(str(cut))
+    assert info.value.offset == offsets[1]
+
+
+def test_damaged_files(tmp_path):
+    path = tmp_path / "bad.pulse"
+    path.write_bytes(b"{\"timestamp\": 1}\n")
+    with pytest.raises(CorruptRecording, match="bad header") as info:
+        core.load_recording(str(path))
+    assert info.value.offset == 0
+
+    garbage = b"\xff\xfe not json"
+    path.write_bytes(recording.MAGIC + len(garbage).to_bytes(4, "big") + garbage)
+    with pytest.raises(CorruptRecording, match=f"undecodable record.*offset {len(recording.MAGIC)}"):
+        core.load_recording(str(path))
+
+    path.write_bytes(recording.MAGIC + (2).to_bytes(4, "big") + b"[]")
+    with pytest.raises(CorruptRecording, match="not a snapshot"):
+        core.load_recording(str(path))
+
+    path.write_bytes(recording.MAGIC)
+    assert len(core.load_recording(str(path))) == 0
+
+
+def test_replay_as_msgpack(recorded):
+    msgpack = pytest.importorskip("msgpack")
+    replay = core.load_recording(recorded[0])
+    assert msgpack.unpackb(replay.memory_info(as_msgpack=True), raw=False) == {
+        **replay.samples[0]["memory"], "timestamp": replay.samples[0]["timestamp"]}
+    with pytest.raises(ValueError, match="cannot be combined"):
+        replay.memory_info(as_json=True, as_msgpack=True)
+    assert replay.memory_info() == replay.samples[1]["memory"]
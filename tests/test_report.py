@@ -0,0 +1,93 @@
+import csv
+import json
+import pytest
+from pulse import recording, report
+
+
+def _samples():
+    return [
+        {"timestamp": 100.0, "cpu": [10.0, 30.0], "memory": {"percent": 40.0, "used": 4000},
+         "network": {"bytes_recv": 1000, "bytes_sent": 100},
+         "disks": [{"mountpoint": "/", "percent": 50.0, "used": 5000}],
+         "processes": [{"pid": 7, "name": "load", "cpu_percent": 0.0, "start_time": 50.0, "cpu_time": 10.0}]},
+        {"timestamp": 102.0, "cpu": [90.0, 70.0], "memory": {"percent": 60.0, "used": 6000},
+         "network": {"bytes_recv": 5000, "bytes_sent": 300},
+         "disks": [{"mountpoint": "/", "percent": 55.0, "used": 5500}],
+         "processes": [{"pid": 7, "name": "load", "cpu_percent": 0.0, "start_time": 50.0, "cpu_time": 13.0},
+                       {"pid": 8, "name": "db", "cpu_percent": 0.0, "start_time": 101.0, "cpu_time": 1.0}]},
+        # The interface went away and its counters reset; memory wasn't sampled
+        {"timestamp": 104.0, "cpu": [20.0, 20.0],
+         "network": {"bytes_recv": 200, "bytes_sent": 10},
+         "disks": [{"mountpoint": "/", "percent": 60.0, "used": 6000}],
+         # pid 7 was reused by a process that started since
+         "processes": [{"pid": 8, "name": "db", "cpu_percent": 0.0, "start_time": 101.0, "cpu_time": 1.5},
+                       {"pid": 7, "name": "load", "cpu_percent": 0.0, "start_time": 103.0, "cpu_time": 0.25}]},
+    ]
+
+
+def test_summarize():
+    summary = report.summarize(_samples())
+    assert (summary["samples"], summary["start"], summary["duration_secs"]) == (3, 100.0, 4.0)
+    assert summary["series"]["cpu_total"] == {"min": 20.0, "max": 80.0, "avg": 40.0}
+    # Integrated from cpu_time, not from the cpu_percent readings
+    assert summary["top_processes"] == [{"pid": 7, "name": "load", "cpu_secs": 3.0},
+                                        {"pid": 8, "name": "db", "cpu_secs": 1.5},
+                                        {"pid": 7, "name": "load", "cpu_secs": 0.25}]
+    assert summary["network"] == {"bytes_recv": 4000, "bytes_sent": 200}
+    assert summary["disk_growth"] == {"/": 1000}
+
+
+def test_summarize_empty():
+    summary = report.summarize([])
+    assert summary["samples"] == 0 and summary["series"] == {} and summary["start"] is None
+
+
+def test_json_report(tmp_path):
+    path = tmp_path / "run.json"
+    report.write_report(str(path), "json", samples=_samples())
+    document = json.loads(path.read_text())
+    assert document["samples"] == _samples()
+    assert document["summary"]["network"]["bytes_recv"] == 4000
+
+
+def test_csv_report_round_trips(tmp_path):
+    path = tmp_path / "run.csv"
+    report.write_report(str(path), "csv", samples=_samples())
+    with open(path, newline="") as f:
+        rows = list(csv.DictReader(f))
+    # One row per sample, one column per series: what pandas.read_csv() needs
+    assert len(rows) == 3
+    assert list(rows[0]) == ["timestamp", "cpu_total", "disk_percent:/", "disk_used:/", "memory_percent",
+                             "memory_used", "net_recv_per_sec", "net_sent_per_sec"]
+    assert float(rows[1]["cpu_total"]) == 80.0 and float(rows[1]["net_recv_per_sec"]) == 2000.0
+    assert rows[0]["net_recv_per_sec"] == "" and rows[2]["memory_used"] == ""
+
+    with open(tmp_path / "run.summary.csv", newline="") as f:
+        summary = list(csv.reader(f))
+    assert summary[0] == list(report.SUMMARY_COLUMNS)
+    assert ["top_processes", "load (7)", "cpu_secs", "3.0"] in summary
+    assert ["disk_growth", "/", "bytes", "1000"] in summary
+    assert all(len(row) == 4 for row in summary)
+
+
+def test_report_of_a_recording(tmp_path):
+    path = tmp_path / "run.rec"
+    with open(path, "wb") as f:
+        f.write(recording.MAGIC)
+        for sample in _samples():
+            data = json.dumps(sample).encode()
+            f.write(len(data).to_bytes(4, "big") + data)
+    report.write_report(str(tmp_path / "run.json"), samples=recording.read_recording(str(path)))
+    assert json.loads((tmp_path / "run.json").read_text())["summary"]["samples"] == 3
+
+
+def test_unwritable_path_names_the_file(tmp_path):
+    path = tmp_path / "missing" / "run.csv"
+    with pytest.raises(OSError) as info:
+        report.write_report(str(path), "csv", samples=_samples())
+    assert info.value.filename == str(path)
+
+
+def test_summary_path():
+    assert report.summary_path("/tmp/run.csv") == "/tmp/run.summary.csv"
+    assert report.summary_path("run") == "run.summary.csv"
@@ -0,0 +1,59 @@
+import types
+import pytest
+from pulse import resctrl
+from pulse.state import STATE
+
+def _group(directory, pids, domains):
+    directory.mkdir(parents=True, exist_ok=True)
+    (directory / "tasks").write_text("".join(f"{p}\n" for p in pids))
+    for domain, values in domains.items():
+        mon = directory / "mon_data" / f"mon_{domain}"
+        mon.mkdir(parents=True)
+        for event, value in zip(resctrl.EVENTS, values):
+            (mon / event).write_text(f"{value}\n")
+
+@pytest.fixture
+def resctrl_root(tmp_path, monkeypatch):
+    """Default group, control group "db" with monitoring subgroup "replica", and "mon_groups/web"."""
+    root = tmp_path / "resctrl"
+    (root / "info" / "L3_MON").mkdir(parents=True)
+    (root / "schemata").write_text("L3:0=fff;1=fff\n")
+    _group(root, [1, 2, 3], {"L3_00": (1048576, 5000, 4000), "L3_01": (524288, 1000, 1000)})
+    _group(root / "db", [40], {"L3_00": (4194304, 90000, 80000), "L3_01": ("Unavailable", 0, 0)})
+    _group(root / "db" / "mon_groups" / "replica", [41, 42], {"L3_00": (0, 10, 10)})
+    _group(root / "mon_groups" / "web", [50], {"L3_00": (2097152, 20000, 20000)})
+    monkeypatch.setattr(resctrl, "SYS_FS_RESCTRL", str(root))
+    monkeypatch.setattr(STATE, "rdt_counters", {})
+    return root
+
+def test_parse_event_value():
+    assert resctrl.parse_event_value("123456\n") == 123456
+    assert resctrl.parse_event_value("Unavailable\n") is None
+    assert resctrl.parse_event_value(None) is None
+
+def test_find_groups(resctrl_root):
+    assert [name for name, _ in resctrl.find_groups(str(resctrl_root))] == [
+        "/", "mon_groups/web", "db", "db/mon_groups/replica",
+    ]
+
+def test_rdt_groups_totals_and_rates(resctrl_root, monkeypatch):
+    clock = iter([10.0, 12.0])
+    monkeypatch.setattr(resctrl, "time", types.SimpleNamespace(monotonic=lambda: next(clock)))
+    groups = {g["name"]: g for g in resctrl.get_rdt_groups()}
+    default = groups["/"]
+    assert default["pids"] == [1, 2, 3]
+    assert default["llc_occupancy"] == 1572864
+    assert default["mbm_total_bytes"] == 6000
+    assert default["mbm_total_bytes_per_sec"] is None
+    assert groups["db"]["llc_occupancy"] is None  # one domain unavailable
+    assert groups["db/mon_groups/replica"]["pids"] == [41, 42]
+
+    (resctrl_root / "mon_data" / "mon_L3_00" / "mbm_total_bytes").write_text("9000\n")
+    groups = {g["name"]: g for g in resctrl.get_rdt_groups()}
+    assert groups["/"]["mbm_total_bytes_per_sec"] == 2000.0
+    assert groups["/"]["mbm_local_bytes_per_sec"] == 0.0
+    assert groups["mon_groups/web"]["domains"]["L3_00"]["llc_occupancy"] == 2097152
+
+def test_no_resctrl_mount_is_empty(tmp_path, monkeypatch):
+    monkeypatch.setattr(resctrl, "SYS_FS_RESCTRL", str(tmp_path / "missing"))
+    assert resctrl.get_rdt_groups() == []
@@ -0,0 +1,134 @@
+import time
+import pytest
+from pulse import core, sampler
+from pulse.state import STATE
+
+
+def _wait_for(condition, timeout=3.0):
+    deadline = time.monotonic() + timeout
+    while not condition() and time.monotonic() < deadline:
+        time.sleep(0.01)
+
+
+def test_sampler_history_is_ordered_and_bounded():
+    sampler.start_sampler(interval_ms=10, capacity=5, sections=["memory", "network"])
+    try:
+        _wait_for(lambda: sampler.sampler_status()["collected"] >= 12)
+        status = sampler.sampler_status()
+    finally:
+        sampler.stop_sampler()
+
+    assert status["running"] is True
+    assert status["collected"] >= 12
+    assert status["samples"] == 5
+    assert status["dropped"] == status["collected"] - 5
+    samples = sampler.get_samples()
+    assert len(samples) == 5
+    timestamps = [s["timestamp"] for s in samples]
+    assert timestamps == sorted(timestamps) and len(set(timestamps)) == 5
+    assert all(set(s) == {"timestamp", "memory", "network"} for s in samples)
+    assert all(s["network"]["interfaces"] for s in samples)
+    assert sampler.sampler_status()["running"] is False
+    assert sampler._thread is None
+
+
+def test_get_samples_since_timestamp():
+    sampler.start_sampler(interval_ms=10, capacity=100, sections=["memory"])
+    try:
+        _wait_for(lambda: len(sampler.get_samples()) >= 3)
+        first = sampler.get_samples()
+        _wait_for(lambda: len(sampler.get_samples()) > len(first))
+        newer = sampler.get_samples(since_timestamp=first[-1]["timestamp"])
+    finally:
+        sampler.stop_sampler()
+    assert newer
+    assert all(s["timestamp"] > first[-1]["timestamp"] for s in newer)
+
+
+def test_sampler_timestamps_follow_the_chosen_clock():
+    core.set_clock("monotonic")
+    try:
+        sampler.start_sampler(interval_ms=10, sections=["memory"])
+        try:
+            _wait_for(lambda: sampler.sampler_status()["collected"] >= 2)
+        finally:
+            sampler.stop_sampler()
+    finally:
+        core.set_clock("wall")
+    assert all(0 < time.monotonic() - s["timestamp"] < 60 for s in sampler.get_samples())
+
+
+def test_sampler_readers_stay_off_monitor():
+    monitor = core.Monitor()
+    assert not hasattr(monitor, "samples") and not hasattr(monitor, "sampler_status")
+    assert hasattr(monitor, "memory_info")
+
+
+def test_sampler_uses_its_own_baselines(monkeypatch):
+    monkeypatch.setattr(STATE, "cpu_times", None)
+    sampler.start_sampler(interval_ms=10, sections=["cpu"])
+    try:
+        _wait_for(lambda: len(sampler.get_samples()) >= 2)
+    finally:
+        sampler.stop_sampler()
+    assert STATE.cpu_times is None
+
+
+def test_sampler_records_errors_and_keeps_running(monkeypatch):
+    def broken(**kwargs):
+        raise OSError("meminfo vanished")
+    monkeypatch.setitem(core.SNAPSHOT_SECTIONS, "memory", broken)
+    sampler.start_sampler(interval_ms=10, sections=["memory"])
+    try:
+        _wait_for(lambda: sampler.sampler_status()["last_error"] is not None)
+        status = sampler.sampler_status()
+    finally:
+        sampler.stop_sampler()
+    assert status["last_error"] == "OSError: meminfo vanished"
+    assert status["running"] is True
+
+
+def test_start_sampler_validation():
+    with pytest.raises(ValueError):
+        sampler.start_sampler(interval_ms=0)
+    with pytest.raises(ValueError):
+        sampler.start_sampler(capacity=0)
+    with pytest.raises(ValueError):
+        sampler.start_sampler(sections=["gpu"])
+    assert sampler._thread is None
+
+
+def test_cpu_history_buffer_follows_ring_order(monkeypatch):
+    monkeypatch.setattr(sampler, "_samples", sampler.deque(maxlen=3))
+    for i in range(5):
+        sampler._record({"timestamp": 100.0 + i, "cpu": [float(i), i + 0.5]})
+    cpu, timestamps = core.get_cpu_history_buffer()
+    assert cpu.format == "f" and cpu.shape == (3, 2) and cpu.c_contiguous
+    assert cpu.tolist() == [[2.0, 2.5], [3.0, 3.5], [4.0, 4.5]]
+    assert timestamps.format == "d" and timestamps.tolist() == [102.0, 103.0, 104.0]
+
+    cpu, timestamps = core.get_cpu_history_buffer(last=2)
+    assert cpu.tolist() == [[3.0, 3.5], [4.0, 4.5]]
+    assert bytes(cpu) == sampler.array("f", [3.0, 3.5, 4.0, 4.5]).tobytes()
+
+
+def test_cpu_history_buffer_without_cpu_samples(monkeypatch):
+    monkeypatch.setattr(sampler, "_samples", sampler.deque(maxlen=3))
+    assert [len(view) for view in core.get_cpu_history_buffer()] == [0, 0]
+    sampler._record({"timestamp": 1.0, "memory": {}})
+    sampler._record({"timestamp": 2.0, "cpu": [1.0]})
+    sampler._record({"timestamp": 3.0, "cpu": [1.0, 2.0]})
+    cpu, timestamps = core.get_cpu_history_buffer()
+    assert cpu.tolist() == [[1.0, 2.0]] and timestamps.tolist() == [3.0]
+
+
+def test_cpu_history_buffer_from_live_sampler():
+    sampler.start_sampler(interval_ms=10, sections=["cpu"])
+    try:
+        _wait_for(lambda: len(sampler.get_samples()) >= 3)
+        cpu, timestamps = core.get_cpu_history_buffer()
+    finally:
+        sampler.stop_sampler()
+    assert cpu.shape[0] == len(timestamps) >= 3
+    assert cpu.shape[1] == len(core.get_cpu_percents())
+    assert list(timestamps) == sorted(timestamps)
@@ -0,0 +1,88 @@
+import functools
+import os
+import pytest
+from pulse import scanner
+
+@pytest.fixture
+def tree(tmp_path):
+    """big/ (3000 bytes over two levels), small/ (100), and a 50 byte top-level file."""
+    (tmp_path / "big" / "nested").mkdir(parents=True)
+    (tmp_path / "big" / "a.bin").write_bytes(b"x" * 1000)
+    (tmp_path / "big" / "nested" / "b.bin").write_bytes(b"x" * 2000)
+    (tmp_path / "small").mkdir()
+    (tmp_path / "small" / "c.txt").write_bytes(b"x" * 100)
+    (tmp_path / "top.txt").write_bytes(b"x" * 50)
+    return tmp_path
+
+def test_scan_directory_sizes(tree):
+    result = scanner.scan_directory(str(tree))
+    assert result["total_size"] == 3150
+    assert result["files"] == 4
+    assert result["dirs"] == 3
+    assert result["errors"] == 0
+    assert result["timed_out"] is False
+    assert [(c["name"], c["size"]) for c in result["children"]] == [
+        ("big", 3000), ("small", 100), ("top.txt", 50),
+    ]
+
+def test_scan_directory_max_depth(tree):
+    shallow = scanner.scan_directory(str(tree), max_depth=1)
+    sizes = {c["name"]: c["size"] for c in shallow["children"]}
+    assert sizes["big"] == 1000  # big/nested is not read
+    assert scanner.scan_directory(str(tree), max_depth=0)["total_size"] == 50
+
+def test_scan_directory_counts_hard_links_once(tree):
+    try:
+        os.link(tree / "big" / "a.bin", tree / "small" / "a-link.bin")
+    except (OSError, NotImplementedError):
+        pytest.skip("hard links not supported here")
+    assert scanner.scan_directory(str(tree))["total_size"] == 3150
+
+def test_scan_directory_skips_unreadable(tree, monkeypatch):
+    real_scandir = os.scandir
+    blocked = str(tree / "big" / "nested")
+
+    def scandir(path):
+        if str(path) == blocked:
+            raise PermissionError(path)
+        return real_scandir(path)
+
+    monkeypatch.setattr(scanner.os, "scandir", scandir)
+    result = scanner.scan_directory(str(tree))
+    assert result["errors"] == 1
+    assert result["total_size"] == 1150
+
+def test_scan_directory_timeout(tree, monkeypatch):
+    # A clock that never moves, so expiry can't depend on how fast the scan runs
+    monkeypatch.setattr(scanner, "_ScanContext", functools.partial(scanner._ScanContext, clock=lambda: 100.0))
+    result = scanner.scan_directory(str(tree), timeout_secs=0)
+    assert result["timed_out"] is True
+    # Only the top-level file; neither subdirectory walk started
+    assert result["total_size"] == 50
+    assert scanner.scan_directory(str(tree), timeout_secs=1)["timed_out"] is False
+
+def test_scan_directory_rejects_files(tree):
+    with pytest.raises(NotADirectoryError):
+        scanner.scan_directory(str(tree / "top.txt"))
+
+def test_find_largest_files_ordering(tree):
+    (tree / "big" / "nested" / "huge.bin").write_bytes(b"x" * 5000)
+    result = scanner.find_largest_files(str(tree), n=3)
+    assert [os.path.basename(f["path"]) for f in result["files"]] == ["huge.bin", "b.bin", "a.bin"]
+    assert [f["size"] for f in result["files"]] == [5000, 2000, 1000]
+    assert result["files"][0]["modified"] > 0
+    assert result["errors"] == 0
+
+def test_find_largest_files_min_size(tree):
+    result = scanner.find_largest_files(str(tree), n=10, min_size=1000)
+    assert sorted(f["size"] for f in result["files"]) == [1000, 2000]
+
+def test_find_largest_files_skips_symlinks_by_default(tree):
+    try:
+        (tree / "link.bin").symlink_to(tree / "big" / "nested" / "b.bin")
+    except OSError:
+        pytest.skip("symlinks not permitted")
+    names = [os.path.basename(f["path"]) for f in scanner.find_largest_files(str(tree), n=10)["files"]]
+    assert "link.bin" not in names
+    followed = scanner.find_largest_files(str(tree), n=10, follow_symlinks=True)["files"]
+    assert "link.bin" in [os.path.basename(f["path"]) for f in followed]
@@ -0,0 +1,105 @@
+import pytest
+from pulse import sched
+from pulse.state import STATE
+
+PROC_STAT = """cpu  10132153 290696 3084719 46828483 16683 0 25195 0 0 0
+cpu0 100 10 50 800 40 0 0 0 0 0
+intr 199292 0 9 0
+ctxt 1990473
+btime 1062191376
+processes 2915
+procs_running 3
+procs_blocked 1
+"""
+
+# Version 15 (2.6.23+): nine cpu fields, then per-domain lines
+SCHEDSTAT_V15 = """version 15
+timestamp 4297299139
+cpu0 0 0 0 0 0 0 1011437325 3462217 12345
+domain0 00000003 1 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+cpu1 0 0 0 0 0 0 900000000 5000000 20000
+"""
+
+# debugfs sched/debug (6.x): cfs_rq sections carry their own queue counts
+SCHED_DEBUG = """Sched Debug Version: v0.11, 6.18.44 #1
+ktime                                   : 6977440.908195
+
+sysctl_sched
+  .sysctl_sched_base_slice                 : 0.700000
+
+cpu#0, 2100.000 MHz
+  .nr_running                    : 2
+  .nr_switches                   : 2575531
+
+cfs_rq[0]:/autogroup-1019
+  .nr_running                    : 5
+  .h_nr_queued                   : 2
+
+cpu#1, 2100.000 MHz
+  .nr_running                    : 0
+  .nr_switches                   : 2410022
+
+rt_rq[1]:
+  .rt_nr_running                 : 0
+"""
+
+def test_parse_stat_sched():
+    assert sched.parse_stat_sched(PROC_STAT) == {
+        "context_switches": 1990473, "forks": 2915, "procs_running": 3, "procs_blocked": 1,
+    }
+
+def test_parse_schedstat_reads_the_last_three_cpu_fields():
+    assert sched.parse_schedstat(SCHEDSTAT_V15) == [
+        {"cpu": 0, "running_ns": 1011437325, "waiting_ns": 3462217, "timeslices": 12345},
+        {"cpu": 1, "running_ns": 900000000, "waiting_ns": 5000000, "timeslices": 20000},
+    ]
+
+def test_parse_sched_debug_reads_each_cpus_own_queue():
+    assert sched.parse_sched_debug(SCHED_DEBUG) == {0: 2, 1: 0}
+    assert sched.parse_sched_debug("") == {}
+
+def test_parse_task_schedstat():
+    assert sched.parse_task_schedstat("2502542 117283 29\n") == {
+        "running_ns": 2502542, "waiting_ns": 117283, "timeslices": 29,
+    }
+
+@pytest.fixture
+def fake_proc(tmp_path, monkeypatch):
+    paths = {"PROC_STAT": PROC_STAT, "PROC_SCHEDSTAT": SCHEDSTAT_V15, "PROC_SELF_SCHEDSTAT": "1000 200 5\n"}
+    for name, text in paths.items():
+        (tmp_path / name).write_text(text)
+        monkeypatch.setattr(sched, name, str(tmp_path / name))
+    (tmp_path / "sched_debug").write_text(SCHED_DEBUG)
+    monkeypatch.setattr(sched, "SCHED_DEBUG_PATHS", (str(tmp_path / "missing"), str(tmp_path / "sched_debug")))
+    monkeypatch.setattr(STATE, "sched_counters", None)
+    return tmp_path
+
+@pytest.mark.skipif(not sched.LINUX, reason="scheduler statistics are Linux-only")
+def test_scheduler_stats_deltas(fake_proc):
+    first = sched.get_scheduler_stats(include_self=True)
+    assert first["procs_running"] == 3
+    assert first["deltas"] == {"context_switches": 0, "forks": 0}
+    assert first["cpus"][1]["deltas"] == {"running_ns": 0, "waiting_ns": 0, "timeslices": 0}
+    assert [cpu["nr_running"] for cpu in first["cpus"]] == [2, 0]
+    assert first["self"]["running_ns"] == 1000
+
+    (fake_proc / "PROC_STAT").write_text(PROC_STAT.replace("ctxt 1990473", "ctxt 1990573"))
+    (fake_proc / "PROC_SCHEDSTAT").write_text(SCHEDSTAT_V15.replace("5000000 20000", "5500000 20010"))
+    (fake_proc / "PROC_SELF_SCHEDSTAT").write_text("1600 260 7\n")
+    second = sched.get_scheduler_stats(include_self=True)
+    assert second["deltas"] == {"context_switches": 100, "forks": 0}
+    assert second["cpus"][1]["deltas"] == {"running_ns": 0, "waiting_ns": 500000, "timeslices": 10}
+    assert second["self"]["deltas"] == {"running_ns": 600, "waiting_ns": 60, "timeslices": 2}
+    assert "self" not in sched.get_scheduler_stats()
+
+@pytest.mark.skipif(not sched.LINUX, reason="scheduler statistics are Linux-only")
+def test_run_queue_length_unknown_without_sched_debug(fake_proc):
+    (fake_proc / "sched_debug").unlink()
+    assert [cpu["nr_running"] for cpu in sched.get_scheduler_stats()["cpus"]] == [None, None]
+
+@pytest.mark.skipif(not sched.LINUX, reason="scheduler statistics are Linux-only")
+def test_scheduler_stats_without_schedstats(fake_proc):
+    (fake_proc / "PROC_SCHEDSTAT").unlink()
+    stats = sched.get_scheduler_stats()
+    assert "cpus" not in stats
+    assert stats["procs_blocked"] == 1
@@ -0,0 +1,115 @@
+import pytest
+from pulse import sensors
+from pulse.state import STATE
+
+@pytest.fixture
+def hwmon(tmp_path, monkeypatch):
+    """Two chips: a labelled laptop EC fan and an unlabelled Super I/O pair."""
+    ec = tmp_path / "hwmon3"
+    ec.mkdir()
+    (ec / "name").write_text("thinkpad\n")
+    (ec / "fan1_input").write_text("2650\n")
+    (ec / "fan1_label").write_text("CPU Fan\n")
+    (ec / "temp1_input").write_text("51000\n")
+    superio = tmp_path / "hwmon10"
+    superio.mkdir()
+    (superio / "name").write_text("nct6775\n")
+    (superio / "fan2_input").write_text("900\n")
+    (superio / "fan2_min").write_text("300\n")
+    (superio / "fan2_max").write_text("2200\n")
+    (superio / "fan1_input").write_text("1200\n")
+    (superio / "fan3_min").write_text("0\n")  # header without a fan connected
+    monkeypatch.setattr(sensors, "SYS_CLASS_HWMON", str(tmp_path))
+    return tmp_path
+
+def test_hwmon_chips_numeric_order(hwmon):
+    assert [c.rsplit("/", 1)[-1] for c in sensors.hwmon_chips()] == ["hwmon3", "hwmon10"]
+
+def test_read_channels(hwmon):
+    channels = sensors.read_channels(str(hwmon / "hwmon10"), "fan")
+    assert [c["index"] for c in channels] == [1, 2]
+    assert channels[1] == {"index": 2, "label": None, "input": 900, "min": 300, "max": 2200}
+    assert sensors.read_channels(str(hwmon / "hwmon3"), "temp")[0]["input"] == 51000
+
+@pytest.mark.skipif(not sensors.LINUX, reason="hwmon fan reader is Linux-only")
+def test_get_fans(hwmon):
+    assert sensors.get_fans() == [
+        {"label": "CPU Fan", "rpm": 2650, "min_rpm": None, "max_rpm": None},
+        {"label": "nct6775 fan1", "rpm": 1200, "min_rpm": None, "max_rpm": None},
+        {"label": "nct6775 fan2", "rpm": 900, "min_rpm": 300, "max_rpm": 2200},
+    ]
+
+@pytest.mark.skipif(not sensors.LINUX, reason="hwmon fan reader is Linux-only")
+def test_no_fans_under_wsl(hwmon, monkeypatch):
+    monkeypatch.setattr(sensors.environment, "running_under_wsl", lambda: True)
+    assert sensors.get_fans() == []
+
+@pytest.mark.skipif(not sensors.LINUX, reason="hwmon fan reader is Linux-only")
+def test_no_fans(tmp_path, monkeypatch):
+    monkeypatch.setattr(sensors, "SYS_CLASS_HWMON", str(tmp_path))
+    assert sensors.get_fans() == []
+
+@pytest.fixture
+def thermal(tmp_path, monkeypatch):
+    """A laptop's thermal class: two zones (one disabled) and a cpufreq cooling device."""
+    thermal_root = tmp_path / "thermal"
+    for name, kind, temp in (("thermal_zone0", "acpitz", "45000"),
+                             ("thermal_zone1", "x86_pkg_temp", None),
+                             ("thermal_zone10", "iwlwifi_1", "38000")):
+        (thermal_root / name).mkdir(parents=True)
+        (thermal_root / name / "type").write_text(kind + "\n")
+        if temp is not None:
+            (thermal_root / name / "temp").write_text(temp + "\n")
+    for name, kind, cur in (("cooling_device0", "Processor", "0"), ("cooling_device1", "Fan", "1")):
+        (thermal_root / name).mkdir()
+        (thermal_root / name / "type").write_text(kind + "\n")
+        (thermal_root / name / "cur_state").write_text(cur + "\n")
+        (thermal_root / name / "max_state").write_text("10\n")
+    cpu_root = tmp_path / "cpu"
+    for cpu in range(2):
+        throttle = cpu_root / f"cpu{cpu}" / "thermal_throttle"
+        throttle.mkdir(parents=True)
+        (throttle / "core_throttle_count").write_text(f"{3 + cpu}\n")
+        (throttle / "package_throttle_count").write_text("7\n")
+        (cpu_root / f"cpu{cpu}" / "topology").mkdir()
+        (cpu_root / f"cpu{cpu}" / "topology" / "physical_package_id").write_text("0\n")
+    (cpu_root / "cpufreq").mkdir()
+    monkeypatch.setattr(sensors, "SYS_CLASS_THERMAL", str(thermal_root))
+    monkeypatch.setattr(sensors, "SYS_DEVICES_CPU", str(cpu_root))
+    monkeypatch.setattr(STATE, "thermal_throttle", None)
+    return tmp_path
+
+def test_read_thermal_zones(thermal):
+    assert sensors.read_thermal_zones() == [
+        {"name": "thermal_zone0", "type": "acpitz", "temperature_c": 45.0},
+        {"name": "thermal_zone1", "type": "x86_pkg_temp", "temperature_c": None},
+        {"name": "thermal_zone10", "type": "iwlwifi_1", "temperature_c": 38.0},
+    ]
+
+def test_throttle_counts_package_counted_once(thermal):
+    assert sensors.read_throttle_counts() == {"core_throttle_count": 7, "package_throttle_count": 7}
+
+@pytest.mark.skipif(not sensors.LINUX, reason="thermal sysfs reader is Linux-only")
+def test_thermal_status_detects_new_throttle_events(thermal):
+    first = sensors.get_thermal_status()
+    assert first["is_throttling"] is False
+    assert first["cooling_devices"][1] == {"name": "cooling_device1", "type": "Fan", "cur_state": 1, "max_state": 10}
+
+    (thermal / "cpu" / "cpu1" / "thermal_throttle" / "core_throttle_count").write_text("9\n")
+    second = sensors.get_thermal_status()
+    assert second["is_throttling"] is True
+    assert second["core_throttle_count"] == 12
+    assert sensors.get_thermal_status()["is_throttling"] is False
+
+@pytest.mark.skipif(not sensors.LINUX, reason="thermal sysfs reader is Linux-only")
+def test_thermal_status_cpu_cooling_engaged(thermal):
+    (thermal / "thermal" / "cooling_device0" / "cur_state").write_text("4\n")
+    assert sensors.get_thermal_status()["is_throttling"] is True
+
+@pytest.mark.skipif(not sensors.LINUX, reason="thermal sysfs reader is Linux-only")
+def test_thermal_status_empty_under_wsl(thermal, monkeypatch):
+    monkeypatch.setattr(sensors.environment, "running_under_wsl", lambda: True)
+    assert sensors.get_thermal_status() == {
+        "zones": [], "cooling_devices": [], "core_throttle_count": None,
+        "package_throttle_count": None, "is_throttling": False,
+    }
@@ -0,0 +1,151 @@
+import pytest
+from pulse import capabilities, cgroups, core, services
+from pulse.state import STATE
+
+SHOW_OUTPUT = """Id=nginx.service
+LoadState=loaded
+ActiveState=active
+SubState=running
+MainPID=812
+ControlGroup=/system.slice/nginx.service
+MemoryCurrent=9000000
+CPUUsageNSec=4000000000
+Description=A high performance web server
+
+Id=backup.service
+LoadState=loaded
+ActiveState=inactive
+SubState=dead
+MainPID=0
+ControlGroup=
+MemoryCurrent=18446744073709551615
+CPUUsageNSec=18446744073709551615
+Description=Nightly backup
+"""
+
+def test_parse_systemctl_show():
+    units = services.parse_systemctl_show(SHOW_OUTPUT)
+    assert [u["Id"] for u in units] == ["nginx.service", "backup.service"]
+    assert units[0]["MainPID"] == "812"
+
+def test_service_entry_prefers_cgroup_files(tmp_path, monkeypatch):
+    group = tmp_path / "system.slice" / "nginx.service"
+    group.mkdir(parents=True)
+    (group / "cpu.stat").write_text("usage_usec 4200000\n")
+    (group / "memory.current").write_text("12582912\n")
+    (group / "memory.max").write_text("268435456\n")
+    monkeypatch.setattr(cgroups, "SYS_FS_CGROUP", str(tmp_path))
+    monkeypatch.setattr(STATE, "cgroup_cpu", {})
+    entry = services.service_entry(services.parse_systemctl_show(SHOW_OUTPUT)[0])
+    assert entry == {
+        "name": "nginx.service",
+        "description": "A high performance web server",
+        "load_state": "loaded",
+        "active_state": "active",
+        "sub_state": "running",
+        "main_pid": 812,
+        "cgroup": "/system.slice/nginx.service",
+        "memory_current": 12582912,
+        "memory_max": 268435456,
+        "cpu_usage_usec": 4200000,
+        "cpu_percent": None,
+    }
+
+def test_service_entry_falls_back_to_systemd_counters(tmp_path, monkeypatch):
+    monkeypatch.setattr(cgroups, "SYS_FS_CGROUP", str(tmp_path))
+    monkeypatch.setattr(STATE, "cgroup_cpu", {})
+    nginx, backup = (services.service_entry(p) for p in services.parse_systemctl_show(SHOW_OUTPUT))
+    assert nginx["memory_current"] == 9000000
+    assert nginx["cpu_usage_usec"] == 4000000
+    assert backup["main_pid"] is None
+    assert backup["cgroup"] is None
+    assert backup["memory_current"] is None
+    assert backup["cpu_usage_usec"] is None
+
+def test_non_systemd_host_raises(tmp_path, monkeypatch):
+    monkeypatch.setattr(services, "RUN_SYSTEMD_SYSTEM", str(tmp_path / "missing"))
+    with pytest.raises(NotImplementedError):
+        services.list_services()
+    with pytest.raises(NotImplementedError):
+        services.get_service("nginx")
+
+@pytest.mark.skipif(not services.LINUX, reason="systemd units")
+def test_unknown_unit_is_service_not_found(monkeypatch):
+    monkeypatch.setitem(capabilities.FEATURES, "services", ("list_services", lambda: True))
+    monkeypatch.setattr(services, "_show", lambda names: [{"name": names[0], "load_state": "not-found"}])
+    with pytest.raises(core.ServiceNotFound, match="nosuch.service"):
+        services.get_service("nosuch")
+    # Code written against the builtin still catches it
+    with pytest.raises(KeyError):
+        services.get_service("nosuch")
+
+def test_windows_service_entry_joins_process():
+    processes = {1204: {"pid": 1204, "name": "svchost.exe", "cpu_percent": 1.5, "memory_info": 20_000_000}}
+    assert services.windows_service_entry("Dnscache", "DNS Client", 4, 2, 1204, processes) == {
+        "name": "Dnscache",
+        "display_name": "DNS Client",
+        "state": "running",
+        "start_type": "automatic",
+        "pid": 1204,
+        "cpu_percent": 1.5,
+        "memory_info": 20_000_000,
+    }
+
+def test_windows_service_entry_stopped_and_unqueryable():
+    entry = services.windows_service_entry("WSearch", "Windows Search", 1, None, 0, {})
+    assert entry["state"] == "stopped"
+    assert entry["start_type"] is None
+    assert entry["pid"] is None
+    assert entry["cpu_percent"] is None
+
+@pytest.mark.skipif(services.WINDOWS, reason="checks the non-Windows stub")
+def test_list_windows_services_elsewhere():
+    with pytest.raises(NotImplementedError):
+        services.list_windows_services()
+
+BOOT_SHOW_OUTPUT = """FirmwareTimestampMonotonic=8412339
+LoaderTimestampMonotonic=3120544
+InitRDTimestampMonotonic=1904032
+UserspaceTimestampMonotonic=4511870
+FinishTimestampMonotonic=12037615
+"""
+
+def test_boot_phases_from_captured_properties():
+    phases = services.boot_phases(services.parse_systemctl_show(BOOT_SHOW_OUTPUT)[0])
+    assert phases == pytest.approx({
+        "firmware_secs": 5.291795,
+        "loader_secs": 3.120544,
+        "kernel_secs": 1.904032,
+        "initrd_secs": 2.607838,
+        "userspace_secs": 7.525745,
+        "total_secs": 20.449954,
+        "finished": True,
+    })
+
+def test_boot_phases_without_initrd_or_efi_while_booting():
+    # A VM booted straight into the root filesystem, queried before boot finished
+    props = {"FirmwareTimestampMonotonic": "0", "LoaderTimestampMonotonic": "0",
+             "InitRDTimestampMonotonic": "0", "UserspaceTimestampMonotonic": "950000",
+             "FinishTimestampMonotonic": "0"}
+    assert services.boot_phases(props) == {
+        "firmware_secs": None,
+        "loader_secs": None,
+        "kernel_secs": 0.95,
+        "initrd_secs": None,
+        "userspace_secs": None,
+        "total_secs": None,
+        "finished": False,
+    }
+
+def test_parse_activating_units():
+    text = ('[{"unit":"postgresql.service","load":"loaded","active":"activating","sub":"start"},'
+            '{"unit":"apt-daily.service","load":"loaded","active":"activating","sub":"start"}]')
+    assert services.parse_activating_units(text) == ["apt-daily.service", "postgresql.service"]
+    assert services.parse_activating_units("") == []
+
+def test_boot_metrics_without_systemd(tmp_path, monkeypatch):
+    monkeypatch.setattr(services, "RUN_SYSTEMD_SYSTEM", str(tmp_path / "missing"))
+    metrics = services.get_boot_metrics()
+    assert metrics["systemd"] is None
+    assert metrics["activating_units"] is None
+    assert set(metrics) == {"boot_time", "uptime_seconds", "systemd", "activating_units"}
@@ -0,0 +1,76 @@
+import pytest
+from pulse import core, snapshot_diff
+from pulse.snapshot_diff import Snapshot
+
+MIB = 1024 ** 2
+
+BEFORE = Snapshot(
+    100.0,
+    (10.0, 30.0),
+    (8000 * MIB, 3000 * MIB, 5000 * MIB),
+    (("eth0", 1000, 500), ("lo", 50, 50), ("wg0", 7, 7)),
+    (("/", 100_000 * MIB, 40_000 * MIB), ("/home", 500 * MIB, 200 * MIB), ("/mnt/nfs", None, None)),
+    ((1, "systemd", "sleeping"), (200, "make", "running"), (300, "cc1", "running")),
+)
+AFTER = Snapshot(
+    105.0,
+    (50.0, 70.0),
+    (8000 * MIB, 3500 * MIB, 4500 * MIB),
+    (("eth0", 6000, 900), ("lo", 50, 50), ("docker0", 0, 0)),
+    (("/", 100_000 * MIB, 39_000 * MIB), ("/home", 500 * MIB, 200 * MIB + 1), ("/mnt/nfs", None, None),
+     ("/media/usb", 16_000 * MIB, 16_000 * MIB)),
+    ((1, "systemd", "sleeping"), (200, "make", "disk-sleep"), (400, "ld", "running")),
+)
+
+
+def test_diff_every_category():
+    diff = snapshot_diff.diff_snapshots(BEFORE, AFTER)
+    assert diff["interval"] == 5.0
+    assert diff["cpu_average_delta"] == pytest.approx(40.0)
+    assert diff["memory"] == {"total_delta": 0, "used_delta": 500 * MIB, "available_delta": -500 * MIB}
+    assert diff["interfaces"] == {
+        "changed": {"eth0": {"bytes_recv_delta": 5000, "bytes_sent_delta": 400},
+                    "lo": {"bytes_recv_delta": 0, "bytes_sent_delta": 0}},
+        "added": ["docker0"],
+        "removed": ["wg0"],
+    }
+    # /home moved by one byte, under the threshold; the stale NFS mount has no figures
+    assert diff["disks"] == {
+        "changed": [{"mountpoint": "/", "available_delta": -1000 * MIB, "total_delta": 0}],
+        "added": ["/media/usb"],
+        "removed": [],
+    }
+    assert diff["processes"] == {
+        "started": [{"pid": 400, "name": "ld", "status": "running"}],
+        "exited": [{"pid": 300, "name": "cc1", "status": "running"}],
+        "changed_state": [{"pid": 200, "name": "make", "from": "running", "to": "disk-sleep"}],
+    }
+
+
+def test_reverse_diff_flips_signs():
+    forward = snapshot_diff.diff_snapshots(BEFORE, AFTER)
+    backward = snapshot_diff.diff_snapshots(AFTER, BEFORE)
+    assert backward["interval"] == -forward["interval"]
+    assert backward["cpu_average_delta"] == pytest.approx(-40.0)
+    assert backward["memory"]["used_delta"] == -forward["memory"]["used_delta"]
+    assert backward["interfaces"]["changed"]["eth0"] == {"bytes_recv_delta": -5000, "bytes_sent_delta": -400}
+    assert backward["disks"]["changed"][0]["available_delta"] == 1000 * MIB
+    assert backward["processes"]["started"] == forward["processes"]["exited"]
+    assert backward["processes"]["changed_state"][0]["to"] == "running"
+
+
+def test_disk_threshold():
+    diff = snapshot_diff.diff_snapshots(BEFORE, AFTER, disk_threshold_bytes=0)
+    assert [d["mountpoint"] for d in diff["disks"]["changed"]] == ["/", "/home"]
+
+
+def test_take_snapshot_is_compact():
+    snap = core.take_snapshot()
+    assert not hasattr(snap, "__dict__")
+    for field in set(Snapshot.__slots__) - {"timestamp", "monotonic"}:
+        value = getattr(snap, field)
+        assert isinstance(value, tuple)
+        assert not any(isinstance(item, dict) for item in value)
+    assert snap.processes and snap.memory[0] > 0
+    diff = core.diff_snapshots(snap, core.Monitor().take_snapshot())
+    assert diff["interval"] >= 0
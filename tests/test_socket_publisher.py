@@ -0,0 +1,87 @@
+import json
+import os
+import socket
+import time
+import pytest
+from pulse import core, sampler, socket_publisher
+
+pytestmark = pytest.mark.skipif(not hasattr(socket, "AF_UNIX"), reason="needs Unix domain sockets")
+
+
+def _wait_for(condition, timeout=5.0):
+    deadline = time.monotonic() + timeout
+    while not condition():
+        assert time.monotonic() < deadline, "timed out"
+        time.sleep(0.01)
+
+
+def _connect(path):
+    client = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+    client.connect(path)
+    client.settimeout(5)
+    return client
+
+
+@pytest.fixture
+def publishing(tmp_path):
+    path = str(tmp_path / "pulse.sock")
+    core.start_socket_publisher(path, interval_ms=10, sections=["cpu", "memory"])
+    yield path
+    core.stop_socket_publisher()
+
+
+def test_clients_read_snapshot_lines(publishing):
+    clients = [_connect(publishing), _connect(publishing)]
+    _wait_for(lambda: socket_publisher.socket_publisher_status()["clients"] == 2)
+    expected = json.loads(core.snapshot(include=["cpu", "memory"], as_json=True))
+    for client in clients:
+        reader = client.makefile("r")
+        frames = [json.loads(reader.readline()) for _ in range(2)]
+        for frame in frames:
+            assert set(frame) == set(expected)
+            assert isinstance(frame["cpu"], list) and set(frame["memory"]) == set(expected["memory"])
+        assert frames[1]["timestamp"] > frames[0]["timestamp"]
+        reader.close()
+        client.close()
+
+
+def test_stop_removes_the_socket_and_the_sampler(publishing):
+    assert sampler.sampler_status()["running"] is True
+    assert os.path.exists(publishing)
+    core.stop_socket_publisher()
+    assert not os.path.exists(publishing)
+    assert sampler.sampler_status()["running"] is False
+    assert socket_publisher.socket_publisher_status()["path"] is None
+
+
+def test_slow_client_is_dropped(publishing):
+    slow = _connect(publishing)
+    slow.setsockopt(socket.SOL_SOCKET, socket.SO_RCVBUF, 1024)
+    _wait_for(lambda: socket_publisher.socket_publisher_status()["clients"] == 1)
+    # Never reading: once the socket buffers fill the publisher gives up on it
+    _wait_for(lambda: socket_publisher.socket_publisher_status()["dropped"] == 1, timeout=30)
+    assert socket_publisher.socket_publisher_status()["clients"] == 0
+    slow.close()
+
+
+def test_second_publisher_and_stale_socket(tmp_path, publishing):
+    with pytest.raises(RuntimeError):
+        core.start_socket_publisher(str(tmp_path / "other.sock"))
+    core.stop_socket_publisher()
+    # A socket file nobody listens on, as a crashed publisher leaves
+    stale = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+    stale.bind(publishing)
+    stale.close()
+    core.start_socket_publisher(publishing, interval_ms=10, sections=["memory"])
+    client = _connect(publishing)
+    assert set(json.loads(client.makefile("r").readline())) == {"timestamp", "memory"}
+    client.close()
+
+
+def test_refuses_a_regular_file(tmp_path):
+    path = tmp_path / "notes.txt"
+    path.write_text("keep me")
+    with pytest.raises(OSError, match="notes.txt"):
+        core.start_socket_publisher(str(path))
+    assert path.read_text() == "keep me"
+    assert sampler.sampler_status()["running"] is False
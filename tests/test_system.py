@@ -0,0 +1,87 @@
+import time
+import pytest
+from pulse import core, system
+
+OS_RELEASE = """# Ubuntu ships both quoted and bare values
+NAME="Ubuntu"
+VERSION_ID="24.04"
+ID=ubuntu
+PRETTY_NAME='Ubuntu 24.04 LTS'
+VERSION_CODENAME=noble
+"""
+
+def test_parse_os_release():
+    release = system.parse_os_release(OS_RELEASE)
+    assert release["NAME"] == "Ubuntu"
+    assert release["VERSION_ID"] == "24.04"
+    assert release["ID"] == "ubuntu"
+    assert release["PRETTY_NAME"] == "Ubuntu 24.04 LTS"
+
+def test_parse_btime():
+    stat = "cpu  10 0 5 100 0 0 0 0 0 0\nintr 12345\nbtime 1718000000\nprocesses 900\n"
+    assert system.parse_btime(stat) == 1718000000
+    assert system.parse_btime("cpu 1 2 3\n") is None
+
+def test_get_system_info():
+    info = core.get_system_info()
+    assert set(info) == {
+        "hostname", "os_name", "os_version", "kernel_version",
+        "architecture", "boot_time", "uptime_seconds",
+    }
+    assert info["uptime_seconds"] > 0
+    assert info["boot_time"] == pytest.approx(time.time() - info["uptime_seconds"], abs=5)
+
+@pytest.mark.skipif(not system.LINUX, reason="/proc/uptime reader is Linux-only")
+def test_get_uptime_missing_proc(tmp_path, monkeypatch):
+    monkeypatch.setattr(system, "PROC_UPTIME", str(tmp_path / "uptime"))
+    assert system.get_uptime() is None
+
+def _smbios_struct(kind, formatted, *strings):
+    header = bytes([kind, 4 + len(formatted), 0, 0])
+    tail = b"".join(s.encode() + b"\0" for s in strings) + b"\0" if strings else b"\0\0"
+    return header + formatted + tail
+
+# BIOS, system, board, and a locked notebook chassis, as Windows' RSMB table returns them
+SMBIOS_TABLE = (
+    _smbios_struct(0, bytes([1, 2, 0, 0, 3]), "LENOVO", "N2HET77W (1.60 )", "02/06/2024")
+    + _smbios_struct(1, bytes([1, 2, 0, 3]), "LENOVO", "20XW0055US", "PF3ABCDE")
+    + _smbios_struct(2, bytes([1, 2]), "LENOVO", "20XW0055US")
+    + _smbios_struct(3, bytes([1, 0x80 | 10]), "LENOVO")
+    + _smbios_struct(127, b"")
+)
+
+def test_parse_smbios():
+    info = system.parse_smbios(SMBIOS_TABLE)
+    assert info["bios_vendor"] == "LENOVO"
+    assert info["bios_version"] == "N2HET77W (1.60 )"
+    assert info["bios_date"] == "02/06/2024"
+    assert info["product_name"] == "20XW0055US"
+    assert info["serial"] == "PF3ABCDE"
+    assert info["board_name"] == "20XW0055US"
+    assert info["chassis_type"] == 10
+
+@pytest.mark.parametrize("chassis, vendor, product, expected", [
+    (10, "LENOVO", "20XW0055US", "laptop"),
+    (3, "Dell Inc.", "OptiPlex 7090", "desktop"),
+    (23, "Supermicro", "SYS-1029P", "server"),
+    (1, "QEMU", "Standard PC (Q35 + ICH9, 2009)", "vm"),
+    (3, "Microsoft Corporation", "Virtual Machine", "vm"),
+    (None, None, None, "unknown"),
+])
+def test_form_factor(chassis, vendor, product, expected):
+    assert system.form_factor(chassis, vendor, product) == expected
+
+@pytest.mark.skipif(not system.LINUX, reason="DMI sysfs reader is Linux-only")
+def test_get_hardware_info_from_sysfs(tmp_path, monkeypatch):
+    for name, value in (("sys_vendor", "Framework\n"), ("product_name", "Laptop 13\n"),
+                        ("board_vendor", "Framework\n"), ("bios_version", "03.05\n"),
+                        ("board_name", "Default string\n"), ("chassis_type", "10\n")):
+        (tmp_path / name).write_text(value)
+    # product_serial is root-only and missing here
+    monkeypatch.setattr(system, "SYS_CLASS_DMI_ID", str(tmp_path))
+    info = system.get_hardware_info()
+    assert info["manufacturer"] == "Framework"
+    assert info["bios_version"] == "03.05"
+    assert info["board_name"] is None
+    assert info["serial"] is None
+    assert info["form_factor"] == "laptop"
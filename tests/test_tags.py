@@ -0,0 +1,159 @@
+import os
+import subprocess
+import sys
+import time
+import pytest
+from pulse import backend, core, tags
+
+PROCESSES = [
+    {"pid": 100, "name": "postgres", "cpu_percent": 2.0, "memory_info": 300},
+    {"pid": 101, "name": "postgres", "cpu_percent": 1.0, "memory_info": 200},
+    {"pid": 200, "name": "nginx", "cpu_percent": 0.5, "memory_info": 50},
+    {"pid": 300, "name": "python3", "cpu_percent": 4.0, "memory_info": 80},
+    {"pid": 400, "name": "bash", "cpu_percent": 0.0, "memory_info": 10},
+]
+DETAILS = {
+    100: {"cmdline": "postgres: checkpointer", "exe": "/usr/lib/postgresql/16/bin/postgres", "username": "postgres"},
+    101: {"cmdline": "postgres: walwriter", "exe": "/usr/lib/postgresql/16/bin/postgres", "username": "postgres"},
+    200: {"cmdline": "nginx: worker process", "exe": "/usr/sbin/nginx", "username": "www-data"},
+    300: {"cmdline": "python3 -m http.server 8000", "exe": "/usr/bin/python3.12", "username": "alice"},
+    400: {"cmdline": "-bash", "exe": None, "username": "alice"},
+}
+RULES = [
+    {"tag": "database", "match": {"name_regex": "^postgres$"}},
+    {"tag": "webserver", "match": {"name_regex": "nginx"}},
+    {"tag": "webserver", "match": {"cmdline_regex": r"http\.server"}},
+    {"tag": "system", "match": {"exe_prefix": "/usr/sbin/"}},
+    {"tag": "alice", "match": {"username": "alice", "exe_prefix": "/usr/bin/"}},
+]
+
+
+@pytest.fixture
+def tagged(monkeypatch):
+    reads = []
+
+    def read_details(pid, fields):
+        reads.append(pid)
+        return {field: DETAILS[pid][field] for field in fields}
+
+    monkeypatch.setattr(tags, "read_details", read_details)
+    core.set_backend("mock")
+    core.mock_set_data("processes", PROCESSES)
+    core.set_process_tags(RULES)
+    yield reads
+    core.set_process_tags(None)
+    core.mock_clear()
+    core.set_backend("live")
+
+
+def test_compile_errors_name_the_rule():
+    with pytest.raises(ValueError, match=r"rule 1: invalid cmdline_regex '\['"):
+        core.set_process_tags([RULES[0], {"tag": "x", "match": {"cmdline_regex": "["}}])
+    with pytest.raises(ValueError, match="rule 0: unknown match key 'path'"):
+        core.set_process_tags([{"tag": "x", "match": {"path": "/usr"}}])
+    with pytest.raises(ValueError, match="rule 2: tag must be a non-empty string"):
+        core.set_process_tags(RULES[:2] + [{"tag": "", "match": {"username": "root"}}])
+    with pytest.raises(ValueError, match="rule 0: match must be a non-empty dict"):
+        core.set_process_tags([{"tag": "x", "match": {}}])
+    with pytest.raises(ValueError, match='rule 0: expected a dict with exactly "tag" and "match"'):
+        core.set_process_tags([{"tag": "x"}])
+    # A rejected rule set leaves no rules behind
+    assert core.get_process_tags() == []
+
+
+def test_rule_conditions_all_hold():
+    rule = tags.compile_rule(0, RULES[4])
+    assert tags.rule_matches(rule, "python3", DETAILS[300])
+    assert not tags.rule_matches(rule, "bash", DETAILS[400])
+    assert not tags.rule_matches(tags.compile_rule(0, RULES[2]), "python3", {"cmdline": None})
+
+
+def test_process_list_carries_tags(tagged):
+    by_pid = {p["pid"]: p["tags"] for p in core.get_process_list()}
+    assert by_pid == {
+        100: ["database"], 101: ["database"], 200: ["webserver", "system"],
+        300: ["webserver", "alice"], 400: [],
+    }
+    assert core.get_process_tags() == RULES
+
+
+def test_no_tags_key_without_rules():
+    assert all("tags" not in p for p in core.get_process_list(limit=5))
+    assert core.get_tag_summary() == {}
+
+
+def test_details_read_once_per_process(tagged):
+    core.get_process_list()
+    assert sorted(tagged) == [100, 101, 200, 300, 400]
+    core.get_process_list()
+    core.get_tag_summary()
+    assert len(tagged) == 5
+
+    # A new process is read; an exited one is forgotten, so a reused pid is read again
+    core.mock_set_data("processes", PROCESSES[1:] + [{"pid": 500, "name": "nginx", "cpu_percent": 0.0,
+                                                      "memory_info": 40}])
+    DETAILS[500] = DETAILS[200]
+    try:
+        core.get_process_list()
+        core.mock_set_data("processes", PROCESSES)
+        core.get_process_list()
+    finally:
+        del DETAILS[500]
+    assert tagged[5:] == [500, 100]
+
+
+def test_reused_pid_with_the_same_name_is_read_again(tagged, monkeypatch):
+    starts = {pid: 1000.0 for pid in DETAILS}
+    monkeypatch.setattr(backend.MOCK, "process_start_times", lambda: dict(starts))
+    core.get_process_list(limit=1)
+    # pid 100 exited and another postgres took its pid between two polls
+    starts[100] = 2000.0
+    core.get_process_list(limit=1)
+    assert tagged == [100, 100]
+
+
+def test_name_only_rules_read_no_details(tagged):
+    core.set_process_tags([{"tag": "shell", "match": {"name_regex": "sh$"}}])
+    assert [p["pid"] for p in core.get_process_list() if p["tags"] == ["shell"]] == [400]
+    assert tagged == []
+
+
+def test_tag_summary(tagged):
+    assert core.get_tag_summary() == {
+        "database": {"count": 2, "cpu_percent": 3.0, "memory_info": 500},
+        "webserver": {"count": 2, "cpu_percent": 4.5, "memory_info": 130},
+        "system": {"count": 1, "cpu_percent": 0.5, "memory_info": 50},
+        "alice": {"count": 1, "cpu_percent": 4.0, "memory_info": 80},
+    }
+    core.mock_set_data("processes", PROCESSES[2:])
+    assert core.get_tag_summary()["database"] == {"count": 0, "cpu_percent": 0.0, "memory_info": 0}
+
+
+@pytest.mark.skipif(not tags.LINUX, reason="reads /proc")
+def test_tag_summary_sums_live_cpu():
+    child = subprocess.Popen([sys.executable, "-c", "while True: pass", "pulse-busy-tag"])
+    core.set_process_tags([{"tag": "busy", "match": {"cmdline_regex": "pulse-busy-tag"}}])
+    try:
+        deadline = time.monotonic() + 5
+        # Until it execs, the child still has this process's command line
+        while b"pulse-busy-tag" not in open(f"/proc/{child.pid}/cmdline", "rb").read():
+            time.sleep(0.01)
+        summary = core.get_tag_summary()
+        while time.monotonic() < deadline and summary["busy"]["cpu_percent"] <= 10:
+            time.sleep(0.3)
+            summary = core.get_tag_summary()
+        assert summary["busy"]["count"] == 1 and summary["busy"]["cpu_percent"] > 10
+    finally:
+        core.set_process_tags(None)
+        child.kill()
+        child.wait()
+
+
+@pytest.mark.skipif(not tags.LINUX, reason="reads /proc")
+def test_read_details_of_this_process():
+    import pwd
+
+    details = tags.read_details(os.getpid(), {"cmdline", "exe", "username"})
+    assert details["exe"] == os.path.realpath(os.readlink("/proc/self/exe"))
+    assert details["username"] == pwd.getpwuid(os.getuid()).pw_name
+    assert "python" in details["cmdline"] or "pytest" in details["cmdline"]
@@ -0,0 +1,91 @@
+import subprocess
+import sys
+import time
+import pytest
+from pulse import core, targets
+from pulse.errors import ProcessNotFound
+
+# A child whose command line is unique to this test run
+MARKER = f"pulse-target-{time.time_ns()}"
+
+
+@pytest.fixture
+def child():
+    process = subprocess.Popen([sys.executable, "-c", "import time; time.sleep(60)", MARKER])
+    # Wait until the exec is visible, so name and cmdline are the child's own
+    deadline = time.monotonic() + 5
+    while time.monotonic() < deadline:
+        described = targets.describe_process(process.pid)
+        if described and MARKER in described["cmdline"]:
+            break
+        time.sleep(0.01)
+    yield process
+    process.kill()
+    process.wait()
+
+
+def test_parse_spec_and_pid():
+    assert targets.parse_spec("cmdline:~a:b") == ("cmdline", "~a:b")
+    with pytest.raises(ValueError, match="Invalid spec 'nginx'"):
+        targets.parse_spec("nginx")
+    with pytest.raises(ValueError, match="name must not be empty"):
+        targets.parse_spec("name: ")
+    assert targets.parse_pid("1234\n") == 1234
+    assert targets.parse_pid(" 42 \t\n") == 42
+    assert targets.parse_pid("12 34") is None
+    assert targets.parse_pid("0") is None
+    assert targets.parse_pid("") is None
+
+
+def test_name_matches_truncated_comm():
+    assert targets.name_matches("nginx", "nginx")
+    assert targets.name_matches("gnome-shell-cal", "gnome-shell-calendar-server")
+    assert not targets.name_matches("nginx", "nginx-debug")
+
+
+def test_pid_spec(child):
+    [found] = core.resolve_target(f"pid:{child.pid}")
+    assert found["pid"] == child.pid
+    assert MARKER in found["cmdline"]
+    assert abs(found["start_time"] - time.time()) < 60
+
+
+def test_pidfile_with_trailing_whitespace(child, tmp_path):
+    pidfile = tmp_path / "service.pid"
+    pidfile.write_text(f"{child.pid}\n\n")
+    [found] = core.resolve_target(f"pidfile:{pidfile}")
+    assert found["pid"] == child.pid
+    name = found["name"]
+    assert core.resolve_target(f"pidfile:{pidfile}", expected_name=name)[0]["pid"] == child.pid
+    with pytest.raises(ProcessNotFound, match="stale pidfile"):
+        core.resolve_target(f"pidfile:{pidfile}", expected_name="nginx")
+
+
+def test_stale_and_bad_pidfiles(tmp_path):
+    exited = subprocess.Popen([sys.executable, "-c", "pass"])
+    exited.wait()
+    pidfile = tmp_path / "stale.pid"
+    pidfile.write_text(f"{exited.pid}\n")
+    with pytest.raises(ProcessNotFound, match=f"No such process: {exited.pid}"):
+        core.resolve_target(f"pidfile:{pidfile}")
+    with pytest.raises(ProcessNotFound, match="No pidfile"):
+        core.resolve_target(f"pidfile:{tmp_path / 'missing.pid'}")
+    pidfile.write_text("not a pid\n")
+    with pytest.raises(ValueError, match="holds no pid"):
+        core.resolve_target(f"pidfile:{pidfile}")
+
+
+def test_name_spec(child):
+    name = targets.describe_process(child.pid)["name"]
+    assert child.pid in [p["pid"] for p in core.resolve_target(f"name:{name}")]
+    with pytest.raises(ProcessNotFound, match="No process matches"):
+        core.resolve_target("name:no-such-process-name")
+
+
+def test_cmdline_spec(child):
+    assert [p["pid"] for p in core.resolve_target(f"cmdline:{MARKER}")] == [child.pid]
+    assert [p["pid"] for p in core.resolve_target(f"cmdline:~sleep\\(60\\).*{MARKER}$")] == [child.pid]
+    with pytest.raises(ProcessNotFound):
+        core.resolve_target(f"cmdline:~^{MARKER}")
+    with pytest.raises(ValueError, match="Invalid cmdline pattern"):
+        core.resolve_target("cmdline:~(")
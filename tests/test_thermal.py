@@ -0,0 +1,79 @@
+import time
+import pytest
+from pulse import capabilities, core, sampler, thermal
+
+
+def test_align_pairs_nearest_reading_within_tolerance():
+    series = [(100.0, 10.0), (101.0, 20.0), (102.0, 30.0), (103.0, 40.0)]
+    readings = [(100.1, 60.0, None), (100.9, 61.0, None), (103.6, 63.0, None)]
+    pairs = thermal.align(series, readings, tolerance_secs=0.5)
+    # 102.0 has no reading within half a second and 103.6 is too late for 103.0
+    assert [(t, v, r[1]) for t, v, r in pairs] == [(100.0, 10.0, 60.0), (101.0, 20.0, 61.0)]
+
+
+def test_align_uses_each_reading_once():
+    series = [(100.0, 1.0), (100.2, 2.0)]
+    pairs = thermal.align(series, [(100.1, 60.0, None)], tolerance_secs=0.5)
+    assert [t for t, _, _ in pairs] == [100.0]
+    assert thermal.align(series, [], 0.5) == []
+
+
+def test_pearson():
+    assert thermal.pearson([1, 2, 3, 4], [2, 4, 6, 8]) == pytest.approx(1.0)
+    assert thermal.pearson([1, 2, 3, 4], [8, 6, 4, 2]) == pytest.approx(-1.0)
+    assert thermal.pearson([1, 2, 3, 4], [5, 1, 1, 5]) == pytest.approx(0.0)
+    assert thermal.pearson([1], [1]) is None
+    assert thermal.pearson([1, 2, 3], [70, 70, 70]) is None
+
+
+def test_correlate_counts_hot_intervals_where_frequency_dropped():
+    series = [(float(t), cpu) for t, cpu in enumerate([40, 60, 90, 95, 95, 50])]
+    readings = [(0.0, 70.0, 3600.0), (1.0, 85.0, 3600.0), (2.0, 96.0, 3600.0),
+                (3.0, 98.0, 2800.0), (4.0, 97.0, 2400.0), (5.0, 90.0, 3000.0)]
+    result = thermal.correlate(series, readings, tolerance_secs=0.5, threshold_c=95.0)
+    assert len(result["samples"]) == 6
+    assert result["samples"][3] == {"timestamp": 3.0, "cpu_percent": 95, "temperature_c": 98.0,
+                                    "frequency_mhz": 2800.0}
+    assert result["hot_intervals"] == 3
+    assert result["hot_frequency_drops"] == 2
+    assert result["correlation"] > 0.8
+    assert result["threshold_c"] == 95.0
+
+
+def test_correlate_without_frequency_or_temperature():
+    series = [(0.0, 10.0), (1.0, 20.0), (2.0, 30.0)]
+    result = thermal.correlate(series, [(0.0, 96.0, None), (1.0, None, None), (2.0, 97.0, None)], 0.5, 95.0)
+    # The reading without a temperature leaves its sample out
+    assert [s["timestamp"] for s in result["samples"]] == [0.0, 2.0]
+    assert result["hot_intervals"] == 1
+    assert result["hot_frequency_drops"] is None
+    assert thermal.correlate([], [], 0.0, 95.0) == {"samples": [], "correlation": None, "threshold_c": 95.0,
+                                                    "hot_intervals": 0, "hot_frequency_drops": None}
+
+
+def test_package_channel():
+    assert thermal.package_channel("coretemp", ["Core 0", "Package id 0", "Core 1"]) == 1
+    assert thermal.package_channel("k10temp", ["Tctl", "Tccd1"]) == 0
+    assert thermal.package_channel("zenpower", [None, None]) == 0
+    assert thermal.package_channel("nvme", ["Composite"]) is None
+    assert thermal.package_channel("coretemp", []) is None
+
+
+def test_sampler_records_temperature_alongside_cpu(monkeypatch):
+    monkeypatch.setitem(capabilities.FEATURES, "temperatures", ("get_thermal_status", lambda: True))
+    temperatures = iter(range(60, 1000))
+    monkeypatch.setattr(thermal, "read_package_temperature", lambda: float(next(temperatures)))
+    monkeypatch.setattr(thermal, "read_frequency_mhz", lambda: 3000.0)
+    sampler.start_sampler(interval_ms=20, sections=["cpu"])
+    try:
+        deadline = time.monotonic() + 3
+        while time.monotonic() < deadline and len(core.get_thermal_correlation()["samples"]) < 3:
+            time.sleep(0.02)
+    finally:
+        sampler.stop_sampler()
+    result = core.get_thermal_correlation(threshold_c=1000)
+    assert len(result["samples"]) >= 3
+    held = {s["timestamp"] for s in sampler.get_samples()}
+    assert all(s["timestamp"] in held and s["frequency_mhz"] == 3000.0 for s in result["samples"])
+    temps = [s["temperature_c"] for s in result["samples"]]
+    assert temps == sorted(temps) and result["hot_intervals"] == 0
@@ -0,0 +1,118 @@
+import contextlib
+import inspect
+import os
+import threading
+import time
+from pulse import core, isolation, sampler
+from pulse.errors import PermissionDenied, ProcessNotFound, Unsupported
+
+# Arguments for the collectors that need one
+ARGS = {
+    "get_process_limits": lambda tmp: (os.getpid(),),
+    "get_process_namespaces": lambda tmp: (os.getpid(),),
+    "get_process_scheduling": lambda tmp: (os.getpid(),),
+    "get_process_threads": lambda tmp: (os.getpid(),),
+    "get_open_files": lambda tmp: (os.getpid(),),
+    "who_has_open": lambda tmp: (str(tmp),),
+    "get_limit_headroom": lambda tmp: (os.getpid(),),
+    "get_namespace_network": lambda tmp: (os.getpid(),),
+    "get_path_usage": lambda tmp: (str(tmp),),
+    "scan_directory": lambda tmp: (str(tmp),),
+    "find_largest_files": lambda tmp: (str(tmp),),
+    "query_history": lambda tmp: ("cpu_total",),
+    "resolve_target": lambda tmp: (f"pid:{os.getpid()}",),
+}
+SKIPPED = {"get_service", "get_disk_projection"}
+EXPECTED = (Unsupported, PermissionDenied, ProcessNotFound)
+
+
+def _calls(target, tmp):
+    calls = []
+    for name in core._COLLECTORS:
+        if name in SKIPPED or (target is not core and name in isolation.LOCAL_ONLY):
+            continue
+        method = getattr(target, name if target is core else
+                         (name[len("get_"):] if name.startswith("get_") else name))
+        required = [p for p in inspect.signature(inspect.unwrap(getattr(core, name))).parameters.values()
+                    if p.default is p.empty and p.kind is p.POSITIONAL_OR_KEYWORD]
+        args = ARGS[name](tmp) if required else ()
+        calls.append((name, method, args))
+    return calls
+
+
+def _hammer(calls, rounds):
+    errors = []
+
+    def worker(offset):
+        # Each thread walks the getters from a different starting point
+        ordered = calls[offset:] + calls[:offset]
+        for _ in range(rounds):
+            for name, method, args in ordered:
+                try:
+                    method(*args)
+                except EXPECTED:
+                    pass
+                except Exception as e:
+                    errors.append((name, e))
+    threads = [threading.Thread(target=worker, args=(i * 7 % len(calls),), daemon=True)
+               for i in range(8)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join(timeout=60)
+    assert not any(thread.is_alive() for thread in threads), "getter threads deadlocked"
+    return errors
+
+
+@contextlib.contextmanager
+def _sampling(sections):
+    # The sampler collects alongside, and query_history needs its samples
+    sampler.start_sampler(interval_ms=10, sections=sections)
+    try:
+        deadline = time.monotonic() + 3
+        while not sampler.get_samples() and time.monotonic() < deadline:
+            time.sleep(0.01)
+        yield
+    finally:
+        sampler.stop_sampler()
+
+
+def test_every_getter_from_many_threads(tmp_path):
+    """Passes under the GIL and on free-threaded builds (python3.13t) alike."""
+    (tmp_path / "file").write_bytes(b"x" * 100)
+    with _sampling(["cpu", "memory"]):
+        errors = _hammer(_calls(core, tmp_path), rounds=3)
+    assert errors == []
+
+
+def test_shared_monitor_from_many_threads(tmp_path):
+    monitor = core.Monitor()
+    with _sampling(["cpu"]):
+        errors = _hammer(_calls(monitor, tmp_path), rounds=2)
+    assert errors == []
+
+
+def test_sampler_start_and_stop_race():
+    barrier = threading.Barrier(6)
+    errors = []
+
+    def churn():
+        try:
+            barrier.wait(5)
+            for _ in range(5):
+                sampler.start_sampler(interval_ms=5, sections=["memory"])
+                sampler.get_samples()
+                sampler.stop_sampler()
+        except Exception as e:
+            errors.append(e)
+    threads = [threading.Thread(target=churn, daemon=True) for _ in range(6)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join(timeout=30)
+    assert not any(thread.is_alive() for thread in threads)
+    assert errors == []
+    # Only one sampler thread ever ran at a time, and none is left behind
+    assert sampler.sampler_status()["running"] is False
+    assert not [t for t in threading.enumerate() if t.name == "pulse-sampler"]
+
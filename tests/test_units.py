@@ -0,0 +1,69 @@
+import pytest
+from pulse import core, units
+
+
+@pytest.fixture(autouse=True)
+def default_units():
+    yield
+    units.set_units()
+
+
+@pytest.fixture
+def fake_network(monkeypatch):
+    monkeypatch.setattr(core.direct_os, "get_network_stats",
+                        lambda: {"bytes_recv": 5 * 1024 ** 3 + 200 * 1024 ** 2, "bytes_sent": 1536})
+
+
+@pytest.mark.parametrize("n, expected", [
+    (0, "0 B"),
+    (1, "1 B"),
+    (1023, "1023 B"),
+    (1024, "1.0 KiB"),
+    (1536, "1.5 KiB"),
+    (1024 ** 2 - 1, "1.0 MiB"),
+    (5 * 1024 ** 3 + 200 * 1024 ** 2, "5.2 GiB"),
+    (1024 ** 5, "1.0 PiB"),
+    (2 ** 64 - 1, "16.0 EiB"),
+    (-2048, "-2.0 KiB"),
+    (None, None),
+])
+def test_humanize(n, expected):
+    assert units.humanize(n) == expected
+
+
+def test_default_is_raw_bytes(fake_network):
+    assert units.get_units() == {"memory": "bytes", "disk": "bytes", "network": "bytes"}
+    assert core.get_network_stats() == {"bytes_recv": 5 * 1024 ** 3 + 200 * 1024 ** 2, "bytes_sent": 1536}
+
+
+def test_kib_and_mib(fake_network):
+    units.set_units(network="kib")
+    assert core.get_network_stats()["bytes_sent"] == 1.5
+    units.set_units(network="mib", memory="mib")
+    assert core.get_network_stats()["bytes_recv"] == pytest.approx(5 * 1024 + 200)
+    memory = core.get_memory_info()
+    assert memory["total"] < 1024 ** 3
+    assert isinstance(memory["percent"], (int, float)) and memory["percent"] <= 100
+
+
+def test_auto_human_adds_string_fields(fake_network):
+    units.set_units(network="auto_human", disk="auto_human")
+    stats = core.get_network_stats()
+    assert stats["bytes_recv"] == 5 * 1024 ** 3 + 200 * 1024 ** 2
+    assert stats["bytes_recv_human"] == "5.2 GiB"
+    assert stats["bytes_sent_human"] == "1.5 KiB"
+    for disk in core.get_disk_info():
+        assert disk["total_human"] == units.humanize(disk["total"])
+        assert "inodes_total_human" not in disk
+
+
+def test_snapshot_follows_units_but_prometheus_stays_in_bytes(fake_network):
+    units.set_units(network="kib")
+    assert core.snapshot(include=["network"])["network"]["bytes_sent"] == 1.5
+    assert "pulse_network_sent_bytes_total 1536\n" in core.get_prometheus_metrics()
+
+
+def test_invalid_unit():
+    with pytest.raises(ValueError, match="disk"):
+        units.set_units(disk="gb")
+    assert units.get_units()["disk"] == "bytes"
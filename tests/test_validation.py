@@ -0,0 +1,158 @@
+import asyncio
+import inspect
+import os
+import pytest
+from pulse import aio, capabilities, core, validation
+
+# (function name in pulse.core, call, message the ValueError must match)
+CASES = [
+    ("get_process_list", lambda: core.get_process_list(sort_by="memory"), r"sort_by 'memory'; expected one of cpu, mem"),
+    ("get_process_list", lambda: core.get_process_list(limit=0), r"limit must be >= 1, got 0"),
+    ("get_process_list", lambda: core.get_process_list(limit=-1), r"limit must be >= 1, got -1"),
+    ("get_process_list", lambda: core.get_process_list(max_age_ms=-5), r"max_age_ms must be >= 0"),
+    ("get_network_stats", lambda: core.get_network_stats(dedupe_bonded=True, max_age_ms=-1),
+     r"max_age_ms must be >= 0"),
+    ("iter_processes", lambda: core.iter_processes(fields=["pid", "rss"]), r"fields 'rss'; expected any of pid,"),
+    ("iter_processes", lambda: core.iter_processes(fields="pid"), r"fields must be a list of names"),
+    ("iter_processes", lambda: core.iter_processes(sort_by="CPU"), r"sort_by 'CPU'"),
+    ("get_io_hogs", lambda: core.get_io_hogs(0), r"n must be >= 1"),
+    ("get_process_limits", lambda: core.get_process_limits(-1), r"pid must be >= 1"),
+    ("get_namespace_network", lambda: core.get_namespace_network(0), r"pid must be >= 1"),
+    ("get_process_namespaces", lambda: core.get_process_namespaces(0), r"pid must be >= 1"),
+    ("get_process_scheduling", lambda: core.get_process_scheduling(0), r"pid must be >= 1"),
+    ("get_process_threads", lambda: core.get_process_threads(-2), r"pid must be >= 1"),
+    ("get_open_files", lambda: core.get_open_files(1, limit=0), r"limit must be >= 1"),
+    ("who_has_open", lambda: core.who_has_open("/", timeout_secs=0), r"timeout_secs must be > 0"),
+    ("get_anomalies", lambda: core.get_anomalies(window_secs=0), r"window_secs must be > 0"),
+    ("query_history", lambda: core.query_history("cpu_total", aggregates=["p90"]), r"Invalid aggregates 'p90'"),
+    ("get_crash_loops", lambda: core.get_crash_loops(window_secs=7200), r"window_secs must be between"),
+    ("get_runaway_processes", lambda: core.get_runaway_processes(cpu_threshold=0), r"cpu_threshold must be > 0"),
+    ("write_report", lambda: core.write_report("run.xml", format="xml"), r"Invalid format 'xml'"),
+    ("start_socket_publisher", lambda: core.start_socket_publisher(""), r"path must not be empty"),
+    ("set_min_refresh_interval", lambda: core.set_min_refresh_interval("gpu", 100), r"Invalid subsystem 'gpu'"),
+    ("set_call_timeout", lambda: core.set_call_timeout(-1), r"timeout_ms must be >= 0"),
+    ("enable_logging", lambda: core.enable_logging("verbose"), r"Invalid level 'verbose'"),
+    ("get_process_changes", lambda: core.get_process_changes(cpu_epsilon=-1), r"cpu_epsilon must be >= 0"),
+    ("resolve_target", lambda: core.resolve_target("port:80"), r"Invalid spec kind 'port'"),
+    ("set_process_tags", lambda: core.set_process_tags([{"tag": "db", "match": {"name_regex": "("}}]),
+     r"rule 0: invalid name_regex"),
+    ("get_cpu_leaderboard", lambda: core.get_cpu_leaderboard(group_by="user"), r"Invalid group_by 'user'"),
+    ("get_thermal_correlation", lambda: core.get_thermal_correlation(threshold_c=0), r"threshold_c must be > 0"),
+    ("sample_cpu", lambda: core.sample_cpu(duration_secs=0.1, interval_secs=0.5),
+     r"interval_secs must be <= duration_secs"),
+    ("set_privacy_mode", lambda: core.set_privacy_mode({"hash_cmdlines": True}), r"Invalid options 'hash_cmdlines'"),
+    ("save_state", lambda: core.save_state(""), r"path must not be empty"),
+    ("load_state", lambda: core.load_state(" "), r"path must not be empty"),
+    ("set_backend", lambda: core.set_backend("replay"), r"Invalid name 'replay'"),
+    ("mock_set_data", lambda: core.mock_set_data("gpu", {}), r"Invalid section 'gpu'"),
+    ("mock_clear", lambda: core.mock_clear("gpu"), r"Invalid section 'gpu'"),
+    ("get_limit_headroom", lambda: core.get_limit_headroom(1, warn_percent=150), r"warn_percent must be between"),
+    ("list_limit_headroom", lambda: core.list_limit_headroom([0]), r"pid must be >= 1"),
+    ("group_processes_by_namespace", lambda: core.group_processes_by_namespace("ipc"),
+     r"kind 'ipc'; expected one of pid, net, mnt, uts"),
+    ("kill_process", lambda: core.kill_process(-1), r"pid must be >= 1, got -1"),
+    ("kill_process", lambda: core.kill_process(0), r"pid must be >= 1, got 0"),
+    ("renice_process", lambda: core.renice_process(1, 20), r"nice_value must be between -20 and 19"),
+    ("get_connections", lambda: core.get_connections("tcp5"), r"kind 'tcp5'; expected one of tcp, tcp4"),
+    ("get_kernel_messages", lambda: core.get_kernel_messages(level="loud"), r"level 'loud'; expected one of emerg"),
+    ("get_kernel_messages", lambda: core.get_kernel_messages(since_seq=-2), r"since_seq must be >= 0"),
+    ("list_services", lambda: core.list_services(""), r"pattern must not be empty"),
+    ("get_service", lambda: core.get_service("  "), r"name must not be empty"),
+    ("get_disk_info", lambda: core.get_disk_info(exclude_fs=["tmpfs"], include_fs=["ext4"]),
+     r"include_fs cannot be combined with exclude_fs"),
+    ("get_disk_info", lambda: core.get_disk_info(physical_only=False, include_fs=["ext4"]),
+     r"cannot be combined with physical_only=False"),
+    ("get_disk_info", lambda: core.get_disk_info(include_fs="ext4"), r"include_fs must be a list of names"),
+    ("get_disk_info", lambda: core.get_disk_info(exclude_fs=[""]), r"exclude_fs must not be empty"),
+    ("get_disk_info", lambda: core.get_disk_info(network_timeout=0), r"network_timeout must be > 0"),
+    ("get_disk_info", lambda: core.get_disk_info(timeout_ms=-5), r"timeout_ms must be >= 0"),
+    ("get_path_usage", lambda: core.get_path_usage(""), r"path must not be empty"),
+    ("scan_directory", lambda: core.scan_directory(".", max_depth=-1), r"max_depth must be >= 0"),
+    ("scan_directory", lambda: core.scan_directory(".", timeout_secs=-1), r"timeout_secs must be >= 0"),
+    ("scan_directory", lambda: core.scan_directory(""), r"path must not be empty"),
+    ("find_largest_files", lambda: core.find_largest_files(".", n=0), r"n must be >= 1"),
+    ("find_largest_files", lambda: core.find_largest_files(".", min_size=-1), r"min_size must be >= 0"),
+    ("snapshot", lambda: core.snapshot(include=["gpu"]), r"include 'gpu'; expected any of cpu,"),
+    ("snapshot", lambda: core.snapshot(options={"sensors": {}}), r"options 'sensors'"),
+    ("snapshot", lambda: core.snapshot(options={"processes": {"fields": ["pid", "cmdline"]}}), r"fields 'cmdline'"),
+    ("snapshot", lambda: core.snapshot(options={"processes": {"sort_by": "name"}}), r"sort_by 'name'"),
+    ("check_sections", lambda: core.check_sections("cpu", {}), r"include must be a list of names"),
+    ("init", lambda: core.init(["gpu"]), r"refresh 'gpu'; expected any of cpu,"),
+    ("get_prometheus_metrics", lambda: core.get_prometheus_metrics(process_limit=0), r"process_limit must be >= 1"),
+    ("get_prometheus_metrics", lambda: core.get_prometheus_metrics(prefix="1bad"), r"Invalid prefix '1bad'"),
+    ("diff_snapshots", lambda: core.diff_snapshots(core.take_snapshot(), core.take_snapshot(), -1),
+     r"disk_threshold_bytes must be >= 0"),
+    ("set_units", lambda: core.set_units(disk="gb"), r"Invalid disk 'gb'; expected one of bytes"),
+    ("set_compat_mode", lambda: core.set_compat_mode("procps"), r"Invalid mode 'procps'"),
+    ("set_clock", lambda: core.set_clock("tai"), r"Invalid source 'tai'; expected one of wall, monotonic"),
+    ("set_brightness", lambda: core.set_brightness(101), r"percent must be between 0 and 100"),
+    ("register_callback", lambda: core.register_callback("tick", print), r"Invalid event 'tick'"),
+    ("unregister_callback", lambda: core.unregister_callback("tick", print), r"Invalid event 'tick'"),
+    ("start_sampler", lambda: core.start_sampler(interval_ms=0), r"interval_ms must be > 0"),
+    ("start_sampler", lambda: core.start_sampler(capacity=0), r"capacity must be >= 1"),
+    ("start_sampler", lambda: core.start_sampler(sections=["gpu"]), r"include 'gpu'"),
+    ("get_cpu_history_buffer", lambda: core.get_cpu_history_buffer(last=0), r"last must be >= 1"),
+    ("start_disk_sampler", lambda: core.start_disk_sampler(0), r"interval_secs must be > 0"),
+    ("start_mount_watcher", lambda: core.start_mount_watcher(-1), r"interval_secs must be > 0"),
+    ("start_recording", lambda: core.start_recording("", 100), r"path must not be empty"),
+    ("start_recording", lambda: core.start_recording("x.pulse", 0), r"interval_ms must be > 0"),
+    ("start_http_server", lambda: core.start_http_server("localhost"), r"Invalid addr 'localhost'; expected host:port"),
+    ("start_http_server", lambda: core.start_http_server(":99999"), r"Invalid addr"),
+    ("start_http_server", lambda: core.start_http_server(""), r"addr must not be empty"),
+    ("get_snapshot_msgpack", lambda: core.get_snapshot_msgpack(include=["gpu"]), r"include 'gpu'"),
+    ("spawn_worker", lambda: core.spawn_worker(timeout=0), r"timeout must be > 0"),
+    ("set_default_max_age", lambda: core.set_default_max_age(-1), r"max_age_ms must be >= 0"),
+    ("async_cpu_percents", lambda: asyncio.run(aio.async_cpu_percents(0)), r"interval must be >= 0.05"),
+    ("async_wait_for_process_exit", lambda: asyncio.run(aio.async_wait_for_process_exit(-1)), r"pid must be >= 1"),
+    ("async_wait_for_process_exit", lambda: asyncio.run(aio.async_wait_for_process_exit(1, timeout=-1)),
+     r"timeout must be >= 0"),
+]
+
+# Public functions whose arguments can't be wrong in a way worth a ValueError,
+# or that validate elsewhere (set_alerts in test_alerts)
+UNCHECKED = {
+    "disable_exit_accounting", "get_block_devices", "get_capabilities", "get_disk_projection", "get_samples",
+    "get_scheduler_stats", "list_containers", "load_recording", "set_alerts", "shutdown", "stop_disk_sampler", "stop_http_server",
+    "stop_mount_watcher", "stop_recording", "stop_sampler", "stop_socket_publisher", "stop_worker",
+    "using",
+}
+
+
+@pytest.fixture(autouse=True)
+def guarded_host(monkeypatch):
+    # Argument checks run whether or not systemd is there to answer
+    monkeypatch.setitem(capabilities.FEATURES, "services", ("list_services", lambda: True))
+    # A missing pid check must fail the test, not signal the test runner's process group
+    def refuse(pid, sig):
+        raise AssertionError(f"os.kill({pid}, {sig}) reached")
+    monkeypatch.setattr(os, "kill", refuse)
+
+
+@pytest.mark.parametrize("name, call, message", CASES, ids=[f"{case[0]}-{i}" for i, case in enumerate(CASES)])
+def test_invalid_argument(name, call, message):
+    with pytest.raises(ValueError, match=message):
+        call()
+    assert core.sampler_status()["running"] is False
+
+
+def test_every_public_function_with_arguments_is_covered():
+    covered = {name for name, _, _ in CASES} | UNCHECKED
+    for name in dir(core):
+        function = getattr(core, name)
+        if name.startswith("_") or not inspect.isfunction(function):
+            continue
+        parameters = set(inspect.signature(function).parameters) - {"as_json", "as_msgpack", "max_age_ms", "args", "kwargs"}
+        if parameters:
+            assert name in covered, f"add an invalid-argument case for core.{name}"
+
+
+def test_messages_name_parameter_and_accepted_values():
+    with pytest.raises(ValueError) as info:
+        validation.one_of("sort_by", "size", ("cpu", "mem"))
+    assert str(info.value) == "Invalid sort_by 'size'; expected one of cpu, mem"
+    with pytest.raises(ValueError) as info:
+        validation.all_of("fields", ["pid", "b", "a"], ("pid", "name"))
+    assert str(info.value) == "Invalid fields 'a', 'b'; expected any of pid, name"
+    assert validation.all_of("fields", iter(["pid"]), ("pid",)) == ("pid",)
+    validation.optional_at_least("limit", None, 1)
+    validation.not_empty("pattern", None)
@@ -0,0 +1,48 @@
+import pytest
+from pulse import core, win_processes
+from pulse.errors import Unsupported
+
+ENGINES = {
+    "pid_1200_luid_0x00000000_0x0000D1A4_phys_0_eng_0_engtype_3D": 30.0,
+    "pid_1200_luid_0x00000000_0x0000D1A4_phys_0_eng_1_engtype_3D": 25.0,
+    "pid_1200_luid_0x00000000_0x0000D1A4_phys_0_eng_5_engtype_Copy": 40.0,
+    "pid_88_luid_0x00000000_0x0000D1A4_phys_0_eng_2_engtype_VideoDecode": 12.5,
+    "_Total": 99.0,
+}
+MEMORY = {
+    "pid_1200_luid_0x00000000_0x0000D1A4_phys_0": 512 * 1024 ** 2,
+    "pid_1200_luid_0x00000000_0x0000E2B0_phys_0": 64 * 1024 ** 2,
+    "pid_88_luid_0x00000000_0x0000D1A4_phys_0": 0.0,
+}
+
+
+def test_parse_gpu_instance():
+    assert win_processes.parse_gpu_instance(
+        "pid_1200_luid_0x00000000_0x0000D1A4_phys_0_eng_0_engtype_VideoEncode") == (1200, "VideoEncode")
+    assert win_processes.parse_gpu_instance("pid_4_luid_0x00000000_0x0000D1A4_phys_0") == (4, None)
+    assert win_processes.parse_gpu_instance("_Total") is None
+
+
+def test_busiest_engine_type_wins():
+    assert win_processes.gpu_percents(ENGINES) == {1200: 55.0, 88: 12.5}
+    assert win_processes.gpu_percents({"pid_7_x_engtype_3D": 80.0, "pid_7_y_engtype_3D": 70.0}) == {7: 100.0}
+
+
+def test_memory_sums_adapters():
+    assert win_processes.gpu_memory(MEMORY) == {1200: 576 * 1024 ** 2, 88: 0}
+
+
+def test_denied_and_idle_processes_stay_listed():
+    percents, memory = win_processes.gpu_percents(ENGINES), win_processes.gpu_memory(MEMORY)
+    assert win_processes.windows_process_entry(4, "System", None, percents, memory) == {
+        "pid": 4, "name": "System", "handle_count": None, "gpu_percent": 0.0, "gpu_memory": 0,
+    }
+    assert win_processes.windows_process_entry(1200, "game.exe", 830, percents, memory)["gpu_percent"] == 55.0
+    entry = win_processes.windows_process_entry(1200, "game.exe", 830, None, None)
+    assert entry["gpu_percent"] is None and entry["gpu_memory"] is None
+
+
+@pytest.mark.skipif(win_processes.WINDOWS, reason="checks the non-Windows stub")
+def test_unsupported_elsewhere():
+    with pytest.raises(Unsupported):
+        core.get_windows_process_info()